@@ -53,9 +53,23 @@ bind! {
     // ext_php_rs_zend_string_release,
     // ext_php_rs_is_known_valid_utf8,
     // ext_php_rs_set_known_valid_utf8,
+    // ext_php_rs_zend_new_packed_long,
+    // ext_php_rs_zend_new_packed_double,
+    // ext_php_rs_zend_new_packed_bool,
+    // ext_php_rs_zend_new_packed_zval,
+    // ext_php_rs_zend_new_persistent_array,
+    // ext_php_rs_zend_persistent_array_destroy,
+    // ext_php_rs_zend_hash_copy_longs,
+    // ext_php_rs_zend_hash_copy_doubles,
+    // ext_php_rs_zend_hash_copy_bools,
     object_properties_init,
+    object_properties_load,
     php_error_docref,
     php_info_print_table_end,
+    php_info_print_table_colspan_header,
+    php_info_print_box_start,
+    php_info_print_box_end,
+    php_info_print_hr,
     php_info_print_table_header,
     php_info_print_table_row,
     php_info_print_table_start,
@@ -69,6 +83,7 @@ bind! {
     zend_ce_arithmetic_error,
     zend_ce_compile_error,
     zend_ce_division_by_zero_error,
+    zend_ce_error,
     zend_ce_error_exception,
     zend_ce_exception,
     zend_ce_parse_error,
@@ -84,8 +99,11 @@ bind! {
     zend_ce_countable,
     zend_ce_stringable,
     zend_class_entry,
+    zend_class_constant,
     zend_declare_class_constant,
+    zend_declare_class_constant_ex,
     zend_declare_property,
+    zend_declare_property_ex,
     zend_do_implement_interface,
     zend_empty_array,
     zend_read_static_property,
@@ -95,16 +113,47 @@ bind! {
     zend_enum_new,
     zend_execute_data,
     zend_function_entry,
+    zend_compare,
+    _convert_to_string,
+    convert_to_array,
+    convert_to_boolean,
+    convert_to_double,
+    convert_to_long,
+    add_function,
+    sub_function,
+    mul_function,
+    div_function,
+    mod_function,
+    pow_function,
+    concat_function,
+    bitwise_and_function,
+    bitwise_or_function,
+    bitwise_xor_function,
+    shift_left_function,
+    shift_right_function,
+    zend_hash_apply_with_argument,
+    apply_func_arg_t,
     zend_hash_clean,
+    zend_hash_del_bucket,
+    zend_hash_extend,
     zend_hash_find_known_hash,
     _zend_hash_find_known_hash,
+    zend_hash_index_add,
     zend_hash_index_del,
     zend_hash_index_find,
     zend_hash_index_update,
+    zend_hash_rehash,
+    zend_hash_merge,
     zend_hash_next_index_insert,
+    zend_hash_packed_del_val,
+    zend_hash_sort_ex,
+    zend_sort,
+    zend_hash_str_add,
     zend_hash_str_del,
+    zend_hash_str_del_ind,
     zend_hash_str_find,
     zend_hash_str_update,
+    zval_add_ref,
     zend_internal_arg_info,
     zend_is_callable,
     zend_is_identical,
@@ -120,6 +169,8 @@ bind! {
     zend_register_bool_constant,
     zend_register_double_constant,
     zend_register_ini_entries,
+    zend_alter_ini_entry_chars,
+    zend_restore_ini_entry,
     zend_register_internal_enum,
     zend_register_internal_interface,
     zend_ini_entry_def,
@@ -158,7 +209,13 @@ bind! {
     GC_FLAGS_MASK,
     GC_FLAGS_SHIFT,
     GC_IMMUTABLE,
+    GC_PERSISTENT,
+    HASH_FLAG_PACKED,
+    HASH_FLAG_UNINITIALIZED,
     HT_MIN_SIZE,
+    ZEND_HASH_APPLY_KEEP,
+    ZEND_HASH_APPLY_REMOVE,
+    ZEND_HASH_APPLY_STOP,
     IS_ARRAY,
     IS_ARRAY_EX,
     IS_CALLABLE,
@@ -170,6 +227,7 @@ bind! {
     IS_INTERNED_STRING_EX,
     IS_LONG,
     IS_MIXED,
+    IS_NEVER,
     IS_NULL,
     IS_OBJECT,
     IS_OBJECT_EX,
@@ -192,6 +250,18 @@ bind! {
     PHP_INI_PERDIR,
     PHP_INI_SYSTEM,
     PHP_INI_ALL,
+    ZEND_INI_STAGE_STARTUP,
+    ZEND_INI_STAGE_SHUTDOWN,
+    ZEND_INI_STAGE_ACTIVATE,
+    ZEND_INI_STAGE_DEACTIVATE,
+    ZEND_INI_STAGE_RUNTIME,
+    ZEND_INI_STAGE_HTACCESS,
+    BP_VAR_R,
+    BP_VAR_W,
+    BP_VAR_RW,
+    BP_VAR_IS,
+    BP_VAR_FUNC_ARG,
+    BP_VAR_UNSET,
     USING_ZTS,
     ZEND_ACC_ABSTRACT,
     ZEND_ACC_ANON_CLASS,
@@ -254,9 +324,11 @@ bind! {
     ts_rsrc_id,
     _ZEND_TYPE_NAME_BIT,
     _ZEND_TYPE_LITERAL_NAME_BIT,
+    _ZEND_TYPE_ITERABLE_BIT,
     ZEND_INTERNAL_FUNCTION,
     ZEND_USER_FUNCTION,
     ZEND_EVAL_CODE,
+    ZEND_INTERNAL_CLASS,
     zval_ptr_dtor,
     zend_refcounted_h,
     zend_is_true,
@@ -264,7 +336,14 @@ bind! {
     zend_std_read_property,
     zend_std_write_property,
     zend_std_get_properties,
+    zend_std_cast_object_tostring,
+    zend_std_read_dimension,
+    zend_std_write_dimension,
+    zend_std_has_dimension,
+    zend_std_unset_dimension,
+    zend_std_get_property_ptr_ptr,
     zend_std_has_property,
+    zend_std_unset_property,
     zend_objects_new,
     zend_standard_class_def,
     zend_class_serialize_deny,
@@ -318,14 +397,75 @@ bind! {
     php_register_url_stream_wrapper_volatile,
     php_stream_wrapper,
     php_stream_stdio_ops,
+    php_stream_context_alloc,
+    php_stream_context_set_option,
+    php_stream_context_get_option,
+    php_stream_notification_free,
+    _php_stream_open_wrapper_ex,
+    _php_stream_read,
+    _php_stream_write,
+    _php_stream_free,
+    _php_stream_flush,
+    _php_stream_cast,
+    _php_stream_set_option,
+    _php_stream_xport_create,
+    STREAM_XPORT_CLIENT,
+    STREAM_XPORT_CONNECT,
+    PHP_STREAM_AS_FD_FOR_SELECT,
+    PHP_STREAM_CAST_INTERNAL,
+    PHP_STREAM_OPTION_BLOCKING,
+    zend_list_delete,
+    zend_list_close,
+    zend_register_list_destructors_ex,
+    zend_register_resource,
+    zend_rsrc_list_get_rsrc_type,
+    REPORT_ERRORS,
+    PHP_STREAM_FREE_CLOSE,
+    PHP_STREAM_NOTIFY_RESOLVE,
+    PHP_STREAM_NOTIFY_CONNECT,
+    PHP_STREAM_NOTIFY_AUTH_REQUIRED,
+    PHP_STREAM_NOTIFY_MIME_TYPE_IS,
+    PHP_STREAM_NOTIFY_FILE_SIZE_IS,
+    PHP_STREAM_NOTIFY_REDIRECTED,
+    PHP_STREAM_NOTIFY_PROGRESS,
+    PHP_STREAM_NOTIFY_COMPLETED,
+    PHP_STREAM_NOTIFY_FAILURE,
+    PHP_STREAM_NOTIFY_AUTH_RESULT,
+    PHP_STREAM_NOTIFY_SEVERITY_INFO,
+    PHP_STREAM_NOTIFY_SEVERITY_WARN,
+    PHP_STREAM_NOTIFY_SEVERITY_ERR,
+    ZEND_COMPILE_EXTENDED_STMT,
+    ZEND_COMPILE_EXTENDED_FCALL,
+    ZEND_COMPILE_HANDLE_OP_ARRAY,
+    ZEND_COMPILE_IGNORE_INTERNAL_FUNCTIONS,
+    ZEND_COMPILE_IGNORE_INTERNAL_CLASSES,
+    ZEND_COMPILE_DELAYED_BINDING,
+    ZEND_COMPILE_NO_CONSTANT_SUBSTITUTION,
+    ZEND_COMPILE_NO_PERSISTENT_CONSTANT_SUBSTITUTION,
+    ZEND_COMPILE_IGNORE_USER_FUNCTIONS,
+    ZEND_COMPILE_GUARDS,
+    ZEND_COMPILE_NO_BUILTINS,
+    ZEND_COMPILE_WITH_FILE_CACHE,
+    ZEND_COMPILE_IGNORE_OTHER_FILES,
+    ZEND_COMPILE_WITHOUT_EXECUTION,
+    ZEND_COMPILE_PRELOAD,
+    ZEND_COMPILE_NO_JUMPTABLES,
+    ZEND_COMPILE_PRELOAD_IN_CHILD,
+    zend_multibyte_get_encoding_name,
+    zend_atomic_bool_load,
     zend_atomic_bool_store,
     zend_interrupt_function,
     zend_eval_string,
+    zend_rebuild_symbol_table,
+    zend_set_local_var_str,
     zend_file_handle,
     zend_stream_init_filename,
     zend_destroy_file_handle,
     php_execute_script,
     zend_register_module_ex,
+    zend_register_functions,
+    module_registry,
+    zend_get_constant_str,
     _zend_bailout,
     sapi_startup,
     sapi_shutdown,
@@ -345,5 +485,17 @@ bind! {
     php_ini_builder_unquoted,
     php_ini_builder_quoted,
     php_ini_builder_define,
-    php_output_write
+    php_output_write,
+    php_output_start_default,
+    php_output_get_contents,
+    php_output_discard,
+    php_var_dump,
+    php_var_export,
+    zend_new_interned_string,
+    zend_interned_string_find_permanent,
+    IS_STR_PERMANENT,
+    smart_str,
+    zend_hash_func,
+    zend_string_tolower_ex,
+    zend_string_toupper_ex
 }
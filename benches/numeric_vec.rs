@@ -0,0 +1,76 @@
+//! Compares converting large numeric vectors into PHP arrays and back through
+//! the packed fast paths with converting each value separately.
+//!
+//! Run with `cargo bench --features embed --bench numeric_vec`.
+
+use std::{hint::black_box, time::Instant};
+
+use ext_php_rs::{boxed::ZBox, embed::Embed, types::ZendHashTable};
+
+const LEN: usize = 1_000_000;
+const RUNS: u32 = 20;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    f();
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        f();
+    }
+    println!("{name:<32} {:>10.3?}", start.elapsed() / RUNS);
+}
+
+fn per_value<T: Copy + ext_php_rs::convert::IntoZval>(values: &[T]) -> ZBox<ZendHashTable> {
+    let mut ht = ZendHashTable::with_capacity(values.len().try_into().unwrap());
+    for &val in values {
+        ht.push(val).unwrap();
+    }
+    ht
+}
+
+fn per_value_back<'a, T: ext_php_rs::convert::FromZval<'a>>(ht: &'a ZendHashTable) -> Vec<T> {
+    ht.values().map(|val| T::from_zval(val).unwrap()).collect()
+}
+
+fn main() {
+    Embed::run(|| {
+        let longs: Vec<i64> = (0..LEN as i64).collect();
+        let doubles: Vec<f64> = (0..LEN).map(|i| i as f64 * 0.5).collect();
+        let bools: Vec<bool> = (0..LEN).map(|i| i % 3 == 0).collect();
+
+        println!("{LEN} values, average of {RUNS} runs");
+
+        bench("Vec<i64> into array (per value)", || {
+            black_box(per_value(&longs));
+        });
+        bench("Vec<i64> into array (packed)", || {
+            black_box(ZBox::<ZendHashTable>::try_from(longs.as_slice()).unwrap());
+        });
+        bench("Vec<f64> into array (per value)", || {
+            black_box(per_value(&doubles));
+        });
+        bench("Vec<f64> into array (packed)", || {
+            black_box(ZBox::<ZendHashTable>::try_from(doubles.as_slice()).unwrap());
+        });
+        bench("Vec<bool> into array (per value)", || {
+            black_box(per_value(&bools));
+        });
+        bench("Vec<bool> into array (packed)", || {
+            black_box(ZBox::<ZendHashTable>::try_from(bools.as_slice()).unwrap());
+        });
+
+        let ht = ZBox::<ZendHashTable>::try_from(longs.as_slice()).unwrap();
+        bench("array into Vec<i64> (per value)", || {
+            black_box(per_value_back::<i64>(&ht));
+        });
+        bench("array into Vec<i64> (packed)", || {
+            black_box(Vec::<i64>::try_from(&*ht).unwrap());
+        });
+        let ht = ZBox::<ZendHashTable>::try_from(doubles.as_slice()).unwrap();
+        bench("array into Vec<f64> (per value)", || {
+            black_box(per_value_back::<f64>(&ht));
+        });
+        bench("array into Vec<f64> (packed)", || {
+            black_box(Vec::<f64>::try_from(&*ht).unwrap());
+        });
+    });
+}
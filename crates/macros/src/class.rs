@@ -2,10 +2,14 @@ use darling::util::Flag;
 use darling::{FromAttributes, FromMeta, ToTokens};
 use proc_macro2::TokenStream;
 use quote::{TokenStreamExt, quote};
-use syn::{Attribute, Expr, Fields, ItemStruct};
+use syn::spanned::Spanned;
+use syn::{Attribute, Expr, Fields, ItemStruct, parse_quote};
 
 use crate::helpers::get_docs;
-use crate::parsing::{PhpNameContext, PhpRename, RenameRule, ident_to_php_name, validate_php_name};
+use crate::parsing::{
+    PhpNameContext, PhpRename, RenameRule, apply_namespace, ident_to_php_name, parse_requirements,
+    validate_php_name,
+};
 use crate::prelude::*;
 
 #[derive(FromAttributes, Debug, Default)]
@@ -14,6 +18,8 @@ pub struct StructAttributes {
     /// The name of the PHP class. Defaults to the same name as the struct.
     #[darling(flatten)]
     rename: PhpRename,
+    /// The namespace of the PHP class.
+    namespace: Option<String>,
     /// A modifier function which should accept one argument, a `ClassBuilder`,
     /// and return the same object. Allows the user to modify the class before
     /// it is built.
@@ -23,9 +29,49 @@ pub struct StructAttributes {
     extends: Option<ClassEntryAttribute>,
     #[darling(multiple)]
     implements: Vec<ClassEntryAttribute>,
+    /// Names of interfaces implemented only if they exist at module startup.
+    #[darling(multiple)]
+    implements_if_exists: Vec<String>,
+    /// Extensions which must be loaded for the class to be registered.
+    #[darling(multiple)]
+    requires_ext: Vec<String>,
+    /// The minimum PHP version for the class to be registered.
+    min_php: Option<String>,
+    /// Whether to install the `DimensionHandlers` implementation of the class
+    /// as its dimension handlers.
+    dimensions: Flag,
+    /// Whether to implement `ArrayAccess` using the `PhpArrayAccess`
+    /// implementation of the class, which is also installed as its dimension
+    /// handlers.
+    array_access: Flag,
+    /// Whether to install the `PropertyHandlers` implementation of the class
+    /// as its property handlers.
+    property_handlers: Flag,
+    /// Whether to install the `DebugInfo` implementation of the class as its
+    /// debug info handler.
+    debug_info: Flag,
+    /// Whether to install the `ToPhpString` implementation of the class as
+    /// its cast handler.
+    to_string: Flag,
+    /// Whether the `#[php(prop)]` fields are only accessed through the
+    /// property handlers, rather than being declared as PHP properties.
+    proxy_props: Flag,
+    /// Concrete instantiations of a generic struct registered as PHP classes.
+    #[darling(multiple)]
+    instantiate: Vec<Instantiation>,
     attrs: Vec<Attribute>,
 }
 
+/// A concrete instantiation of a generic struct, registered as a separate PHP
+/// class.
+#[derive(FromMeta, Debug)]
+pub struct Instantiation {
+    /// The concrete type, e.g. `Collection<i64>`.
+    ty: syn::Type,
+    /// The name of the PHP class.
+    name: String,
+}
+
 #[derive(FromMeta, Debug)]
 pub struct ClassEntryAttribute {
     pub ce: syn::Expr,
@@ -44,11 +90,13 @@ impl ToTokens for ClassEntryAttribute {
 pub fn parser(mut input: ItemStruct) -> Result<TokenStream> {
     let attr = StructAttributes::from_attributes(&input.attrs)?;
     let ident = &input.ident;
-    let name = attr
-        .rename
-        .rename(ident_to_php_name(ident), RenameRule::Pascal);
-    validate_php_name(&name, PhpNameContext::Class, ident.span())?;
+    let classes = instantiations(&input, &attr)?;
+    if attr.dimensions.is_present() && attr.array_access.is_present() {
+        bail!(ident => "`#[php(dimensions)]` and `#[php(array_access)]` cannot be used together, as both install the dimension handlers of the class.");
+    }
     let docs = get_docs(&attr.attrs)?;
+    let requirements =
+        parse_requirements(&attr.requires_ext, attr.min_php.as_deref(), ident.span())?;
     input.attrs.retain(|attr| !attr.path().is_ident("php"));
 
     let fields = match &mut input.fields {
@@ -56,25 +104,81 @@ pub fn parser(mut input: ItemStruct) -> Result<TokenStream> {
         _ => vec![],
     };
 
-    let class_impl = generate_registered_class_impl(
-        ident,
-        &name,
-        attr.modifier.as_ref(),
-        attr.extends.as_ref(),
-        &attr.implements,
-        &fields,
-        attr.flags.as_ref(),
-        &docs,
-    );
+    let class_impls = classes.iter().map(|(ty, name)| {
+        let class_impl = generate_registered_class_impl(
+            ty,
+            name,
+            attr.modifier.as_ref(),
+            attr.extends.as_ref(),
+            &attr.implements,
+            &attr.implements_if_exists,
+            &fields,
+            attr.flags.as_ref(),
+            &docs,
+            &requirements,
+            attr.dimensions.is_present(),
+            attr.array_access.is_present(),
+            attr.property_handlers.is_present(),
+            attr.debug_info.is_present(),
+            attr.to_string.is_present(),
+            attr.proxy_props.is_present(),
+        );
+
+        quote! {
+            #class_impl
+
+            ::ext_php_rs::class_derives!(#ty);
+        }
+    });
 
     Ok(quote! {
         #input
-        #class_impl
-
-        ::ext_php_rs::class_derives!(#ident);
+        #(#class_impls)*
     })
 }
 
+/// Returns the types registered as PHP classes and their names, which is the
+/// struct itself unless it is generic, in which case each of the concrete
+/// instantiations given by the `instantiate` attribute is registered.
+fn instantiations(input: &ItemStruct, attr: &StructAttributes) -> Result<Vec<(syn::Type, String)>> {
+    let ident = &input.ident;
+    if input.generics.params.is_empty() {
+        if let Some(instance) = attr.instantiate.first() {
+            bail!(instance.ty => "Only generic structs can be instantiated.");
+        }
+        let name = attr
+            .rename
+            .rename(ident_to_php_name(ident), RenameRule::Pascal);
+        validate_php_name(&name, PhpNameContext::Class, ident.span())?;
+        let name = apply_namespace(attr.namespace.as_deref(), name, ident.span())?;
+        return Ok(vec![(parse_quote! { #ident }, name)]);
+    }
+
+    if attr.instantiate.is_empty() {
+        bail!(input.generics => "Generic structs must list the instantiations registered as PHP classes, e.g. `#[php(instantiate(ty = \"Collection<i64>\", name = \"IntCollection\"))]`.");
+    }
+    attr.instantiate
+        .iter()
+        .map(|instance| {
+            let is_struct = matches!(
+                &instance.ty,
+                syn::Type::Path(ty)
+                    if ty.path.segments.last().is_some_and(|segment| segment.ident == *ident)
+            );
+            if !is_struct {
+                bail!(instance.ty => "Instantiations must be of the struct `{}`.", ident);
+            }
+            validate_php_name(&instance.name, PhpNameContext::Class, instance.ty.span())?;
+            let name = apply_namespace(
+                attr.namespace.as_deref(),
+                instance.name.clone(),
+                instance.ty.span(),
+            )?;
+            Ok((instance.ty.clone(), name))
+        })
+        .collect()
+}
+
 #[derive(FromAttributes, Debug, Default)]
 #[darling(attributes(php), forward_attrs(doc), default)]
 struct PropAttributes {
@@ -131,17 +235,25 @@ impl Property<'_> {
     }
 }
 
-/// Generates an implementation of `RegisteredClass` for struct `ident`.
+/// Generates an implementation of `RegisteredClass` for the type `ident`.
 #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
 fn generate_registered_class_impl(
-    ident: &syn::Ident,
+    ident: &syn::Type,
     class_name: &str,
     modifier: Option<&syn::Ident>,
     extends: Option<&ClassEntryAttribute>,
     implements: &[ClassEntryAttribute],
+    optional_implements: &[String],
     fields: &[Property],
     flags: Option<&syn::Expr>,
     docs: &[String],
+    requirements: &[TokenStream],
+    dimensions: bool,
+    array_access: bool,
+    property_handlers: bool,
+    debug_info: bool,
+    to_string: bool,
+    proxy_props: bool,
 ) -> TokenStream {
     let modifier = modifier.option_tokens();
 
@@ -223,6 +335,86 @@ fn generate_registered_class_impl(
         }
     });
 
+    let optional_implements = (!optional_implements.is_empty()).then(|| {
+        let optional_implements = optional_implements
+            .iter()
+            .map(|name| name.trim_start_matches('\\'));
+        quote! {
+            const OPTIONAL_IMPLEMENTS: &'static [&'static str] = &[
+                #(#optional_implements,)*
+            ];
+        }
+    });
+
+    let requirements = (!requirements.is_empty()).then(|| {
+        quote! {
+            const REQUIREMENTS: &'static [::ext_php_rs::builders::Requirement] = &[
+                #(#requirements,)*
+            ];
+        }
+    });
+
+    let dimensions = dimensions.then(|| {
+        quote! {
+            const DIMENSION_HANDLERS: ::std::option::Option<
+                ::ext_php_rs::zend::DimensionHandlerTable
+            > = ::std::option::Option::Some(
+                ::ext_php_rs::zend::DimensionHandlerTable::of::<Self>()
+            );
+        }
+    });
+
+    let array_access = array_access.then(|| {
+        quote! {
+            const DIMENSION_HANDLERS: ::std::option::Option<
+                ::ext_php_rs::zend::DimensionHandlerTable
+            > = ::std::option::Option::Some(
+                ::ext_php_rs::zend::DimensionHandlerTable::of_array_access::<Self>()
+            );
+            const ARRAY_ACCESS: ::std::option::Option<
+                ::ext_php_rs::zend::ArrayAccessMethods
+            > = ::std::option::Option::Some(
+                ::ext_php_rs::zend::ArrayAccessMethods::of::<Self>()
+            );
+        }
+    });
+
+    let property_handlers = property_handlers.then(|| {
+        quote! {
+            const PROPERTY_HANDLERS: ::std::option::Option<
+                ::ext_php_rs::zend::PropertyHandlerTable
+            > = ::std::option::Option::Some(
+                ::ext_php_rs::zend::PropertyHandlerTable::of::<Self>()
+            );
+        }
+    });
+
+    let debug_info = debug_info.then(|| {
+        quote! {
+            const DEBUG_INFO_HANDLER: ::std::option::Option<
+                ::ext_php_rs::zend::DebugInfoHandler
+            > = ::std::option::Option::Some(
+                ::ext_php_rs::zend::DebugInfoHandler::of::<Self>()
+            );
+        }
+    });
+
+    let to_string = to_string.then(|| {
+        quote! {
+            const TO_STRING_HANDLER: ::std::option::Option<
+                ::ext_php_rs::zend::ToStringHandler
+            > = ::std::option::Option::Some(
+                ::ext_php_rs::zend::ToStringHandler::of::<Self>()
+            );
+        }
+    });
+
+    let proxy_props = proxy_props.then(|| {
+        quote! {
+            const PROXY_PROPERTIES: bool = true;
+        }
+    });
+
     quote! {
         impl ::ext_php_rs::class::RegisteredClass for #ident {
             const CLASS_NAME: &'static str = #class_name;
@@ -235,10 +427,18 @@ fn generate_registered_class_impl(
             const IMPLEMENTS: &'static [::ext_php_rs::class::ClassEntryInfo] = &[
                 #(#implements,)*
             ];
+            #optional_implements
             const FLAGS: ::ext_php_rs::flags::ClassFlags = #flags;
             const DOC_COMMENTS: &'static [&'static str] = &[
                 #docs
             ];
+            #requirements
+            #dimensions
+            #array_access
+            #property_handlers
+            #debug_info
+            #to_string
+            #proxy_props
 
             #[inline]
             fn get_metadata() -> &'static ::ext_php_rs::class::ClassMetadata<Self> {
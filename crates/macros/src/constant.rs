@@ -4,17 +4,25 @@ use quote::{format_ident, quote};
 use syn::ItemConst;
 
 use crate::helpers::get_docs;
-use crate::parsing::{PhpNameContext, PhpRename, RenameRule, ident_to_php_name, validate_php_name};
+use crate::parsing::{
+    PhpNameContext, PhpRename, RenameRule, apply_namespace, ident_to_php_name, parse_requirements,
+    validate_php_name,
+};
 use crate::prelude::*;
 
 const INTERNAL_CONST_DOC_PREFIX: &str = "_internal_const_docs_";
 const INTERNAL_CONST_NAME_PREFIX: &str = "_internal_const_name_";
+const INTERNAL_CONST_REQUIREMENTS_PREFIX: &str = "_internal_const_requirements_";
 
 #[derive(FromAttributes, Default, Debug)]
 #[darling(default, attributes(php), forward_attrs(doc))]
 pub(crate) struct PhpConstAttribute {
     #[darling(flatten)]
     pub(crate) rename: PhpRename,
+    pub(crate) namespace: Option<String>,
+    #[darling(multiple)]
+    pub(crate) requires_ext: Vec<String>,
+    pub(crate) min_php: Option<String>,
     // TODO: Implement const Visibility
     // pub(crate) vis: Option<Visibility>,
     pub(crate) attrs: Vec<syn::Attribute>,
@@ -27,10 +35,17 @@ pub fn parser(mut item: ItemConst) -> Result<TokenStream> {
         .rename
         .rename(ident_to_php_name(&item.ident), RenameRule::ScreamingSnake);
     validate_php_name(&name, PhpNameContext::Constant, item.ident.span())?;
+    let name = apply_namespace(attr.namespace.as_deref(), name, item.ident.span())?;
     let name_ident = format_ident!("{INTERNAL_CONST_NAME_PREFIX}{}", item.ident);
 
     let docs = get_docs(&attr.attrs)?;
     let docs_ident = format_ident!("{INTERNAL_CONST_DOC_PREFIX}{}", item.ident);
+    let requirements = parse_requirements(
+        &attr.requires_ext,
+        attr.min_php.as_deref(),
+        item.ident.span(),
+    )?;
+    let requirements_ident = format_ident!("{INTERNAL_CONST_REQUIREMENTS_PREFIX}{}", item.ident);
     item.attrs.retain(|attr| !attr.path().is_ident("php"));
 
     Ok(quote! {
@@ -39,6 +54,8 @@ pub fn parser(mut item: ItemConst) -> Result<TokenStream> {
         const #docs_ident: &[&str] = &[#(#docs),*];
         #[allow(non_upper_case_globals)]
         const #name_ident: &str = #name;
+        #[allow(non_upper_case_globals)]
+        const #requirements_ident: &[::ext_php_rs::builders::Requirement] = &[#(#requirements),*];
     })
 }
 
@@ -47,9 +64,14 @@ pub fn wrap(input: &syn::Path) -> Result<TokenStream> {
         bail!(input => "Pass a PHP const into `wrap_constant!()`.");
     };
     let doc_const = format_ident!("{INTERNAL_CONST_DOC_PREFIX}{const_name}");
+    let requirements_const = format_ident!("{INTERNAL_CONST_REQUIREMENTS_PREFIX}{const_name}");
     let const_name = format_ident!("{INTERNAL_CONST_NAME_PREFIX}{const_name}");
 
     Ok(quote! {
-        (#const_name, #input, #doc_const)
+        (
+            #const_name,
+            ::ext_php_rs::constant::ConditionalConst::new(#input, #requirements_const),
+            #doc_const,
+        )
     })
 }
@@ -9,7 +9,8 @@ use syn::{Fields, Ident, ItemEnum, Lit};
 use crate::{
     helpers::get_docs,
     parsing::{
-        PhpNameContext, PhpRename, RenameRule, Visibility, ident_to_php_name, validate_php_name,
+        PhpNameContext, PhpRename, RenameRule, Visibility, apply_namespace, ident_to_php_name,
+        parse_requirements, validate_php_name,
     },
     prelude::*,
 };
@@ -19,11 +20,15 @@ use crate::{
 struct PhpEnumAttribute {
     #[darling(flatten)]
     rename: PhpRename,
+    namespace: Option<String>,
     #[darling(default)]
     allow_native_discriminants: Flag,
     rename_cases: Option<RenameRule>,
     // TODO: Implement visibility support
     vis: Option<Visibility>,
+    #[darling(multiple)]
+    requires_ext: Vec<String>,
+    min_php: Option<String>,
     attrs: Vec<syn::Attribute>,
 }
 
@@ -131,6 +136,7 @@ pub struct Enum<'a> {
     docs: Vec<String>,
     cases: Vec<EnumCase>,
     flags: Option<String>,
+    requirements: Vec<TokenStream>,
 }
 
 impl<'a> Enum<'a> {
@@ -146,6 +152,9 @@ impl<'a> Enum<'a> {
             .rename
             .rename(ident_to_php_name(ident), RenameRule::Pascal);
         validate_php_name(&name, PhpNameContext::Enum, ident.span())?;
+        let name = apply_namespace(attrs.namespace.as_deref(), name, ident.span())?;
+        let requirements =
+            parse_requirements(&attrs.requires_ext, attrs.min_php.as_deref(), ident.span())?;
 
         Ok(Self {
             ident,
@@ -154,6 +163,7 @@ impl<'a> Enum<'a> {
             docs,
             cases,
             flags,
+            requirements,
         })
     }
 
@@ -167,6 +177,14 @@ impl<'a> Enum<'a> {
             .unwrap_or_default();
         let flags = quote! { ::ext_php_rs::flags::ClassFlags::Enum #flags };
         let docs = &self.docs;
+        let requirements = (!self.requirements.is_empty()).then(|| {
+            let requirements = &self.requirements;
+            quote! {
+                const REQUIREMENTS: &'static [::ext_php_rs::builders::Requirement] = &[
+                    #(#requirements,)*
+                ];
+            }
+        });
 
         quote! {
             impl ::ext_php_rs::class::RegisteredClass for #ident {
@@ -182,6 +200,7 @@ impl<'a> Enum<'a> {
                 const DOC_COMMENTS: &'static [&'static str] = &[
                     #(#docs,)*
                 ];
+                #requirements
 
                 fn get_metadata() -> &'static ::ext_php_rs::class::ClassMetadata<Self> {
                     static METADATA: ::ext_php_rs::class::ClassMetadata<#ident> =
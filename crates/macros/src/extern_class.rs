@@ -0,0 +1,237 @@
+use darling::FromAttributes;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{FnArg, ItemTrait, LitStr, Pat, ReturnType, TraitItem, TraitItemFn, Type};
+
+use crate::parsing::{PhpNameContext, PhpRename, RenameRule, ident_to_php_name, validate_php_name};
+use crate::prelude::*;
+
+#[derive(FromAttributes, Debug, Default)]
+#[darling(attributes(php), default)]
+struct MethodAttributes {
+    #[darling(flatten)]
+    rename: PhpRename,
+}
+
+pub fn parser(class: LitStr, input: ItemTrait) -> Result<TokenStream> {
+    if let Some(param) = input.generics.params.first() {
+        bail!(param => "Wrappers of PHP classes cannot be generic.");
+    }
+    if let Some(supertrait) = input.supertraits.first() {
+        bail!(supertrait => "Wrappers of PHP classes cannot have supertraits.");
+    }
+
+    let ItemTrait {
+        attrs,
+        vis,
+        ident,
+        items,
+        ..
+    } = input;
+    let methods = items
+        .into_iter()
+        .map(|item| match item {
+            TraitItem::Fn(func) => parse_method(func),
+            _ => bail!(item => "Only methods are supported by `#[php_extern_class]`."),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis struct #ident {
+            object: ::ext_php_rs::boxed::ZBox<::ext_php_rs::types::ZendObject>,
+        }
+
+        impl #ident {
+            /// The name of the wrapped PHP class.
+            pub const CLASS_NAME: &'static str = #class;
+
+            fn extern_class_entry() -> &'static ::ext_php_rs::class::ExternClassEntry {
+                static CE: ::ext_php_rs::class::ExternClassEntry =
+                    ::ext_php_rs::class::ExternClassEntry::new(#class);
+                &CE
+            }
+
+            /// Returns the class entry of the wrapped PHP class, if it is
+            /// registered by an extension.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the class does not exist or was declared by
+            /// userland code.
+            pub fn class_entry() -> ::ext_php_rs::error::Result<&'static ::ext_php_rs::zend::ClassEntry> {
+                Self::extern_class_entry().get()
+            }
+
+            /// Wraps the given object, returning it back if it is not an
+            /// instance of the class.
+            pub fn from_object(
+                object: ::ext_php_rs::boxed::ZBox<::ext_php_rs::types::ZendObject>,
+            ) -> ::std::result::Result<Self, ::ext_php_rs::boxed::ZBox<::ext_php_rs::types::ZendObject>> {
+                match Self::extern_class_entry().with(|ce| object.instance_of(ce)) {
+                    Ok(true) => Ok(Self { object }),
+                    _ => Err(object),
+                }
+            }
+
+            /// Returns the wrapped object.
+            #[must_use]
+            pub fn as_object(&self) -> &::ext_php_rs::types::ZendObject {
+                &self.object
+            }
+
+            /// Returns the wrapped object.
+            #[must_use]
+            pub fn into_object(self) -> ::ext_php_rs::boxed::ZBox<::ext_php_rs::types::ZendObject> {
+                self.object
+            }
+
+            #(#methods)*
+        }
+
+        impl<'a> ::ext_php_rs::convert::FromZval<'a> for #ident {
+            const TYPE: ::ext_php_rs::flags::DataType =
+                ::ext_php_rs::flags::DataType::Object(Some(#class));
+
+            fn from_zval(zval: &'a ::ext_php_rs::types::Zval) -> ::std::option::Option<Self> {
+                let object = Self::extern_class_entry().instance(zval.object()?)?;
+                Some(Self { object })
+            }
+        }
+
+        impl ::ext_php_rs::convert::IntoZval for #ident {
+            const TYPE: ::ext_php_rs::flags::DataType =
+                ::ext_php_rs::flags::DataType::Object(Some(#class));
+            const NULLABLE: bool = false;
+
+            fn set_zval(
+                self,
+                zv: &mut ::ext_php_rs::types::Zval,
+                persistent: bool,
+            ) -> ::ext_php_rs::error::Result<()> {
+                self.object.set_zval(zv, persistent)
+            }
+        }
+    })
+}
+
+fn parse_method(func: TraitItemFn) -> Result<TokenStream> {
+    let TraitItemFn {
+        attrs,
+        sig,
+        default,
+        ..
+    } = func;
+    if let Some(default) = default {
+        bail!(default => "Methods of `#[php_extern_class]` cannot have a body.");
+    }
+    if let Some(param) = sig.generics.params.first() {
+        bail!(param => "Methods of `#[php_extern_class]` cannot be generic.");
+    }
+    if let Some(asyncness) = sig.asyncness {
+        bail!(asyncness => "Methods of `#[php_extern_class]` cannot be `async`.");
+    }
+
+    let attr = MethodAttributes::from_attributes(&attrs)?;
+    let attrs = attrs
+        .into_iter()
+        .filter(|attr| !attr.path().is_ident("php"))
+        .collect::<Vec<_>>();
+    let name = attr
+        .rename
+        .rename_method(ident_to_php_name(&sig.ident), RenameRule::Camel);
+    validate_php_name(&name, PhpNameContext::Method, sig.ident.span())?;
+
+    let mut receiver = None;
+    let mut inputs = vec![];
+    let mut params = vec![];
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(recv) => {
+                if recv.reference.is_none() {
+                    bail!(recv => "Methods of `#[php_extern_class]` must take `&self` or `&mut self`.");
+                }
+                receiver = Some(recv);
+            }
+            FnArg::Typed(arg) => {
+                let Pat::Ident(pat) = &*arg.pat else {
+                    bail!(arg.pat => "Parameters of `#[php_extern_class]` methods must be identifiers.");
+                };
+                let (ident, ty) = (&pat.ident, &arg.ty);
+                inputs.push(quote! { #ident: #ty });
+                params.push(quote! { &#ident });
+            }
+        }
+    }
+
+    let ret = match &sig.output {
+        ReturnType::Default => None,
+        ReturnType::Type(_, ty) => Some(ty),
+    };
+    let is_constructor = sig.ident == "__construct";
+    if is_constructor {
+        if receiver.is_some() || !ret.is_some_and(|ty| is_ident(ty, "Self")) {
+            bail!(sig => "The constructor of `#[php_extern_class]` must be declared as `fn __construct(...) -> Self`.");
+        }
+        return Ok(quote! {
+            #(#attrs)*
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the class does not exist, a parameter could
+            /// not be converted or the constructor threw an exception.
+            pub fn new(#(#inputs),*) -> ::ext_php_rs::error::Result<Self> {
+                let object = Self::extern_class_entry().instantiate(vec![#(#params),*])?;
+                Ok(Self { object })
+            }
+        });
+    }
+
+    let ident = &sig.ident;
+    let call = match receiver {
+        Some(_) => quote! {
+            ::ext_php_rs::class::ExternClassEntry::call_method(&self.object, #name, vec![#(#params),*])?
+        },
+        None => quote! {
+            Self::extern_class_entry().call_static(#name, vec![#(#params),*])?
+        },
+    };
+    let receiver = receiver.map(|receiver| {
+        let mutability = receiver.mutability;
+        quote! { &#mutability self, }
+    });
+    let (ret_ty, convert) = match ret {
+        None => (quote! { () }, quote! { let _ = retval; Ok(()) }),
+        // The return value is passed on as is if it is not converted.
+        Some(ty) if is_ident(ty, "Zval") => (quote! { #ty }, quote! { Ok(retval) }),
+        Some(ty) => (
+            quote! { #ty },
+            quote! {
+                <#ty as ::ext_php_rs::convert::FromZval>::from_zval(&retval)
+                    .ok_or_else(|| ::ext_php_rs::error::Error::ZvalConversion(retval.get_type()))
+            },
+        ),
+    };
+    Ok(quote! {
+        #(#attrs)*
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the method does not exist, a parameter could not
+        /// be converted, the method threw an exception or its return value
+        /// could not be converted.
+        pub fn #ident(#receiver #(#inputs),*) -> ::ext_php_rs::error::Result<#ret_ty> {
+            let retval = #call;
+            #convert
+        }
+    })
+}
+
+/// Returns whether the type is a path ending in the given identifier.
+fn is_ident(ty: &Type, ident: &str) -> bool {
+    matches!(
+        ty,
+        Type::Path(path)
+            if path.qself.is_none() && path.path.segments.last().is_some_and(|segment| segment.ident == ident)
+    )
+}
@@ -1,17 +1,25 @@
 use std::collections::HashMap;
 
-use darling::{FromAttributes, ToTokens};
+use darling::util::Flag;
+use darling::{FromAttributes, FromMeta, ToTokens};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
+use syn::parse::Parser as _;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned as _;
-use syn::{Expr, FnArg, GenericArgument, ItemFn, PatType, PathArguments, Type, TypePath};
+use syn::visit_mut::VisitMut as _;
+use syn::{
+    Expr, FnArg, GenericArgument, GenericParam, ItemFn, PatType, PathArguments, Signature, Token,
+    Type, TypePath,
+};
 
 use crate::helpers::get_docs;
 use crate::parsing::{
-    PhpNameContext, PhpRename, RenameRule, Visibility, ident_to_php_name, validate_php_name,
+    PhpNameContext, PhpRename, RenameRule, Visibility, apply_namespace, ident_to_php_name,
+    parse_requirements, validate_php_name,
 };
 use crate::prelude::*;
-use crate::syn_ext::DropLifetimes;
+use crate::syn_ext::{DropLifetimes, SubstituteGenerics};
 
 /// Checks if the return type is a reference to Self (`&Self` or `&mut Self`).
 /// This is used to detect methods that return `$this` in PHP.
@@ -29,6 +37,27 @@ fn returns_self_ref(output: Option<&Type>) -> bool {
     false
 }
 
+/// Checks if the body of a function ends in a call to `bailout()` or
+/// `exit()`, in which case the function never returns to PHP.
+pub fn ends_in_exit(block: &syn::Block) -> bool {
+    let Some(syn::Stmt::Expr(expr, _)) = block.stmts.last() else {
+        return false;
+    };
+    match expr {
+        Expr::Call(call) => matches!(
+            &*call.func,
+            Expr::Path(path) if path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "bailout" || segment.ident == "exit")
+        ),
+        Expr::Unsafe(expr) => ends_in_exit(&expr.block),
+        Expr::Block(expr) => ends_in_exit(&expr.block),
+        _ => false,
+    }
+}
+
 /// Checks if the return type is `Self` (not a reference).
 /// This is used to detect methods that return a new instance of the same class.
 fn returns_self(output: Option<&Type>) -> bool {
@@ -60,29 +89,132 @@ pub fn wrap(input: &syn::Path) -> Result<TokenStream> {
 struct PhpFunctionAttribute {
     #[darling(flatten)]
     rename: PhpRename,
+    namespace: Option<String>,
     defaults: HashMap<Ident, Expr>,
     optional: Option<Ident>,
     vis: Option<Visibility>,
+    nullable: Flag,
+    not_null: Flag,
+    mixed: Flag,
+    #[darling(multiple)]
+    instantiate: Vec<Instantiation>,
+    #[darling(multiple)]
+    alias: Vec<String>,
+    #[darling(multiple)]
+    requires_ext: Vec<String>,
+    min_php: Option<String>,
     attrs: Vec<syn::Attribute>,
 }
 
+/// A concrete instantiation of a generic function, e.g.
+/// `#[php(instantiate(name = "sum_ints", types = "i64"))]`.
+#[derive(FromMeta, Debug)]
+struct Instantiation {
+    /// Name of the generated function. Used as the PHP function name and as
+    /// the Rust identifier passed to `wrap_function!`.
+    name: String,
+    /// Comma separated list of concrete types, in the order of the generic
+    /// type parameters.
+    types: String,
+}
+
+/// Attributes applied to a single function argument, e.g.
+/// `fn foo(#[php(not_null)] a: Option<i32>)`.
+#[derive(FromAttributes, Default, Debug)]
+#[darling(default, attributes(php))]
+pub struct PhpArgAttribute {
+    nullable: Flag,
+    not_null: Flag,
+    coerce: Flag,
+}
+
+/// Behaviour of an argument overridden through its `#[php]` attributes.
+#[derive(Default, Debug, Clone)]
+pub struct ArgOverride {
+    /// Explicit nullability set through `#[php(nullable)]` or
+    /// `#[php(not_null)]`.
+    pub nullable: Option<bool>,
+    /// Whether the value is coerced into a string before it is converted, set
+    /// through `#[php(coerce)]`.
+    pub coerce: bool,
+}
+
+impl PhpArgAttribute {
+    /// Parses the `#[php]` attributes of the given arguments, removing them
+    /// from the arguments as they are not valid outside of the macro.
+    ///
+    /// Returns a map of argument identifiers to their overrides.
+    pub fn take_from_fnargs<'b>(
+        args: impl Iterator<Item = &'b mut FnArg>,
+    ) -> Result<HashMap<Ident, ArgOverride>> {
+        let mut overrides = HashMap::new();
+        for arg in args {
+            let FnArg::Typed(PatType { attrs, pat, .. }) = arg else {
+                continue;
+            };
+            let attr = Self::from_attributes(attrs)?;
+            attrs.retain(|attr| !attr.path().is_ident("php"));
+
+            let arg_override = ArgOverride {
+                nullable: nullability_override(&attr.nullable, &attr.not_null)?,
+                coerce: attr.coerce.is_present(),
+            };
+            if let syn::Pat::Ident(syn::PatIdent { ident, .. }) = &**pat {
+                overrides.insert(ident.clone(), arg_override);
+            }
+        }
+        Ok(overrides)
+    }
+}
+
+/// Resolves the `nullable` and `not_null` flags into an explicit nullability
+/// override. Returns [`None`] if neither flag is present, in which case the
+/// nullability is derived from the Rust type.
+pub fn nullability_override(nullable: &Flag, not_null: &Flag) -> Result<Option<bool>> {
+    match (nullable.is_present(), not_null.is_present()) {
+        (true, true) => Err(syn::Error::new(
+            not_null.span(),
+            "`nullable` and `not_null` cannot be used together.",
+        )),
+        (true, false) => Ok(Some(true)),
+        (false, true) => Ok(Some(false)),
+        (false, false) => Ok(None),
+    }
+}
+
 pub fn parser(mut input: ItemFn) -> Result<TokenStream> {
     let php_attr = PhpFunctionAttribute::from_attributes(&input.attrs)?;
     input.attrs.retain(|attr| !attr.path().is_ident("php"));
 
-    let args = Args::parse_from_fnargs(input.sig.inputs.iter(), php_attr.defaults)?;
-    if let Some(ReceiverArg { span, .. }) = args.receiver {
-        bail!(span => "Receiver arguments are invalid on PHP functions. See `#[php_impl]`.");
-    }
+    let overrides = PhpArgAttribute::take_from_fnargs(input.sig.inputs.iter_mut())?;
 
-    let docs = get_docs(&php_attr.attrs)?;
+    if !php_attr.instantiate.is_empty() || input.sig.generics.type_params().next().is_some() {
+        if !php_attr.alias.is_empty() {
+            bail!(input.sig.ident => "`alias` cannot be used on generic functions, as every instantiation would register the same aliases.");
+        }
+        let instantiations = monomorphize(&input, &php_attr, &overrides)?;
+        return Ok(quote! {
+            #input
+            #instantiations
+        });
+    }
 
     let func_name = php_attr
         .rename
         .rename(ident_to_php_name(&input.sig.ident), RenameRule::Snake);
     validate_php_name(&func_name, PhpNameContext::Function, input.sig.ident.span())?;
-    let func = Function::new(&input.sig, func_name, args, php_attr.optional, docs);
-    let function_impl = func.php_function_impl();
+    let func_name = apply_namespace(
+        php_attr.namespace.as_deref(),
+        func_name,
+        input.sig.ident.span(),
+    )?;
+    let function_impl = function_impl(
+        &input.sig,
+        func_name,
+        &php_attr,
+        overrides,
+        ends_in_exit(&input.block),
+    )?;
 
     Ok(quote! {
         #input
@@ -90,6 +222,140 @@ pub fn parser(mut input: ItemFn) -> Result<TokenStream> {
     })
 }
 
+/// Generates the `PhpFunction` implementation for the function with the given
+/// signature.
+fn function_impl(
+    sig: &Signature,
+    name: String,
+    php_attr: &PhpFunctionAttribute,
+    overrides: HashMap<Ident, ArgOverride>,
+    diverges: bool,
+) -> Result<TokenStream> {
+    let args = Args::parse_from_fnargs(sig.inputs.iter(), php_attr.defaults.clone(), overrides)?;
+    if let Some(ReceiverArg { span, .. }) = args.receiver {
+        bail!(span => "Receiver arguments are invalid on PHP functions. See `#[php_impl]`.");
+    }
+
+    let docs = get_docs(&php_attr.attrs)?;
+
+    let mut aliases = Vec::with_capacity(php_attr.alias.len());
+    for alias in &php_attr.alias {
+        validate_php_name(alias, PhpNameContext::Function, sig.ident.span())?;
+        // Aliases which are already namespaced are used as is.
+        aliases.push(if alias.contains('\\') {
+            alias.clone()
+        } else {
+            apply_namespace(
+                php_attr.namespace.as_deref(),
+                alias.clone(),
+                sig.ident.span(),
+            )?
+        });
+    }
+
+    let mut func = Function::new(sig, name, args, php_attr.optional.clone(), docs);
+    func.nullable = nullability_override(&php_attr.nullable, &php_attr.not_null)?;
+    func.mixed = php_attr.mixed.is_present();
+    func.diverges |= diverges;
+    func.aliases = aliases;
+    func.requirements = parse_requirements(
+        &php_attr.requires_ext,
+        php_attr.min_php.as_deref(),
+        sig.ident.span(),
+    )?;
+    Ok(func.php_function_impl())
+}
+
+/// Generates a non-generic wrapper function and its `PhpFunction`
+/// implementation for every `#[php(instantiate(...))]` of a generic function.
+fn monomorphize(
+    input: &ItemFn,
+    php_attr: &PhpFunctionAttribute,
+    overrides: &HashMap<Ident, ArgOverride>,
+) -> Result<TokenStream> {
+    let ident = &input.sig.ident;
+    let generics = &input.sig.generics;
+    if generics.const_params().next().is_some() {
+        bail!(generics => "Const generic parameters are not supported on PHP functions.");
+    }
+    let params: Vec<&Ident> = generics.type_params().map(|param| &param.ident).collect();
+    if params.is_empty() {
+        bail!(ident => "`instantiate` can only be used on generic functions.");
+    }
+    if php_attr.instantiate.is_empty() {
+        bail!(generics => "Generic functions must list their concrete instantiations, e.g. `#[php(instantiate(name = \"...\", types = \"...\"))]`.");
+    }
+
+    let vis = &input.vis;
+    let diverges = ends_in_exit(&input.block);
+    let mut result = TokenStream::new();
+    for instantiation in &php_attr.instantiate {
+        let Ok(name) = syn::parse_str::<Ident>(&instantiation.name) else {
+            bail!(ident => "Instantiation name `{}` is not a valid Rust identifier.", instantiation.name);
+        };
+        validate_php_name(&instantiation.name, PhpNameContext::Function, ident.span())?;
+        let php_name = apply_namespace(
+            php_attr.namespace.as_deref(),
+            instantiation.name.clone(),
+            ident.span(),
+        )?;
+        let types = Punctuated::<Type, Token![,]>::parse_terminated
+            .parse_str(&instantiation.types)
+            .map_err(
+                |e| err!(ident => "Invalid types for instantiation `{}`: {e}", instantiation.name),
+            )?;
+        if types.len() != params.len() {
+            bail!(ident => "Instantiation `{}` has {} type(s), but `{}` has {} generic type parameter(s).", instantiation.name, types.len(), ident, params.len());
+        }
+
+        let substitutions = params
+            .iter()
+            .map(|param| (*param).clone())
+            .zip(types.iter().cloned())
+            .collect::<HashMap<_, _>>();
+
+        let mut sig = input.sig.clone();
+        sig.ident = name;
+        sig.generics.params = sig
+            .generics
+            .params
+            .into_iter()
+            .filter(|param| matches!(param, GenericParam::Lifetime(_)))
+            .collect();
+        sig.generics.where_clause = None;
+        for arg in &mut sig.inputs {
+            if let FnArg::Typed(PatType { pat, .. }) = arg
+                && let syn::Pat::Ident(pat) = &mut **pat
+            {
+                pat.mutability = None;
+            }
+        }
+        SubstituteGenerics(&substitutions).visit_signature_mut(&mut sig);
+
+        let arg_names = sig.inputs.iter().filter_map(|arg| match arg {
+            FnArg::Typed(PatType { pat, .. }) => match &**pat {
+                syn::Pat::Ident(pat) => Some(&pat.ident),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        });
+        let types = types.iter();
+        let awaited = sig.asyncness.is_some().then(|| quote! { .await });
+        let function_impl = function_impl(&sig, php_name, php_attr, overrides.clone(), diverges)?;
+
+        result.extend(quote! {
+            #[doc = concat!("Instantiation of [`", stringify!(#ident), "`] exported to PHP.")]
+            #[inline]
+            #vis #sig {
+                #ident::<#(#types),*>(#(#arg_names),*)#awaited
+            }
+            #function_impl
+        });
+    }
+
+    Ok(result)
+}
+
 #[derive(Debug)]
 pub struct Function<'a> {
     /// Identifier of the Rust function associated with the function.
@@ -104,6 +370,23 @@ pub struct Function<'a> {
     pub optional: Option<Ident>,
     /// Doc comments for the function.
     pub docs: Vec<String>,
+    /// Explicit nullability of the return type. If [`None`], the nullability
+    /// is taken from the `IntoZval` implementation of the return type.
+    pub nullable: Option<bool>,
+    /// Whether the return type is declared as `mixed`, regardless of the Rust
+    /// return type.
+    pub mixed: bool,
+    /// Whether the function never returns, e.g. as it ends in a call to
+    /// `bailout()` or `exit()`. The return type is declared as `never`.
+    pub diverges: bool,
+    /// Whether the function is an `async fn`. The returned future is driven to
+    /// completion by the executor set in `ext_php_rs::async_`.
+    pub asyncness: bool,
+    /// Additional PHP names the function is registered under.
+    pub aliases: Vec<String>,
+    /// `Requirement`s which must be met at module startup for the function to
+    /// be registered.
+    pub requirements: Vec<TokenStream>,
 }
 
 #[derive(Debug)]
@@ -112,9 +395,49 @@ pub enum CallType<'a> {
     Method {
         class: &'a syn::Path,
         receiver: MethodReceiver,
+        /// Generic parameters of the `impl` block, which the generated handlers
+        /// are generic over.
+        generics: &'a syn::Generics,
     },
 }
 
+impl CallType<'_> {
+    /// Returns the generic parameters of the `impl` block of methods.
+    fn generics(&self) -> Option<&syn::Generics> {
+        match self {
+            Self::Function => None,
+            Self::Method { generics, .. } => Some(generics),
+        }
+    }
+}
+
+/// Returns the path of a class usable in expressions, i.e. with generic
+/// arguments written as `Class::<T>`.
+pub fn expr_path(class: &syn::Path) -> syn::Path {
+    let mut class = class.clone();
+    for segment in &mut class.segments {
+        if let syn::PathArguments::AngleBracketed(args) = &mut segment.arguments {
+            args.colon2_token.get_or_insert_with(Default::default);
+        }
+    }
+    class
+}
+
+/// Returns the generic parameters of an `impl` block, the turbofish to
+/// instantiate a handler with them and its where clause.
+fn handler_generics(generics: Option<&syn::Generics>) -> (TokenStream, TokenStream, TokenStream) {
+    let Some(generics) = generics else {
+        return (quote! {}, quote! {}, quote! {});
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish();
+    (
+        quote! { #impl_generics },
+        quote! { #turbofish },
+        quote! { #where_clause },
+    )
+}
+
 /// Type of receiver on the method.
 #[derive(Debug)]
 pub enum MethodReceiver {
@@ -152,6 +475,12 @@ impl<'a> Function<'a> {
             },
             optional,
             docs,
+            nullable: None,
+            mixed: false,
+            diverges: matches!(&sig.output, syn::ReturnType::Type(_, ty) if matches!(**ty, Type::Never(_))),
+            asyncness: sig.asyncness.is_some(),
+            aliases: vec![],
+            requirements: vec![],
         }
     }
 
@@ -227,6 +556,8 @@ impl<'a> Function<'a> {
                 .docs(&[#(#docs),*])
             }
         };
+        let aliases = &self.aliases;
+        let requirements = &self.requirements;
 
         // Static methods cannot return &Self or &mut Self
         if returns_self_ref(self.output)
@@ -268,6 +599,14 @@ impl<'a> Function<'a> {
                     e.throw().expect("Failed to throw PHP exception.");
                 }
             }
+        } else if matches!(self.output, Some(Type::Never(_))) {
+            quote! {
+                #(#arg_declarations)*
+                let _ = retval;
+                let _: () = {
+                    #result
+                };
+            }
         } else {
             quote! {
                 use ::ext_php_rs::convert::IntoZval;
@@ -284,13 +623,15 @@ impl<'a> Function<'a> {
             }
         };
 
+        let (generics, turbofish, where_clause) = handler_generics(call_type.generics());
+
         quote! {
             ::ext_php_rs::builders::FunctionBuilder::new(#name, {
                 ::ext_php_rs::zend_fastcall! {
-                    extern fn handler(
+                    extern fn handler #generics(
                         ex: &mut ::ext_php_rs::zend::ExecuteData,
                         retval: &mut ::ext_php_rs::types::Zval,
-                    ) {
+                    ) #where_clause {
                         use ::ext_php_rs::zend::try_catch;
                         use ::std::panic::AssertUnwindSafe;
 
@@ -307,55 +648,89 @@ impl<'a> Function<'a> {
                         }
                     }
                 }
-                handler
+                handler #turbofish
             })
             #(.arg(#required_args))*
             .not_required()
             #(.arg(#not_required_args))*
             #returns
             #docs
+            #(.alias(#aliases))*
+            #(.requires(#requirements))*
         }
     }
 
     fn build_returns(&self, call_type: Option<&CallType>) -> Option<TokenStream> {
-        self.output.cloned().map(|mut output| {
-            output.drop_lifetimes();
+        // An explicit `#[php(nullable)]` or `#[php(not_null)]` takes precedence
+        // over the nullability of the return type.
+        let nullable = |ty: TokenStream| match self.nullable {
+            Some(nullable) => quote! { #nullable },
+            None => quote! { <#ty as ::ext_php_rs::convert::IntoZval>::NULLABLE },
+        };
 
-            // If returning &Self or &mut Self from a method, use the class type
-            // for return type information since we return `this` (ZendClassObject)
-            if returns_self_ref(self.output)
-                && let Some(CallType::Method { class, .. }) = call_type
-            {
-                return quote! {
-                    .returns(
-                        <&mut ::ext_php_rs::types::ZendClassObject<#class> as ::ext_php_rs::convert::IntoZval>::TYPE,
-                        false,
-                        <&mut ::ext_php_rs::types::ZendClassObject<#class> as ::ext_php_rs::convert::IntoZval>::NULLABLE,
-                    )
-                };
-            }
+        if self.mixed {
+            return Some(quote! {
+                .returns(::ext_php_rs::flags::DataType::Mixed, false, false)
+            });
+        }
+        if self.diverges {
+            return Some(quote! {
+                .returns(::ext_php_rs::flags::DataType::Never, false, false)
+            });
+        }
+        // Functions without a return type return `null`, which is declared as
+        // `void`. Destructors cannot declare a return type, and interface
+        // methods (without a call type) are left untyped, so that classes
+        // implementing them do not have to declare `void`.
+        let Some(output) = self.output else {
+            let untyped = call_type.is_none() || self.name.eq_ignore_ascii_case("__destruct");
+            return (!untyped).then(|| {
+                quote! {
+                    .returns(::ext_php_rs::flags::DataType::Void, false, false)
+                }
+            });
+        };
 
-            // If returning Self (new instance) from a method, replace Self with
-            // the actual class type since Self won't resolve in generated code
-            if returns_self(self.output)
-                && let Some(CallType::Method { class, .. }) = call_type
-            {
-                return quote! {
-                    .returns(
-                        <#class as ::ext_php_rs::convert::IntoZval>::TYPE,
-                        false,
-                        <#class as ::ext_php_rs::convert::IntoZval>::NULLABLE,
-                    )
-                };
-            }
+        let mut output = output.clone();
+        output.drop_lifetimes();
 
-            quote! {
+        // If returning &Self or &mut Self from a method, use the class type
+        // for return type information since we return `this` (ZendClassObject)
+        if returns_self_ref(self.output)
+            && let Some(CallType::Method { class, .. }) = call_type
+        {
+            let nullable = nullable(quote! { &mut ::ext_php_rs::types::ZendClassObject<#class> });
+            return Some(quote! {
                 .returns(
-                    <#output as ::ext_php_rs::convert::IntoZval>::TYPE,
+                    <&mut ::ext_php_rs::types::ZendClassObject<#class> as ::ext_php_rs::convert::IntoZval>::TYPE,
                     false,
-                    <#output as ::ext_php_rs::convert::IntoZval>::NULLABLE,
+                    #nullable,
                 )
-            }
+            });
+        }
+
+        // If returning Self (new instance) from a method, replace Self with
+        // the actual class type since Self won't resolve in generated code
+        if returns_self(self.output)
+            && let Some(CallType::Method { class, .. }) = call_type
+        {
+            let nullable = nullable(quote! { #class });
+            return Some(quote! {
+                .returns(
+                    <#class as ::ext_php_rs::convert::IntoZval>::TYPE,
+                    false,
+                    #nullable,
+                )
+            });
+        }
+
+        let nullable = nullable(quote! { #output });
+        Some(quote! {
+            .returns(
+                <#output as ::ext_php_rs::convert::IntoZval>::TYPE,
+                false,
+                #nullable,
+            )
         })
     }
 
@@ -382,8 +757,8 @@ impl<'a> Function<'a> {
             }
         });
 
-        let arg_accessors = self.args.typed.iter().map(|arg| {
-            arg.accessor(|e| {
+        let arg_accessors = self.args.typed.iter().enumerate().map(|(i, arg)| {
+            arg.accessor(i + 1, |e| {
                 quote! {
                     #e.throw().expect("Failed to throw PHP exception.");
                     return;
@@ -394,21 +769,46 @@ impl<'a> Function<'a> {
         // Check if this method returns &Self or &mut Self
         let returns_this = returns_self_ref(self.output);
 
-        match call_type {
-            CallType::Function => quote! {
-                let parse = ex.parser()
-                    #(.arg(&mut #required_arg_names))*
-                    .not_required()
-                    #(.arg(&mut #not_required_arg_names))*
-                    .parse();
-                if parse.is_err() {
-                    return;
+        // Futures returned by `async fn`s are driven to completion before the
+        // result is converted, throwing if the executor fails.
+        let complete = |call: TokenStream| {
+            if self.asyncness {
+                quote! {
+                    match ::ext_php_rs::async_::block_on(#call) {
+                        Ok(result) => result,
+                        Err(e) => {
+                            let e: ::ext_php_rs::exception::PhpException = e.into();
+                            e.throw().expect("Failed to throw PHP exception.");
+                            return;
+                        }
+                    }
                 }
-                #(#variadic_bindings)*
+            } else {
+                call
+            }
+        };
 
-                #ident(#({#arg_accessors}),*)
-            },
-            CallType::Method { class, receiver } => {
+        match call_type {
+            CallType::Function => {
+                let call = complete(quote! { #ident(#({#arg_accessors}),*) });
+                quote! {
+                    let parse = ex.parser()
+                        #(.arg(&mut #required_arg_names))*
+                        .not_required()
+                        #(.arg(&mut #not_required_arg_names))*
+                        .parse();
+                    if parse.is_err() {
+                        return;
+                    }
+                    #(#variadic_bindings)*
+
+                    #call
+                }
+            }
+            CallType::Method {
+                class, receiver, ..
+            } => {
+                let class_expr = expr_path(class);
                 let this = match receiver {
                     MethodReceiver::Static => quote! {
                         let parse = ex.parser();
@@ -431,25 +831,28 @@ impl<'a> Function<'a> {
                 // (we'll use `this` directly in the handler)
                 let call = match (receiver, returns_this) {
                     (MethodReceiver::Static, _) => {
-                        quote! { #class::#ident(#({#arg_accessors}),*) }
+                        complete(quote! { #class_expr::#ident(#({#arg_accessors}),*) })
                     }
                     (MethodReceiver::Class, true) => {
-                        quote! { let _ = this.#ident(#({#arg_accessors}),*); }
+                        let call = complete(quote! { this.#ident(#({#arg_accessors}),*) });
+                        quote! { let _ = #call; }
                     }
                     (MethodReceiver::Class, false) => {
-                        quote! { this.#ident(#({#arg_accessors}),*) }
+                        complete(quote! { this.#ident(#({#arg_accessors}),*) })
                     }
                     (MethodReceiver::ZendClassObject, true) => {
                         // Explicit scope helps with mutable borrow lifetime when
                         // the method returns `&mut Self`
+                        let call =
+                            complete(quote! { #class_expr::#ident(this, #({#arg_accessors}),*) });
                         quote! {
                             {
-                                let _ = #class::#ident(this, #({#arg_accessors}),*);
+                                let _ = #call;
                             }
                         }
                     }
                     (MethodReceiver::ZendClassObject, false) => {
-                        quote! { #class::#ident(this, #({#arg_accessors}),*) }
+                        complete(quote! { #class_expr::#ident(this, #({#arg_accessors}),*) })
                     }
                 };
 
@@ -498,9 +901,12 @@ impl<'a> Function<'a> {
     pub fn constructor_meta(
         &self,
         class: &syn::Path,
+        generics: Option<&syn::Generics>,
         visibility: Option<&Visibility>,
     ) -> TokenStream {
         let ident = self.ident;
+        let class_expr = expr_path(class);
+        let (generics, turbofish, where_clause) = handler_generics(generics);
         let (required, not_required) = self.args.split_args(self.optional.as_ref());
         let required_args = required
             .iter()
@@ -531,8 +937,9 @@ impl<'a> Function<'a> {
                 None
             }
         });
-        let arg_accessors = self.args.typed.iter().map(|arg| {
+        let arg_accessors = self.args.typed.iter().enumerate().map(|(i, arg)| {
             arg.accessor(
+                i + 1,
                 |e| quote! { return ::ext_php_rs::class::ConstructorResult::Exception(#e); },
             )
         });
@@ -547,7 +954,7 @@ impl<'a> Function<'a> {
         quote! {
             ::ext_php_rs::class::ConstructorMeta {
                 constructor: {
-                    fn inner(ex: &mut ::ext_php_rs::zend::ExecuteData) -> ::ext_php_rs::class::ConstructorResult<#class> {
+                    fn inner #generics(ex: &mut ::ext_php_rs::zend::ExecuteData) -> ::ext_php_rs::class::ConstructorResult<#class> #where_clause {
                         use ::ext_php_rs::zend::try_catch;
                         use ::std::panic::AssertUnwindSafe;
 
@@ -565,7 +972,7 @@ impl<'a> Function<'a> {
                                 return ::ext_php_rs::class::ConstructorResult::ArgError;
                             }
                             #(#variadic_bindings)*
-                            #class::#ident(#({#arg_accessors}),*).into()
+                            #class_expr::#ident(#({#arg_accessors}),*).into()
                         }));
 
                         // If there was a bailout, run BailoutGuard cleanups and re-trigger
@@ -577,10 +984,10 @@ impl<'a> Function<'a> {
                             }
                         }
                     }
-                    inner
+                    inner #turbofish
                 },
                 build_fn: {
-                    fn inner(func: ::ext_php_rs::builders::FunctionBuilder) -> ::ext_php_rs::builders::FunctionBuilder {
+                    fn inner #generics(func: ::ext_php_rs::builders::FunctionBuilder) -> ::ext_php_rs::builders::FunctionBuilder #where_clause {
                         func
                             .docs(&[#(#docs),*])
                             #(.arg(#required_args))*
@@ -588,7 +995,7 @@ impl<'a> Function<'a> {
                             #(.arg(#not_required_args))*
                             #variadic
                     }
-                    inner
+                    inner #turbofish
                 },
                 flags: #flags
             }
@@ -598,7 +1005,7 @@ impl<'a> Function<'a> {
 
 #[derive(Debug)]
 pub struct ReceiverArg {
-    pub _mutable: bool,
+    pub mutable: bool,
     pub span: Span,
 }
 
@@ -607,6 +1014,16 @@ pub struct TypedArg<'a> {
     pub name: &'a Ident,
     pub ty: Type,
     pub nullable: bool,
+    /// Explicit nullability set through `#[php(nullable)]` or
+    /// `#[php(not_null)]`. Only affects the argument info, not how the value
+    /// is extracted.
+    pub nullable_override: Option<bool>,
+    /// Whether the value is coerced into a string before it is converted, set
+    /// through `#[php(coerce)]`.
+    pub coerce: bool,
+    /// Whether the argument is a `MaybeUndef<T>`, which is optional and
+    /// distinguishes an omitted argument from `null`.
+    pub maybe_undef: bool,
     pub default: Option<Expr>,
     pub as_ref: bool,
     pub variadic: bool,
@@ -622,6 +1039,7 @@ impl<'a> Args<'a> {
     pub fn parse_from_fnargs(
         args: impl Iterator<Item = &'a FnArg>,
         mut defaults: HashMap<Ident, Expr>,
+        mut overrides: HashMap<Ident, ArgOverride>,
     ) -> Result<Self> {
         let mut result = Self {
             receiver: None,
@@ -636,7 +1054,7 @@ impl<'a> Args<'a> {
                         bail!(receiver => "Too many receivers specified.")
                     }
                     result.receiver.replace(ReceiverArg {
-                        _mutable: receiver.mutability.is_some(),
+                        mutable: receiver.mutability.is_some(),
                         span: receiver.span(),
                     });
                 }
@@ -647,12 +1065,20 @@ impl<'a> Args<'a> {
 
                     // If the variable is `&[&Zval]` treat it as the variadic argument.
                     let default = defaults.remove(ident);
-                    let nullable = type_is_nullable(ty.as_ref())?;
+                    let maybe_undef = maybe_undef_inner(ty.as_ref());
+                    let nullable = type_is_nullable(maybe_undef.unwrap_or(ty.as_ref()))?;
                     let (variadic, as_ref, ty) = Self::parse_typed(ty);
+                    let arg_override = overrides.remove(ident).unwrap_or_default();
+                    if arg_override.coerce && (variadic || maybe_undef.is_some()) {
+                        bail!(pat => "`coerce` cannot be used on variadic or `MaybeUndef` arguments.");
+                    }
                     result.typed.push(TypedArg {
                         name: ident,
                         ty,
                         nullable,
+                        nullable_override: arg_override.nullable,
+                        coerce: arg_override.coerce,
+                        maybe_undef: maybe_undef.is_some(),
                         default,
                         as_ref,
                         variadic,
@@ -729,8 +1155,9 @@ impl<'a> Args<'a> {
     pub fn split_args(&self, optional: Option<&Ident>) -> (&[TypedArg<'a>], &[TypedArg<'a>]) {
         let mut mid = None;
         for (i, arg) in self.typed.iter().enumerate() {
-            // An argument is optional if it's nullable (Option<T>) or has a default value.
-            let is_optional = arg.nullable || arg.default.is_some();
+            // An argument is optional if it's nullable (Option<T>), may be undefined
+            // (MaybeUndef<T>) or has a default value.
+            let is_optional = arg.nullable || arg.maybe_undef || arg.default.is_some();
             if let Some(optional) = optional {
                 if optional == arg.name {
                     mid.replace(i);
@@ -785,7 +1212,7 @@ impl TypedArg<'_> {
     fn arg_builder(&self) -> TokenStream {
         let name = ident_to_php_name(self.name);
         let ty = self.clean_ty();
-        let null = if self.nullable {
+        let null = if self.nullable_override.unwrap_or(self.nullable) {
             Some(quote! { .allow_null() })
         } else {
             None
@@ -811,10 +1238,70 @@ impl TypedArg<'_> {
         }
     }
 
-    /// Get the accessor used to access the value of the argument.
-    fn accessor(&self, bail_fn: impl Fn(TokenStream) -> TokenStream) -> TokenStream {
+    /// Get the accessor used to access the value of the argument, which is
+    /// the `arg_num`th argument of the function, starting at 1.
+    fn accessor(
+        &self,
+        arg_num: usize,
+        bail_fn: impl Fn(TokenStream) -> TokenStream,
+    ) -> TokenStream {
+        let accessor = self.value_accessor(&bail_fn);
+        if !self.coerce {
+            return accessor;
+        }
+        // Non-string values are converted in place like PHP does for string
+        // parameters in coercive typing mode, before the usual conversion.
+        let name = self.name;
+        let bail = bail_fn(quote! {
+            match err {
+                ::ext_php_rs::error::Error::TypeMismatch { .. } => {
+                    ::ext_php_rs::exception::PhpException::argument_type_error(
+                        #arg_num,
+                        stringify!(#name),
+                        "string",
+                        zval,
+                    )
+                }
+                err => ::ext_php_rs::exception::PhpException::from(err),
+            }
+        });
+        quote! {{
+            if let Some(zval) = #name.zval() {
+                if !zval.is_null() && !zval.is_string() {
+                    match zval.coerce_string() {
+                        Ok(str) => zval.set_zend_string(str),
+                        Err(err) => {
+                            #bail;
+                        }
+                    }
+                }
+            }
+            #accessor
+        }}
+    }
+
+    /// Returns a token stream which converts the argument into its Rust type.
+    fn value_accessor(&self, bail_fn: impl Fn(TokenStream) -> TokenStream) -> TokenStream {
         let name = self.name;
-        if let Some(default) = &self.default {
+        if self.maybe_undef {
+            let bail_invalid = bail_fn(quote! {
+                ::ext_php_rs::exception::PhpException::default(
+                    concat!("Invalid value given for argument `", stringify!(#name), "`.").into()
+                )
+            });
+            quote! {
+                match #name.zval() {
+                    Some(_) => match #name.val() {
+                        Some(val) => val,
+                        None => {
+                            #bail_invalid
+                        }
+                    },
+                    // Argument was omitted
+                    None => ::ext_php_rs::types::MaybeUndef::Undef,
+                }
+            }
+        } else if let Some(default) = &self.default {
             if self.nullable {
                 // For nullable types with defaults, null is acceptable
                 quote! {
@@ -999,6 +1486,24 @@ fn expr_to_php_stub(expr: &Expr) -> String {
     }
 }
 
+/// Returns the type wrapped by a `MaybeUndef<T>`, if the type is one.
+fn maybe_undef_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let seg = path.path.segments.last()?;
+    if seg.ident != "MaybeUndef" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
 /// Returns true if the given type is nullable in PHP (i.e., it's an
 /// `Option<T>`).
 ///
@@ -3,10 +3,12 @@ use darling::util::Flag;
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::collections::{HashMap, HashSet};
-use syn::{Expr, Ident, ItemImpl};
+use syn::{Expr, Ident, ItemImpl, parse_quote};
 
 use crate::constant::PhpConstAttribute;
-use crate::function::{Args, CallType, Function, MethodReceiver};
+use crate::function::{
+    Args, CallType, Function, MethodReceiver, PhpArgAttribute, ends_in_exit, nullability_override,
+};
 use crate::helpers::get_docs;
 use crate::parsing::{
     PhpNameContext, PhpRename, RenameRule, Visibility, ident_to_php_name, validate_php_name,
@@ -47,8 +49,19 @@ pub fn parser(mut input: ItemImpl) -> Result<TokenStream> {
         }
     };
 
+    // Methods of generic classes are registered for each instantiation of the
+    // class, see the `instantiate` attribute of `#[php_class]`.
+    let mut generics = input.generics.clone();
+    if !generics.params.is_empty() {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { #path: ::ext_php_rs::class::RegisteredClass });
+    }
+
     let mut parsed = ParsedImpl::new(
         path,
+        generics,
         args.change_method_case.unwrap_or(RenameRule::Camel),
         args.change_constant_case
             .unwrap_or(RenameRule::ScreamingSnake),
@@ -75,6 +88,12 @@ struct MethodArgs {
     vis: Visibility,
     /// Method type.
     ty: MethodTy,
+    /// Explicit nullability of the return type.
+    nullable: Option<bool>,
+    /// Whether the return type is declared as `mixed`.
+    mixed: bool,
+    /// Additional PHP names the method is registered under.
+    aliases: Vec<String>,
 }
 
 #[derive(FromAttributes, Default, Debug)]
@@ -86,6 +105,11 @@ pub struct PhpFunctionImplAttribute {
     optional: Option<Ident>,
     vis: Option<Visibility>,
     attrs: Vec<syn::Attribute>,
+    nullable: Flag,
+    not_null: Flag,
+    mixed: Flag,
+    #[darling(multiple)]
+    alias: Vec<String>,
     getter: Flag,
     setter: Flag,
     constructor: Flag,
@@ -93,7 +117,7 @@ pub struct PhpFunctionImplAttribute {
 }
 
 impl MethodArgs {
-    fn new(name: String, attr: PhpFunctionImplAttribute) -> Self {
+    fn new(name: String, attr: PhpFunctionImplAttribute) -> Result<Self> {
         let ty = if name == "__construct" || attr.constructor.is_present() {
             MethodTy::Constructor
         } else if attr.getter.is_present() {
@@ -106,19 +130,23 @@ impl MethodArgs {
             MethodTy::Normal
         };
 
-        Self {
+        Ok(Self {
             name,
             optional: attr.optional,
             defaults: attr.defaults,
             vis: attr.vis.unwrap_or(Visibility::Public),
             ty,
-        }
+            nullable: nullability_override(&attr.nullable, &attr.not_null)?,
+            mixed: attr.mixed.is_present(),
+            aliases: attr.alias,
+        })
     }
 }
 
 #[derive(Debug)]
 struct ParsedImpl<'a> {
     path: &'a syn::Path,
+    generics: syn::Generics,
     change_method_case: RenameRule,
     change_constant_case: RenameRule,
     functions: Vec<FnBuilder>,
@@ -168,11 +196,18 @@ impl<'a> ParsedImpl<'a> {
     /// # Parameters
     ///
     /// * `path` - Path of the type the `impl` block is for.
+    /// * `generics` - Generic parameters of the `impl` block.
     /// * `rename_methods` - Rule to rename methods with.
     /// * `rename_constants` - Rule to rename constants with.
-    fn new(path: &'a syn::Path, rename_methods: RenameRule, rename_constants: RenameRule) -> Self {
+    fn new(
+        path: &'a syn::Path,
+        generics: syn::Generics,
+        rename_methods: RenameRule,
+        rename_constants: RenameRule,
+    ) -> Self {
         Self {
             path,
+            generics,
             change_method_case: rename_methods,
             change_constant_case: rename_constants,
             functions: Vec::default(),
@@ -187,6 +222,12 @@ impl<'a> ParsedImpl<'a> {
             match items {
                 syn::ImplItem::Const(c) => {
                     let attr = PhpConstAttribute::from_attributes(&c.attrs)?;
+                    if !attr.requires_ext.is_empty() || attr.min_php.is_some() {
+                        bail!(c => "`requires_ext` and `min_php` cannot be used on class constants, use them on the class instead.");
+                    }
+                    if !self.generics.params.is_empty() {
+                        bail!(c => "Constants are not supported in generic `#[php_impl]` blocks.");
+                    }
                     let name = attr
                         .rename
                         .rename(ident_to_php_name(&c.ident), self.change_constant_case);
@@ -210,19 +251,50 @@ impl<'a> ParsedImpl<'a> {
                     let docs = get_docs(&attr.attrs)?;
                     method.attrs.retain(|attr| !attr.path().is_ident("php"));
 
-                    let opts = MethodArgs::new(name, attr);
-                    let args = Args::parse_from_fnargs(method.sig.inputs.iter(), opts.defaults)?;
+                    let opts = MethodArgs::new(name, attr)?;
+                    let overrides =
+                        PhpArgAttribute::take_from_fnargs(method.sig.inputs.iter_mut())?;
+                    let args = Args::parse_from_fnargs(
+                        method.sig.inputs.iter(),
+                        opts.defaults,
+                        overrides,
+                    )?;
+                    for alias in &opts.aliases {
+                        validate_php_name(alias, PhpNameContext::Method, method.sig.ident.span())?;
+                    }
                     let mut func = Function::new(&method.sig, opts.name, args, opts.optional, docs);
+                    func.nullable = opts.nullable;
+                    func.mixed = opts.mixed;
+                    func.diverges |= ends_in_exit(&method.block);
+                    func.aliases = opts.aliases;
 
                     let mut modifiers: HashSet<MethodModifier> = HashSet::new();
 
                     if matches!(opts.ty, MethodTy::Constructor) {
+                        if let Some(asyncness) = &method.sig.asyncness {
+                            bail!(asyncness => "Constructors cannot be `async`.");
+                        }
+                        if !func.aliases.is_empty() {
+                            bail!(method.sig.ident => "Constructors cannot have aliases.");
+                        }
                         if self.constructor.replace((func, opts.vis.into())).is_some() {
                             bail!(method => "Only one constructor can be provided per class.");
                         }
                     } else {
+                        // The object may be accessed by other code, e.g. another
+                        // fiber, while the method is suspended.
+                        if let Some(asyncness) = &method.sig.asyncness
+                            && (func.args.receiver.as_ref().is_some_and(|r| r.mutable)
+                                || func.args.typed.first().is_some_and(|arg| {
+                                    arg.name == "self_"
+                                        && matches!(&arg.ty, syn::Type::Reference(r) if r.mutability.is_some())
+                                }))
+                        {
+                            bail!(asyncness => "`async` methods cannot take `&mut self`, as the object may be accessed while the method is suspended. Use `&self` instead.");
+                        }
                         let call_type = CallType::Method {
                             class: self.path,
+                            generics: &self.generics,
                             receiver: if func.args.receiver.is_some() {
                                 // `&self` or `&mut self`
                                 MethodReceiver::Class
@@ -269,7 +341,7 @@ impl<'a> ParsedImpl<'a> {
         let constructor = self
             .constructor
             .as_ref()
-            .map(|(func, vis)| func.constructor_meta(self.path, vis.as_ref()))
+            .map(|(func, vis)| func.constructor_meta(self.path, Some(&self.generics), vis.as_ref()))
             .option_tokens();
         let constants = self.constants.iter().map(|c| {
             let name = &c.name;
@@ -280,9 +352,12 @@ impl<'a> ParsedImpl<'a> {
             }
         });
 
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+
         quote! {
-            impl ::ext_php_rs::internal::class::PhpClassImpl<#path>
+            impl #impl_generics ::ext_php_rs::internal::class::PhpClassImpl<#path>
                 for ::ext_php_rs::internal::class::PhpClassImplCollector<#path>
+                #where_clause
             {
                 fn get_methods(self) -> ::std::vec::Vec<
                     (::ext_php_rs::builders::FunctionBuilder<'static>, ::ext_php_rs::flags::MethodFlags)
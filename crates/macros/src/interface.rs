@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use crate::class::ClassEntryAttribute;
 use crate::constant::PhpConstAttribute;
-use crate::function::{Args, Function};
+use crate::function::{Args, Function, PhpArgAttribute, nullability_override};
 use crate::helpers::{CleanPhpAttr, get_docs};
 use darling::FromAttributes;
 use darling::util::Flag;
@@ -12,7 +12,8 @@ use syn::{Expr, Ident, ItemTrait, Path, TraitItem, TraitItemConst, TraitItemFn};
 
 use crate::impl_::{FnBuilder, MethodModifier};
 use crate::parsing::{
-    PhpNameContext, PhpRename, RenameRule, Visibility, ident_to_php_name, validate_php_name,
+    PhpNameContext, PhpRename, RenameRule, Visibility, apply_namespace, ident_to_php_name,
+    parse_requirements, validate_php_name,
 };
 use crate::prelude::*;
 
@@ -23,12 +24,19 @@ const INTERNAL_INTERFACE_NAME_PREFIX: &str = "PhpInterface";
 pub struct TraitAttributes {
     #[darling(flatten)]
     rename: PhpRename,
+    /// The namespace of the PHP interface.
+    namespace: Option<String>,
     /// Rename methods to match the given rule.
     change_method_case: Option<RenameRule>,
     /// Rename constants to match the given rule.
     change_constant_case: Option<RenameRule>,
     #[darling(multiple)]
     extends: Vec<ClassEntryAttribute>,
+    /// Extensions which must be loaded for the interface to be registered.
+    #[darling(multiple)]
+    requires_ext: Vec<String>,
+    /// The minimum PHP version for the interface to be registered.
+    min_php: Option<String>,
     attrs: Vec<syn::Attribute>,
 }
 
@@ -56,6 +64,7 @@ struct InterfaceData<'a> {
     methods: Vec<FnBuilder>,
     constants: Vec<Constant<'a>>,
     docs: Vec<String>,
+    requirements: Vec<TokenStream>,
 }
 
 impl ToTokens for InterfaceData<'_> {
@@ -67,11 +76,19 @@ impl ToTokens for InterfaceData<'_> {
         let methods_sig = &self.methods;
         let constants = &self.constants;
         let docs = &self.docs;
+        let requirements = (!self.requirements.is_empty()).then(|| {
+            let requirements = &self.requirements;
+            quote! {
+                const REQUIREMENTS: &'static [::ext_php_rs::builders::Requirement] = &[
+                    #(#requirements,)*
+                ];
+            }
+        });
 
         let _constructor = self
             .constructor
             .as_ref()
-            .map(|func| func.constructor_meta(&self.path, Some(&Visibility::Public)))
+            .map(|func| func.constructor_meta(&self.path, None, Some(&Visibility::Public)))
             .option_tokens();
 
         quote! {
@@ -96,6 +113,8 @@ impl ToTokens for InterfaceData<'_> {
                     #(#docs,)*
                 ];
 
+                #requirements
+
                 fn get_metadata() -> &'static ::ext_php_rs::class::ClassMetadata<Self> {
                     static METADATA: ::ext_php_rs::class::ClassMetadata<#interface_name> =
                     ::ext_php_rs::class::ClassMetadata::new();
@@ -202,7 +221,10 @@ impl<'a> Parse<'a, InterfaceData<'a>> for ItemTrait {
             .rename
             .rename(ident_to_php_name(ident), RenameRule::Pascal);
         validate_php_name(&name, PhpNameContext::Interface, ident.span())?;
+        let name = apply_namespace(attrs.namespace.as_deref(), name, ident.span())?;
         let docs = get_docs(&attrs.attrs)?;
+        let requirements =
+            parse_requirements(&attrs.requires_ext, attrs.min_php.as_deref(), ident.span())?;
         self.attrs.clean_php();
         let interface_name = format_ident!("{INTERNAL_INTERFACE_NAME_PREFIX}{ident}");
         let ts = quote! { #interface_name };
@@ -216,6 +238,7 @@ impl<'a> Parse<'a, InterfaceData<'a>> for ItemTrait {
             methods: Vec::default(),
             constants: Vec::default(),
             docs,
+            requirements,
         };
 
         for item in &mut self.items {
@@ -248,6 +271,9 @@ pub struct PhpFunctionInterfaceAttribute {
     optional: Option<Ident>,
     vis: Option<Visibility>,
     attrs: Vec<syn::Attribute>,
+    nullable: Flag,
+    not_null: Flag,
+    mixed: Flag,
     getter: Flag,
     setter: Flag,
     constructor: Flag,
@@ -269,7 +295,9 @@ fn parse_trait_item_fn(
     let php_attr = PhpFunctionInterfaceAttribute::from_attributes(&fn_item.attrs)?;
     fn_item.attrs.clean_php();
 
-    let mut args = Args::parse_from_fnargs(fn_item.sig.inputs.iter(), php_attr.defaults)?;
+    let overrides = PhpArgAttribute::take_from_fnargs(fn_item.sig.inputs.iter_mut())?;
+    let mut args =
+        Args::parse_from_fnargs(fn_item.sig.inputs.iter(), php_attr.defaults, overrides)?;
 
     let docs = get_docs(&php_attr.attrs)?;
 
@@ -291,7 +319,9 @@ fn parse_trait_item_fn(
         PhpNameContext::Method,
         fn_item.sig.ident.span(),
     )?;
-    let f = Function::new(&fn_item.sig, method_name, args, php_attr.optional, docs);
+    let mut f = Function::new(&fn_item.sig, method_name, args, php_attr.optional, docs);
+    f.nullable = nullability_override(&php_attr.nullable, &php_attr.not_null)?;
+    f.mixed = php_attr.mixed.is_present();
 
     if php_attr.constructor.is_present() {
         Ok(MethodKind::Constructor(f))
@@ -340,6 +370,9 @@ fn parse_trait_item_const(
     }
 
     let attr = PhpConstAttribute::from_attributes(&const_item.attrs)?;
+    if !attr.requires_ext.is_empty() || attr.min_php.is_some() {
+        bail!(const_item => "`requires_ext` and `min_php` cannot be used on interface constants, use them on the interface instead.");
+    }
     let name = attr.rename.rename(
         ident_to_php_name(&const_item.ident),
         change_case.unwrap_or(RenameRule::ScreamingSnake),
@@ -4,6 +4,7 @@ mod class;
 mod constant;
 mod enum_;
 mod extern_;
+mod extern_class;
 mod fastcall;
 mod function;
 mod helpers;
@@ -18,6 +19,7 @@ use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use syn::{
     DeriveInput, ItemConst, ItemEnum, ItemFn, ItemForeignMod, ItemImpl, ItemStruct, ItemTrait,
+    LitStr,
 };
 
 extern crate proc_macro;
@@ -45,6 +47,30 @@ extern crate proc_macro;
 /// - `#[php(implements(ce = ce_fn, stub = "InterfaceName"))]` - Implements the
 ///   given interface on the class. Can be used multiple times. `ce_fn` must be
 ///   a valid function with the signature `fn() -> &'static ClassEntry`.
+/// - `#[php(implements_if_exists = "InterfaceName")]` - Implements the interface
+///   with the given name only if it exists when the extension starts up. Can be
+///   used multiple times. See [Optional Interfaces](#optional-interfaces).
+/// - `#[php(dimensions)]` - Installs the `DimensionHandlers` implementation of the
+///   class, allowing objects to be used as arrays. See
+///   [Dimension Handlers](#dimension-handlers).
+/// - `#[php(array_access)]` - Implements `ArrayAccess` using the `PhpArrayAccess`
+///   implementation of the class. See [Array Access](#array-access).
+/// - `#[php(property_handlers)]` - Installs the `PropertyHandlers` implementation
+///   of the class, controlling `isset()`, `empty()` and `unset()` of properties.
+///   See [Property Handlers](#property-handlers).
+/// - `#[php(debug_info)]` - Installs the `DebugInfo` implementation of the class,
+///   providing the properties shown by `var_dump()` and `print_r()`. See
+///   [Debug Info](#debug-info).
+/// - `#[php(to_string)]` - Installs the `ToPhpString` implementation of the
+///   class, allowing objects to be converted to strings. See
+///   [String Conversion](#string-conversion).
+/// - `#[php(proxy_props)]` - Accesses the `#[php(prop)]` fields only through the
+///   property handlers of the class, without declaring them as PHP properties.
+///   See [Proxied Properties](#proxied-properties).
+/// - `#[php(instantiate(ty = "Collection<i64>", name = "IntCollection"))]` -
+///   Registers a concrete instantiation of a generic struct as a PHP class with
+///   the given name. Can be used multiple times. See
+///   [Generic parameters](#generic-parameters).
 ///
 /// You may also use the `#[php(prop)]` attribute on a struct field to use the
 /// field as a PHP property. By default, the field will be accessible from PHP
@@ -84,14 +110,56 @@ extern crate proc_macro;
 /// instead of using borrowed references with lifetimes, consider using
 /// reference-counted smart pointers such as [Arc](https://doc.rust-lang.org/std/sync/struct.Arc.html).
 ///
-/// ### No generic parameters
+/// ### Generic parameters
 ///
 /// A Rust struct `Foo<T>` with a generic parameter `T` generates new compiled
 /// implementations each time it is used with a different concrete type for `T`.
-/// These new implementations are generated by the compiler at each usage site.
-/// This is incompatible with wrapping `Foo` in PHP,
-/// where there needs to be a single compiled implementation of `Foo` which is
-/// integrated with the PHP interpreter.
+/// PHP classes have a single implementation, so each concrete type used from PHP
+/// is registered as a separate class. The instantiations are listed with the
+/// `#[php(instantiate(ty = "...", name = "..."))]` attribute, which can be used
+/// multiple times, and registered with the module one by one.
+///
+/// Methods are added with a generic `#[php_impl]` block, whose bounds must be met
+/// by all instantiations. Constants are not supported in generic `impl` blocks.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{
+///     convert::{FromZval, IntoZval},
+///     prelude::*,
+/// };
+///
+/// #[php_class]
+/// #[php(instantiate(ty = "TypedCollection<i64>", name = "IntCollection"))]
+/// #[php(instantiate(ty = "TypedCollection<String>", name = "StringCollection"))]
+/// pub struct TypedCollection<T: 'static> {
+///     items: Vec<T>,
+/// }
+///
+/// #[php_impl]
+/// impl<T: Clone + IntoZval + for<'a> FromZval<'a> + 'static> TypedCollection<T> {
+///     pub fn __construct() -> Self {
+///         Self { items: vec![] }
+///     }
+///
+///     pub fn add(&mut self, item: T) {
+///         self.items.push(item);
+///     }
+///
+///     pub fn get(&self, index: usize) -> Option<T> {
+///         self.items.get(index).cloned()
+///     }
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+///         .class::<TypedCollection<i64>>()
+///         .class::<TypedCollection<String>>()
+/// }
+/// # fn main() {}
+/// ```
 ///
 /// ## Example
 ///
@@ -209,6 +277,301 @@ extern crate proc_macro;
 /// # fn main() {}
 /// ````
 ///
+/// ### Optional Interfaces
+///
+/// Interfaces provided by other extensions may not always be available. Use
+/// `#[php(implements_if_exists = "...")]` to implement an interface only if it
+/// exists when the class is registered, e.g. `Ds\Hashable` if `ext-ds` is loaded:
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{prelude::*, types::Zval, zend::ce};
+///
+/// #[php_class]
+/// #[php(implements(ce = ce::countable, stub = "\\Countable"))]
+/// #[php(implements_if_exists = "Ds\\Hashable")]
+/// pub struct Point;
+///
+/// #[php_impl]
+/// impl Point {
+///     pub fn count(&self) -> i64 {
+///         2
+///     }
+///
+///     pub fn hash(&self) -> String {
+///         "point".into()
+///     }
+///
+///     pub fn equals(&self, _other: &Zval) -> bool {
+///         false
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The interface is looked up by name at module startup, so the extension
+/// providing it must be loaded before your extension. The class must implement
+/// the methods of the interface itself, as they are only checked if the interface
+/// exists.
+///
+/// ## Dimension Handlers
+///
+/// Objects can support array syntax such as `$obj['key']`, `$obj[] = $value`,
+/// `isset($obj['key'])` and `unset($obj['key'])` without implementing
+/// `ArrayAccess`. Implement the `DimensionHandlers` trait and add the
+/// `#[php(dimensions)]` attribute to the class. The handlers are called directly
+/// by the engine instead of through PHP method calls, and receive the mode in
+/// which the element is fetched, e.g. `FetchMode::Isset` for `isset()` and `??`.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{
+///     exception::PhpResult, flags::FetchMode, prelude::*, types::Zval,
+///     zend::DimensionHandlers,
+/// };
+///
+/// #[php_class]
+/// #[php(dimensions)]
+/// #[derive(Default)]
+/// pub struct Vector {
+///     items: Vec<i64>,
+/// }
+///
+/// impl DimensionHandlers for Vector {
+///     fn read_dimension(&mut self, offset: Option<&Zval>, _: FetchMode) -> PhpResult<Option<Zval>> {
+///         let index = offset.and_then(Zval::long).ok_or("Invalid index")?;
+///         let item = usize::try_from(index).ok().and_then(|i| self.items.get(i));
+///         Ok(item.map(|&item| item.into()))
+///     }
+///
+///     fn write_dimension(&mut self, offset: Option<&Zval>, value: &Zval) -> PhpResult<()> {
+///         let value = value.long().ok_or("Only integers can be stored")?;
+///         match offset {
+///             None => self.items.push(value),
+///             Some(_) => return Err("Elements can only be appended".into()),
+///         }
+///         Ok(())
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Handlers which are not implemented throw an `Error`. `has_dimension` defaults
+/// to reading the element and checking that it is not `null`.
+///
+/// ## Array Access
+///
+/// To implement the `ArrayAccess` interface instead, e.g. so that objects pass
+/// `ArrayAccess` type checks, implement the `PhpArrayAccess` trait and add the
+/// `#[php(array_access)]` attribute to the class. The `offsetExists()`,
+/// `offsetGet()`, `offsetSet()` and `offsetUnset()` methods of the interface are
+/// declared on the class, and array syntax on the object calls the trait directly
+/// through the dimension handlers of the class. Objects of PHP subclasses call the
+/// methods instead, so they can be overridden. The attribute cannot be combined
+/// with `#[php(dimensions)]`.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use std::collections::HashMap;
+/// use ext_php_rs::{exception::PhpResult, prelude::*, types::Zval, zend::PhpArrayAccess};
+///
+/// #[php_class]
+/// #[php(array_access)]
+/// #[derive(Default)]
+/// pub struct Registry {
+///     entries: HashMap<String, Zval>,
+/// }
+///
+/// impl PhpArrayAccess for Registry {
+///     fn offset_exists(&mut self, offset: &Zval) -> PhpResult<bool> {
+///         Ok(offset.string().is_some_and(|key| self.entries.contains_key(&key)))
+///     }
+///
+///     fn offset_get(&mut self, offset: &Zval) -> PhpResult<Option<Zval>> {
+///         let key = offset.string().ok_or("Invalid key")?;
+///         Ok(self.entries.get(&key).map(Zval::shallow_clone))
+///     }
+///
+///     fn offset_set(&mut self, offset: &Zval, value: &Zval) -> PhpResult<()> {
+///         let key = offset.string().ok_or("Entries must have a key")?;
+///         self.entries.insert(key, value.shallow_clone());
+///         Ok(())
+///     }
+///
+///     fn offset_unset(&mut self, offset: &Zval) -> PhpResult<()> {
+///         if let Some(key) = offset.string() {
+///             self.entries.remove(&key);
+///         }
+///         Ok(())
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ## Property Handlers
+///
+/// Classes exposing virtual properties, e.g. through `__get()` and `__set()`, can
+/// control how their properties behave under `isset()`, `empty()`,
+/// `property_exists()` and `unset()`. Implement the `PropertyHandlers` trait and
+/// add the `#[php(property_handlers)]` attribute to the class. `has_property`
+/// receives the check made for the property, where `PropertyCheck::NotEmpty` is
+/// used by `empty()`. Returning `None` from `has_property` or `false` from
+/// `unset_property` falls back to the default behaviour for the property.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use std::collections::HashMap;
+/// use ext_php_rs::{
+///     exception::PhpResult, flags::PropertyCheck, prelude::*,
+///     zend::PropertyHandlers,
+/// };
+///
+/// #[php_class]
+/// #[php(property_handlers)]
+/// #[derive(Default)]
+/// pub struct Attributes {
+///     values: HashMap<String, String>,
+/// }
+///
+/// #[php_impl]
+/// impl Attributes {
+///     pub fn __get(&self, name: String) -> Option<String> {
+///         self.values.get(&name).cloned()
+///     }
+///
+///     pub fn __set(&mut self, name: String, value: String) {
+///         self.values.insert(name, value);
+///     }
+/// }
+///
+/// impl PropertyHandlers for Attributes {
+///     fn has_property(&mut self, name: &str, check: PropertyCheck) -> PhpResult<Option<bool>> {
+///         let value = self.values.get(name);
+///         Ok(Some(match check {
+///             PropertyCheck::NotEmpty => value.is_some_and(|value| !value.is_empty()),
+///             PropertyCheck::Isset | PropertyCheck::Exists => value.is_some(),
+///         }))
+///     }
+///
+///     fn unset_property(&mut self, name: &str) -> PhpResult<bool> {
+///         self.values.remove(name);
+///         Ok(true)
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// When `unset_property` falls back to the default behaviour, properties backed
+/// by `#[php(prop)]` fields cannot be unset, as the field is part of the struct.
+/// Unsetting them throws an `Error`. Classes without the attribute keep PHP's
+/// default `unset()` behaviour.
+///
+/// ## Debug Info
+///
+/// By default, `var_dump()` and `print_r()` only show the `#[php(prop)]` fields
+/// and PHP properties of an object. To show the internal state of the Rust struct
+/// instead, implement the `DebugInfo` trait and add the `#[php(debug_info)]`
+/// attribute to the class. The handler is called directly by the engine and takes
+/// precedence over a `__debugInfo()` method.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{boxed::ZBox, prelude::*, types::ZendHashTable, zend::DebugInfo};
+///
+/// #[php_class]
+/// #[php(debug_info)]
+/// pub struct Connection {
+///     host: String,
+///     queries: Vec<String>,
+/// }
+///
+/// impl DebugInfo for Connection {
+///     fn debug_info(&self) -> ZBox<ZendHashTable> {
+///         let mut info = ZendHashTable::new();
+///         let _ = info.insert("host", self.host.as_str());
+///         let _ = info.insert("queries", self.queries.len() as i64);
+///         info
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ## String Conversion
+///
+/// Objects of a class without a `__toString()` method cannot be converted to
+/// strings, so `echo $obj` throws an `Error`. To convert them from Rust instead,
+/// implement the `ToPhpString` trait and add the `#[php(to_string)]` attribute to
+/// the class. The handler is called directly by the engine and takes precedence
+/// over a `__toString()` method. Returned errors are thrown as exceptions.
+/// Unless the class declares its own, a `__toString()` method calling the
+/// implementation is also added, and the class implements `Stringable`.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{exception::PhpResult, prelude::*, zend::ToPhpString};
+///
+/// #[php_class]
+/// #[php(to_string)]
+/// pub struct Point {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// impl ToPhpString for Point {
+///     fn to_php_string(&self) -> PhpResult<String> {
+///         Ok(format!("({}, {})", self.x, self.y))
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ## Proxied Properties
+///
+/// The `#[php(prop)]` fields of a class are also declared as PHP properties, so
+/// each object holds a zval for them besides the Rust field. Reading and writing
+/// the property goes to the field, but operations modifying the property in
+/// place, such as `$obj->count++` or `$obj->name .= 'suffix'`, work on the zval
+/// and leave the field unchanged.
+///
+/// Adding the `#[php(proxy_props)]` attribute to the class stops declaring the
+/// fields as PHP properties, so every access converts the value from or into the
+/// Rust field. Operations modifying the property in place then read the field,
+/// modify the value and write it back. As with `__get()`, the property cannot be
+/// modified by reference, e.g. by appending to an array property.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[php_class]
+/// #[php(proxy_props)]
+/// pub struct Counter {
+///     #[php(prop)]
+///     count: i64,
+/// }
+///
+/// #[php_impl]
+/// impl Counter {
+///     pub fn __construct() -> Self {
+///         Self { count: 0 }
+///     }
+///
+///     /// Returns the value of the Rust field, which is incremented by
+///     /// `$counter->count++`.
+///     pub fn get(&self) -> i64 {
+///         self.count
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
 /// ## Static Properties
 ///
 /// Static properties are shared across all instances of a class. Use
@@ -626,6 +989,144 @@ fn php_interface_internal(_args: TokenStream2, input: TokenStream2) -> TokenStre
 /// # fn main() {}
 /// ```
 ///
+/// ## Aliases
+///
+/// A function can be registered under additional names with the `alias`
+/// attribute, which may be repeated. This is useful to keep old names working
+/// after renaming a function, without writing wrapper functions. Aliases are
+/// used as is, without applying any renaming rules.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[php_function]
+/// #[php(alias = "legacy_greet", alias = "say_hello")]
+/// pub fn greet(name: String) -> String {
+///     format!("Hello, {name}!")
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.function(wrap_function!(greet))
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The aliases share the arguments and return type of the function, and also
+/// appear in the generated stubs.
+///
+/// ## Nullability
+///
+/// By default, an argument or return type is nullable in PHP when the Rust type
+/// is a variant of `Option<T>`. This can be overridden with the
+/// `#[php(nullable)]` and `#[php(not_null)]` attributes. When placed on an
+/// argument, they change the nullability of that argument. When placed on the
+/// function itself, they change the nullability of the return type.
+///
+/// The override only changes the generated argument info and stubs, the
+/// value is still extracted through the Rust type. For example, an `Option<T>`
+/// which is only used to detect an omitted argument can be marked `not_null`,
+/// so that PHP sees `int $count` rather than `?int $count`:
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[php_function]
+/// pub fn skip(input: String, #[php(not_null)] count: Option<i64>) -> String {
+///     let count = usize::try_from(count.unwrap_or(0)).unwrap_or(0);
+///     input.chars().skip(count).collect()
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.function(wrap_function!(skip))
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ## String Coercion
+///
+/// String arguments only accept PHP strings by default. Arguments marked with
+/// `#[php(coerce)]` also accept integers, floats, booleans and objects which
+/// can be cast to a string, e.g. with a `__toString()` method, which are
+/// converted like PHP does for `string` parameters without `strict_types`.
+/// Other values, e.g. arrays, throw the same `TypeError` as PHP, e.g.
+/// `shout(): Argument #1 ($input) must be of type string, array given`. `null`
+/// is passed through, so that it can be accepted by an `Option<T>`:
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[php_function]
+/// pub fn shout(#[php(coerce)] input: String) -> String {
+///     input.to_uppercase()
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.function(wrap_function!(shout))
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The conversion is also available as `Zval::coerce_string()`.
+///
+/// ## Return Types
+///
+/// The return type declared to PHP is derived from the Rust return type. In
+/// addition:
+///
+/// - Functions without a return type, or returning `()`, are declared as
+///   returning `void`. Destructors are the exception, as PHP does not allow them
+///   to declare a return type. Interface methods without a return type are left
+///   untyped, so that classes implementing them do not have to declare `void`.
+/// - Functions returning `!`, or whose body ends in a call to `bailout()` or
+///   `exit()`, are declared as returning `never`. PHP 8.0 does not support
+///   `never`, so these are declared as returning `void` there.
+/// - The `#[php(mixed)]` attribute declares the return type as `mixed`,
+///   regardless of the Rust return type. This is useful for functions returning a
+///   value whose PHP type is not known from the Rust type.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{prelude::*, zend::bailout};
+///
+/// /// Declared as `function log_message(string $message): void`.
+/// #[php_function]
+/// pub fn log_message(message: String) {
+///     eprintln!("{message}");
+/// }
+///
+/// /// Declared as `function abort_request(): never`.
+/// #[php_function]
+/// pub fn abort_request() -> ! {
+///     unsafe { bailout() }
+/// }
+///
+/// /// Declared as `function first_byte(string $input): mixed`.
+/// #[php_function]
+/// #[php(mixed)]
+/// pub fn first_byte(input: String) -> Option<u8> {
+///     input.bytes().next()
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+///         .function(wrap_function!(log_message))
+///         .function(wrap_function!(abort_request))
+///         .function(wrap_function!(first_byte))
+/// }
+/// # fn main() {}
+/// ```
+///
 /// ## Variadic Functions
 ///
 /// Variadic functions can be implemented by specifying the last argument in the
@@ -651,11 +1152,115 @@ fn php_interface_internal(_args: TokenStream2, input: TokenStream2) -> TokenStre
 /// # fn main() {}
 /// ```
 ///
+/// ## Generic Functions
+///
+/// Generic functions cannot be exported to PHP directly, as PHP needs a single
+/// compiled implementation of each function. Instead, the concrete
+/// instantiations are listed with the `instantiate` attribute. Each
+/// instantiation takes the name of the generated function, which is used both
+/// in PHP and with `wrap_function!`, and a comma separated list of types
+/// substituted for the generic type parameters in order:
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[php_function]
+/// #[php(instantiate(name = "sum_ints", types = "i64"))]
+/// #[php(instantiate(name = "sum_floats", types = "f64"))]
+/// pub fn sum<T: std::iter::Sum<T>>(values: Vec<T>) -> T {
+///     values.into_iter().sum()
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+///         .function(wrap_function!(sum_ints))
+///         .function(wrap_function!(sum_floats))
+/// }
+/// # fn main() {}
+/// ```
+///
+/// A non-generic Rust function is generated for every instantiation, which
+/// forwards to the generic function. The generic function itself is not
+/// registered.
+///
+/// ## Async Functions
+///
+/// Functions declared with `async fn` can be exported as well. From PHP they
+/// are called like any other function: the returned future is driven to
+/// completion before the function returns. How the future is driven is
+/// decided by the executor set with `ext_php_rs::async_::set_executor`:
+///
+/// - `BlockOn` (the default) blocks the calling thread until the future is
+///   complete.
+/// - `FiberExecutor` suspends the calling PHP `Fiber` while the future is
+///   pending, which lets event loops such as [Revolt](https://revolt.run) run
+///   other fibers in the meantime. The fiber is not resumed when the future is
+///   woken: the event loop has to resume it, and the future is polled again
+///   each time. Outside of a fiber it blocks like `BlockOn`.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+/// use ext_php_rs::async_::{set_executor, FiberExecutor};
+///
+/// #[php_function]
+/// pub async fn answer() -> i64 {
+///     42
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     set_executor(FiberExecutor);
+///     module.function(wrap_function!(answer))
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Methods in `#[php_impl]` blocks may also be `async`, with the exception of
+/// constructors. If the executor fails, for example because the fiber could
+/// not be suspended, an exception is thrown. As other fibers may use the object
+/// while the method is suspended, `async` methods cannot take `&mut self`, and
+/// methods taking `&mut self` must not be called on the object until it
+/// completes. Use interior mutability, e.g. a `Cell`, to modify the object.
+///
 /// ## Returning `Result<T, E>`
 ///
 /// You can also return a `Result` from the function. The error variant will be
 /// translated into an exception and thrown. See the section on
 /// [exceptions](../exceptions.md) for more details.
+///
+/// ## Doc Comments
+///
+/// The doc comments of functions are written to the generated stubs. Since PHP
+/// 8.4 they are also passed to the engine, so they are returned by
+/// `ReflectionFunction::getDocComment()`. The doc comments of classes, methods,
+/// properties and constants are handled in the same way, with properties and
+/// constants being supported on all PHP versions:
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// /// Adds two numbers.
+/// ///
+/// /// @param int $a
+/// /// @param int $b
+/// #[php_function]
+/// pub fn add(a: i64, b: i64) -> i64 {
+///     a + b
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.function(wrap_function!(add))
+/// }
+/// # fn main() {}
+/// ```
 // END DOCS FROM function.md
 #[proc_macro_attribute]
 pub fn php_function(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -807,6 +1412,158 @@ fn php_const_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2 {
 /// }
 /// # fn main() {}
 /// ```
+///
+/// ## Namespaces
+///
+/// Functions and constants can be placed in a namespace by default with
+/// `ModuleBuilder::namespace`. The namespace is applied to every function,
+/// alias and constant whose name is not namespaced already. Items using
+/// `#[php(namespace = "...")]` keep their own namespace:
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[php_function]
+/// pub fn hello_world() -> &'static str {
+///     "Hello, world!"
+/// }
+///
+/// #[php_class]
+/// #[php(namespace = "Vendor\\Package")]
+/// pub struct Test;
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     // `Vendor\Package\hello_world()`
+///     module
+///         .namespace("Vendor\\Package")
+///         .function(wrap_function!(hello_world))
+///         .class::<Test>()
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Classes, interfaces and enums are not affected by the module namespace, as
+/// their names are also used in type declarations and must be known when the
+/// extension is compiled. Use the `namespace` attribute on them instead.
+///
+/// ## Startup Hooks
+///
+/// Items which depend on the environment the extension is loaded into, e.g. on
+/// ini settings or other extensions, can be added with
+/// `ModuleBuilder::startup_hook`. The hook is called during module startup with
+/// an empty builder, and the functions, constants, classes, interfaces and enums
+/// added to it are registered once it returns:
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[php_function]
+/// pub fn debug_dump_state() -> String {
+///     "...".to_string()
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.startup_hook(|module| {
+///         if std::env::var_os("MY_EXT_DEBUG").is_some() {
+///             module.function(wrap_function!(debug_dump_state))
+///         } else {
+///             module
+///         }
+///     })
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Items added by hooks are not included in generated stubs.
+///
+/// ## Raw Module Entry
+///
+/// Function entries which are not built by ext-php-rs, e.g. of functions
+/// implemented in C, can be added with the unsafe
+/// `ModuleBuilder::raw_functions`. Fields of the module entry which are not
+/// supported by the builder, like module dependencies, can be set with
+/// `ModuleBuilder::entry_modifier`, which is called once the module entry has
+/// been built. The shutdown functions of the entry should not be replaced, as
+/// they track the end of requests and of the engine; set them on the builder
+/// instead:
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{ffi::_zend_module_dep, prelude::*};
+///
+/// struct Deps([_zend_module_dep; 2]);
+///
+/// // SAFETY: The dependencies only point to static strings.
+/// unsafe impl Sync for Deps {}
+///
+/// static DEPS: Deps = Deps([
+///     _zend_module_dep {
+///         name: c"json".as_ptr(),
+///         rel: std::ptr::null(),
+///         version: std::ptr::null(),
+///         type_: 1, // MODULE_DEP_REQUIRED
+///     },
+///     _zend_module_dep {
+///         name: std::ptr::null(),
+///         rel: std::ptr::null(),
+///         version: std::ptr::null(),
+///         type_: 0,
+///     },
+/// ]);
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.entry_modifier(|entry| entry.deps = DEPS.0.as_ptr())
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ## Extension Information
+///
+/// Instead of writing an info function by hand, a function taking a
+/// `&mut ModuleInfo` can be set with `#[php(minfo = ...)]`. It is called by
+/// `phpinfo()` and can write any number of tables, nested sections, text and
+/// HTML blocks. Text is escaped for HTML output, and plain text is written on
+/// the CLI:
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{prelude::*, zend::ModuleInfo};
+///
+/// fn minfo(info: &mut ModuleInfo) {
+///     info.table(|table| {
+///         table.header(&["my extension", "enabled"]);
+///         table.entry("Version", env!("CARGO_PKG_VERSION"));
+///     });
+///     info.section("Cache", |table| {
+///         table.entries([("Hits", 10), ("Misses", 2)]);
+///         table.section("Limits", |table| {
+///             table.entry("Max entries", 1024);
+///         });
+///     });
+///     info.html(
+///         "<p>See <a href=\"https://example.com\">the docs</a>.</p>",
+///         "See https://example.com for the docs.",
+///     );
+/// }
+///
+/// #[php_module]
+/// #[php(minfo = minfo)]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The hook replaces any function set with `ModuleBuilder::info_function`.
 // END DOCS FROM module.md
 #[proc_macro_attribute]
 pub fn php_module(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -878,6 +1635,11 @@ fn php_module_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2
 ///   "private")]` - Sets the visibility of the method.
 /// - `#[php(name = "method_name")]` - Renames the PHP method to a different
 ///   identifier, without renaming the Rust method name.
+/// - `#[php(alias = "oldName")]` - Registers the method under an additional
+///   name, used as is. May be repeated.
+/// - `#[php(mixed)]` - Declares the return type of the method as `mixed`. See
+///   [Return Types](./function.md#return-types) for how return types are
+///   derived.
 ///
 /// The `#[php(defaults)]` and `#[php(optional)]` attributes operate the same as
 /// the equivalent function attribute parameters.
@@ -1128,6 +1890,88 @@ fn php_extern_internal(_: TokenStream2, input: TokenStream2) -> TokenStream2 {
     extern_::parser(input).unwrap_or_else(|e| e.to_compile_error())
 }
 
+// BEGIN DOCS FROM extern_class.md
+/// # `#[php_extern_class]` Attribute
+///
+/// Attribute used to generate a typed wrapper for a class registered by another
+/// extension or by userland code, such as `Redis` or `ArrayObject`.
+///
+/// The attribute is placed on a trait declaring the methods of the class, and
+/// takes the name of the PHP class. The trait is replaced by a struct with the
+/// same name, holding a reference to a PHP object which is an instance of the
+/// class. Each declared method becomes a method of the struct, which converts the
+/// parameters with [`IntoZval`] and the return value with [`FromZval`], and
+/// returns an [`ext_php_rs::error::Result`] in case the call fails or throws.
+/// Methods returning [`Zval`] return the value without converting it.
+///
+/// - Methods taking `&self` or `&mut self` call the method on the object.
+/// - Methods without a receiver call the static method of the class.
+/// - `fn __construct(...) -> Self` declares the constructor, which creates a new
+///   instance of the class with the generated `new` function.
+///
+/// Method names are converted to camel case, and can be changed with the
+/// `#[php(name = "...")]` and `#[php(change_case = ...)]` attributes.
+///
+/// The wrapper implements [`FromZval`] and [`IntoZval`], so it can be used as the
+/// parameter or return type of functions. The class entry is looked up when it is
+/// first required and cached for classes registered by extensions.
+///
+/// ## Example
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{prelude::*, types::Zval};
+///
+/// #[php_extern_class("ArrayObject")]
+/// pub trait ArrayObject {
+///     fn __construct(array: Vec<i64>) -> Self;
+///     fn count(&self) -> i64;
+///     fn offset_get(&self, key: i64) -> Zval;
+///     fn append(&mut self, value: i64);
+/// }
+///
+/// #[php_function]
+/// pub fn sum_array_object(mut object: ArrayObject) -> PhpResult<i64> {
+///     object.append(10)?;
+///     let mut sum = 0;
+///     for i in 0..object.count()? {
+///         sum += object.offset_get(i)?.long().unwrap_or(0);
+///     }
+///     Ok(sum)
+/// }
+///
+/// #[php_function]
+/// pub fn new_array_object() -> PhpResult<ArrayObject> {
+///     Ok(ArrayObject::new(vec![1, 2, 3])?)
+/// }
+///
+/// #[php_module]
+/// pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+///         .function(wrap_function!(sum_array_object))
+///         .function(wrap_function!(new_array_object))
+/// }
+/// # fn main() {}
+/// ```
+///
+/// [`IntoZval`]: crate::convert::IntoZval
+/// [`FromZval`]: crate::convert::FromZval
+/// [`ext_php_rs::error::Result`]: crate::error::Result
+/// [`Zval`]: crate::types::Zval
+// END DOCS FROM extern_class.md
+#[proc_macro_attribute]
+pub fn php_extern_class(args: TokenStream, input: TokenStream) -> TokenStream {
+    php_extern_class_internal(args.into(), input.into()).into()
+}
+
+fn php_extern_class_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2 {
+    let class = parse_macro_input2!(args as LitStr);
+    let input = parse_macro_input2!(input as ItemTrait);
+
+    extern_class::parser(class, input).unwrap_or_else(|e| e.to_compile_error())
+}
+
 // BEGIN DOCS FROM zval_convert.md
 /// # `ZvalConvert` Derive Macro
 ///
@@ -1451,6 +2295,7 @@ mod tests {
                 ("php_enum", php_enum_internal as AttributeFn),
                 ("php_interface", php_interface_internal as AttributeFn),
                 ("php_extern", php_extern_internal as AttributeFn),
+                ("php_extern_class", php_extern_class_internal as AttributeFn),
                 ("php_function", php_function_internal as AttributeFn),
                 ("php_impl", php_impl_internal as AttributeFn),
                 ("php_module", php_module_internal as AttributeFn),
@@ -9,6 +9,7 @@ use crate::prelude::*;
 #[darling(default, attributes(php))]
 pub(crate) struct PhpModuleAttribute {
     startup: Option<Ident>,
+    minfo: Option<Ident>,
 }
 
 pub fn parser(input: ItemFn) -> Result<TokenStream> {
@@ -22,6 +23,18 @@ pub fn parser(input: ItemFn) -> Result<TokenStream> {
     } else {
         quote! { 0i32 }
     };
+    let (info_func, info_builder) = if let Some(minfo) = attr.minfo {
+        (
+            quote! {
+                extern "C" fn ext_php_rs_info(_module: *mut ::ext_php_rs::zend::ModuleEntry) {
+                    #minfo(&mut ::ext_php_rs::zend::ModuleInfo::new());
+                }
+            },
+            quote! { .info_function(ext_php_rs_info) },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
 
     Ok(quote! {
         #[doc(hidden)]
@@ -48,6 +61,8 @@ pub fn parser(input: ItemFn) -> Result<TokenStream> {
                 a | b
             }
 
+            #info_func
+
             #[inline]
             fn internal(#inputs) #output {
                 #(#stmts)*
@@ -57,7 +72,8 @@ pub fn parser(input: ItemFn) -> Result<TokenStream> {
                 env!("CARGO_PKG_NAME"),
                 env!("CARGO_PKG_VERSION")
             ))
-            .startup_function(ext_php_rs_startup);
+            .startup_function(ext_php_rs_startup)
+            #info_builder;
 
             match builder.try_into() {
                 Ok((entry, startup)) => {
@@ -226,6 +226,94 @@ pub fn validate_php_name(
     Ok(())
 }
 
+/// Prefixes a PHP name with the namespace given through
+/// `#[php(namespace = "Vendor\\Package")]`, if any.
+///
+/// Leading and trailing backslashes of the namespace are ignored.
+///
+/// # Errors
+///
+/// Returns a `syn::Error` if a segment of the namespace is not a valid PHP
+/// identifier.
+pub fn apply_namespace(
+    namespace: Option<&str>,
+    name: String,
+    span: proc_macro2::Span,
+) -> Result<String, syn::Error> {
+    let Some(namespace) = namespace.map(|ns| ns.trim_matches('\\')) else {
+        return Ok(name);
+    };
+
+    let is_valid_segment = |segment: &str| {
+        segment
+            .chars()
+            .next()
+            .is_some_and(|c| c == '_' || c.is_ascii_alphabetic() || !c.is_ascii())
+            && segment
+                .chars()
+                .all(|c| c == '_' || c.is_ascii_alphanumeric() || !c.is_ascii())
+    };
+    if !namespace.split('\\').all(is_valid_segment) {
+        return Err(syn::Error::new(
+            span,
+            format!("'{namespace}' is not a valid PHP namespace."),
+        ));
+    }
+
+    Ok(format!("{namespace}\\{name}"))
+}
+
+/// Returns the `::ext_php_rs::builders::Requirement` expressions for the
+/// `#[php(requires_ext = "...")]` and `#[php(min_php = "...")]` attributes
+/// of an item.
+///
+/// # Errors
+///
+/// Returns a `syn::Error` if the minimum PHP version is not of the form
+/// `major.minor` or `major.minor.patch`.
+pub fn parse_requirements(
+    requires_ext: &[String],
+    min_php: Option<&str>,
+    span: proc_macro2::Span,
+) -> Result<Vec<proc_macro2::TokenStream>, syn::Error> {
+    let mut requirements = requires_ext
+        .iter()
+        .map(|ext| quote! { ::ext_php_rs::builders::Requirement::Extension(#ext) })
+        .collect::<Vec<_>>();
+
+    if let Some(version) = min_php {
+        let Some(version_id) = parse_php_version(version) else {
+            return Err(syn::Error::new(
+                span,
+                format!("'{version}' is not a valid PHP version, expected e.g. '8.2' or '8.2.1'."),
+            ));
+        };
+        requirements
+            .push(quote! { ::ext_php_rs::builders::Requirement::MinPhpVersion(#version_id) });
+    }
+
+    Ok(requirements)
+}
+
+/// Parses a PHP version of the form `major.minor[.patch]` into the format of
+/// `PHP_VERSION_ID`.
+fn parse_php_version(version: &str) -> Option<u32> {
+    let parts = version
+        .split('.')
+        .map(|part| part.parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let (major, minor, patch) = match parts[..] {
+        [major, minor] => (major, minor, 0),
+        [major, minor, patch] => (major, minor, patch),
+        _ => return None,
+    };
+    if minor > 99 || patch > 99 {
+        return None;
+    }
+
+    Some(major * 10000 + minor * 100 + patch)
+}
+
 const MAGIC_METHOD: [&str; 17] = [
     "__construct",
     "__destruct",
@@ -370,7 +458,17 @@ where
 mod tests {
     use crate::parsing::{MethodRename, Rename};
 
-    use super::{PhpRename, RenameRule};
+    use super::{PhpRename, RenameRule, parse_php_version};
+
+    #[test]
+    fn php_version() {
+        assert_eq!(parse_php_version("8.2"), Some(80200));
+        assert_eq!(parse_php_version("8.1.12"), Some(80112));
+        assert_eq!(parse_php_version("8"), None);
+        assert_eq!(parse_php_version("8.x"), None);
+        assert_eq!(parse_php_version("8.100"), None);
+        assert_eq!(parse_php_version("8.2.1.0"), None);
+    }
 
     #[test]
     fn php_rename() {
@@ -681,4 +779,38 @@ mod tests {
         validate_php_name("resource", PhpNameContext::Class, Span::call_site()).unwrap();
         validate_php_name("numeric", PhpNameContext::Class, Span::call_site()).unwrap();
     }
+
+    #[test]
+    fn test_apply_namespace() {
+        use super::apply_namespace;
+        use proc_macro2::Span;
+
+        assert_eq!(
+            apply_namespace(None, "Foo".to_string(), Span::call_site()).unwrap(),
+            "Foo"
+        );
+        assert_eq!(
+            apply_namespace(Some("Vendor\\Pkg"), "Foo".to_string(), Span::call_site()).unwrap(),
+            "Vendor\\Pkg\\Foo"
+        );
+        assert_eq!(
+            apply_namespace(Some("\\Vendor\\"), "foo".to_string(), Span::call_site()).unwrap(),
+            "Vendor\\foo"
+        );
+    }
+
+    #[test]
+    fn test_apply_namespace_rejects_invalid_namespace() {
+        use super::apply_namespace;
+        use proc_macro2::Span;
+
+        assert!(
+            apply_namespace(Some("Vendor\\\\Pkg"), "Foo".to_string(), Span::call_site()).is_err()
+        );
+        assert!(
+            apply_namespace(Some("Vendor\\1Pkg"), "Foo".to_string(), Span::call_site()).is_err()
+        );
+        assert!(apply_namespace(Some("Vendor-Pkg"), "Foo".to_string(), Span::call_site()).is_err());
+        assert!(apply_namespace(Some(""), "Foo".to_string(), Span::call_site()).is_err());
+    }
 }
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
 use std::mem;
 
+use syn::visit_mut::{self, VisitMut};
+
 /// Implemented on syn types which can contain lifetimes.
 pub trait DropLifetimes {
     /// Drops any lifetimes inside `self`.
@@ -184,3 +187,21 @@ impl<T: DropLifetimes, P> DropLifetimes for syn::punctuated::Punctuated<T, P> {
         }
     }
 }
+
+/// Replaces generic type parameters with concrete types, e.g. `Vec<T>` with
+/// `Vec<i64>` when `T` maps to `i64`.
+pub struct SubstituteGenerics<'a>(pub &'a HashMap<syn::Ident, syn::Type>);
+
+impl VisitMut for SubstituteGenerics<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Path(path) = ty
+            && path.qself.is_none()
+            && let Some(ident) = path.path.get_ident()
+            && let Some(concrete) = self.0.get(ident)
+        {
+            *ty = concrete.clone();
+            return;
+        }
+        visit_mut::visit_type_mut(self, ty);
+    }
+}
@@ -5,6 +5,15 @@ const MY_CONST: &str = "Hello, world!";
 const _internal_const_docs_MY_CONST: &[&str] = &[];
 #[allow(non_upper_case_globals)]
 const _internal_const_name_MY_CONST: &str = "MY_CONST";
+#[allow(non_upper_case_globals)]
+const _internal_const_requirements_MY_CONST: &[::ext_php_rs::builders::Requirement] = &[];
 fn main() {
-    (_internal_const_name_MY_CONST, MY_CONST, _internal_const_docs_MY_CONST);
+    (
+        _internal_const_name_MY_CONST,
+        ::ext_php_rs::constant::ConditionalConst::new(
+            MY_CONST,
+            _internal_const_requirements_MY_CONST,
+        ),
+        _internal_const_docs_MY_CONST,
+    );
 }
@@ -143,8 +143,14 @@ where
 pub const ZEND_DEBUG: u32 = 1;
 pub const _ZEND_TYPE_NAME_BIT: u32 = 16777216;
 pub const _ZEND_TYPE_LITERAL_NAME_BIT: u32 = 8388608;
+pub const _ZEND_TYPE_ITERABLE_BIT: u32 = 2097152;
 pub const _ZEND_TYPE_NULLABLE_BIT: u32 = 2;
 pub const HT_MIN_SIZE: u32 = 8;
+pub const ZEND_HASH_APPLY_KEEP: u32 = 0;
+pub const ZEND_HASH_APPLY_REMOVE: u32 = 1;
+pub const ZEND_HASH_APPLY_STOP: u32 = 2;
+pub const HASH_FLAG_PACKED: u32 = 4;
+pub const HASH_FLAG_UNINITIALIZED: u32 = 8;
 pub const IS_UNDEF: u32 = 0;
 pub const IS_NULL: u32 = 1;
 pub const IS_FALSE: u32 = 2;
@@ -161,6 +167,7 @@ pub const IS_CALLABLE: u32 = 12;
 pub const IS_ITERABLE: u32 = 13;
 pub const IS_VOID: u32 = 14;
 pub const IS_MIXED: u32 = 16;
+pub const IS_NEVER: u32 = 17;
 pub const IS_INDIRECT: u32 = 12;
 pub const IS_PTR: u32 = 13;
 pub const _IS_BOOL: u32 = 18;
@@ -168,6 +175,8 @@ pub const Z_TYPE_FLAGS_SHIFT: u32 = 8;
 pub const GC_FLAGS_MASK: u32 = 1008;
 pub const GC_FLAGS_SHIFT: u32 = 0;
 pub const GC_IMMUTABLE: u32 = 64;
+pub const GC_PERSISTENT: u32 = 128;
+pub const IS_STR_PERMANENT: u32 = 256;
 pub const IS_TYPE_REFCOUNTED: u32 = 1;
 pub const IS_TYPE_COLLECTABLE: u32 = 2;
 pub const IS_INTERNED_STRING_EX: u32 = 6;
@@ -194,6 +203,23 @@ pub const E_DEPRECATED: u32 = 8192;
 pub const E_USER_DEPRECATED: u32 = 16384;
 pub const ZEND_PROPERTY_ISSET: u32 = 0;
 pub const ZEND_PROPERTY_EXISTS: u32 = 2;
+pub const ZEND_COMPILE_EXTENDED_STMT: u32 = 1;
+pub const ZEND_COMPILE_EXTENDED_FCALL: u32 = 2;
+pub const ZEND_COMPILE_HANDLE_OP_ARRAY: u32 = 4;
+pub const ZEND_COMPILE_IGNORE_INTERNAL_FUNCTIONS: u32 = 8;
+pub const ZEND_COMPILE_IGNORE_INTERNAL_CLASSES: u32 = 16;
+pub const ZEND_COMPILE_DELAYED_BINDING: u32 = 32;
+pub const ZEND_COMPILE_NO_CONSTANT_SUBSTITUTION: u32 = 64;
+pub const ZEND_COMPILE_NO_PERSISTENT_CONSTANT_SUBSTITUTION: u32 = 256;
+pub const ZEND_COMPILE_IGNORE_USER_FUNCTIONS: u32 = 512;
+pub const ZEND_COMPILE_GUARDS: u32 = 1024;
+pub const ZEND_COMPILE_NO_BUILTINS: u32 = 2048;
+pub const ZEND_COMPILE_WITH_FILE_CACHE: u32 = 4096;
+pub const ZEND_COMPILE_IGNORE_OTHER_FILES: u32 = 8192;
+pub const ZEND_COMPILE_WITHOUT_EXECUTION: u32 = 16384;
+pub const ZEND_COMPILE_PRELOAD: u32 = 32768;
+pub const ZEND_COMPILE_NO_JUMPTABLES: u32 = 65536;
+pub const ZEND_COMPILE_PRELOAD_IN_CHILD: u32 = 131072;
 pub const ZEND_ACC_PUBLIC: u32 = 1;
 pub const ZEND_ACC_PROTECTED: u32 = 2;
 pub const ZEND_ACC_PRIVATE: u32 = 4;
@@ -241,6 +267,7 @@ pub const ZEND_ACC_STRICT_TYPES: u32 = 2147483648;
 pub const ZEND_INTERNAL_FUNCTION: u32 = 1;
 pub const ZEND_USER_FUNCTION: u32 = 2;
 pub const ZEND_EVAL_CODE: u32 = 4;
+pub const ZEND_INTERNAL_CLASS: u32 = 1;
 pub const ZEND_ISEMPTY: u32 = 1;
 pub const _ZEND_SEND_MODE_SHIFT: u32 = 25;
 pub const _ZEND_IS_VARIADIC_BIT: u32 = 134217728;
@@ -263,6 +290,38 @@ pub const PHP_INI_USER: u32 = 1;
 pub const PHP_INI_PERDIR: u32 = 2;
 pub const PHP_INI_SYSTEM: u32 = 4;
 pub const PHP_INI_ALL: u32 = 7;
+pub const ZEND_INI_STAGE_STARTUP: u32 = 1;
+pub const ZEND_INI_STAGE_SHUTDOWN: u32 = 2;
+pub const ZEND_INI_STAGE_ACTIVATE: u32 = 4;
+pub const ZEND_INI_STAGE_DEACTIVATE: u32 = 8;
+pub const ZEND_INI_STAGE_RUNTIME: u32 = 16;
+pub const ZEND_INI_STAGE_HTACCESS: u32 = 32;
+pub const BP_VAR_R: u32 = 0;
+pub const BP_VAR_W: u32 = 1;
+pub const BP_VAR_RW: u32 = 2;
+pub const BP_VAR_IS: u32 = 3;
+pub const BP_VAR_FUNC_ARG: u32 = 4;
+pub const BP_VAR_UNSET: u32 = 5;
+pub const REPORT_ERRORS: u32 = 8;
+pub const PHP_STREAM_FREE_CLOSE: u32 = 3;
+pub const PHP_STREAM_AS_FD_FOR_SELECT: u32 = 3;
+pub const PHP_STREAM_CAST_INTERNAL: u32 = 536870912;
+pub const PHP_STREAM_OPTION_BLOCKING: u32 = 1;
+pub const STREAM_XPORT_CLIENT: u32 = 0;
+pub const STREAM_XPORT_CONNECT: u32 = 2;
+pub const PHP_STREAM_NOTIFY_RESOLVE: u32 = 1;
+pub const PHP_STREAM_NOTIFY_CONNECT: u32 = 2;
+pub const PHP_STREAM_NOTIFY_AUTH_REQUIRED: u32 = 3;
+pub const PHP_STREAM_NOTIFY_MIME_TYPE_IS: u32 = 4;
+pub const PHP_STREAM_NOTIFY_FILE_SIZE_IS: u32 = 5;
+pub const PHP_STREAM_NOTIFY_REDIRECTED: u32 = 6;
+pub const PHP_STREAM_NOTIFY_PROGRESS: u32 = 7;
+pub const PHP_STREAM_NOTIFY_COMPLETED: u32 = 8;
+pub const PHP_STREAM_NOTIFY_FAILURE: u32 = 9;
+pub const PHP_STREAM_NOTIFY_AUTH_RESULT: u32 = 10;
+pub const PHP_STREAM_NOTIFY_SEVERITY_INFO: u32 = 0;
+pub const PHP_STREAM_NOTIFY_SEVERITY_WARN: u32 = 1;
+pub const PHP_STREAM_NOTIFY_SEVERITY_ERR: u32 = 2;
 pub type __dev_t = ::std::os::raw::c_ulong;
 pub type __uid_t = ::std::os::raw::c_uint;
 pub type __gid_t = ::std::os::raw::c_uint;
@@ -272,6 +331,7 @@ pub type __nlink_t = ::std::os::raw::c_ulong;
 pub type __off_t = ::std::os::raw::c_long;
 pub type __off64_t = ::std::os::raw::c_long;
 pub type __time_t = ::std::os::raw::c_long;
+pub type __suseconds_t = ::std::os::raw::c_long;
 pub type __blksize_t = ::std::os::raw::c_long;
 pub type __blkcnt_t = ::std::os::raw::c_long;
 pub type __syscall_slong_t = ::std::os::raw::c_long;
@@ -288,6 +348,12 @@ pub struct timespec {
     pub tv_sec: __time_t,
     pub tv_nsec: __syscall_slong_t,
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct timeval {
+    pub tv_sec: __time_t,
+    pub tv_usec: __suseconds_t,
+}
 pub type FILE = _IO_FILE;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -675,6 +741,23 @@ pub type zend_string_init_interned_func_t = ::std::option::Option<
 unsafe extern "C" {
     pub static mut zend_string_init_interned: zend_string_init_interned_func_t;
 }
+pub type zend_new_interned_string_func_t =
+    ::std::option::Option<unsafe extern "C" fn(str_: *mut zend_string) -> *mut zend_string>;
+unsafe extern "C" {
+    pub static mut zend_new_interned_string: zend_new_interned_string_func_t;
+}
+unsafe extern "C" {
+    pub fn zend_interned_string_find_permanent(str_: *mut zend_string) -> *mut zend_string;
+}
+unsafe extern "C" {
+    pub fn zend_hash_func(str_: *const ::std::os::raw::c_char, len: usize) -> zend_ulong;
+}
+unsafe extern "C" {
+    pub fn zend_string_tolower_ex(str_: *mut zend_string, persistent: bool) -> *mut zend_string;
+}
+unsafe extern "C" {
+    pub fn zend_string_toupper_ex(str_: *mut zend_string, persistent: bool) -> *mut zend_string;
+}
 unsafe extern "C" {
     pub static mut zend_known_strings: *mut *mut zend_string;
 }
@@ -778,6 +861,23 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn zend_hash_clean(ht: *mut HashTable);
 }
+unsafe extern "C" {
+    pub fn zend_hash_extend(ht: *mut HashTable, nSize: u32, packed: bool);
+}
+unsafe extern "C" {
+    pub fn zend_hash_rehash(ht: *mut HashTable);
+}
+unsafe extern "C" {
+    pub fn zend_hash_str_add(
+        ht: *mut HashTable,
+        key: *const ::std::os::raw::c_char,
+        len: usize,
+        pData: *mut zval,
+    ) -> *mut zval;
+}
+unsafe extern "C" {
+    pub fn zend_hash_index_add(ht: *mut HashTable, h: zend_ulong, pData: *mut zval) -> *mut zval;
+}
 unsafe extern "C" {
     pub fn zend_hash_str_update(
         ht: *mut HashTable,
@@ -800,9 +900,139 @@ unsafe extern "C" {
         len: usize,
     ) -> zend_result;
 }
+unsafe extern "C" {
+    pub fn zend_hash_str_del_ind(
+        ht: *mut HashTable,
+        key: *const ::std::os::raw::c_char,
+        len: usize,
+    ) -> zend_result;
+}
 unsafe extern "C" {
     pub fn zend_hash_index_del(ht: *mut HashTable, h: zend_ulong) -> zend_result;
 }
+unsafe extern "C" {
+    pub fn zend_hash_del_bucket(ht: *mut HashTable, p: *mut Bucket);
+}
+unsafe extern "C" {
+    pub fn zend_hash_packed_del_val(ht: *mut HashTable, zv: *mut zval);
+}
+pub type apply_func_arg_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        pDest: *mut zval,
+        argument: *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int,
+>;
+unsafe extern "C" {
+    pub fn zend_hash_apply_with_argument(
+        ht: *mut HashTable,
+        apply_func: apply_func_arg_t,
+        arg1: *mut ::std::os::raw::c_void,
+    );
+}
+pub type compare_func_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        arg1: *const ::std::os::raw::c_void,
+        arg2: *const ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int,
+>;
+pub type swap_func_t = ::std::option::Option<
+    unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void, arg2: *mut ::std::os::raw::c_void),
+>;
+pub type sort_func_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        arg1: *mut ::std::os::raw::c_void,
+        arg2: usize,
+        arg3: usize,
+        arg4: compare_func_t,
+        arg5: swap_func_t,
+    ),
+>;
+pub type bucket_compare_func_t = ::std::option::Option<
+    unsafe extern "C" fn(a: *mut Bucket, b: *mut Bucket) -> ::std::os::raw::c_int,
+>;
+unsafe extern "C" {
+    pub fn zend_hash_sort_ex(
+        ht: *mut HashTable,
+        sort_func: sort_func_t,
+        compare_func: bucket_compare_func_t,
+        renumber: bool,
+    );
+}
+unsafe extern "C" {
+    pub fn zend_sort(
+        base: *mut ::std::os::raw::c_void,
+        nmemb: usize,
+        siz: usize,
+        cmp: compare_func_t,
+        swp: swap_func_t,
+    );
+}
+unsafe extern "C" {
+    pub fn zend_compare(op1: *mut zval, op2: *mut zval) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn _convert_to_string(op: *mut zval);
+}
+unsafe extern "C" {
+    pub fn convert_to_array(op: *mut zval);
+}
+unsafe extern "C" {
+    pub fn convert_to_boolean(op: *mut zval);
+}
+unsafe extern "C" {
+    pub fn convert_to_double(op: *mut zval);
+}
+unsafe extern "C" {
+    pub fn convert_to_long(op: *mut zval);
+}
+unsafe extern "C" {
+    pub fn add_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn sub_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn mul_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn div_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn mod_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn pow_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn concat_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn bitwise_and_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn bitwise_or_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn bitwise_xor_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn shift_left_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn shift_right_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+pub type copy_ctor_func_t = ::std::option::Option<unsafe extern "C" fn(pElement: *mut zval)>;
+unsafe extern "C" {
+    pub fn zend_hash_merge(
+        target: *mut HashTable,
+        source: *const HashTable,
+        pCopyConstructor: copy_ctor_func_t,
+        overwrite: bool,
+    );
+}
+unsafe extern "C" {
+    pub fn zval_add_ref(p: *mut zval);
+}
 unsafe extern "C" {
     pub fn zend_hash_str_find(
         ht: *const HashTable,
@@ -1408,6 +1638,42 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn zend_std_get_properties(object: *mut zend_object) -> *mut HashTable;
 }
+unsafe extern "C" {
+    pub fn zend_std_cast_object_tostring(
+        readobj: *mut zend_object,
+        writeobj: *mut zval,
+        type_: ::std::os::raw::c_int,
+    ) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn zend_std_read_dimension(
+        object: *mut zend_object,
+        offset: *mut zval,
+        type_: ::std::os::raw::c_int,
+        rv: *mut zval,
+    ) -> *mut zval;
+}
+unsafe extern "C" {
+    pub fn zend_std_write_dimension(object: *mut zend_object, offset: *mut zval, value: *mut zval);
+}
+unsafe extern "C" {
+    pub fn zend_std_has_dimension(
+        object: *mut zend_object,
+        offset: *mut zval,
+        check_empty: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn zend_std_unset_dimension(object: *mut zend_object, offset: *mut zval);
+}
+unsafe extern "C" {
+    pub fn zend_std_get_property_ptr_ptr(
+        object: *mut zend_object,
+        member: *mut zend_string,
+        type_: ::std::os::raw::c_int,
+        cache_slot: *mut *mut ::std::os::raw::c_void,
+    ) -> *mut zval;
+}
 unsafe extern "C" {
     pub fn zend_std_read_property(
         object: *mut zend_object,
@@ -1433,6 +1699,13 @@ unsafe extern "C" {
         cache_slot: *mut *mut ::std::os::raw::c_void,
     ) -> ::std::os::raw::c_int;
 }
+unsafe extern "C" {
+    pub fn zend_std_unset_property(
+        object: *mut zend_object,
+        member: *mut zend_string,
+        cache_slot: *mut *mut ::std::os::raw::c_void,
+    );
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct _zend_strtod_bigint {
@@ -1570,6 +1843,15 @@ pub struct _zend_property_info {
     pub hooks: *mut *mut zend_function,
 }
 #[repr(C)]
+pub struct _zend_class_constant {
+    pub value: zval,
+    pub doc_comment: *mut zend_string,
+    pub attributes: *mut HashTable,
+    pub ce: *mut zend_class_entry,
+    pub type_: zend_type,
+}
+pub type zend_class_constant = _zend_class_constant;
+#[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct _zend_internal_arg_info {
     pub name: *const ::std::os::raw::c_char,
@@ -1762,6 +2044,9 @@ pub struct zend_atomic_bool_s {
     pub value: bool,
 }
 pub type zend_atomic_bool = zend_atomic_bool_s;
+unsafe extern "C" {
+    pub fn zend_atomic_bool_load(obj: *const zend_atomic_bool) -> bool;
+}
 unsafe extern "C" {
     pub fn zend_atomic_bool_store(obj: *mut zend_atomic_bool, desired: bool);
 }
@@ -1804,6 +2089,11 @@ pub struct _zend_encoding {
     _unused: [u8; 0],
 }
 pub type zend_encoding = _zend_encoding;
+unsafe extern "C" {
+    pub fn zend_multibyte_get_encoding_name(
+        encoding: *const zend_encoding,
+    ) -> *const ::std::os::raw::c_char;
+}
 pub type zend_arena = _zend_arena;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -1996,6 +2286,12 @@ pub struct _zend_constant {
     pub attributes: *mut HashTable,
 }
 pub type zend_constant = _zend_constant;
+unsafe extern "C" {
+    pub fn zend_get_constant_str(
+        name: *const ::std::os::raw::c_char,
+        name_len: usize,
+    ) -> *mut zval;
+}
 unsafe extern "C" {
     pub fn zend_register_bool_constant(
         name: *const ::std::os::raw::c_char,
@@ -2046,6 +2342,17 @@ unsafe extern "C" {
         string_name: *const ::std::os::raw::c_char,
     ) -> zend_result;
 }
+unsafe extern "C" {
+    pub fn zend_rebuild_symbol_table() -> *mut zend_array;
+}
+unsafe extern "C" {
+    pub fn zend_set_local_var_str(
+        name: *const ::std::os::raw::c_char,
+        len: usize,
+        value: *mut zval,
+        force: bool,
+    ) -> zend_result;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct _zend_vm_stack {
@@ -2073,6 +2380,12 @@ pub struct _zend_function_entry {
 pub type zend_function_entry = _zend_function_entry;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct smart_str {
+    pub s: *mut zend_string,
+    pub a: usize,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct _zend_fcall_info_cache {
     pub function_handler: *mut zend_function,
     pub calling_scope: *mut zend_class_entry,
@@ -2080,6 +2393,17 @@ pub struct _zend_fcall_info_cache {
     pub object: *mut zend_object,
     pub closure: *mut zend_object,
 }
+unsafe extern "C" {
+    pub static mut module_registry: HashTable;
+}
+unsafe extern "C" {
+    pub fn zend_register_functions(
+        scope: *mut zend_class_entry,
+        functions: *const zend_function_entry,
+        function_table: *mut HashTable,
+        type_: ::std::os::raw::c_int,
+    ) -> zend_result;
+}
 unsafe extern "C" {
     pub fn zend_register_module_ex(
         module: *mut zend_module_entry,
@@ -2113,6 +2437,24 @@ unsafe extern "C" {
         access_type: ::std::os::raw::c_int,
     );
 }
+unsafe extern "C" {
+    pub fn zend_declare_property_ex(
+        ce: *mut zend_class_entry,
+        name: *mut zend_string,
+        property: *mut zval,
+        access_type: ::std::os::raw::c_int,
+        doc_comment: *mut zend_string,
+    );
+}
+unsafe extern "C" {
+    pub fn zend_declare_class_constant_ex(
+        ce: *mut zend_class_entry,
+        name: *mut zend_string,
+        value: *mut zval,
+        flags: ::std::os::raw::c_int,
+        doc_comment: *mut zend_string,
+    ) -> *mut zend_class_constant;
+}
 unsafe extern "C" {
     pub fn zend_declare_class_constant(
         ce: *mut zend_class_entry,
@@ -2140,6 +2482,9 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn object_properties_init(object: *mut zend_object, class_type: *mut zend_class_entry);
 }
+unsafe extern "C" {
+    pub fn object_properties_load(object: *mut zend_object, properties: *mut HashTable);
+}
 unsafe extern "C" {
     pub fn _call_user_function_impl(
         object: *mut zval,
@@ -2217,6 +2562,21 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn php_output_write(str_: *const ::std::os::raw::c_char, len: usize) -> usize;
 }
+unsafe extern "C" {
+    pub fn php_output_start_default() -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn php_output_get_contents(p: *mut zval) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn php_output_discard() -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn php_var_dump(struc: *mut zval, level: ::std::os::raw::c_int);
+}
+unsafe extern "C" {
+    pub fn php_var_export(struc: *mut zval, level: ::std::os::raw::c_int);
+}
 pub type php_stream = _php_stream;
 pub type php_stream_wrapper = _php_stream_wrapper;
 pub type php_stream_context = _php_stream_context;
@@ -2845,6 +3205,115 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn php_unregister_url_stream_wrapper_volatile(protocol: *mut zend_string) -> zend_result;
 }
+unsafe extern "C" {
+    pub fn php_stream_context_alloc() -> *mut php_stream_context;
+}
+unsafe extern "C" {
+    pub fn php_stream_context_get_option(
+        context: *mut php_stream_context,
+        wrappername: *const ::std::os::raw::c_char,
+        optionname: *const ::std::os::raw::c_char,
+    ) -> *mut zval;
+}
+unsafe extern "C" {
+    pub fn php_stream_context_set_option(
+        context: *mut php_stream_context,
+        wrappername: *const ::std::os::raw::c_char,
+        optionname: *const ::std::os::raw::c_char,
+        optionvalue: *mut zval,
+    );
+}
+unsafe extern "C" {
+    pub fn php_stream_notification_free(notifier: *mut php_stream_notifier);
+}
+unsafe extern "C" {
+    pub fn _php_stream_open_wrapper_ex(
+        path: *const ::std::os::raw::c_char,
+        mode: *const ::std::os::raw::c_char,
+        options: ::std::os::raw::c_int,
+        opened_path: *mut *mut zend_string,
+        context: *mut php_stream_context,
+    ) -> *mut php_stream;
+}
+unsafe extern "C" {
+    pub fn _php_stream_read(
+        stream: *mut php_stream,
+        buf: *mut ::std::os::raw::c_char,
+        count: usize,
+    ) -> isize;
+}
+unsafe extern "C" {
+    pub fn _php_stream_write(
+        stream: *mut php_stream,
+        buf: *const ::std::os::raw::c_char,
+        count: usize,
+    ) -> isize;
+}
+unsafe extern "C" {
+    pub fn _php_stream_free(
+        stream: *mut php_stream,
+        close_options: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn _php_stream_flush(
+        stream: *mut php_stream,
+        closing: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn _php_stream_cast(
+        stream: *mut php_stream,
+        castas: ::std::os::raw::c_int,
+        ret: *mut *mut ::std::os::raw::c_void,
+        show_err: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn _php_stream_set_option(
+        stream: *mut php_stream,
+        option: ::std::os::raw::c_int,
+        value: ::std::os::raw::c_int,
+        ptrparam: *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn _php_stream_xport_create(
+        name: *const ::std::os::raw::c_char,
+        namelen: usize,
+        options: ::std::os::raw::c_int,
+        flags: ::std::os::raw::c_int,
+        persistent_id: *const ::std::os::raw::c_char,
+        timeout: *mut timeval,
+        context: *mut php_stream_context,
+        error_string: *mut *mut zend_string,
+        error_code: *mut ::std::os::raw::c_int,
+    ) -> *mut php_stream;
+}
+unsafe extern "C" {
+    pub fn zend_list_delete(res: *mut zend_resource);
+}
+unsafe extern "C" {
+    pub fn zend_list_close(res: *mut zend_resource);
+}
+pub type rsrc_dtor_func_t = ::std::option::Option<unsafe extern "C" fn(res: *mut zend_resource)>;
+unsafe extern "C" {
+    pub fn zend_register_list_destructors_ex(
+        ld: rsrc_dtor_func_t,
+        pld: rsrc_dtor_func_t,
+        type_name: *const ::std::os::raw::c_char,
+        module_number: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn zend_register_resource(
+        rsrc_pointer: *mut ::std::os::raw::c_void,
+        rsrc_type: ::std::os::raw::c_int,
+    ) -> *mut zend_resource;
+}
+unsafe extern "C" {
+    pub fn zend_rsrc_list_get_rsrc_type(res: *mut zend_resource) -> *const ::std::os::raw::c_char;
+}
 unsafe extern "C" {
     pub fn php_stream_locate_url_wrapper(
         path: *const ::std::os::raw::c_char,
@@ -3004,6 +3473,21 @@ unsafe extern "C" {
         module_number: ::std::os::raw::c_int,
     ) -> zend_result;
 }
+unsafe extern "C" {
+    pub fn zend_alter_ini_entry_chars(
+        name: *mut zend_string,
+        value: *const ::std::os::raw::c_char,
+        value_length: usize,
+        modify_type: ::std::os::raw::c_int,
+        stage: ::std::os::raw::c_int,
+    ) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn zend_restore_ini_entry(
+        name: *mut zend_string,
+        stage: ::std::os::raw::c_int,
+    ) -> zend_result;
+}
 pub type zend_ini_parser_cb_t = ::std::option::Option<
     unsafe extern "C" fn(
         arg1: *mut zval,
@@ -3031,6 +3515,21 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn php_info_print_table_end();
 }
+unsafe extern "C" {
+    pub fn php_info_print_table_colspan_header(
+        num_cols: ::std::os::raw::c_int,
+        header: *const ::std::os::raw::c_char,
+    );
+}
+unsafe extern "C" {
+    pub fn php_info_print_box_start(bg: ::std::os::raw::c_int);
+}
+unsafe extern "C" {
+    pub fn php_info_print_box_end();
+}
+unsafe extern "C" {
+    pub fn php_info_print_hr();
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct hostent {
@@ -3096,6 +3595,9 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub static mut zend_ce_exception: *mut zend_class_entry;
 }
+unsafe extern "C" {
+    pub static mut zend_ce_error: *mut zend_class_entry;
+}
 unsafe extern "C" {
     pub static mut zend_ce_error_exception: *mut zend_class_entry;
 }
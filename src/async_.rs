@@ -0,0 +1,175 @@
+//! Support for exporting `async fn`s to PHP.
+//!
+//! Functions and methods declared with `async fn` are driven to completion by
+//! an [`AsyncExecutor`] when called from PHP. The executor is chosen through
+//! [`set_executor`]:
+//!
+//! * [`BlockOn`] (the default) blocks the calling thread until the future
+//!   completes.
+//! * [`FiberExecutor`] suspends the calling PHP `Fiber` every time the future
+//!   is pending, letting the PHP event loop run other fibers in the meantime.
+//!   The fiber is not resumed when the future is woken, so the event loop has
+//!   to resume it. Outside of a fiber it falls back to [`BlockOn`].
+//!
+//! ```rust,no_run
+//! # #![cfg_attr(windows, feature(abi_vectorcall))]
+//! use ext_php_rs::prelude::*;
+//! use ext_php_rs::async_::{set_executor, FiberExecutor};
+//!
+//! #[php_function]
+//! pub async fn answer() -> i64 {
+//!     42
+//! }
+//!
+//! #[php_module]
+//! pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+//!     set_executor(FiberExecutor);
+//!     module.function(wrap_function!(answer))
+//! }
+//! ```
+
+use std::{
+    future::Future,
+    pin::{Pin, pin},
+    sync::Arc,
+    task::{Context, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use parking_lot::RwLock;
+
+#[cfg(php81)]
+use crate::zend::ExecutorGlobals;
+use crate::{error::Result, types::ZendCallable};
+
+/// Strategy used to drive the futures returned by exported `async fn`s.
+pub trait AsyncExecutor: Send + Sync {
+    /// Drives the given future to completion.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the future could not be driven to completion, for
+    /// example if suspending the current fiber failed. The error is thrown as
+    /// a PHP exception by the generated function.
+    fn block_on(&self, future: Pin<&mut dyn Future<Output = ()>>) -> Result<()>;
+}
+
+/// Blocks the calling thread until the future completes, parking the thread
+/// while the future is pending.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BlockOn;
+
+/// Waker unparking the thread that is blocked on the future.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+impl AsyncExecutor for BlockOn {
+    fn block_on(&self, mut future: Pin<&mut dyn Future<Output = ()>>) -> Result<()> {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        while future.as_mut().poll(&mut cx).is_pending() {
+            thread::park();
+        }
+        Ok(())
+    }
+}
+
+/// Suspends the current PHP `Fiber` while the future is pending.
+///
+/// Every time the future returns [`Poll::Pending`], `Fiber::suspend()` is
+/// called. The future is polled again once the fiber is resumed by the PHP
+/// scheduler, so the future itself must make progress on a background
+/// runtime (e.g. by awaiting a task spawned on a tokio runtime).
+///
+/// Wakes are not tracked: the future is polled with a no-op waker, and the
+/// fiber is only resumed by PHP code, as PHP fibers cannot be resumed from
+/// the threads waking futures. The scheduler must therefore resume suspended
+/// fibers on its own, e.g. on every tick of the event loop, and the future is
+/// polled each time, whether or not it was woken.
+///
+/// When called outside of a fiber, or on PHP versions without fibers, the
+/// future is driven by [`BlockOn`] instead.
+///
+/// Other fibers may use the object of an `async` method while the method is
+/// suspended, so `async` methods cannot take `&mut self`.
+///
+/// [`Poll::Pending`]: std::task::Poll::Pending
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FiberExecutor;
+
+impl AsyncExecutor for FiberExecutor {
+    fn block_on(&self, mut future: Pin<&mut dyn Future<Output = ()>>) -> Result<()> {
+        if !in_fiber() {
+            return BlockOn.block_on(future);
+        }
+
+        let suspend = ZendCallable::try_from_name("Fiber::suspend")?;
+        let mut cx = Context::from_waker(Waker::noop());
+        while future.as_mut().poll(&mut cx).is_pending() {
+            suspend.try_call(vec![])?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether the current code is running inside of a PHP `Fiber`.
+#[must_use]
+pub fn in_fiber() -> bool {
+    cfg_if::cfg_if! {
+        if #[cfg(php81)] {
+            !ExecutorGlobals::get().active_fiber.is_null()
+        } else {
+            false
+        }
+    }
+}
+
+static EXECUTOR: RwLock<Option<Arc<dyn AsyncExecutor>>> = RwLock::new(None);
+
+/// Sets the executor used to drive exported `async fn`s, replacing the
+/// previous one.
+pub fn set_executor(executor: impl AsyncExecutor + 'static) {
+    *EXECUTOR.write() = Some(Arc::new(executor));
+}
+
+/// Drives the future to completion using the executor set through
+/// [`set_executor`], or [`BlockOn`] if no executor was set.
+///
+/// This is used by the code generated for exported `async fn`s.
+///
+/// # Errors
+///
+/// Returns the error of the executor if the future could not be driven to
+/// completion.
+///
+/// # Panics
+///
+/// Panics if the executor returns successfully without completing the
+/// future.
+pub fn block_on<F: Future>(future: F) -> Result<F::Output> {
+    // The lock is not held while the future runs, as the future may call back
+    // into PHP code which sets the executor.
+    let executor = EXECUTOR.read().clone();
+
+    let mut output = None;
+    {
+        let future = pin!(async {
+            output = Some(future.await);
+        });
+        match executor {
+            Some(executor) => executor.block_on(future)?,
+            None => BlockOn.block_on(future)?,
+        }
+    }
+
+    Ok(output.expect("Executor returned before the future completed"))
+}
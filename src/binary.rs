@@ -16,7 +16,7 @@ use crate::{
     convert::{FromZval, IntoZval},
     error::{Error, Result},
     flags::DataType,
-    types::Zval,
+    types::{ZendStr, Zval},
 };
 
 /// Acts as a wrapper around [`Vec<T>`] where `T` implements [`Pack`]. Primarily
@@ -96,6 +96,36 @@ impl<T: Pack> FromIterator<T> for Binary<T> {
     }
 }
 
+/// Boxed byte slices are converted into binary strings, without validating
+/// them as UTF-8.
+impl IntoZval for Box<[u8]> {
+    const TYPE: DataType = DataType::String;
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        zv.set_binary(self.into_vec());
+        Ok(())
+    }
+}
+
+impl FromZval<'_> for Box<[u8]> {
+    const TYPE: DataType = DataType::String;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        zval.zend_str().map(|str| str.as_bytes().into())
+    }
+}
+
+/// Borrows the bytes of a string without copying them. Unlike `&str`, the
+/// string does not have to be valid UTF-8.
+impl<'a> FromZval<'a> for &'a [u8] {
+    const TYPE: DataType = DataType::String;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.zend_str().map(ZendStr::as_bytes)
+    }
+}
+
 /// Used to convert between Zend binary strings and vectors. Useful in
 /// conjunction with the [`pack`] and [`unpack`] functions built-in to PHP.
 ///
@@ -30,6 +30,7 @@ use std::{
 };
 
 use super::alloc::efree;
+use crate::rc::PhpRc;
 
 /// A pointer type for heap allocation using the Zend memory manager.
 ///
@@ -68,6 +69,41 @@ impl<T: ZBoxable> ZBox<T> {
     }
 }
 
+impl<T: ZBoxable + PhpRc> ZBox<T> {
+    /// Returns a new box pointing to the same value, incrementing its
+    /// reference counter instead of duplicating the value. Values which are
+    /// not reference counted, like interned strings, are shared as is.
+    ///
+    /// Each box releases its reference when dropped, so the value is freed
+    /// once the last box or zval referencing it is gone. Use [`Clone`], where
+    /// implemented, to duplicate the value instead.
+    ///
+    /// Shared strings and arrays should not be modified, as PHP only modifies
+    /// them in place while [`PhpRc::get_count`] is 1.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::{rc::PhpRc, types::ZendStr};
+    ///
+    /// let s = ZendStr::new("Hello, world!", false);
+    /// let shared = s.share();
+    /// assert_eq!(s.get_count(), 2);
+    /// drop(s);
+    /// assert_eq!(shared.get_count(), 1);
+    /// ```
+    #[must_use]
+    pub fn share(&self) -> Self {
+        let mut ptr = self.0;
+        // SAFETY: The value is kept alive by the reference held by `self`.
+        let value = unsafe { ptr.as_mut() };
+        if !value.get_rc().is_immutable() {
+            value.inc_count();
+        }
+        Self(ptr)
+    }
+}
+
 impl<T: ZBoxable> Drop for ZBox<T> {
     #[inline]
     fn drop(&mut self) {
@@ -137,3 +173,43 @@ pub unsafe trait ZBoxable {
         unsafe { efree(ptr::from_mut(self).cast::<u8>()) };
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+mod tests {
+    use super::*;
+    use crate::{
+        embed::Embed,
+        types::{ZendHashTable, ZendObject, ZendStr},
+    };
+
+    #[test]
+    fn test_share() {
+        Embed::run(|| {
+            let s = ZendStr::new("Hello, world!", false);
+            let shared = s.share();
+            assert_eq!(s.get_count(), 2);
+            assert!(ptr::eq(&raw const *s, &raw const *shared));
+            drop(s);
+            assert_eq!(shared.get_count(), 1);
+
+            let interned = ZendStr::new_interned("PHP", false);
+            let shared = interned.share();
+            assert_eq!(interned.get_count(), shared.get_count());
+
+            let mut ht = ZendHashTable::new();
+            ht.push(1).expect("Failed to push value");
+            let shared = ht.share();
+            assert_eq!(ht.get_count(), 2);
+            drop(ht);
+            assert_eq!(shared.get_count(), 1);
+            assert_eq!(shared.len(), 1);
+
+            let obj = ZendObject::new_stdclass();
+            let shared = obj.share();
+            assert_eq!(obj.get_count(), 2);
+            drop(obj);
+            assert_eq!(shared.get_count(), 1);
+        });
+    }
+}
@@ -1,15 +1,16 @@
-use std::{ffi::CString, mem::MaybeUninit, ptr, rc::Rc};
+use std::{mem::MaybeUninit, ptr, rc::Rc};
 
 use crate::{
-    builders::FunctionBuilder,
+    builders::{FunctionBuilder, Requirement},
     class::{ClassEntryInfo, ConstructorMeta, ConstructorResult, RegisteredClass},
     convert::{IntoZval, IntoZvalDyn},
-    describe::DocComments,
+    describe::{DocComments, doc_block},
     error::{Error, Result},
     exception::PhpException,
     ffi::{
-        zend_declare_class_constant, zend_declare_property, zend_do_implement_interface,
-        zend_register_internal_class_ex, zend_register_internal_interface,
+        ZEND_ACC_PUBLIC, zend_declare_class_constant_ex, zend_declare_property_ex,
+        zend_do_implement_interface, zend_register_internal_class_ex,
+        zend_register_internal_interface, zend_string,
     },
     flags::{ClassFlags, MethodFlags, PropertyFlags},
     types::{ZendClassObject, ZendObject, ZendStr, Zval},
@@ -33,12 +34,14 @@ pub struct ClassBuilder {
     ce: ClassEntry,
     pub(crate) extends: Option<ClassEntryInfo>,
     pub(crate) interfaces: Vec<ClassEntryInfo>,
+    pub(crate) optional_interfaces: Vec<String>,
     pub(crate) methods: Vec<(FunctionBuilder<'static>, MethodFlags)>,
     object_override: Option<unsafe extern "C" fn(class_type: *mut ClassEntry) -> *mut ZendObject>,
     pub(crate) properties: Vec<(String, PropertyFlags, PropertyDefault, DocComments)>,
     pub(crate) constants: Vec<ConstantEntry>,
     register: Option<fn(&'static mut ClassEntry)>,
     pub(crate) docs: DocComments,
+    requirements: Vec<Requirement>,
 }
 
 impl ClassBuilder {
@@ -56,12 +59,14 @@ impl ClassBuilder {
             ce: unsafe { MaybeUninit::zeroed().assume_init() },
             extends: None,
             interfaces: vec![],
+            optional_interfaces: vec![],
             methods: vec![],
             object_override: None,
             properties: vec![],
             constants: vec![],
             register: None,
             docs: &[],
+            requirements: vec![],
         }
     }
 
@@ -95,6 +100,25 @@ impl ClassBuilder {
         self
     }
 
+    /// Implements an interface on the class if an interface with the given
+    /// name exists when the class is registered, e.g. an interface provided by
+    /// an optional extension. The interface is looked up when the class is
+    /// registered at module startup, so the extension providing it must be
+    /// loaded first.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - Name of the interface to implement on the class.
+    ///
+    /// # Panics
+    ///
+    /// Panics when registering the class if a class with the given name exists
+    /// but is not an interface.
+    pub fn implements_if_exists<T: Into<String>>(mut self, name: T) -> Self {
+        self.optional_interfaces.push(name.into());
+        self
+    }
+
     /// Adds a method to the class.
     ///
     /// # Parameters
@@ -313,6 +337,24 @@ impl ClassBuilder {
         self
     }
 
+    /// Only registers the class if the given requirement is met at module
+    /// startup.
+    ///
+    /// # Parameters
+    ///
+    /// * `requirement` - The requirement of the class.
+    pub fn requires(mut self, requirement: Requirement) -> Self {
+        self.requirements.push(requirement);
+        self
+    }
+
+    /// Returns whether all requirements of the class are met, see
+    /// [`ClassBuilder::requires`].
+    #[must_use]
+    pub fn requirements_met(&self) -> bool {
+        Requirement::all_met(&self.requirements)
+    }
+
     /// Builds and registers the class.
     ///
     /// # Errors
@@ -332,12 +374,17 @@ impl ClassBuilder {
             .methods
             .into_iter()
             .map(|(method, flags)| {
-                method.build().map(|mut method| {
-                    method.flags |= flags.bits();
-                    method
+                method.build_with_aliases().map(|methods| {
+                    methods.into_iter().map(move |mut method| {
+                        method.flags |= flags.bits();
+                        method
+                    })
                 })
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
 
         methods.push(FunctionEntry::end());
         let func = Box::into_raw(methods.into_boxed_slice()) as *const FunctionEntry;
@@ -381,34 +428,58 @@ impl ClassBuilder {
                 interface.is_interface(),
                 "Given class entry was not an interface."
             );
+            // The engine rejects implementing an interface twice, e.g. one
+            // already implemented along with a `__toString()` method.
+            if class.instance_of(interface) {
+                continue;
+            }
+
+            unsafe { zend_do_implement_interface(class, ptr::from_ref(interface).cast_mut()) };
+        }
+
+        for name in self.optional_interfaces {
+            let Some(interface) = ClassEntry::try_find(&name) else {
+                continue;
+            };
+            assert!(
+                interface.is_interface(),
+                "Class entry {name} was not an interface."
+            );
 
             unsafe { zend_do_implement_interface(class, ptr::from_ref(interface).cast_mut()) };
         }
 
-        for (name, flags, default, _) in self.properties {
+        // Doc comments of internal classes are only supported since PHP 8.4.
+        #[cfg(php84)]
+        {
+            class.doc_comment = doc_comment(self.docs);
+        }
+
+        for (name, flags, default, docs) in self.properties {
             let mut default_zval = match default {
                 Some(f) => f()?,
                 None => Zval::new(),
             };
             unsafe {
-                zend_declare_property(
+                zend_declare_property_ex(
                     class,
-                    CString::new(name.as_str())?.as_ptr(),
-                    name.len() as _,
+                    ZendStr::new_interned(&name, true).into_raw(),
                     &raw mut default_zval,
                     flags.bits().try_into()?,
+                    doc_comment(docs),
                 );
             }
         }
 
-        for (name, value, _, _) in self.constants {
+        for (name, value, docs, _) in self.constants {
             let value = Box::into_raw(Box::new(value()?));
             unsafe {
-                zend_declare_class_constant(
+                zend_declare_class_constant_ex(
                     class,
-                    CString::new(name.as_str())?.as_ptr(),
-                    name.len(),
+                    ZendStr::new_interned(&name, true).into_raw(),
                     value,
+                    ZEND_ACC_PUBLIC.try_into()?,
+                    doc_comment(docs),
                 );
             };
         }
@@ -427,6 +498,14 @@ impl ClassBuilder {
     }
 }
 
+/// Creates the persistent doc comment string of a class or class member, which
+/// is null if there are no comments.
+pub(super) fn doc_comment(docs: DocComments) -> *mut zend_string {
+    doc_block(docs).map_or(ptr::null_mut(), |docs| {
+        ZendStr::new_interned(docs, true).into_raw()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test::test_function;
@@ -1,7 +1,7 @@
 use std::{ffi::CString, ptr};
 
 use crate::{
-    builders::FunctionBuilder,
+    builders::{FunctionBuilder, Requirement},
     convert::IntoZval,
     describe::DocComments,
     enum_::{Discriminant, EnumCase},
@@ -21,6 +21,7 @@ pub struct EnumBuilder {
     pub(crate) datatype: DataType,
     register: Option<fn(&'static mut ClassEntry)>,
     pub(crate) docs: DocComments,
+    requirements: Vec<Requirement>,
 }
 
 impl EnumBuilder {
@@ -33,6 +34,7 @@ impl EnumBuilder {
             datatype: DataType::Undef,
             register: None,
             docs: DocComments::default(),
+            requirements: Vec::default(),
         }
     }
 
@@ -79,6 +81,24 @@ impl EnumBuilder {
         self
     }
 
+    /// Only registers the enum if the given requirement is met at module
+    /// startup.
+    ///
+    /// # Parameters
+    ///
+    /// * `requirement` - The requirement of the enum.
+    pub fn requires(mut self, requirement: Requirement) -> Self {
+        self.requirements.push(requirement);
+        self
+    }
+
+    /// Returns whether all requirements of the enum are met, see
+    /// [`EnumBuilder::requires`].
+    #[must_use]
+    pub fn requirements_met(&self) -> bool {
+        Requirement::all_met(&self.requirements)
+    }
+
     /// Registers the enum with PHP.
     ///
     /// # Panics
@@ -95,12 +115,17 @@ impl EnumBuilder {
             .methods
             .into_iter()
             .map(|(method, flags)| {
-                method.build().map(|mut method| {
-                    method.flags |= flags.bits();
-                    method
+                method.build_with_aliases().map(|methods| {
+                    methods.into_iter().map(move |mut method| {
+                        method.flags |= flags.bits();
+                        method
+                    })
                 })
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
         methods.push(FunctionEntry::end());
 
         let class = unsafe {
@@ -111,6 +136,12 @@ impl EnumBuilder {
             )
         };
 
+        // Doc comments of internal classes are only supported since PHP 8.4.
+        #[cfg(php84)]
+        unsafe {
+            (*class).doc_comment = super::class::doc_comment(self.docs);
+        }
+
         for case in self.cases {
             let name = ZendStr::new_interned(case.name, true);
             let value = match &case.discriminant {
@@ -1,5 +1,6 @@
 use crate::{
     args::{Arg, ArgInfo},
+    builders::Requirement,
     describe::DocComments,
     error::{Error, Result},
     flags::{DataType, MethodFlags},
@@ -34,6 +35,8 @@ pub struct FunctionBuilder<'a> {
     ret_as_ref: bool,
     pub(crate) ret_as_null: bool,
     pub(crate) docs: DocComments,
+    pub(crate) aliases: Vec<String>,
+    pub(crate) requirements: Vec<Requirement>,
 }
 
 impl<'a> FunctionBuilder<'a> {
@@ -69,6 +72,8 @@ impl<'a> FunctionBuilder<'a> {
             ret_as_ref: false,
             ret_as_null: false,
             docs: &[],
+            aliases: vec![],
+            requirements: vec![],
         }
     }
 
@@ -98,6 +103,8 @@ impl<'a> FunctionBuilder<'a> {
             ret_as_ref: false,
             ret_as_null: false,
             docs: &[],
+            aliases: vec![],
+            requirements: vec![],
         }
     }
 
@@ -138,7 +145,8 @@ impl<'a> FunctionBuilder<'a> {
     pub fn returns(mut self, type_: DataType, as_ref: bool, allow_null: bool) -> Self {
         self.retval = Some(type_);
         self.ret_as_ref = as_ref;
-        self.ret_as_null = allow_null && type_ != DataType::Void && type_ != DataType::Mixed;
+        self.ret_as_null =
+            allow_null && !matches!(type_, DataType::Void | DataType::Never | DataType::Mixed);
         self
     }
 
@@ -153,8 +161,34 @@ impl<'a> FunctionBuilder<'a> {
         self
     }
 
+    /// Registers the function under an additional name. The alias shares the
+    /// handler, arguments and return type of the function.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The alternative name of the function.
+    pub fn alias<T: Into<String>>(mut self, name: T) -> Self {
+        self.aliases.push(name.into());
+        self
+    }
+
+    /// Only registers the function if the given requirement is met at module
+    /// startup. Has no effect on methods, which are registered with their
+    /// class.
+    ///
+    /// # Parameters
+    ///
+    /// * `requirement` - The requirement of the function.
+    pub fn requires(mut self, requirement: Requirement) -> Self {
+        self.requirements.push(requirement);
+        self
+    }
+
     /// Builds the function converting it into a Zend function entry.
     ///
+    /// Aliases of the function are not included, see
+    /// [`FunctionBuilder::build_with_aliases`].
+    ///
     /// Returns a result containing the function entry if successful.
     ///
     /// # Errors
@@ -200,9 +234,37 @@ impl<'a> FunctionBuilder<'a> {
         );
 
         self.function.fname = CString::new(self.name)?.into_raw();
+        // Doc comments of internal functions are only supported since PHP 8.4.
+        #[cfg(php84)]
+        if let Some(docs) = crate::describe::doc_block(self.docs) {
+            self.function.doc_comment = CString::new(docs)?.into_raw();
+        }
         self.function.num_args = (args.len() - 1).try_into()?;
         self.function.arg_info = Box::into_raw(args.into_boxed_slice()) as *const ArgInfo;
 
         Ok(self.function)
     }
+
+    /// Builds the function converting it into Zend function entries, one for
+    /// the function followed by one for each of its aliases.
+    ///
+    /// # Errors
+    ///
+    /// * If the function could not be built, see [`FunctionBuilder::build`].
+    /// * `Error::InvalidCString` - If an alias is not a valid C string.
+    pub fn build_with_aliases(mut self) -> Result<Vec<FunctionEntry>> {
+        let aliases = mem::take(&mut self.aliases);
+        let function = self.build()?;
+
+        let mut entries = Vec::with_capacity(aliases.len() + 1);
+        entries.push(function);
+        for alias in aliases {
+            entries.push(FunctionEntry {
+                fname: CString::new(alias)?.into_raw(),
+                ..function
+            });
+        }
+
+        Ok(entries)
+    }
 }
@@ -8,6 +8,7 @@ mod function;
 #[cfg(all(php82, feature = "embed"))]
 mod ini;
 mod module;
+mod requirement;
 #[cfg(feature = "embed")]
 mod sapi;
 
@@ -18,5 +19,6 @@ pub use function::FunctionBuilder;
 #[cfg(all(php82, feature = "embed"))]
 pub use ini::IniBuilder;
 pub use module::{ModuleBuilder, ModuleStartup};
+pub use requirement::Requirement;
 #[cfg(feature = "embed")]
 pub use sapi::SapiBuilder;
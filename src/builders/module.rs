@@ -1,15 +1,21 @@
 use std::{convert::TryFrom, ffi::CString, mem, ptr};
 
-use super::{ClassBuilder, FunctionBuilder};
+use parking_lot::{RwLock, const_rwlock};
+
+use super::{ClassBuilder, FunctionBuilder, Requirement};
 use crate::{
     PHP_DEBUG, PHP_ZTS,
     class::RegisteredClass,
     constant::IntoConst,
     describe::DocComments,
-    error::Result,
-    ffi::{ZEND_MODULE_API_NO, ext_php_rs_php_build_id},
-    flags::ClassFlags,
-    zend::{FunctionEntry, ModuleEntry},
+    error::{Error, Result},
+    ffi::{
+        ZEND_MODULE_API_NO, ZEND_RESULT_CODE_SUCCESS, ext_php_rs_php_build_id,
+        zend_register_functions,
+    },
+    flags::{ClassFlags, MethodFlags},
+    types::PhpResource,
+    zend::{FunctionEntry, ModuleEntry, ce, engine, request},
 };
 #[cfg(feature = "enum")]
 use crate::{builders::enum_builder::EnumBuilder, enum_::RegisteredEnum};
@@ -46,18 +52,23 @@ use crate::{builders::enum_builder::EnumBuilder, enum_::RegisteredEnum};
 pub struct ModuleBuilder<'a> {
     pub(crate) name: String,
     pub(crate) version: String,
+    namespace: Option<String>,
     pub(crate) functions: Vec<FunctionBuilder<'a>>,
     pub(crate) constants: Vec<(String, Box<dyn IntoConst + Send>, DocComments)>,
     pub(crate) classes: Vec<fn() -> ClassBuilder>,
     pub(crate) interfaces: Vec<fn() -> ClassBuilder>,
     #[cfg(feature = "enum")]
     pub(crate) enums: Vec<fn() -> EnumBuilder>,
+    resources: Vec<(String, ResourceRegistration)>,
     startup_func: Option<StartupShutdownFunc>,
     shutdown_func: Option<StartupShutdownFunc>,
     request_startup_func: Option<StartupShutdownFunc>,
     request_shutdown_func: Option<StartupShutdownFunc>,
     post_deactivate_func: Option<unsafe extern "C" fn() -> i32>,
     info_func: Option<InfoFunc>,
+    raw_functions: Vec<FunctionEntry>,
+    startup_hooks: Vec<StartupHook>,
+    entry_modifiers: Vec<fn(&mut ModuleEntry)>,
 }
 
 impl ModuleBuilder<'_> {
@@ -163,6 +174,87 @@ impl ModuleBuilder<'_> {
         self
     }
 
+    /// Adds a hook which is called during module startup, after the items of
+    /// the module have been registered.
+    ///
+    /// The hook is passed an empty builder, to which functions, constants,
+    /// classes, interfaces and enums can be added. These are registered once
+    /// the hook returns, which allows registering items depending on the
+    /// environment the extension was loaded into, e.g. on ini settings or
+    /// other extensions. Other settings of the returned builder, like startup
+    /// functions, are ignored.
+    ///
+    /// Items added by hooks are not included in stubs generated by `cargo
+    /// php stubs`.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - The function adding items to the builder.
+    pub fn startup_hook(mut self, hook: StartupHook) -> Self {
+        self.startup_hooks.push(hook);
+        self
+    }
+
+    /// Adds raw function entries to the extension, e.g. entries of functions
+    /// implemented in C. Entries after the first one without a name are
+    /// ignored, so terminated tables can be passed as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The function entries to be added to the extension.
+    ///
+    /// # Safety
+    ///
+    /// The entries must be valid function entries, whose names, handlers and
+    /// argument information live as long as the extension is loaded.
+    pub unsafe fn raw_functions(mut self, entries: &[FunctionEntry]) -> Self {
+        self.raw_functions.extend(
+            entries
+                .iter()
+                .take_while(|entry| !entry.fname.is_null())
+                .copied(),
+        );
+        self
+    }
+
+    /// Adds a function which modifies the module entry once it has been
+    /// built, allowing to set fields which are not supported by the builder,
+    /// e.g. module dependencies or ini entries. Modifiers are called in the
+    /// order they were added.
+    ///
+    /// The module and request shutdown functions of the entry call the
+    /// functions set on the builder and then track the end of the engine and
+    /// of the request, e.g. for
+    /// [`PersistentCallable`](crate::types::PersistentCallable). A modifier
+    /// replacing them disables this tracking, so these functions should be set
+    /// with [`ModuleBuilder::shutdown_function`] and
+    /// [`ModuleBuilder::request_shutdown_function`] instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `modifier` - The function modifying the module entry.
+    pub fn entry_modifier(mut self, modifier: fn(&mut ModuleEntry)) -> Self {
+        self.entry_modifiers.push(modifier);
+        self
+    }
+
+    /// Sets the default namespace of the functions and constants of the
+    /// extension. The names of functions, aliases and constants which are not
+    /// namespaced already are prefixed with the namespace when the module is
+    /// built.
+    ///
+    /// Classes, interfaces and enums are not affected, as their names are also
+    /// used in type declarations. Use `#[php(namespace = "...")]` on them
+    /// instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace, e.g. `Vendor\Package`.
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
     /// Adds a function to the extension.
     ///
     /// # Arguments
@@ -218,6 +310,10 @@ impl ModuleBuilder<'_> {
                 builder = modifier(builder);
             }
 
+            for requirement in T::REQUIREMENTS {
+                builder = builder.requires(*requirement);
+            }
+
             builder = builder.flags(ClassFlags::Interface);
             builder
                 .object_override::<T>()
@@ -246,13 +342,35 @@ impl ModuleBuilder<'_> {
             for interface in T::IMPLEMENTS {
                 builder = builder.implements(*interface);
             }
+            for interface in T::OPTIONAL_IMPLEMENTS {
+                builder = builder.implements_if_exists(*interface);
+            }
+            if let Some(array_access) = T::ARRAY_ACCESS {
+                for (method, flags) in array_access.method_builders() {
+                    builder = builder.method(method, flags);
+                }
+                builder = builder.implements((ce::arrayaccess, "ArrayAccess"));
+            }
+            if let Some(to_string) = T::TO_STRING_HANDLER {
+                // A `__toString()` method declared by the class is kept.
+                if !builder
+                    .methods
+                    .iter()
+                    .any(|(method, _)| method.name.eq_ignore_ascii_case("__toString"))
+                {
+                    builder = builder.method(to_string.method_builder(), MethodFlags::Public);
+                }
+                builder = builder.implements((ce::stringable, "Stringable"));
+            }
             for (name, value, docs) in T::constants() {
                 builder = builder
                     .dyn_constant(*name, *value, docs)
                     .expect("Failed to register constant");
             }
-            for (name, prop_info) in T::get_properties() {
-                builder = builder.property(name, prop_info.flags, None, prop_info.docs);
+            if !T::PROXY_PROPERTIES {
+                for (name, prop_info) in T::get_properties() {
+                    builder = builder.property(name, prop_info.flags, None, prop_info.docs);
+                }
             }
             for (name, flags, default, docs) in T::static_properties() {
                 let default_fn = default.map(|v| {
@@ -264,6 +382,9 @@ impl ModuleBuilder<'_> {
             if let Some(modifier) = T::BUILDER_MODIFIER {
                 builder = modifier(builder);
             }
+            for requirement in T::REQUIREMENTS {
+                builder = builder.requires(*requirement);
+            }
 
             builder
                 .object_override::<T>()
@@ -289,6 +410,9 @@ impl ModuleBuilder<'_> {
             for (method, flags) in T::method_builders() {
                 builder = builder.method(method, flags);
             }
+            for requirement in T::REQUIREMENTS {
+                builder = builder.requires(*requirement);
+            }
 
             builder
                 .registration(|ce| {
@@ -299,50 +423,201 @@ impl ModuleBuilder<'_> {
 
         self
     }
+
+    /// Registers the resource type of `T` at module startup, see
+    /// [`PhpResource`].
+    ///
+    /// Resource types which are not registered at module startup are
+    /// registered the first time a resource of the type is created, which is
+    /// not possible in thread safe builds of PHP.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the resource type, returned by
+    ///   `get_resource_type()` in PHP.
+    pub fn resource<T: 'static>(mut self, name: impl Into<String>) -> Self {
+        self.resources
+            .push((name.into(), PhpResource::<T>::register));
+        self
+    }
+}
+
+impl ModuleBuilder<'_> {
+    /// Prefixes the names of the functions and constants which are not
+    /// namespaced with the default namespace of the module, if any.
+    pub(crate) fn apply_namespace(&mut self) {
+        let Some(namespace) = self.namespace.take() else {
+            return;
+        };
+        let namespace = namespace.trim_matches('\\');
+        let qualify = |name: &mut String| {
+            if !name.contains('\\') {
+                *name = format!("{namespace}\\{name}");
+            }
+        };
+
+        for func in &mut self.functions {
+            qualify(&mut func.name);
+            func.aliases.iter_mut().for_each(qualify);
+        }
+        for (name, _, _) in &mut self.constants {
+            qualify(name);
+        }
+    }
 }
 
 /// Artifacts from the [`ModuleBuilder`] that should be revisited inside the
 /// extension startup function.
 pub struct ModuleStartup {
+    name: String,
+    version: String,
+    namespace: Option<String>,
+    functions: Vec<ConditionalFunctions>,
     constants: Vec<(String, Box<dyn IntoConst + Send>)>,
     classes: Vec<fn() -> ClassBuilder>,
     interfaces: Vec<fn() -> ClassBuilder>,
     #[cfg(feature = "enum")]
     enums: Vec<fn() -> EnumBuilder>,
+    resources: Vec<(String, ResourceRegistration)>,
+    hooks: Vec<StartupHook>,
 }
 
 impl ModuleStartup {
+    /// Collects the items of the builder which are registered inside the
+    /// extension startup function. Functions are only collected if they are
+    /// not part of the module entry, i.e. if they have requirements or
+    /// `all_functions` is set.
+    fn new(builder: &mut ModuleBuilder, all_functions: bool) -> Result<Self> {
+        let namespace = builder.namespace.clone();
+        builder.apply_namespace();
+        let (functions, conditional): (Vec<_>, Vec<_>) = mem::take(&mut builder.functions)
+            .into_iter()
+            .partition(|func| !all_functions && func.requirements.is_empty());
+        builder.functions = functions;
+        let mut conditional = conditional
+            .into_iter()
+            .map(ConditionalFunctions::new)
+            .collect::<Result<Vec<_>>>()?;
+        if all_functions {
+            conditional.push(ConditionalFunctions {
+                requirements: vec![],
+                entries: mem::take(&mut builder.raw_functions),
+            });
+        }
+
+        Ok(Self {
+            name: builder.name.clone(),
+            version: builder.version.clone(),
+            namespace,
+            functions: conditional,
+            constants: mem::take(&mut builder.constants)
+                .into_iter()
+                .map(|(n, v, _)| (n, v))
+                .collect(),
+            classes: mem::take(&mut builder.classes),
+            interfaces: mem::take(&mut builder.interfaces),
+            #[cfg(feature = "enum")]
+            enums: mem::take(&mut builder.enums),
+            resources: mem::take(&mut builder.resources),
+            hooks: mem::take(&mut builder.startup_hooks),
+        })
+    }
+
     /// Completes startup of the module. Should only be called inside the module
     /// startup function.
     ///
     /// # Errors
     ///
-    /// * Returns an error if a constant could not be registered.
+    /// * Returns an error if a function or constant could not be registered.
     ///
     /// # Panics
     ///
     /// * Panics if a class could not be registered.
-    pub fn startup(self, _ty: i32, mod_num: i32) -> Result<()> {
+    pub fn startup(self, ty: i32, mod_num: i32) -> Result<()> {
+        for functions in self.functions {
+            functions.register(ty)?;
+        }
+
         for (name, val) in self.constants {
             val.register_constant(&name, mod_num)?;
         }
 
-        self.classes.into_iter().map(|c| c()).for_each(|c| {
-            c.register().expect("Failed to build class");
-        });
+        for (name, register) in self.resources {
+            register(&name, mod_num);
+        }
 
-        self.interfaces.into_iter().map(|c| c()).for_each(|c| {
-            c.register().expect("Failed to build interface");
-        });
+        self.classes
+            .into_iter()
+            .map(|c| c())
+            .filter(ClassBuilder::requirements_met)
+            .for_each(|c| {
+                c.register().expect("Failed to build class");
+            });
+
+        self.interfaces
+            .into_iter()
+            .map(|c| c())
+            .filter(ClassBuilder::requirements_met)
+            .for_each(|c| {
+                c.register().expect("Failed to build interface");
+            });
 
         #[cfg(feature = "enum")]
         self.enums
             .into_iter()
             .map(|builder| builder())
+            .filter(EnumBuilder::requirements_met)
             .for_each(|e| {
                 e.register().expect("Failed to build enum");
             });
 
+        for hook in self.hooks {
+            let mut builder = ModuleBuilder::new(self.name.clone(), self.version.clone());
+            builder.namespace.clone_from(&self.namespace);
+            let mut builder = hook(builder);
+            Self::new(&mut builder, true)?.startup(ty, mod_num)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Functions which are only registered at module startup if their
+/// requirements are met, instead of being part of the module entry.
+struct ConditionalFunctions {
+    requirements: Vec<Requirement>,
+    entries: Vec<FunctionEntry>,
+}
+
+// SAFETY: The entries only point to the leaked names and argument infos of the
+// functions, which are never mutated.
+unsafe impl Send for ConditionalFunctions {}
+
+impl ConditionalFunctions {
+    fn new(mut func: FunctionBuilder) -> Result<Self> {
+        let requirements = mem::take(&mut func.requirements);
+        Ok(Self {
+            requirements,
+            entries: func.build_with_aliases()?,
+        })
+    }
+
+    fn register(self, ty: i32) -> Result<()> {
+        if !Requirement::all_met(&self.requirements) {
+            return Ok(());
+        }
+
+        let mut entries = self.entries;
+        entries.push(FunctionEntry::end());
+        // The function table keeps pointers into the entries, so they live as long
+        // as the module.
+        let entries = Box::leak(entries.into_boxed_slice());
+        let result = unsafe {
+            zend_register_functions(ptr::null_mut(), entries.as_ptr(), ptr::null_mut(), ty)
+        };
+        if result != ZEND_RESULT_CODE_SUCCESS {
+            return Err(Error::FunctionRegistrationFailure);
+        }
         Ok(())
     }
 }
@@ -350,6 +625,43 @@ impl ModuleStartup {
 /// A function to be called when the extension is starting up or shutting down.
 pub type StartupShutdownFunc = unsafe extern "C" fn(_type: i32, _module_number: i32) -> i32;
 
+/// The request shutdown function of the extension, called by
+/// [`request_shutdown`].
+static REQUEST_SHUTDOWN_FUNC: RwLock<Option<StartupShutdownFunc>> = const_rwlock(None);
+
+/// The request shutdown function registered for the module, which calls the
+/// function of the extension before marking the request as finished.
+unsafe extern "C" fn request_shutdown(type_: i32, module_number: i32) -> i32 {
+    let func = *REQUEST_SHUTDOWN_FUNC.read();
+    let result = func.map_or(ZEND_RESULT_CODE_SUCCESS, |func| unsafe {
+        func(type_, module_number)
+    });
+    request::finish_request();
+    result
+}
+
+/// The shutdown function of the extension, called by [`module_shutdown`].
+static SHUTDOWN_FUNC: RwLock<Option<StartupShutdownFunc>> = const_rwlock(None);
+
+/// The shutdown function registered for the module, which calls the function
+/// of the extension before marking the run of the engine as finished.
+unsafe extern "C" fn module_shutdown(type_: i32, module_number: i32) -> i32 {
+    let func = *SHUTDOWN_FUNC.read();
+    let result = func.map_or(ZEND_RESULT_CODE_SUCCESS, |func| unsafe {
+        func(type_, module_number)
+    });
+    engine::finish_engine();
+    result
+}
+
+/// Registers a resource type with the given name and module number, see
+/// [`ModuleBuilder::resource`].
+type ResourceRegistration = fn(&str, i32) -> i32;
+
+/// A hook called during module startup, adding items to the given builder.
+/// See [`ModuleBuilder::startup_hook`].
+pub type StartupHook = fn(ModuleBuilder<'static>) -> ModuleBuilder<'static>;
+
 /// A function to be called when `phpinfo();` is called.
 pub type InfoFunc = unsafe extern "C" fn(zend_module: *mut ModuleEntry);
 
@@ -358,32 +670,27 @@ pub type InfoFunc = unsafe extern "C" fn(zend_module: *mut ModuleEntry);
 impl TryFrom<ModuleBuilder<'_>> for (ModuleEntry, ModuleStartup) {
     type Error = crate::error::Error;
 
-    fn try_from(builder: ModuleBuilder) -> Result<Self, Self::Error> {
+    fn try_from(mut builder: ModuleBuilder) -> Result<Self, Self::Error> {
+        let startup = ModuleStartup::new(&mut builder, false)?;
         let mut functions = builder
             .functions
             .into_iter()
-            .map(FunctionBuilder::build)
-            .collect::<Result<Vec<_>>>()?;
+            .map(FunctionBuilder::build_with_aliases)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        functions.extend(builder.raw_functions);
         functions.push(FunctionEntry::end());
         let functions = Box::into_raw(functions.into_boxed_slice()) as *const FunctionEntry;
 
+        *REQUEST_SHUTDOWN_FUNC.write() = builder.request_shutdown_func;
+        *SHUTDOWN_FUNC.write() = builder.shutdown_func;
         let name = CString::new(builder.name)?.into_raw();
         let version = CString::new(builder.version)?.into_raw();
 
-        let startup = ModuleStartup {
-            constants: builder
-                .constants
-                .into_iter()
-                .map(|(n, v, _)| (n, v))
-                .collect(),
-            classes: builder.classes,
-            interfaces: builder.interfaces,
-            #[cfg(feature = "enum")]
-            enums: builder.enums,
-        };
-
         #[cfg(not(php_zts))]
-        let module_entry = ModuleEntry {
+        let mut module_entry = ModuleEntry {
             size: mem::size_of::<ModuleEntry>().try_into()?,
             zend_api: ZEND_MODULE_API_NO,
             zend_debug: u8::from(PHP_DEBUG),
@@ -393,9 +700,9 @@ impl TryFrom<ModuleBuilder<'_>> for (ModuleEntry, ModuleStartup) {
             name,
             functions,
             module_startup_func: builder.startup_func,
-            module_shutdown_func: builder.shutdown_func,
+            module_shutdown_func: Some(module_shutdown),
             request_startup_func: builder.request_startup_func,
-            request_shutdown_func: builder.request_shutdown_func,
+            request_shutdown_func: Some(request_shutdown),
             info_func: builder.info_func,
             version,
             globals_size: 0,
@@ -411,7 +718,7 @@ impl TryFrom<ModuleBuilder<'_>> for (ModuleEntry, ModuleStartup) {
         };
 
         #[cfg(php_zts)]
-        let module_entry = ModuleEntry {
+        let mut module_entry = ModuleEntry {
             size: mem::size_of::<ModuleEntry>().try_into()?,
             zend_api: ZEND_MODULE_API_NO,
             zend_debug: u8::from(PHP_DEBUG),
@@ -421,9 +728,9 @@ impl TryFrom<ModuleBuilder<'_>> for (ModuleEntry, ModuleStartup) {
             name,
             functions,
             module_startup_func: builder.startup_func,
-            module_shutdown_func: builder.shutdown_func,
+            module_shutdown_func: Some(module_shutdown),
             request_startup_func: builder.request_startup_func,
-            request_shutdown_func: builder.request_shutdown_func,
+            request_shutdown_func: Some(request_shutdown),
             info_func: builder.info_func,
             version,
             globals_size: 0,
@@ -438,6 +745,10 @@ impl TryFrom<ModuleBuilder<'_>> for (ModuleEntry, ModuleStartup) {
             build_id: unsafe { ext_php_rs_php_build_id() },
         };
 
+        for modifier in builder.entry_modifiers {
+            modifier(&mut module_entry);
+        }
+
         Ok((module_entry, startup))
     }
 }
@@ -529,6 +840,68 @@ mod tests {
         assert_eq!(builder.functions.len(), 1);
     }
 
+    #[test]
+    fn test_startup_hook() {
+        let builder = ModuleBuilder::new("test", "1.0")
+            .startup_hook(|module| module.function(FunctionBuilder::new("test", test_function)));
+        assert_eq!(builder.startup_hooks.len(), 1);
+    }
+
+    #[test]
+    fn test_raw_functions() {
+        let entries = [
+            FunctionBuilder::new("test", test_function)
+                .build()
+                .expect("Failed to build function"),
+            FunctionEntry::end(),
+            FunctionBuilder::new("ignored", test_function)
+                .build()
+                .expect("Failed to build function"),
+        ];
+        let builder = unsafe { ModuleBuilder::new("test", "1.0").raw_functions(&entries) };
+        assert_eq!(builder.raw_functions.len(), 1);
+    }
+
+    #[test]
+    fn test_resource() {
+        let builder = ModuleBuilder::new("test", "1.0").resource::<i32>("test resource");
+        assert_eq!(builder.resources.len(), 1);
+        assert_eq!(builder.resources[0].0, "test resource");
+    }
+
+    #[test]
+    fn test_entry_modifier() {
+        let builder = ModuleBuilder::new("test", "1.0").entry_modifier(|entry| {
+            entry.globals_size = 4;
+        });
+        assert_eq!(builder.entry_modifiers.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "embed")]
+    fn test_entry_modifier_applied() {
+        let builder = ModuleBuilder::new("test", "1.0")
+            .entry_modifier(|entry| entry.globals_size = 4)
+            .entry_modifier(|entry| entry.globals_size *= 2);
+        let (entry, _): (ModuleEntry, ModuleStartup) =
+            builder.try_into().expect("Failed to build module");
+        assert_eq!(entry.globals_size, 8);
+        assert!(entry.module_shutdown_func.is_some());
+        assert!(entry.request_shutdown_func.is_some());
+    }
+
+    #[test]
+    fn test_namespace() {
+        let mut builder = ModuleBuilder::new("test", "1.0")
+            .namespace("\\Vendor\\Pkg\\")
+            .function(FunctionBuilder::new("test", test_function).alias("test_alias"))
+            .function(FunctionBuilder::new("Other\\test", test_function));
+        builder.apply_namespace();
+        assert_eq!(builder.functions[0].name, "Vendor\\Pkg\\test");
+        assert_eq!(builder.functions[0].aliases, ["Vendor\\Pkg\\test_alias"]);
+        assert_eq!(builder.functions[1].name, "Other\\test");
+    }
+
     #[test]
     #[cfg(feature = "embed")]
     fn test_add_constant() {
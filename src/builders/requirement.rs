@@ -0,0 +1,76 @@
+use crate::{
+    ffi::{module_registry, zend_get_constant_str, zend_hash_str_find_ptr_lc},
+    types::{ZendLong, Zval},
+};
+
+/// A condition which must be met at module startup for an item (function,
+/// class, interface, enum or constant) to be registered with PHP.
+///
+/// Items with unmet requirements are skipped silently, as if they were never
+/// added to the module.
+///
+/// ```rust,no_run
+/// use ext_php_rs::builders::{FunctionBuilder, Requirement};
+/// # use ext_php_rs::{types::Zval, zend::ExecuteData};
+/// # extern "C" fn handler(_: &mut ExecuteData, _: &mut Zval) {}
+///
+/// let func = FunctionBuilder::new("fetch", handler)
+///     .requires(Requirement::Extension("curl"))
+///     .requires(Requirement::MinPhpVersion(80200));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Requirement {
+    /// The extension with the given name must be loaded. The name is
+    /// compared case-insensitively, as done by `extension_loaded()`.
+    ///
+    /// Only extensions started before this extension are visible, so the
+    /// required extension must be loaded first.
+    Extension(&'static str),
+    /// The running PHP version must be at least the given version, in the
+    /// format of `PHP_VERSION_ID` (e.g. `80200` for PHP 8.2.0).
+    MinPhpVersion(u32),
+}
+
+impl Requirement {
+    /// Returns whether the requirement is met by the running PHP instance.
+    ///
+    /// This must only be called after PHP has started up, e.g. inside the
+    /// module startup function.
+    #[must_use]
+    pub fn is_met(&self) -> bool {
+        match self {
+            Self::Extension(name) => {
+                // SAFETY: The module registry is initialized before any module starts up.
+                !unsafe {
+                    zend_hash_str_find_ptr_lc(
+                        &raw const module_registry,
+                        name.as_ptr().cast(),
+                        name.len(),
+                    )
+                }
+                .is_null()
+            }
+            Self::MinPhpVersion(version) => php_version_id().is_some_and(|current| {
+                u32::try_from(current).is_ok_and(|current| current >= *version)
+            }),
+        }
+    }
+
+    /// Returns whether all of the given requirements are met.
+    #[must_use]
+    pub fn all_met(requirements: &[Self]) -> bool {
+        requirements.iter().all(Self::is_met)
+    }
+}
+
+/// Returns the value of the `PHP_VERSION_ID` constant of the running PHP
+/// instance.
+fn php_version_id() -> Option<ZendLong> {
+    const NAME: &str = "PHP_VERSION_ID";
+
+    // SAFETY: The returned pointer is either null or points to the value of the
+    // constant, which lives as long as the constant table.
+    let value: Option<&Zval> =
+        unsafe { zend_get_constant_str(NAME.as_ptr().cast(), NAME.len()).as_ref() };
+    value.and_then(Zval::long)
+}
@@ -3,19 +3,27 @@
 use std::{
     collections::HashMap,
     marker::PhantomData,
+    ptr,
     sync::atomic::{AtomicPtr, Ordering},
 };
 
 use once_cell::sync::OnceCell;
 
 use crate::{
-    builders::{ClassBuilder, FunctionBuilder},
+    boxed::ZBox,
+    builders::{ClassBuilder, FunctionBuilder, Requirement},
     convert::IntoZvalDyn,
     describe::DocComments,
+    error::{Error, Result},
     exception::PhpException,
     flags::{ClassFlags, MethodFlags, PropertyFlags},
     internal::property::PropertyInfo,
-    zend::{ClassEntry, ExecuteData, ZendObjectHandlers},
+    rc::PhpRc,
+    types::{ZendCallable, ZendObject, Zval},
+    zend::{
+        ArrayAccessMethods, ClassEntry, DebugInfoHandler, DimensionHandlerTable, ExecuteData,
+        ExecutorGlobals, PropertyHandlerTable, ToStringHandler, ZendObjectHandlers,
+    },
 };
 
 /// A type alias for a tuple containing a function pointer to a class entry
@@ -38,12 +46,66 @@ pub trait RegisteredClass: Sized + 'static {
     /// Interfaces implemented by the class.
     const IMPLEMENTS: &'static [ClassEntryInfo];
 
+    /// Names of interfaces implemented by the class only if they exist when
+    /// the class is registered.
+    const OPTIONAL_IMPLEMENTS: &'static [&'static str] = &[];
+
     /// PHP flags applied to the class.
     const FLAGS: ClassFlags = ClassFlags::empty();
 
     /// Doc comments for the class.
     const DOC_COMMENTS: DocComments = &[];
 
+    /// Requirements which must be met at module startup for the class to be
+    /// registered.
+    const REQUIREMENTS: &'static [Requirement] = &[];
+
+    /// Handlers for using objects of the class as arrays, set by the
+    /// `#[php(dimensions)]` attribute for classes implementing
+    /// [`DimensionHandlers`], or by the `#[php(array_access)]` attribute for
+    /// classes implementing [`PhpArrayAccess`].
+    ///
+    /// [`DimensionHandlers`]: crate::zend::DimensionHandlers
+    /// [`PhpArrayAccess`]: crate::zend::PhpArrayAccess
+    const DIMENSION_HANDLERS: Option<DimensionHandlerTable> = None;
+
+    /// Handlers for checking and removing properties of the class, set by the
+    /// `#[php(property_handlers)]` attribute for classes implementing
+    /// [`PropertyHandlers`].
+    ///
+    /// [`PropertyHandlers`]: crate::zend::PropertyHandlers
+    const PROPERTY_HANDLERS: Option<PropertyHandlerTable> = None;
+
+    /// Handler providing the properties shown by `var_dump()` and `print_r()`,
+    /// set by the `#[php(debug_info)]` attribute for classes implementing
+    /// [`DebugInfo`].
+    ///
+    /// [`DebugInfo`]: crate::zend::DebugInfo
+    const DEBUG_INFO_HANDLER: Option<DebugInfoHandler> = None;
+
+    /// Handler converting objects of the class to strings, set by the
+    /// `#[php(to_string)]` attribute for classes implementing
+    /// [`ToPhpString`].
+    ///
+    /// [`ToPhpString`]: crate::zend::ToPhpString
+    const TO_STRING_HANDLER: Option<ToStringHandler> = None;
+
+    /// Methods of the `ArrayAccess` interface implemented by the class, set by
+    /// the `#[php(array_access)]` attribute for classes implementing
+    /// [`PhpArrayAccess`].
+    ///
+    /// [`PhpArrayAccess`]: crate::zend::PhpArrayAccess
+    const ARRAY_ACCESS: Option<ArrayAccessMethods> = None;
+
+    /// Whether the `#[php(prop)]` fields of the class are only accessed
+    /// through its property handlers, set by the `#[php(proxy_props)]`
+    /// attribute.
+    ///
+    /// The properties are then not declared with PHP, so objects do not hold
+    /// a separate zval for them which could get out of sync with the field,
+    /// e.g. when incrementing the property or appending to it.
+    const PROXY_PROPERTIES: bool = false;
+
     /// Returns a reference to the class metadata, which stores the class entry
     /// and handlers.
     ///
@@ -217,3 +279,159 @@ impl<T: RegisteredClass> ClassMetadata<T> {
         self.properties.get_or_init(T::get_properties)
     }
 }
+
+/// The class entry of a class registered by another extension or by userland
+/// code, used by the wrappers generated with the `#[php_extern_class]`
+/// attribute.
+///
+/// The class is looked up by name when it is first required. Classes of
+/// extensions live until PHP shuts down, so their class entry is cached and
+/// returned by [`get`](Self::get). Userland classes are freed at the end of
+/// each request, so they are looked up again on every use, and their class
+/// entry can only be borrowed with [`with`](Self::with).
+pub struct ExternClassEntry {
+    name: &'static str,
+    ce: AtomicPtr<ClassEntry>,
+}
+
+impl ExternClassEntry {
+    /// Creates a class entry which looks up the class with the given name.
+    #[must_use]
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            ce: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Returns the name of the class.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the class entry of a class registered by an extension, looking
+    /// it up if it has not been cached.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnknownClass`] - The class does not exist.
+    /// * [`Error::UserlandClass`] - The class was declared by userland code,
+    ///   see [`with`](Self::with).
+    pub fn get(&self) -> Result<&'static ClassEntry> {
+        let ce = self.find()?;
+        if !ce.is_internal() {
+            return Err(Error::UserlandClass(self.name.to_string()));
+        }
+        Ok(ce)
+    }
+
+    /// Calls the given function with the class entry, looking it up if it has
+    /// not been cached. Unlike [`get`](Self::get), this supports userland
+    /// classes, whose class entry is only borrowed for the call.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownClass`] if the class does not exist.
+    pub fn with<R>(&self, f: impl FnOnce(&ClassEntry) -> R) -> Result<R> {
+        Ok(f(self.find()?))
+    }
+
+    /// Returns the class entry, which must not outlive the request unless the
+    /// class is internal.
+    fn find(&self) -> Result<&'static ClassEntry> {
+        // SAFETY: Only class entries of internal classes are stored, which live
+        // until PHP shuts down.
+        if let Some(ce) = unsafe { self.ce.load(Ordering::Acquire).as_ref() } {
+            return Ok(ce);
+        }
+
+        let ce = ClassEntry::try_find(self.name)
+            .ok_or_else(|| Error::UnknownClass(self.name.to_string()))?;
+        if ce.is_internal() {
+            self.ce
+                .store(ptr::from_ref(ce).cast_mut(), Ordering::Release);
+        }
+        Ok(ce)
+    }
+
+    /// Returns a new reference to the given object if it is an instance of
+    /// the class.
+    #[must_use]
+    pub fn instance(&self, object: &ZendObject) -> Option<ZBox<ZendObject>> {
+        if !self.with(|ce| object.instance_of(ce)).unwrap_or(false) {
+            return None;
+        }
+
+        let object = ptr::from_ref(object).cast_mut();
+        // SAFETY: The reference added to the object is owned by the returned box.
+        unsafe {
+            (*object).inc_count();
+            Some(ZBox::from_raw(object))
+        }
+    }
+
+    /// Creates an instance of the class, calling its constructor with the
+    /// given parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the class does not exist or cannot be
+    /// instantiated, a parameter could not be converted or the constructor
+    /// threw an exception.
+    pub fn instantiate(&self, params: Vec<&dyn IntoZvalDyn>) -> Result<ZBox<ZendObject>> {
+        let (object, has_constructor) = self.with(|ce| {
+            // Interfaces, traits, enums and abstract classes cannot be instantiated.
+            let uninstantiable = ClassFlags::Interface
+                | ClassFlags::Trait
+                | ClassFlags::Abstract
+                | ClassFlags::ImplicitAbstractClass;
+            #[cfg(php81)]
+            let uninstantiable = uninstantiable | ClassFlags::Enum;
+            if ce.flags().intersects(uninstantiable) {
+                return Err(Error::UninstantiableClass(self.name.to_string()));
+            }
+            Ok((ZendObject::new(ce), !ce.constructor.is_null()))
+        })??;
+        if has_constructor {
+            Self::call_method(&object, "__construct", params)?;
+        }
+        Ok(object)
+    }
+
+    /// Calls the method with the given name on an object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method does not exist, a parameter could not be
+    /// converted or the method threw an exception.
+    pub fn call_method(
+        object: &ZendObject,
+        name: &str,
+        params: Vec<&dyn IntoZvalDyn>,
+    ) -> Result<Zval> {
+        let retval = object.try_call_method(name, params)?;
+        match ExecutorGlobals::take_exception() {
+            Some(e) => Err(Error::Exception(e)),
+            None => Ok(retval),
+        }
+    }
+
+    /// Calls the static method with the given name on the class.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the class or method does not exist, a parameter
+    /// could not be converted or the method threw an exception.
+    pub fn call_static(&self, name: &str, params: Vec<&dyn IntoZvalDyn>) -> Result<Zval> {
+        self.find()?;
+        let result =
+            ZendCallable::try_from_name(&format!("{}::{name}", self.name))?.try_call(params);
+        // The call fails without returning the exception if it is thrown before
+        // the method is called, e.g. for a non-static method.
+        match ExecutorGlobals::take_exception() {
+            Some(e) => Err(Error::Exception(e)),
+            None => result,
+        }
+    }
+}
@@ -1,15 +1,24 @@
-//! Types and traits for registering constants in PHP.
+//! Types and traits for registering and inspecting constants in PHP.
 
 use cfg_if::cfg_if;
-use std::ffi::CString;
+use parking_lot::Mutex;
+use std::ffi::{CString, c_char};
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 
 use super::flags::GlobalConstantFlags;
-use crate::error::Result;
+use crate::builders::Requirement;
+use crate::error::{Error, Result};
 use crate::ffi::{
-    zend_register_bool_constant, zend_register_double_constant, zend_register_long_constant,
-    zend_register_string_constant,
+    zend_constant, zend_fetch_function_str, zend_register_bool_constant,
+    zend_register_double_constant, zend_register_long_constant, zend_register_string_constant,
+    zif_handler,
 };
+use crate::flags::FunctionType;
+use crate::types::Zval;
+use crate::zend::{ExecuteData, ExecutorGlobals, Function};
+use crate::zend_fastcall;
 
 /// Implemented on types which can be registered as a constant in PHP.
 pub trait IntoConst: Debug {
@@ -190,6 +199,48 @@ impl IntoConst for bool {
     }
 }
 
+/// A constant which is only registered if its requirements are met.
+///
+/// This is used by the [`wrap_constant`] macro for constants declared with
+/// `#[php(requires_ext = "...")]` or `#[php(min_php = "...")]`.
+///
+/// [`wrap_constant`]: crate::wrap_constant
+#[derive(Debug, Clone, Copy)]
+pub struct ConditionalConst<T> {
+    value: T,
+    requirements: &'static [Requirement],
+}
+
+impl<T> ConditionalConst<T> {
+    /// Creates a constant which is only registered if all of the given
+    /// requirements are met.
+    pub const fn new(value: T, requirements: &'static [Requirement]) -> Self {
+        Self {
+            value,
+            requirements,
+        }
+    }
+}
+
+impl<T: IntoConst> IntoConst for ConditionalConst<T> {
+    fn stub_value(&self) -> String {
+        self.value.stub_value()
+    }
+
+    fn register_constant_flags(
+        &self,
+        name: &str,
+        module_number: i32,
+        flags: GlobalConstantFlags,
+    ) -> Result<()> {
+        if !Requirement::all_met(self.requirements) {
+            return Ok(());
+        }
+        self.value
+            .register_constant_flags(name, module_number, flags)
+    }
+}
+
 /// Implements the `IntoConst` trait for a given number type using a given
 /// function.
 macro_rules! into_const_num {
@@ -238,3 +289,250 @@ into_const_num!(i32, zend_register_long_constant);
 into_const_num!(i64, zend_register_long_constant);
 into_const_num!(f32, zend_register_double_constant);
 into_const_num!(f64, zend_register_double_constant);
+
+/// Module number of constants defined by PHP code.
+const USER_CONSTANT: u32 = 0x7f_ffff;
+
+/// A constant defined in PHP, as stored in the constants table.
+///
+/// All defined constants can be iterated with
+/// [`ExecutorGlobals::defined_constants`].
+pub type ZendConstant = zend_constant;
+
+impl ZendConstant {
+    /// Returns the name of the constant, or [`None`] if it is not valid
+    /// UTF-8.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        unsafe { self.name.as_ref() }?.as_str().ok()
+    }
+
+    /// Returns the value of the constant.
+    #[must_use]
+    pub fn value(&self) -> &Zval {
+        &self.value
+    }
+
+    /// Returns the flags of the constant. Constants are always case-sensitive
+    /// since PHP 8.0.
+    #[must_use]
+    pub fn flags(&self) -> GlobalConstantFlags {
+        GlobalConstantFlags::from_bits_truncate(self.raw_flags() & 0xff)
+    }
+
+    /// Returns whether the constant persists across requests, which is the
+    /// case for constants registered by extensions.
+    #[must_use]
+    pub fn is_persistent(&self) -> bool {
+        self.flags().contains(GlobalConstantFlags::Persistent)
+    }
+
+    /// Returns the number of the module which registered the constant, or
+    /// [`None`] if it was defined by PHP code.
+    #[must_use]
+    pub fn module_number(&self) -> Option<i32> {
+        let module_number = self.raw_flags() >> 8;
+        if module_number == USER_CONSTANT {
+            None
+        } else {
+            module_number.try_into().ok()
+        }
+    }
+
+    fn raw_flags(&self) -> u32 {
+        // SAFETY: The flags of constants are stored in the value.
+        unsafe { self.value.u2.constant_flags }
+    }
+}
+
+type ConstantHookFn = dyn Fn(&ZendConstant) + Send + Sync;
+
+/// Source of the identifiers of installed constant hooks.
+static NEXT_HOOK_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The installed constant hooks, and the address of the `define()` function
+/// while any are installed.
+static CONSTANT_HOOKS: Mutex<ConstantHooks> = Mutex::new(ConstantHooks {
+    hooks: Vec::new(),
+    define: None,
+});
+
+/// The original handler of `define()`. It is kept apart from the hooks, so
+/// calls which are dispatched to [`define_handler`] while the last hook is
+/// being removed still define their constant.
+static DEFINE_HANDLER: OnceLock<zif_handler> = OnceLock::new();
+
+struct ConstantHooks {
+    hooks: Vec<(u64, Arc<ConstantHookFn>)>,
+    define: Option<usize>,
+}
+
+/// Calls a function with each constant defined with `define()` until it is
+/// dropped.
+///
+/// Constants declared with `const` statements outside of classes and
+/// constants registered by extensions are not reported, as they do not go
+/// through `define()`. [`ExecutorGlobals::defined_constants`] includes all
+/// constants.
+///
+/// Hooks apply to the whole process and may be installed once the engine
+/// has started, e.g. in the startup function of the module.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ext_php_rs::constant::ConstantHook;
+///
+/// let hook = ConstantHook::new(|constant| {
+///     eprintln!("Defined {:?}", constant.name());
+/// })
+/// .expect("Failed to install hook");
+/// ```
+#[must_use = "The hook is removed when it is dropped"]
+pub struct ConstantHook {
+    id: u64,
+}
+
+impl ConstantHook {
+    /// Installs the given constant hook.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Callable`] if the `define()` function could not be
+    /// found.
+    pub fn new<F>(hook: F) -> Result<Self>
+    where
+        F: Fn(&ZendConstant) + Send + Sync + 'static,
+    {
+        let mut hooks = CONSTANT_HOOKS.lock();
+        if hooks.define.is_none() {
+            let name = "define";
+            let function =
+                unsafe { zend_fetch_function_str(name.as_ptr().cast::<c_char>(), name.len()) };
+            // SAFETY: Functions in the function table live as long as the
+            // process.
+            let function = unsafe { function.as_mut() }
+                .filter(|function| function.function_type() == FunctionType::Internal)
+                .ok_or(Error::Callable)?;
+            let address = std::ptr::from_mut(function).expose_provenance();
+            // SAFETY: The function is an internal function.
+            let handler = unsafe { &mut function.internal_function.handler };
+            let original = *handler;
+            DEFINE_HANDLER.get_or_init(|| original);
+            hooks.define = Some(address);
+            *handler = Some(define_handler);
+        }
+
+        let id = NEXT_HOOK_ID.fetch_add(1, Ordering::Relaxed);
+        hooks.hooks.push((id, Arc::new(hook)));
+        Ok(Self { id })
+    }
+}
+
+impl Drop for ConstantHook {
+    fn drop(&mut self) {
+        let mut hooks = CONSTANT_HOOKS.lock();
+        hooks.hooks.retain(|(id, _)| *id != self.id);
+        if hooks.hooks.is_empty()
+            && let Some(function) = hooks.define.take()
+            && let Some(&original) = DEFINE_HANDLER.get()
+        {
+            let function = std::ptr::with_exposed_provenance_mut::<Function>(function);
+            // SAFETY: The function was an internal function when the hook was
+            // installed, and lives as long as the process.
+            unsafe { (*function).internal_function.handler = original };
+        }
+    }
+}
+
+zend_fastcall! {
+    extern fn define_handler(ex: *mut ExecuteData, retval: *mut Zval) {
+        let constants_len = || {
+            ExecutorGlobals::get()
+                .constants()
+                .map_or(0, crate::types::ZendHashTable::len)
+        };
+
+        // The handler is only installed once the original one was saved.
+        let Some(&Some(original)) = DEFINE_HANDLER.get() else {
+            return;
+        };
+        let before = constants_len();
+        unsafe { original(ex, retval) };
+        if constants_len() <= before {
+            return;
+        }
+
+        // New constants are appended to the constants table.
+        let constant = ExecutorGlobals::get()
+            .defined_constants()
+            .next_back()
+            .map(std::ptr::from_ref);
+        let Some(constant) = constant else {
+            return;
+        };
+        let hooks = CONSTANT_HOOKS
+            .lock()
+            .hooks
+            .iter()
+            .map(|(_, hook)| Arc::clone(hook))
+            .collect::<Vec<_>>();
+        for hook in hooks {
+            // SAFETY: The constant lives in the constants table, which is not
+            // modified while the hooks run.
+            hook(unsafe { &*constant });
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_defined_constants() {
+        Embed::run(|| {
+            let _ = Embed::eval("define('EXT_PHP_RS_USER_CONSTANT', 42);");
+
+            let globals = ExecutorGlobals::get();
+            let version = globals
+                .defined_constants()
+                .find(|constant| constant.name() == Some("PHP_VERSION"))
+                .unwrap();
+            assert!(version.is_persistent());
+            assert!(version.module_number().is_some());
+            assert!(version.value().is_string());
+
+            let user = globals
+                .defined_constants()
+                .find(|constant| constant.name() == Some("EXT_PHP_RS_USER_CONSTANT"))
+                .unwrap();
+            assert!(!user.is_persistent());
+            assert_eq!(user.module_number(), None);
+            assert_eq!(user.value().long(), Some(42));
+        });
+    }
+
+    #[test]
+    fn test_constant_hook() {
+        Embed::run(|| {
+            let defined = Arc::new(Mutex::new(Vec::new()));
+            let captured = Arc::clone(&defined);
+            let hook = ConstantHook::new(move |constant| {
+                let name = constant.name().unwrap_or_default().to_owned();
+                captured.lock().push((name, constant.value().long()));
+            })
+            .unwrap();
+
+            let _ = Embed::eval("define('EXT_PHP_RS_HOOKED', 1);");
+            let _ = Embed::eval("@define('EXT_PHP_RS_HOOKED', 2);");
+            drop(hook);
+            let _ = Embed::eval("define('EXT_PHP_RS_UNHOOKED', 3);");
+
+            assert_eq!(*defined.lock(), [("EXT_PHP_RS_HOOKED".to_owned(), Some(1))]);
+        });
+    }
+}
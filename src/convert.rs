@@ -2,10 +2,10 @@
 
 use crate::{
     boxed::ZBox,
-    error::Result,
+    error::{Error, Result},
     exception::PhpException,
     flags::DataType,
-    types::{ZendObject, Zval},
+    types::{ZendHashTable, ZendObject, Zval, packed},
 };
 
 /// Allows zvals to be converted into Rust types in a fallible way. Reciprocal
@@ -21,6 +21,26 @@ pub trait FromZval<'a>: Sized {
     ///
     /// * `zval` - Zval to get value from.
     fn from_zval(zval: &'a Zval) -> Option<Self>;
+
+    /// Converts the values of a PHP array into a vector, as done when
+    /// converting into a [`Vec`].
+    ///
+    /// Scalar types override this to copy the values out of the array
+    /// directly, rather than converting each value separately.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ZvalConversion`] - If a value could not be converted.
+    #[doc(hidden)]
+    fn vec_from_hashtable(ht: &'a ZendHashTable) -> Result<Vec<Self>> {
+        let mut vec = Vec::with_capacity(ht.len());
+
+        for (_, val) in ht {
+            vec.push(Self::from_zval(val).ok_or_else(|| Error::ZvalConversion(val.get_type()))?);
+        }
+
+        Ok(vec)
+    }
 }
 
 impl<'a, T> FromZval<'a> for Option<T>
@@ -179,6 +199,42 @@ pub trait IntoZval: Sized {
     /// [`Error`]: crate::error::Error
     // TODO: Expand on error information
     fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()>;
+
+    /// Converts a vector of values into a packed PHP array, as done when
+    /// converting a [`Vec`].
+    ///
+    /// Scalar types override this to write the values into the buckets of
+    /// the array directly, rather than converting each value separately.
+    ///
+    /// # Errors
+    ///
+    /// If a value could not be converted, an [`Error`] is returned.
+    #[doc(hidden)]
+    fn vec_into_hashtable(vec: Vec<Self>) -> Result<ZBox<ZendHashTable>> {
+        let values = vec
+            .into_iter()
+            .map(|val| val.into_zval(false))
+            .collect::<Result<Vec<_>>>()?;
+        packed::zvals_into_hashtable(values)
+    }
+
+    /// Converts a slice of values into a packed PHP array, as done when
+    /// converting a slice.
+    ///
+    /// # Errors
+    ///
+    /// If a value could not be converted, an [`Error`] is returned.
+    #[doc(hidden)]
+    fn slice_into_hashtable(slice: &[Self]) -> Result<ZBox<ZendHashTable>>
+    where
+        Self: Clone,
+    {
+        let values = slice
+            .iter()
+            .map(|val| val.clone().into_zval(false))
+            .collect::<Result<Vec<_>>>()?;
+        packed::zvals_into_hashtable(values)
+    }
 }
 
 impl IntoZval for () {
@@ -322,6 +378,12 @@ pub fn zval_to_stub(zval: &Zval) -> String {
                                 let key_escaped = key.replace('\\', "\\\\").replace('\'', "\\'");
                                 parts.push(format!("'{key_escaped}' => {val_str}"));
                             }
+                            key @ (crate::types::ArrayKey::Binary(_)
+                            | crate::types::ArrayKey::Bytes(_)) => {
+                                let key_escaped =
+                                    key.to_string().replace('\\', "\\\\").replace('\'', "\\'");
+                                parts.push(format!("'{key_escaped}' => {val_str}"));
+                            }
                         }
                     }
                 }
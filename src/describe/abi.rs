@@ -49,6 +49,15 @@ impl<T> From<StdVec<T>> for Vec<T> {
     }
 }
 
+impl<T> Clone for Vec<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        self.to_vec().into()
+    }
+}
+
 impl<T> PartialEq for Vec<T>
 where
     T: PartialEq,
@@ -60,7 +69,7 @@ where
 
 /// An immutable, ABI-stable borrowed [`&'static str`][str].
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Str {
     ptr: *const u8,
     len: usize,
@@ -105,7 +114,7 @@ impl PartialEq for Str {
 
 /// An ABI-stable String
 #[repr(C)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RString {
     inner: Vec<u8>,
 }
@@ -152,7 +161,7 @@ impl Display for RString {
 
 /// An ABI-stable [`Option`][std::option::Option].
 #[repr(C, u8)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Option<T> {
     /// [`Option::Some`][std::option::Option::Some] variant.
     Some(T),
@@ -1,6 +1,6 @@
 //! Types used to describe downstream extensions. Used by the `cargo-php`
 //! CLI application to generate PHP stub files used by IDEs.
-use std::vec::Vec as StdVec;
+use std::{mem, vec::Vec as StdVec};
 
 #[cfg(feature = "enum")]
 use crate::builders::EnumBuilder;
@@ -20,6 +20,23 @@ pub use stub::ToStub;
 /// A slice of strings containing documentation comments.
 pub type DocComments = &'static [&'static str];
 
+/// Formats documentation comments as a PHP doc block, as written to stubs and
+/// returned by `getDocComment()` of the reflection classes. Returns [`None`] if
+/// there are no comments.
+pub(crate) fn doc_block(docs: DocComments) -> Option<String> {
+    if docs.is_empty() {
+        return None;
+    }
+    let mut block = String::from("/**\n");
+    for comment in docs {
+        block.push_str(" *");
+        block.push_str(comment);
+        block.push('\n');
+    }
+    block.push_str(" */");
+    Some(block)
+}
+
 /// Representation of the extension used to generate PHP stubs.
 #[repr(C)]
 pub struct Description {
@@ -46,7 +63,7 @@ impl Description {
 
 /// Represents a set of comments on an export.
 #[repr(C)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DocBlock(pub Vec<Str>);
 
 impl From<&'static [&'static str]> for DocBlock {
@@ -79,7 +96,8 @@ pub struct Module {
 /// Builds a [`Module`] from a [`ModuleBuilder`].
 /// This is used to generate the PHP stubs for the module.
 impl From<ModuleBuilder<'_>> for Module {
-    fn from(builder: ModuleBuilder) -> Self {
+    fn from(mut builder: ModuleBuilder) -> Self {
+        builder.apply_namespace();
         let functions = builder.functions;
 
         #[allow(unused_mut)]
@@ -96,7 +114,7 @@ impl From<ModuleBuilder<'_>> for Module {
             name: builder.name.into(),
             functions: functions
                 .into_iter()
-                .map(Function::from)
+                .flat_map(Function::with_aliases)
                 .collect::<StdVec<_>>()
                 .into(),
             classes: classes.into(),
@@ -119,6 +137,7 @@ impl From<ModuleBuilder<'_>> for Module {
 
 /// Represents an exported function.
 #[repr(C)]
+#[derive(Clone)]
 pub struct Function {
     /// Name of the function.
     pub name: RString,
@@ -130,6 +149,24 @@ pub struct Function {
     pub params: Vec<Parameter>,
 }
 
+impl Function {
+    /// Describes the function built by the builder, followed by a copy of the
+    /// description for each alias of the function.
+    fn with_aliases(mut builder: FunctionBuilder<'_>) -> StdVec<Self> {
+        let aliases = mem::take(&mut builder.aliases);
+        let function = Self::from(builder);
+        let mut functions = StdVec::with_capacity(aliases.len() + 1);
+        for alias in aliases {
+            functions.push(Self {
+                name: alias.into(),
+                ..function.clone()
+            });
+        }
+        functions.insert(0, function);
+        functions
+    }
+}
+
 impl From<FunctionBuilder<'_>> for Function {
     fn from(val: FunctionBuilder<'_>) -> Self {
         let ret_allow_null = val.ret_as_null;
@@ -161,7 +198,7 @@ impl From<FunctionBuilder<'_>> for Function {
 
 /// Represents a parameter attached to an exported function or method.
 #[repr(C)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Parameter {
     /// Name of the parameter.
     pub name: RString,
@@ -253,6 +290,7 @@ impl From<ClassBuilder> for Class {
                 .interfaces
                 .into_iter()
                 .map(|(_, stub)| stub.into())
+                .chain(val.optional_interfaces.into_iter().map(Into::into))
                 .collect::<StdVec<_>>()
                 .into(),
             properties: val
@@ -264,7 +302,7 @@ impl From<ClassBuilder> for Class {
             methods: val
                 .methods
                 .into_iter()
-                .map(Method::from)
+                .flat_map(Method::with_aliases)
                 .collect::<StdVec<_>>()
                 .into(),
             constants: val
@@ -409,7 +447,7 @@ impl<D> From<(String, PropertyFlags, D, DocComments)> for Property {
 
 /// Represents a method attached to an exported class.
 #[repr(C)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Method {
     /// Name of the method.
     pub name: RString,
@@ -429,6 +467,24 @@ pub struct Method {
     pub r#abstract: bool,
 }
 
+impl Method {
+    /// Describes the method built by the builder, followed by a copy of the
+    /// description for each alias of the method.
+    fn with_aliases((mut builder, flags): (FunctionBuilder<'_>, MethodFlags)) -> StdVec<Self> {
+        let aliases = mem::take(&mut builder.aliases);
+        let method = Self::from((builder, flags));
+        let mut methods = StdVec::with_capacity(aliases.len() + 1);
+        for alias in aliases {
+            methods.push(Self {
+                name: alias.into(),
+                ..method.clone()
+            });
+        }
+        methods.insert(0, method);
+        methods
+    }
+}
+
 impl From<(FunctionBuilder<'_>, MethodFlags)> for Method {
     fn from(val: (FunctionBuilder<'_>, MethodFlags)) -> Self {
         let (builder, flags) = val;
@@ -466,7 +522,7 @@ impl From<(FunctionBuilder<'_>, MethodFlags)> for Method {
 
 /// Represents a value returned from a function or method.
 #[repr(C)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Retval {
     /// Type of the return value.
     pub ty: DataType,
@@ -606,6 +662,15 @@ mod tests {
         assert_eq!(docs.0[1], "doc2".into());
     }
 
+    #[test]
+    fn test_doc_block() {
+        assert_eq!(doc_block(&[]), None);
+        assert_eq!(
+            doc_block(&[" Adds numbers.", "", " @param int $a"]).as_deref(),
+            Some("/**\n * Adds numbers.\n *\n * @param int $a\n */")
+        );
+    }
+
     #[test]
     fn test_module_from() {
         let builder = ModuleBuilder::new("test", "test_version")
@@ -623,6 +688,20 @@ mod tests {
         assert_eq!(module.constants.len(), 0);
     }
 
+    #[test]
+    fn test_module_from_aliases() {
+        let builder = ModuleBuilder::new("test", "test_version").function(
+            FunctionBuilder::new("test_function", test_function)
+                .arg(Arg::new("foo", DataType::Long))
+                .alias("test_alias"),
+        );
+        let module: Module = builder.into();
+        assert_eq!(module.functions.len(), 2);
+        assert_eq!(module.functions[0].name, "test_function".into());
+        assert_eq!(module.functions[1].name, "test_alias".into());
+        assert_eq!(module.functions[1].params, module.functions[0].params);
+    }
+
     #[test]
     fn test_function_from() {
         let builder = FunctionBuilder::new("test_function", test_function)
@@ -659,6 +738,7 @@ mod tests {
             .extends((|| todo!(), "BaseClass"))
             .implements((|| todo!(), "Interface1"))
             .implements((|| todo!(), "Interface2"))
+            .implements_if_exists("Optional\\Interface3")
             .property("prop1", PropertyFlags::Public, None, &["doc1"])
             .method(
                 FunctionBuilder::new("test_function", test_function),
@@ -671,7 +751,12 @@ mod tests {
         assert_eq!(class.extends, Option::Some("BaseClass".into()));
         assert_eq!(
             class.implements,
-            vec!["Interface1".into(), "Interface2".into()].into()
+            vec![
+                "Interface1".into(),
+                "Interface2".into(),
+                "Optional\\Interface3".into()
+            ]
+            .into()
         );
         assert_eq!(class.properties.len(), 1);
         assert_eq!(
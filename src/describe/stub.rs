@@ -10,8 +10,7 @@ use std::{
 
 use super::{
     Class, Constant, DocBlock, Function, Method, MethodType, Module, Parameter, Property,
-    Visibility,
-    abi::{Option, RString},
+    Visibility, abi::Option,
 };
 
 #[cfg(feature = "enum")]
@@ -207,6 +206,8 @@ impl ToStub for DataType {
                 DataType::Reference => "reference",
                 DataType::Callable => "callable",
                 DataType::Iterable => "iterable",
+                DataType::Void => "void",
+                DataType::Never => "never",
                 _ => "mixed",
             }
         )
@@ -247,7 +248,7 @@ impl ToStub for Class {
         }
 
         if let Option::Some(extends) = &self.extends {
-            write!(buf, "extends {extends} ")?;
+            write!(buf, "extends {} ", fully_qualify(extends.as_str()))?;
         }
 
         if !self.implements.is_empty() && !is_interface {
@@ -256,7 +257,7 @@ impl ToStub for Class {
                 "implements {} ",
                 self.implements
                     .iter()
-                    .map(RString::as_str)
+                    .map(|name| fully_qualify(name.as_str()))
                     .collect::<StdVec<_>>()
                     .join(", ")
             )?;
@@ -268,7 +269,7 @@ impl ToStub for Class {
                 "extends {} ",
                 self.implements
                     .iter()
-                    .map(RString::as_str)
+                    .map(|name| fully_qualify(name.as_str()))
                     .collect::<StdVec<_>>()
                     .join(", ")
             )?;
@@ -333,8 +334,12 @@ impl ToStub for Property {
         if self.static_ {
             write!(buf, "static ")?;
         }
-        if let Option::Some(ty) = &self.ty {
+        // `callable` is not allowed as the type of a property.
+        if let Option::Some(ty) = &self.ty
+            && *ty != DataType::Callable
+        {
             ty.fmt_stub(buf)?;
+            write!(buf, " ")?;
         }
         write!(buf, "${}", self.name)?;
         if let Option::Some(default) = &self.default {
@@ -402,7 +407,8 @@ impl ToStub for Constant {
     fn fmt_stub(&self, buf: &mut String) -> FmtResult {
         self.docs.fmt_stub(buf)?;
 
-        write!(buf, "const {} = ", self.name)?;
+        let (_, name) = split_namespace(self.name.as_ref());
+        write!(buf, "const {name} = ")?;
         if let Option::Some(value) = &self.value {
             write!(buf, "{value}")?;
         } else {
@@ -433,6 +439,16 @@ fn split_namespace(class: &str) -> (StdOption<&str>, &str) {
     }
 }
 
+/// Returns the fully qualified form of a class name, as stubs are declared
+/// inside of namespace blocks.
+fn fully_qualify(class: &str) -> String {
+    if class.starts_with('\\') {
+        class.to_owned()
+    } else {
+        format!("\\{class}")
+    }
+}
+
 /// Indents a given string to a given depth. Depth is given in number of spaces
 /// to be appended. Returns a new string with the new indentation. Will not
 /// indent whitespace lines.
@@ -463,7 +479,7 @@ fn indent(s: &str, depth: usize) -> String {
 
 #[cfg(test)]
 mod test {
-    use super::split_namespace;
+    use super::{fully_qualify, split_namespace};
 
     #[test]
     pub fn test_split_ns() {
@@ -472,6 +488,57 @@ mod test {
         assert_eq!(split_namespace("simple\\ns"), (Some("simple"), "ns"));
     }
 
+    #[test]
+    pub fn test_fully_qualify() {
+        assert_eq!(fully_qualify("ArrayAccess"), "\\ArrayAccess");
+        assert_eq!(fully_qualify("\\Exception"), "\\Exception");
+        assert_eq!(fully_qualify("Vendor\\Pkg\\Foo"), "\\Vendor\\Pkg\\Foo");
+    }
+
+    #[test]
+    pub fn test_property_types() {
+        use super::ToStub;
+        use crate::describe::{DocBlock, Property, Visibility, abi::Option};
+        use crate::flags::DataType;
+
+        let property = |ty| Property {
+            name: "prop".into(),
+            docs: DocBlock(vec![].into()),
+            ty: Option::Some(ty),
+            vis: Visibility::Public,
+            static_: false,
+            nullable: false,
+            default: Option::None,
+        };
+
+        assert_eq!(
+            property(DataType::Iterable)
+                .to_stub()
+                .expect("Failed to create stub")
+                .trim_end(),
+            "public iterable $prop;"
+        );
+        assert_eq!(
+            property(DataType::Callable)
+                .to_stub()
+                .expect("Failed to create stub")
+                .trim_end(),
+            "public $prop;"
+        );
+    }
+
+    #[test]
+    pub fn test_return_types() {
+        use super::ToStub;
+        use crate::flags::DataType;
+
+        let stub = |ty: DataType| ty.to_stub().expect("Failed to create stub");
+
+        assert_eq!(stub(DataType::Void), "void");
+        assert_eq!(stub(DataType::Never), "never");
+        assert_eq!(stub(DataType::Mixed), "mixed");
+    }
+
     #[test]
     #[cfg(not(windows))]
     #[allow(clippy::uninlined_format_args)]
@@ -0,0 +1,154 @@
+//! Cache of compiled scripts, used by [`Embed::run_script_cached`].
+//!
+//! The cache only lives for a single run of the engine, see
+//! [`ScriptCacheScope`].
+//!
+//! [`Embed::run_script_cached`]: super::Embed::run_script_cached
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    ffi::CString,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::ffi::zend_op_array;
+
+use super::ffi::{ext_php_rs_embed_compile_file, ext_php_rs_embed_free_op_array};
+
+/// Statistics of the compiled script cache of the embed SAPI, see
+/// [`Embed::script_cache_stats`].
+///
+/// [`Embed::script_cache_stats`]: super::Embed::script_cache_stats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScriptCacheStats {
+    /// Number of scripts executed from the cache without being compiled.
+    pub hits: u64,
+    /// Number of scripts compiled because they were not cached.
+    pub misses: u64,
+    /// Number of cached scripts discarded because they were modified or
+    /// invalidated.
+    pub invalidations: u64,
+    /// Number of scripts currently cached.
+    pub entries: usize,
+}
+
+struct CachedScript {
+    op_array: *mut zend_op_array,
+    modified: Option<SystemTime>,
+}
+
+#[derive(Default)]
+struct ScriptCache {
+    scripts: HashMap<PathBuf, CachedScript>,
+    /// Op arrays removed from the cache, which may still be executing. They
+    /// are freed when the engine shuts down.
+    retired: Vec<*mut zend_op_array>,
+    stats: ScriptCacheStats,
+}
+
+thread_local! {
+    static CACHE: RefCell<ScriptCache> = RefCell::default();
+}
+
+/// Scope of the cache, which is bound to a single run of the engine as
+/// compiled scripts are allocated in request memory.
+pub(crate) struct ScriptCacheScope;
+
+impl ScriptCacheScope {
+    /// Starts a new scope, discarding the scripts of a previous run. These are
+    /// only left behind if the run bailed out, in which case their memory has
+    /// already been released by PHP.
+    pub(crate) fn enter() -> Self {
+        CACHE.with_borrow_mut(|cache| *cache = ScriptCache::default());
+        Self
+    }
+}
+
+impl Drop for ScriptCacheScope {
+    fn drop(&mut self) {
+        CACHE.with_borrow_mut(|cache| {
+            let retired = cache.scripts.drain().map(|(_, script)| script.op_array);
+            for op_array in retired.chain(cache.retired.drain(..)) {
+                // SAFETY: The op arrays were compiled in the current run of the engine,
+                // which has not been shut down yet.
+                unsafe { ext_php_rs_embed_free_op_array(op_array) };
+            }
+            cache.stats.entries = 0;
+        });
+    }
+}
+
+/// Returns the compiled script at the given path, compiling it if it is not
+/// cached or was modified since it was compiled. Returns `None` if the
+/// script could not be compiled.
+pub(crate) fn get_or_compile(path: &Path, c_path: &CString) -> Option<*mut zend_op_array> {
+    let key = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let modified = fs::metadata(&key).and_then(|meta| meta.modified()).ok();
+
+    let cached = CACHE.with_borrow_mut(|cache| {
+        let script = cache.scripts.get(&key)?;
+        if script.modified == modified {
+            cache.stats.hits += 1;
+            return Some(script.op_array);
+        }
+        cache.retire(&key);
+        None
+    });
+    if cached.is_some() {
+        return cached;
+    }
+
+    // Compiling runs PHP code (e.g. autoloaders), so the cache must not be
+    // borrowed here.
+    let op_array = unsafe { ext_php_rs_embed_compile_file(c_path.as_ptr()) };
+    if op_array.is_null() {
+        return None;
+    }
+
+    CACHE.with_borrow_mut(|cache| {
+        cache.stats.misses += 1;
+        cache.retire(&key);
+        cache
+            .scripts
+            .insert(key, CachedScript { op_array, modified });
+        cache.stats.entries = cache.scripts.len();
+    });
+    Some(op_array)
+}
+
+/// Removes the script at the given path from the cache, returning whether it
+/// was cached.
+pub(crate) fn invalidate(path: &Path) -> bool {
+    let key = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    CACHE.with_borrow_mut(|cache| cache.retire(&key))
+}
+
+/// Removes all scripts from the cache.
+pub(crate) fn clear() {
+    CACHE.with_borrow_mut(|cache| {
+        let keys: Vec<_> = cache.scripts.keys().cloned().collect();
+        for key in keys {
+            cache.retire(&key);
+        }
+    });
+}
+
+/// Returns the statistics of the cache.
+pub(crate) fn stats() -> ScriptCacheStats {
+    CACHE.with_borrow(|cache| cache.stats)
+}
+
+impl ScriptCache {
+    fn retire(&mut self, key: &Path) -> bool {
+        let Some(script) = self.scripts.remove(key) else {
+            return false;
+        };
+        self.retired.push(script.op_array);
+        self.stats.invalidations += 1;
+        self.stats.entries = self.scripts.len();
+        true
+    }
+}
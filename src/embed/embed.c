@@ -58,6 +58,52 @@ void ext_php_rs_php_error(int type, const char *format, ...) {
   va_end(args);
 }
 
+// Wrap `php_stream_alloc` as it's a macro which passes debug information in
+// debug builds of PHP.
+php_stream *ext_php_rs_embed_stream_alloc(const php_stream_ops *ops, void *abstract, const char *mode) {
+  return php_stream_alloc(ops, abstract, NULL, mode);
+}
+
+// Registers a stream as a constant for the current request, as done by the CLI
+// SAPI for `STDIN`, `STDOUT` and `STDERR`.
+void ext_php_rs_embed_register_stream_constant(const char *name, php_stream *stream) {
+  zend_constant c;
+
+  php_stream_to_zval(stream, &c.value);
+  ZEND_CONSTANT_SET_FLAGS(&c, 0, PHP_USER_CONSTANT);
+  c.name = zend_string_init_interned(name, strlen(name), 0);
+  zend_register_constant(&c);
+}
+
+// Compiles a script without executing it, so the op array can be executed
+// multiple times.
+zend_op_array *ext_php_rs_embed_compile_file(const char *path) {
+  zend_file_handle file_handle;
+  zend_op_array *op_array;
+
+  zend_stream_init_filename(&file_handle, path);
+  op_array = zend_compile_file(&file_handle, ZEND_INCLUDE);
+  zend_destroy_file_handle(&file_handle);
+
+  return op_array;
+}
+
+// Executes a compiled script, returning whether it ran without throwing.
+bool ext_php_rs_embed_execute_op_array(zend_op_array *op_array) {
+  zval retval;
+
+  ZVAL_UNDEF(&retval);
+  zend_execute(op_array, &retval);
+  zval_ptr_dtor(&retval);
+
+  return EG(exception) == NULL;
+}
+
+void ext_php_rs_embed_free_op_array(zend_op_array *op_array) {
+  destroy_op_array(op_array);
+  efree_size(op_array, sizeof(zend_op_array));
+}
+
 // Wrap `php_ini_builder_deinit` as it's `static inline` which gets discarded
 // by cbindgen.
 #ifdef EXT_PHP_RS_PHP_82
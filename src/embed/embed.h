@@ -12,3 +12,10 @@ void ext_php_rs_sapi_per_thread_init();
 void ext_php_rs_sapi_per_thread_shutdown();
 
 void ext_php_rs_php_error(int type, const char *format, ...);
+
+php_stream *ext_php_rs_embed_stream_alloc(const php_stream_ops *ops, void *abstract, const char *mode);
+void ext_php_rs_embed_register_stream_constant(const char *name, php_stream *stream);
+
+zend_op_array *ext_php_rs_embed_compile_file(const char *path);
+bool ext_php_rs_embed_execute_op_array(zend_op_array *op_array);
+void ext_php_rs_embed_free_op_array(zend_op_array *op_array);
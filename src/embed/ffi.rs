@@ -6,6 +6,8 @@
 #[cfg(php82)]
 use crate::ffi::php_ini_builder;
 
+use crate::ffi::{php_stream, php_stream_ops, zend_op_array};
+
 use std::ffi::{c_char, c_int, c_void};
 
 #[link(name = "wrapper")]
@@ -28,6 +30,17 @@ unsafe extern "C" {
         ...
     );
 
+    pub fn ext_php_rs_embed_stream_alloc(
+        ops: *const php_stream_ops,
+        abstract_: *mut c_void,
+        mode: *const c_char,
+    ) -> *mut php_stream;
+    pub fn ext_php_rs_embed_register_stream_constant(name: *const c_char, stream: *mut php_stream);
+
+    pub fn ext_php_rs_embed_compile_file(path: *const c_char) -> *mut zend_op_array;
+    pub fn ext_php_rs_embed_execute_op_array(op_array: *mut zend_op_array) -> bool;
+    pub fn ext_php_rs_embed_free_op_array(op_array: *mut zend_op_array);
+
     #[cfg(php82)]
     pub fn ext_php_rs_php_ini_builder_deinit(builder: *mut php_ini_builder);
 }
@@ -0,0 +1,264 @@
+//! Replacement of internal functions with Rust closures, allowing tests to
+//! stub out functions like `time()` or `rand()`.
+//!
+//! Mocks are only meant for tests, so they are only available with the
+//! `embed` feature, which tests of extensions are run with.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::c_char,
+    ptr::NonNull,
+};
+
+use parking_lot::{Mutex, const_mutex};
+
+use crate::{
+    convert::IntoZval,
+    error::{Error, Result},
+    exception::PhpException,
+    ffi::{zend_fetch_function_str, zif_handler},
+    flags::FunctionType,
+    types::Zval,
+    zend::{ExecuteData, Function},
+    zend_fastcall,
+};
+
+type MockFn = Box<dyn FnMut(&[&Zval]) -> Result<Zval>>;
+
+/// A function whose handler has been replaced by [`mock_handler`].
+struct Patched {
+    /// The handler of the function before it was first mocked.
+    original: zif_handler,
+    /// The number of mocks of the function on all threads.
+    mocks: usize,
+}
+
+/// The patched functions, by the address of the function. The handler of a
+/// function is replaced for the whole process, so the original handler must
+/// be reachable from any thread.
+static PATCHED: Mutex<Option<HashMap<usize, Patched>>> = const_mutex(None);
+
+thread_local! {
+    /// The closures of the mocks created on this thread, by the address of
+    /// the function and the identifier of their [`FunctionMock`]. The last
+    /// closure of a function replaces it, and a closure is taken out while it
+    /// runs.
+    static MOCKS: RefCell<HashMap<usize, Vec<(u64, Option<MockFn>)>>> = RefCell::default();
+    /// The identifier of the next mock.
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Replaces the handler of an internal function with a Rust closure until it
+/// is dropped, which restores the original handler.
+///
+/// The closure is passed the arguments of the call, and its return value is
+/// returned to PHP. Mocks should only be used in tests, and dropped before
+/// the end of [`Embed::run`](super::Embed::run). Mocking a function which is
+/// mocked already replaces the previous mock until the new one is dropped,
+/// and the original function is restored once all of its mocks have been
+/// dropped, in any order.
+///
+/// Mocks apply to the thread which created them. Calls on other threads, and
+/// calls of the mocked function from within its own closure, call the
+/// original function.
+///
+/// Calls which PHP compiles to dedicated opcodes, e.g. `strlen()` or
+/// `count()`, and calls to frameless functions since PHP 8.4 do not call the
+/// handler, so they cannot be mocked.
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::embed::{Embed, FunctionMock};
+///
+/// Embed::run(|| {
+///     let _time = FunctionMock::new("time", |_| 1_700_000_000).unwrap();
+///     let year = Embed::eval("date('Y', time());").unwrap();
+///     assert_eq!(year.string().as_deref(), Some("2023"));
+/// });
+/// ```
+pub struct FunctionMock {
+    function: NonNull<Function>,
+    id: u64,
+}
+
+impl FunctionMock {
+    /// Replaces the internal function with the given name with a closure.
+    /// The name is case-insensitive.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Callable`] if there is no internal function with the
+    /// given name.
+    pub fn new<F, R>(name: &str, mut mock: F) -> Result<Self>
+    where
+        F: FnMut(&[&Zval]) -> R + 'static,
+        R: IntoZval,
+    {
+        // The function table is keyed by lowercase names.
+        let name = name.to_ascii_lowercase();
+        let function =
+            unsafe { zend_fetch_function_str(name.as_ptr().cast::<c_char>(), name.len()) };
+        let mut function = NonNull::new(function).ok_or(Error::Callable)?;
+        if unsafe { function.as_ref() }.function_type() != FunctionType::Internal {
+            return Err(Error::Callable);
+        }
+
+        let key = function.addr().get();
+        PATCHED
+            .lock()
+            .get_or_insert_with(HashMap::new)
+            .entry(key)
+            .or_insert_with(|| {
+                // SAFETY: The function is an internal function.
+                let handler = unsafe { &mut function.as_mut().internal_function.handler };
+                Patched {
+                    original: handler.replace(mock_handler),
+                    mocks: 0,
+                }
+            })
+            .mocks += 1;
+
+        let id = NEXT_ID.replace(NEXT_ID.get() + 1);
+        let mock: MockFn = Box::new(move |args| mock(args).into_zval(false));
+        MOCKS.with_borrow_mut(|mocks| mocks.entry(key).or_default().push((id, Some(mock))));
+        Ok(Self { function, id })
+    }
+}
+
+impl Drop for FunctionMock {
+    fn drop(&mut self) {
+        let key = self.function.addr().get();
+        MOCKS.with_borrow_mut(|mocks| {
+            let Some(closures) = mocks.get_mut(&key) else {
+                return;
+            };
+            closures.retain(|(id, _)| *id != self.id);
+            if closures.is_empty() {
+                mocks.remove(&key);
+            }
+        });
+
+        let mut patched = PATCHED.lock();
+        let Some(functions) = patched.as_mut() else {
+            return;
+        };
+        let Some(entry) = functions.get_mut(&key) else {
+            return;
+        };
+        entry.mocks -= 1;
+        if entry.mocks == 0 {
+            // SAFETY: Internal functions live as long as the process.
+            unsafe { self.function.as_mut().internal_function.handler = entry.original };
+            functions.remove(&key);
+        }
+    }
+}
+
+zend_fastcall! {
+    extern fn mock_handler(ex: *mut ExecuteData, retval: *mut Zval) {
+        // SAFETY: PHP passes a valid execution data pointer.
+        let key = unsafe { (*ex).func.addr() };
+
+        // The closure is taken out of the map while it runs, so it may call
+        // other mocked functions.
+        let Some((id, mut mock)) = MOCKS.with_borrow_mut(|mocks| {
+            let (id, mock) = mocks.get_mut(&key)?.last_mut()?;
+            Some((*id, mock.take()?))
+        }) else {
+            // The function is mocked on another thread, or is called by its
+            // own mock.
+            let original = PATCHED
+                .lock()
+                .as_ref()
+                .and_then(|patched| patched.get(&key))
+                .and_then(|entry| entry.original);
+            if let Some(original) = original {
+                // SAFETY: The original handler is called with the arguments
+                // PHP passed to the mock handler.
+                unsafe { original(ex, retval) };
+            }
+            return;
+        };
+
+        // SAFETY: PHP passes valid execution data and return value pointers.
+        let (ex, retval) = unsafe { (&mut *ex, &mut *retval) };
+        let args = ex.args();
+        let result = mock(&args);
+        MOCKS.with_borrow_mut(|mocks| {
+            // The mock may have been dropped while it was running.
+            let slot = mocks
+                .get_mut(&key)
+                .and_then(|closures| closures.iter_mut().find(|(i, _)| *i == id));
+            if let Some((_, slot)) = slot {
+                *slot = Some(mock);
+            }
+        });
+
+        match result {
+            Ok(value) => *retval = value,
+            Err(e) => {
+                let _ = PhpException::default(e.to_string()).throw();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::{convert::IntoZvalDyn, embed::Embed};
+
+    #[test]
+    fn test_function_mock() {
+        Embed::run(|| {
+            let real = Embed::eval("time();").unwrap().long().unwrap();
+            assert!(real > 0);
+
+            {
+                let _time = FunctionMock::new("time", |_| 42).unwrap();
+                assert_eq!(Embed::eval("time();").unwrap().long(), Some(42));
+
+                let _nested = FunctionMock::new("time", |_| 7).unwrap();
+                assert_eq!(Embed::eval("time();").unwrap().long(), Some(7));
+            }
+            assert!(Embed::eval("time();").unwrap().long().unwrap() >= real);
+
+            let first = FunctionMock::new("TIME", |_| 1).unwrap();
+            let second = FunctionMock::new("time", |_| 2).unwrap();
+            drop(first);
+            assert_eq!(Embed::eval("time();").unwrap().long(), Some(2));
+            drop(second);
+            assert!(Embed::eval("time();").unwrap().long().unwrap() >= real);
+
+            let _repeat = FunctionMock::new("str_repeat", |args: &[&Zval]| {
+                args.iter().filter_map(|arg| arg.long()).sum::<i64>()
+            })
+            .unwrap();
+            assert_eq!(Embed::eval("str_repeat('a', 3);").unwrap().long(), Some(3));
+
+            assert!(FunctionMock::new("ext_php_rs_missing", |_| ()).is_err());
+        });
+    }
+
+    #[test]
+    fn test_function_mock_calls_original() {
+        Embed::run(|| {
+            let _repeat = FunctionMock::new("str_repeat", |_| {
+                let original = Function::try_from_function("str_repeat").unwrap();
+                let args: Vec<&dyn IntoZvalDyn> = vec![&"b", &2];
+                let repeated = original.try_call(args).unwrap();
+                format!("{}!", repeated.string().unwrap())
+            })
+            .unwrap();
+            assert_eq!(
+                Embed::eval("str_repeat('a', 3);")
+                    .unwrap()
+                    .string()
+                    .as_deref(),
+                Some("bb!")
+            );
+        });
+    }
+}
@@ -5,8 +5,11 @@
 //! version You should only use this crate for test purpose, it's not production
 //! ready
 
+mod cache;
 mod ffi;
+mod mock;
 mod sapi;
+mod stdio;
 
 use crate::boxed::ZBox;
 use crate::ffi::{
@@ -21,8 +24,11 @@ use std::panic::{AssertUnwindSafe, UnwindSafe, resume_unwind};
 use std::path::Path;
 use std::ptr::null_mut;
 
+pub use cache::ScriptCacheStats;
 pub use ffi::*;
+pub use mock::FunctionMock;
 pub use sapi::SapiModule;
+pub use stdio::Stdio;
 
 /// The embed module provides a way to run php code from rust
 pub struct Embed;
@@ -144,6 +150,17 @@ impl Embed {
     /// });
     /// ```
     pub fn run<R, F: FnOnce() -> R + UnwindSafe>(func: F) -> R
+    where
+        R: Default,
+    {
+        Self::start(move || {
+            let _cache = cache::ScriptCacheScope::enter();
+            func()
+        })
+    }
+
+    /// Starts the engine and runs the given function inside of a request.
+    fn start<R, F: FnOnce() -> R + UnwindSafe>(func: F) -> R
     where
         R: Default,
     {
@@ -165,6 +182,8 @@ impl Embed {
 
         // Prevent the closure from being dropped here since it was consumed in panic_wrapper
         std::mem::forget(func);
+        crate::zend::request::finish_request();
+        crate::zend::engine::finish_engine();
 
         // This can happen if there is a bailout
         if panic.is_null() {
@@ -180,6 +199,124 @@ impl Embed {
         }
     }
 
+    /// Run a php script from a file, keeping the compiled script in a cache so
+    /// running it again within the same call to [`Embed::run`] does not
+    /// compile it again.
+    ///
+    /// Compiled scripts are allocated in request memory, so the cache is
+    /// emptied when the function passed to [`Embed::run`] returns and scripts
+    /// are not shared across runs of the engine. Scripts modified since they
+    /// were compiled are compiled again.
+    ///
+    /// Each call executes the whole script again, as if it was included with
+    /// `include`. Scripts which declare functions or classes conditionally,
+    /// e.g. inside an `if`, fail when they are run again as the declarations
+    /// already exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The script was executed successfully
+    ///
+    /// # Errors
+    ///
+    /// * `Err(EmbedError)` - An error occurred during the compilation or the
+    ///   execution of the script
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ext_php_rs::embed::Embed;
+    ///
+    /// Embed::run(|| {
+    ///     for _ in 0..3 {
+    ///         assert!(Embed::run_script_cached("src/embed/test-script.php").is_ok());
+    ///     }
+    ///
+    ///     let stats = Embed::script_cache_stats();
+    ///     assert_eq!(stats.misses, 1);
+    ///     assert_eq!(stats.hits, 2);
+    /// });
+    /// ```
+    pub fn run_script_cached<P: AsRef<Path>>(path: P) -> Result<(), EmbedError> {
+        let path = path.as_ref();
+        let c_path = match path.to_str() {
+            Some(path) => CString::new(path).map_err(EmbedError::InvalidEvalString)?,
+            None => return Err(EmbedError::InvalidPath),
+        };
+
+        let exec_result = try_catch(AssertUnwindSafe(|| {
+            cache::get_or_compile(path, &c_path)
+                .map(|op_array| unsafe { ext_php_rs_embed_execute_op_array(op_array) })
+        }));
+
+        match exec_result {
+            Err(_) => Err(EmbedError::CatchError),
+            Ok(Some(true)) => Ok(()),
+            Ok(Some(false)) => Err(EmbedError::ExecuteError(ExecutorGlobals::take_exception())),
+            // Compilation fails with an exception on syntax errors.
+            Ok(None) => match ExecutorGlobals::take_exception() {
+                Some(ex) => Err(EmbedError::ExecuteError(Some(ex))),
+                None => Err(EmbedError::ExecuteScriptError),
+            },
+        }
+    }
+
+    /// Returns the statistics of the cache used by
+    /// [`Embed::run_script_cached`] for the current or last run of the
+    /// engine.
+    #[must_use]
+    pub fn script_cache_stats() -> ScriptCacheStats {
+        cache::stats()
+    }
+
+    /// Removes a script from the cache used by [`Embed::run_script_cached`],
+    /// so it is compiled again the next time it runs. Returns whether the
+    /// script was cached.
+    pub fn invalidate_script<P: AsRef<Path>>(path: P) -> bool {
+        cache::invalidate(path.as_ref())
+    }
+
+    /// Removes all scripts from the cache used by
+    /// [`Embed::run_script_cached`].
+    pub fn clear_script_cache() {
+        cache::clear();
+    }
+
+    /// Start and run embed sapi engine with the standard streams redirected to
+    /// the given readers and writers, see [`Stdio`].
+    ///
+    /// Behaves like [`Embed::run`] otherwise. The `STDIN`, `STDOUT` and
+    /// `STDERR` constants are defined for the scripts run inside the function.
+    ///
+    /// # Returns
+    ///
+    /// * R - The result of the function passed to this method
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ext_php_rs::embed::{Embed, Stdio};
+    ///
+    /// let mut output = Vec::new();
+    ///
+    /// Embed::run_with_stdio(Stdio::new().stdout(&mut output), || {
+    ///     let _ = Embed::eval("fwrite(STDOUT, 'foo');");
+    /// });
+    ///
+    /// assert_eq!(output, b"foo");
+    /// ```
+    pub fn run_with_stdio<R, F: FnOnce() -> R + UnwindSafe>(stdio: Stdio<'_>, func: F) -> R
+    where
+        R: Default,
+    {
+        let _stdio = stdio::StdioGuard::new(stdio);
+
+        Self::run(move || {
+            stdio::register_streams();
+            func()
+        })
+    }
+
     /// Evaluate a php code
     ///
     /// This function will only work correctly when used inside the `Embed::run`
@@ -303,6 +440,89 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_run_script_cached() {
+        Embed::run(|| {
+            let script = "src/embed/test-script.php";
+
+            assert!(Embed::run_script_cached(script).is_ok());
+            assert!(Embed::run_script_cached(script).is_ok());
+            assert_eq!(Embed::script_cache_stats().misses, 1);
+            assert_eq!(Embed::script_cache_stats().hits, 1);
+            assert_eq!(Embed::script_cache_stats().entries, 1);
+
+            let zval = Embed::eval("$foo;").unwrap();
+            assert_eq!(zval.object().unwrap().get_class_name().unwrap(), "Test");
+
+            assert!(Embed::invalidate_script(script));
+            assert!(!Embed::invalidate_script(script));
+            assert_eq!(Embed::script_cache_stats().entries, 0);
+
+            assert!(Embed::run_script_cached(script).is_ok());
+            assert_eq!(Embed::script_cache_stats().misses, 2);
+            assert_eq!(Embed::script_cache_stats().invalidations, 1);
+
+            Embed::clear_script_cache();
+            assert_eq!(Embed::script_cache_stats().entries, 0);
+        });
+    }
+
+    #[test]
+    fn test_run_script_cached_error() {
+        Embed::run(|| {
+            let result = Embed::run_script_cached("src/embed/test-script-exception.php");
+            assert!(matches!(
+                result,
+                Err(super::EmbedError::ExecuteError(Some(_)))
+            ));
+
+            let result = Embed::run_script_cached("src/embed/does-not-exist.php");
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn test_run_with_stdio() {
+        use super::Stdio;
+
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+
+        Embed::run_with_stdio(
+            Stdio::new()
+                .stdin(&b"first\nsecond\n"[..])
+                .stdout(&mut output)
+                .stderr(&mut errors),
+            || {
+                Embed::eval("print 'echo:';").unwrap();
+                Embed::eval("fwrite(STDOUT, 'stdout:');").unwrap();
+                Embed::eval("fwrite(STDERR, 'stderr');").unwrap();
+
+                let line = Embed::eval("fgets(STDIN);").unwrap();
+                assert_eq!(line.string().unwrap(), "first\n");
+                let rest = Embed::eval("stream_get_contents(STDIN);").unwrap();
+                assert_eq!(rest.string().unwrap(), "second\n");
+                let eof = Embed::eval("feof(STDIN);").unwrap();
+                assert_eq!(eof.bool(), Some(true));
+            },
+        );
+
+        assert_eq!(output, b"echo:stdout:");
+        assert_eq!(errors, b"stderr");
+    }
+
+    #[test]
+    fn test_run_with_closed_stdio() {
+        use super::Stdio;
+
+        Embed::run_with_stdio(Stdio::new(), || {
+            let result = Embed::eval("fwrite(STDERR, 'lost');").unwrap();
+            assert_eq!(result.bool(), Some(false));
+            let result = Embed::eval("fgets(STDIN);").unwrap();
+            assert_eq!(result.bool(), Some(false));
+        });
+    }
+
     #[test]
     fn test_php_write() {
         use crate::zend::write;
@@ -0,0 +1,268 @@
+//! Redirection of the standard streams of the embed SAPI to Rust readers and
+//! writers.
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, c_int, c_void},
+    io::{Read, Write},
+    mem, ptr,
+};
+
+use crate::ffi::{php_stream, php_stream_ops, sapi_module};
+
+use super::ffi::{ext_php_rs_embed_register_stream_constant, ext_php_rs_embed_stream_alloc};
+
+/// Readers and writers backing the standard streams of a script run with
+/// [`Embed::run_with_stdio`].
+///
+/// Output of the script (e.g. `echo` or `print`) and writes to the `STDOUT` constant go
+/// to the stdout writer, writes to `STDERR` go to the stderr writer and
+/// reads from `STDIN` come from the stdin reader. Streams which are not set
+/// behave as if they were closed.
+///
+/// `php://stdin`, `php://stdout` and `php://stderr` always refer to the
+/// streams of the process and are not redirected.
+///
+/// ```
+/// use ext_php_rs::embed::{Embed, Stdio};
+///
+/// let mut output = Vec::new();
+/// let mut errors = Vec::new();
+///
+/// Embed::run_with_stdio(
+///     Stdio::new()
+///         .stdin(&b"World"[..])
+///         .stdout(&mut output)
+///         .stderr(&mut errors),
+///     || {
+///         let _ = Embed::eval("print 'Hello ' . fgets(STDIN);");
+///         let _ = Embed::eval("fwrite(STDERR, 'Oops');");
+///     },
+/// );
+///
+/// assert_eq!(output, b"Hello World");
+/// assert_eq!(errors, b"Oops");
+/// ```
+///
+/// [`Embed::run_with_stdio`]: super::Embed::run_with_stdio
+#[derive(Default)]
+pub struct Stdio<'a> {
+    stdin: Option<Box<dyn Read + 'a>>,
+    stdout: Option<Box<dyn Write + 'a>>,
+    stderr: Option<Box<dyn Write + 'a>>,
+}
+
+impl<'a> Stdio<'a> {
+    /// Creates a set of standard streams which are all closed.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the reader backing `STDIN`.
+    #[must_use]
+    pub fn stdin(mut self, reader: impl Read + 'a) -> Self {
+        self.stdin = Some(Box::new(reader));
+        self
+    }
+
+    /// Sets the writer receiving the output of the script and writes to
+    /// `STDOUT`.
+    #[must_use]
+    pub fn stdout(mut self, writer: impl Write + 'a) -> Self {
+        self.stdout = Some(Box::new(writer));
+        self
+    }
+
+    /// Sets the writer receiving writes to `STDERR`.
+    #[must_use]
+    pub fn stderr(mut self, writer: impl Write + 'a) -> Self {
+        self.stderr = Some(Box::new(writer));
+        self
+    }
+}
+
+thread_local! {
+    /// The streams of the script currently running on this thread. The
+    /// lifetime is erased, [`StdioGuard`] removes them before it ends.
+    static STDIO: RefCell<Option<Stdio<'static>>> = const { RefCell::new(None) };
+}
+
+/// Keeps the given streams installed until dropped.
+pub(crate) struct StdioGuard<'a> {
+    _stdio: std::marker::PhantomData<Stdio<'a>>,
+}
+
+impl<'a> StdioGuard<'a> {
+    /// Installs the given streams for the current thread.
+    pub(crate) fn new(stdio: Stdio<'a>) -> Self {
+        // SAFETY: The streams are removed when the guard is dropped, which happens
+        // before the lifetime of the streams ends.
+        let stdio = unsafe { mem::transmute::<Stdio<'a>, Stdio<'static>>(stdio) };
+        STDIO.with_borrow_mut(|current| *current = Some(stdio));
+        Self {
+            _stdio: std::marker::PhantomData,
+        }
+    }
+}
+
+impl Drop for StdioGuard<'_> {
+    fn drop(&mut self) {
+        let stdio = STDIO.with_borrow_mut(Option::take);
+        if let Some(mut stdio) = stdio {
+            if let Some(stdout) = stdio.stdout.as_mut() {
+                let _ = stdout.flush();
+            }
+            if let Some(stderr) = stdio.stderr.as_mut() {
+                let _ = stderr.flush();
+            }
+        }
+    }
+}
+
+/// Registers the `STDIN`, `STDOUT` and `STDERR` constants and redirects the
+/// output of the SAPI. Must be called inside of a request.
+pub(crate) fn register_streams() {
+    // SAFETY: The SAPI module is only read by PHP on the current thread.
+    unsafe { sapi_module.ub_write = Some(ub_write) };
+
+    for (stream, name, mode) in [
+        (StdStream::In, c"STDIN", c"rb"),
+        (StdStream::Out, c"STDOUT", c"wb"),
+        (StdStream::Err, c"STDERR", c"wb"),
+    ] {
+        // SAFETY: The ops live as long as the program, and the abstract pointer is
+        // only used as a tag.
+        unsafe {
+            let stream = ext_php_rs_embed_stream_alloc(
+                &raw const STDIO_OPS.0,
+                ptr::without_provenance_mut::<c_void>(stream as usize),
+                mode.as_ptr(),
+            );
+            if !stream.is_null() {
+                ext_php_rs_embed_register_stream_constant(name.as_ptr(), stream);
+            }
+        }
+    }
+}
+
+/// The standard stream a PHP stream refers to, stored in its abstract
+/// pointer.
+#[derive(Clone, Copy)]
+#[repr(usize)]
+enum StdStream {
+    In = 1,
+    Out = 2,
+    Err = 3,
+}
+
+impl StdStream {
+    fn of(stream: *mut php_stream) -> Option<Self> {
+        match unsafe { stream.as_ref() }?.abstract_.addr() {
+            1 => Some(Self::In),
+            2 => Some(Self::Out),
+            3 => Some(Self::Err),
+            _ => None,
+        }
+    }
+}
+
+struct StreamOps(php_stream_ops);
+
+// SAFETY: The ops only contain function pointers and a static label.
+unsafe impl Sync for StreamOps {}
+
+static STDIO_OPS: StreamOps = StreamOps(php_stream_ops {
+    write: Some(stream_write),
+    read: Some(stream_read),
+    close: Some(stream_close),
+    flush: Some(stream_flush),
+    label: c"ext-php-rs stdio".as_ptr(),
+    seek: None,
+    cast: None,
+    stat: None,
+    set_option: None,
+});
+
+/// Writes to the writer of the given stream, returning the number of bytes
+/// written or `None` if the stream is closed or the write failed.
+fn write_to(stream: StdStream, data: &[u8]) -> Option<usize> {
+    STDIO.with_borrow_mut(|stdio| {
+        let stdio = stdio.as_mut()?;
+        let writer = match stream {
+            StdStream::In => return None,
+            StdStream::Out => stdio.stdout.as_mut()?,
+            StdStream::Err => stdio.stderr.as_mut()?,
+        };
+        writer.write_all(data).ok().map(|()| data.len())
+    })
+}
+
+unsafe extern "C" fn ub_write(str_: *const c_char, str_length: usize) -> usize {
+    if str_.is_null() {
+        return 0;
+    }
+    let data = unsafe { std::slice::from_raw_parts(str_.cast::<u8>(), str_length) };
+    // Output is discarded without a stdout writer, but reported as written so
+    // PHP does not consider the connection to be aborted.
+    write_to(StdStream::Out, data).unwrap_or(str_length)
+}
+
+unsafe extern "C" fn stream_write(
+    stream: *mut php_stream,
+    buf: *const c_char,
+    count: usize,
+) -> isize {
+    let Some(std_stream) = StdStream::of(stream) else {
+        return -1;
+    };
+    if buf.is_null() {
+        return 0;
+    }
+    let data = unsafe { std::slice::from_raw_parts(buf.cast::<u8>(), count) };
+    write_to(std_stream, data).map_or(-1, |written| written.try_into().unwrap_or(isize::MAX))
+}
+
+unsafe extern "C" fn stream_read(stream: *mut php_stream, buf: *mut c_char, count: usize) -> isize {
+    if !matches!(StdStream::of(stream), Some(StdStream::In)) || buf.is_null() {
+        return -1;
+    }
+    let data = unsafe { std::slice::from_raw_parts_mut(buf.cast::<u8>(), count) };
+    let read = STDIO.with_borrow_mut(|stdio| {
+        let stdin = stdio.as_mut()?.stdin.as_mut()?;
+        loop {
+            match stdin.read(data) {
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                result => return result.ok(),
+            }
+        }
+    });
+
+    match read {
+        Some(0) | None => {
+            // SAFETY: The stream was checked to be non-null above.
+            unsafe { (*stream).set_eof(1) };
+            if read.is_some() { 0 } else { -1 }
+        }
+        Some(read) => read.try_into().unwrap_or(isize::MAX),
+    }
+}
+
+unsafe extern "C" fn stream_flush(stream: *mut php_stream) -> c_int {
+    let flushed = STDIO.with_borrow_mut(|stdio| {
+        let stdio = stdio.as_mut()?;
+        let writer = match StdStream::of(stream)? {
+            StdStream::In => return Some(()),
+            StdStream::Out => stdio.stdout.as_mut()?,
+            StdStream::Err => stdio.stderr.as_mut()?,
+        };
+        writer.flush().ok()
+    });
+    if flushed.is_some() { 0 } else { -1 }
+}
+
+unsafe extern "C" fn stream_close(_stream: *mut php_stream, _close_handle: c_int) -> c_int {
+    // The readers and writers are owned by the guard, which drops them once the
+    // script has finished running.
+    0
+}
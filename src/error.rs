@@ -9,6 +9,7 @@ use std::{
 
 use crate::{
     boxed::ZBox,
+    convert::IntoZval,
     exception::PhpException,
     ffi::php_error_docref,
     flags::{ClassFlags, DataType, ErrorType, ZvalTypeFlags},
@@ -33,6 +34,13 @@ pub enum Error {
     ///
     /// The enum carries the data type of the Zval.
     ZvalConversion(DataType),
+    /// A Zval did not contain a value of the expected type.
+    TypeMismatch {
+        /// The type that was expected.
+        expected: DataType,
+        /// The type of the value in the Zval.
+        actual: DataType,
+    },
     /// The type of the Zval is unknown.
     ///
     /// The enum carries the integer representation of the type of Zval.
@@ -72,6 +80,47 @@ pub enum Error {
     StreamWrapperUnregistrationFailure,
     /// The SAPI write function is not available
     SapiWriteUnavailable,
+    /// A failure occurred while registering functions
+    FunctionRegistrationFailure,
+    /// A failure occurred while changing or restoring an ini entry
+    IniModificationFailure,
+    /// A failure occurred while opening a stream
+    StreamOpenFailure,
+    /// A failure occurred while reading from or writing to a stream
+    StreamIoFailure,
+    /// The class with the given name could not be found.
+    UnknownClass(String),
+    /// The class with the given name was declared by userland code, so its
+    /// class entry does not live until PHP shuts down.
+    UserlandClass(String),
+    /// The class with the given name is an interface, trait, enum or abstract
+    /// class, which cannot be instantiated.
+    UninstantiableClass(String),
+    /// A persistent callable was used outside of the request or thread which
+    /// created it.
+    StaleCallable,
+    /// No value was found at the given path of a nested array lookup.
+    ///
+    /// The enum carries the path up to the missing segment.
+    PathNotFound(String),
+    /// Data given to `unserialize` is not a valid serialized value.
+    ///
+    /// The enum carries the offset of the byte where unserializing failed.
+    InvalidSerializedData(usize),
+    /// A range was out of bounds of a string.
+    ///
+    /// The enum carries the length of the string.
+    OutOfBounds(usize),
+    /// A value which is freed at the end of the request was inserted into a
+    /// persistent hashtable.
+    ///
+    /// The enum carries the type of the value.
+    NotPersistent(DataType),
+    /// A value could not be serialized into or deserialized from a zval.
+    ///
+    /// The enum carries the message of the error.
+    #[cfg(feature = "serde")]
+    Serde(String),
 }
 
 impl Display for Error {
@@ -85,6 +134,9 @@ impl Display for Error {
                 f,
                 "Could not convert Zval from type {ty} into primitive type."
             ),
+            Error::TypeMismatch { expected, actual } => {
+                write!(f, "Expected {expected}, got {actual}.")
+            }
             Error::UnknownDatatype(dt) => write!(f, "Unknown datatype {dt}."),
             Error::InvalidTypeToDatatype(dt) => {
                 write!(f, "Type flags did not contain a datatype: {dt:?}")
@@ -118,6 +170,43 @@ impl Display for Error {
             Error::SapiWriteUnavailable => {
                 write!(f, "The SAPI write function is not available")
             }
+            Error::FunctionRegistrationFailure => {
+                write!(f, "A failure occurred while registering functions")
+            }
+            Error::IniModificationFailure => {
+                write!(
+                    f,
+                    "A failure occurred while changing or restoring an ini entry"
+                )
+            }
+            Error::StreamOpenFailure => write!(f, "A failure occurred while opening a stream"),
+            Error::StreamIoFailure => {
+                write!(
+                    f,
+                    "A failure occurred while reading from or writing to a stream"
+                )
+            }
+            Error::UnknownClass(name) => write!(f, "Class `{name}` could not be found"),
+            Error::UserlandClass(name) => write!(
+                f,
+                "Class `{name}` was declared by userland code and is freed at the end of the request"
+            ),
+            Error::UninstantiableClass(name) => write!(f, "Cannot instantiate class `{name}`"),
+            Error::StaleCallable => write!(
+                f,
+                "Callable used outside of the request or thread which created it"
+            ),
+            Error::PathNotFound(path) => write!(f, "No value found at path `{path}`"),
+            Error::InvalidSerializedData(offset) => {
+                write!(f, "Invalid serialized data at offset {offset}")
+            }
+            Error::OutOfBounds(len) => write!(f, "Range out of bounds of length {len}"),
+            Error::NotPersistent(ty) => write!(
+                f,
+                "Value of type {ty} cannot be stored in a persistent hashtable"
+            ),
+            #[cfg(feature = "serde")]
+            Error::Serde(msg) => write!(f, "{msg}"),
         }
     }
 }
@@ -138,7 +227,14 @@ impl From<TryFromIntError> for Error {
 
 impl From<Error> for PhpException {
     fn from(err: Error) -> Self {
-        Self::default(err.to_string())
+        match err {
+            // Exceptions thrown by PHP are rethrown as is.
+            Error::Exception(obj) => match obj.into_zval(false) {
+                Ok(obj) => Self::default(String::new()).with_object(obj),
+                Err(err) => Self::default(err.to_string()),
+            },
+            err => Self::default(err.to_string()),
+        }
     }
 }
 
@@ -162,3 +258,146 @@ pub fn php_error(type_: &ErrorType, message: &str) {
         );
     }
 }
+
+/// The level of a non-fatal diagnostic raised from Rust code, see
+/// [`ErrorLevel::raise`].
+///
+/// Fatal levels such as `E_ERROR` are not included, as raising them aborts
+/// the request without running Rust destructors. Return an exception instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorLevel {
+    /// `E_WARNING`
+    Warning,
+    /// `E_NOTICE`
+    Notice,
+    /// `E_DEPRECATED`
+    Deprecated,
+    /// `E_USER_WARNING`
+    UserWarning,
+    /// `E_USER_NOTICE`
+    UserNotice,
+    /// `E_USER_DEPRECATED`
+    UserDeprecated,
+}
+
+impl ErrorLevel {
+    /// Returns the PHP error type of the level.
+    #[must_use]
+    pub fn error_type(self) -> ErrorType {
+        match self {
+            Self::Warning => ErrorType::Warning,
+            Self::Notice => ErrorType::Notice,
+            Self::Deprecated => ErrorType::Deprecated,
+            Self::UserWarning => ErrorType::UserWarning,
+            Self::UserNotice => ErrorType::UserNotice,
+            Self::UserDeprecated => ErrorType::UserDeprecated,
+        }
+    }
+
+    /// Raises a diagnostic with the level and the given message. The message
+    /// is prefixed with the name of the currently executing function, like
+    /// the diagnostics of built-in functions.
+    pub fn raise(self, message: &str) {
+        raise_error(None, self.error_type(), message);
+    }
+
+    /// Raises a diagnostic with the level and the given message, linking to
+    /// the given documentation reference if `html_errors` is enabled.
+    ///
+    /// # Parameters
+    ///
+    /// * `docref` - A page of the manual such as `function.json-decode`, or a
+    ///   full URL.
+    /// * `message` - The message of the diagnostic.
+    pub fn raise_with_docref(self, docref: &str, message: &str) {
+        raise_error(Some(docref), self.error_type(), message);
+    }
+}
+
+impl From<ErrorLevel> for ErrorType {
+    fn from(level: ErrorLevel) -> Self {
+        level.error_type()
+    }
+}
+
+/// Implemented on error types which can be reported as a PHP diagnostic
+/// instead of an exception, with a level depending on the error.
+///
+/// ```rust,no_run
+/// use std::fmt;
+/// use ext_php_rs::error::{Diagnostic, ErrorLevel};
+///
+/// #[derive(Debug)]
+/// enum CacheError {
+///     Miss,
+///     Stale,
+/// }
+///
+/// impl fmt::Display for CacheError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         match self {
+///             Self::Miss => write!(f, "Cache miss"),
+///             Self::Stale => write!(f, "Cache entry is stale"),
+///         }
+///     }
+/// }
+///
+/// impl Diagnostic for CacheError {
+///     fn error_level(&self) -> ErrorLevel {
+///         match self {
+///             Self::Miss => ErrorLevel::Notice,
+///             Self::Stale => ErrorLevel::Warning,
+///         }
+///     }
+/// }
+///
+/// CacheError::Stale.raise();
+/// ```
+pub trait Diagnostic: Display {
+    /// Returns the level the error is raised with.
+    fn error_level(&self) -> ErrorLevel {
+        ErrorLevel::Warning
+    }
+
+    /// Returns the documentation reference linked from the diagnostic, see
+    /// [`ErrorLevel::raise_with_docref`].
+    fn docref(&self) -> Option<&str> {
+        None
+    }
+
+    /// Raises the error as a PHP diagnostic.
+    fn raise(&self) {
+        raise_error(
+            self.docref(),
+            self.error_level().error_type(),
+            &self.to_string(),
+        );
+    }
+}
+
+fn raise_error(docref: Option<&str>, type_: ErrorType, message: &str) {
+    // Messages are passed as an argument rather than as the format string, so
+    // they may contain `%`.
+    const FORMAT: &[u8] = b"%s\0";
+
+    let Ok(message) = CString::new(message) else {
+        return;
+    };
+    let Ok(docref) = docref.map(CString::new).transpose() else {
+        return;
+    };
+    let Ok(type_) = type_.bits().try_into() else {
+        return;
+    };
+
+    unsafe {
+        php_error_docref(
+            docref
+                .as_ref()
+                .map_or(std::ptr::null(), |docref| docref.as_ptr()),
+            type_,
+            FORMAT.as_ptr().cast(),
+            message.as_ptr(),
+        );
+    }
+}
@@ -1,15 +1,15 @@
 //! Types and functions used for throwing exceptions from Rust to PHP.
 
-use std::{ffi::CString, fmt::Debug, ptr};
+use std::{error::Error as ErrorTrait, ffi::CString, fmt::Debug, ptr};
 
 use crate::{
     class::RegisteredClass,
     error::{Error, Result},
     ffi::zend_throw_exception_ex,
     ffi::zend_throw_exception_object,
-    flags::ClassFlags,
+    flags::{ClassFlags, DataType},
     types::Zval,
-    zend::{ClassEntry, ce},
+    zend::{ClassEntry, ExecutorGlobals, ce},
 };
 
 /// Result type with the error variant as a [`PhpException`].
@@ -28,6 +28,7 @@ pub struct PhpException {
     code: i32,
     ex: &'static ClassEntry,
     object: Option<Zval>,
+    previous: Option<Box<PhpException>>,
 }
 
 impl PhpException {
@@ -45,6 +46,7 @@ impl PhpException {
             code,
             ex,
             object: None,
+            previous: None,
         }
     }
 
@@ -60,6 +62,29 @@ impl PhpException {
         Self::new(message, 0, ce::exception())
     }
 
+    /// Creates a `TypeError` for an argument of the function being called
+    /// which has the wrong type, with the message PHP uses, e.g.
+    /// `f(): Argument #1 ($a) must be of type string, array given`.
+    ///
+    /// # Parameters
+    ///
+    /// * `arg_num` - The position of the argument, starting at 1.
+    /// * `name` - The name of the argument, without the `$`.
+    /// * `expected` - The expected type, e.g. `string`.
+    /// * `given` - The value given for the argument.
+    #[must_use]
+    pub fn argument_type_error(arg_num: usize, name: &str, expected: &str, given: &Zval) -> Self {
+        Self::new(
+            format!(
+                "{}(): Argument #{arg_num} (${name}) must be of type {expected}, {} given",
+                current_function_name(),
+                given_type_name(given)
+            ),
+            0,
+            ce::type_error(),
+        )
+    }
+
     /// Creates an instance of an exception from a PHP class type and a message.
     ///
     /// # Parameters
@@ -70,6 +95,101 @@ impl PhpException {
         Self::new(message, 0, T::get_metadata().ce())
     }
 
+    /// Creates an exception from a Rust error, with a chained `previous`
+    /// exception for every error in its [`source`] chain. All exceptions are
+    /// of the default PHP `Exception` type, see [`PhpException::from_error_with`]
+    /// to choose the types.
+    ///
+    /// # Parameters
+    ///
+    /// * `error` - The error to create the exception from.
+    ///
+    /// [`source`]: std::error::Error::source
+    #[must_use]
+    pub fn from_error(error: &(dyn ErrorTrait + 'static)) -> Self {
+        Self::from_error_with(error, |_| None)
+    }
+
+    /// Creates an exception from a Rust error, with a chained `previous`
+    /// exception for every error in its [`source`] chain.
+    ///
+    /// The given function is called for the error and each of its sources,
+    /// and may map them to a specific exception, e.g. by downcasting. Errors
+    /// it returns `None` for become a default PHP `Exception` with the message
+    /// of the error.
+    ///
+    /// # Parameters
+    ///
+    /// * `error` - The error to create the exception from.
+    /// * `map` - Function mapping an error to an exception.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::{exception::PhpException, zend::ce};
+    ///
+    /// fn to_exception(error: &std::io::Error) -> PhpException {
+    ///     PhpException::from_error_with(error, |error| {
+    ///         error.downcast_ref::<std::io::Error>().map(|error| {
+    ///             PhpException::new(error.to_string(), 0, ce::value_error())
+    ///         })
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// [`source`]: std::error::Error::source
+    #[must_use]
+    pub fn from_error_with<F>(error: &(dyn ErrorTrait + 'static), map: F) -> Self
+    where
+        F: Fn(&(dyn ErrorTrait + 'static)) -> Option<Self>,
+    {
+        let chain: Vec<_> = std::iter::successors(Some(error), |error| error.source()).collect();
+        Self::from_chain(chain.into_iter(), map)
+            .expect("Error chain always contains the error itself")
+    }
+
+    /// Creates an exception chain from errors ordered from the outermost to
+    /// the innermost cause.
+    fn from_chain<'a, I, F>(chain: I, map: F) -> Option<Self>
+    where
+        I: DoubleEndedIterator<Item = &'a (dyn ErrorTrait + 'static)>,
+        F: Fn(&(dyn ErrorTrait + 'static)) -> Option<Self>,
+    {
+        chain.rev().fold(None, |previous, error| {
+            let mut ex = map(error).unwrap_or_else(|| Self::default(error.to_string()));
+            ex.previous = previous.map(Box::new);
+            Some(ex)
+        })
+    }
+
+    /// Returns the previous exception chained to the exception, if any.
+    #[must_use]
+    pub fn previous(&self) -> Option<&PhpException> {
+        self.previous.as_deref()
+    }
+
+    /// Sets the previous exception, which is thrown before the exception and
+    /// becomes its `getPrevious()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `previous` - The previous exception.
+    pub fn set_previous(&mut self, previous: Option<PhpException>) {
+        self.previous = previous.map(Box::new);
+    }
+
+    /// Builder function that sets the previous exception, which is thrown
+    /// before the exception and becomes its `getPrevious()` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `previous` - The previous exception.
+    #[must_use]
+    pub fn with_previous(mut self, previous: PhpException) -> Self {
+        self.previous = Some(Box::new(previous));
+        self
+    }
+
     /// Set the Zval object for the exception.
     ///
     /// Exceptions can be based of instantiated Zval objects when you are
@@ -104,7 +224,15 @@ impl PhpException {
     /// * [`Error::InvalidException`] - If the exception type is an interface or
     ///   abstract class.
     /// * If the message contains NUL bytes.
+    ///
+    /// Previous exceptions are thrown first, so PHP chains them to the
+    /// exception. If one of them fails to be thrown, the exceptions thrown
+    /// until then are left in place.
     pub fn throw(self) -> Result<()> {
+        if let Some(previous) = self.previous {
+            previous.throw()?;
+        }
+
         match self.object {
             Some(object) => throw_object(object),
             None => throw_with_code(self.ex, self.code, &self.message),
@@ -127,7 +255,8 @@ impl From<&str> for PhpException {
 #[cfg(feature = "anyhow")]
 impl From<anyhow::Error> for PhpException {
     fn from(err: anyhow::Error) -> Self {
-        Self::new(format!("{err:#}"), 0, crate::zend::ce::exception())
+        Self::from_chain(err.chain(), |_| None)
+            .expect("Error chain always contains the error itself")
     }
 }
 
@@ -252,6 +381,48 @@ pub fn throw_object(zval: Zval) -> Result<()> {
 }
 
 #[cfg(feature = "embed")]
+/// Returns the name of the function currently being executed, prefixed with
+/// its class for methods, as used in PHP's error messages.
+fn current_function_name() -> String {
+    let globals = ExecutorGlobals::get();
+    let Some(func) = globals.current_function() else {
+        return String::new();
+    };
+    let (name, scope) = unsafe {
+        (
+            func.common.function_name.as_ref(),
+            func.common.scope.as_ref(),
+        )
+    };
+    let name = name.and_then(|name| name.as_str().ok()).unwrap_or_default();
+    match scope.and_then(ClassEntry::name) {
+        Some(class) => format!("{class}::{name}"),
+        None => name.to_string(),
+    }
+}
+
+/// Returns the type name of a value as PHP uses it in error messages, e.g.
+/// `array` or the class name of an object.
+fn given_type_name(zv: &Zval) -> String {
+    let zv = zv.dereference();
+    if let Some(obj) = zv.object() {
+        return obj.get_class_name().unwrap_or_else(|_| "object".into());
+    }
+    match zv.get_type() {
+        DataType::Null | DataType::Undef => "null",
+        DataType::True | DataType::False if !cfg!(php83) => "bool",
+        DataType::True => "true",
+        DataType::False => "false",
+        DataType::Long => "int",
+        DataType::Double => "float",
+        DataType::String => "string",
+        DataType::Array => "array",
+        DataType::Resource => "resource",
+        _ => "mixed",
+    }
+    .into()
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::assertions_on_constants)]
@@ -280,6 +451,53 @@ mod tests {
         });
     }
 
+    #[derive(Debug)]
+    struct WrappedError(std::io::Error);
+
+    impl std::fmt::Display for WrappedError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Wrapped")
+        }
+    }
+
+    impl ErrorTrait for WrappedError {
+        fn source(&self) -> Option<&(dyn ErrorTrait + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_from_error() {
+        Embed::run(|| {
+            let err = WrappedError(std::io::Error::other("Inner"));
+            let ex = PhpException::from_error(&err);
+            assert_eq!(ex.message, "Wrapped");
+            assert_eq!(ex.ex, ce::exception());
+
+            let previous = ex.previous().expect("Previous exception");
+            assert_eq!(previous.message, "Inner");
+            assert!(previous.previous().is_none());
+        });
+    }
+
+    #[test]
+    fn test_from_error_with() {
+        Embed::run(|| {
+            let err = WrappedError(std::io::Error::other("Inner"));
+            let ex = PhpException::from_error_with(&err, |error| {
+                error
+                    .downcast_ref::<std::io::Error>()
+                    .map(|error| PhpException::new(error.to_string(), 5, ce::value_error()))
+            });
+            assert_eq!(ex.ex, ce::exception());
+
+            let previous = ex.previous().expect("Previous exception");
+            assert_eq!(previous.message, "Inner");
+            assert_eq!(previous.code, 5);
+            assert_eq!(previous.ex, ce::value_error());
+        });
+    }
+
     #[test]
     fn test_set_object() {
         Embed::run(|| {
@@ -351,6 +569,18 @@ mod tests {
             assert_eq!(ex.code, 0);
             assert_eq!(ex.ex, ce::exception());
             assert!(ex.object.is_none());
+            assert!(ex.previous.is_none());
+        });
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn test_from_anyhow_context() {
+        Embed::run(|| {
+            let err = anyhow::anyhow!("Inner").context("Outer");
+            let ex: PhpException = err.into();
+            assert_eq!(ex.message, "Outer");
+            assert_eq!(ex.previous().map(|ex| ex.message.as_str()), Some("Inner"));
         });
     }
 
@@ -44,6 +44,30 @@ unsafe extern "C" {
     ) -> bool;
 
     pub fn ext_php_rs_zend_bailout() -> !;
+
+    pub fn ext_php_rs_zend_new_packed_long(values: *const zend_long, len: u32) -> *mut HashTable;
+    pub fn ext_php_rs_zend_new_packed_double(values: *const f64, len: u32) -> *mut HashTable;
+    pub fn ext_php_rs_zend_new_packed_bool(values: *const bool, len: u32) -> *mut HashTable;
+    pub fn ext_php_rs_zend_new_packed_zval(values: *const zval, len: u32) -> *mut HashTable;
+    pub fn ext_php_rs_zend_new_persistent_array(size: u32) -> *mut HashTable;
+    pub fn ext_php_rs_zend_persistent_array_destroy(ht: *mut HashTable);
+    pub fn ext_php_rs_zend_hash_copy_longs(ht: *const HashTable, out: *mut zend_long) -> bool;
+    pub fn ext_php_rs_zend_hash_copy_doubles(ht: *const HashTable, out: *mut f64) -> bool;
+    pub fn ext_php_rs_zend_hash_copy_bools(ht: *const HashTable, out: *mut bool) -> bool;
+
+    pub fn ext_php_rs_php_var_serialize(val: *mut zval) -> *mut zend_string;
+    pub fn ext_php_rs_php_var_unserialize(
+        rval: *mut zval,
+        buf: *const c_char,
+        len: usize,
+        allowed_classes: *mut HashTable,
+        offset: *mut usize,
+    ) -> bool;
+
+    pub fn ext_php_rs_smart_str_appendl(dest: *mut smart_str, str_: *const c_char, len: usize);
+    pub fn ext_php_rs_smart_str_alloc(dest: *mut smart_str, len: usize);
+    pub fn ext_php_rs_smart_str_extract(dest: *mut smart_str) -> *mut zend_string;
+    pub fn ext_php_rs_smart_str_free(dest: *mut smart_str);
 }
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
@@ -2,15 +2,14 @@
 
 use bitflags::bitflags;
 
-#[cfg(php81)]
-use crate::ffi::ZEND_ACC_ENUM;
 #[cfg(not(php82))]
 use crate::ffi::ZEND_ACC_REUSE_GET_ITERATOR;
 use crate::ffi::{
-    _IS_BOOL, CONST_CS, CONST_DEPRECATED, CONST_NO_FILE_CACHE, CONST_PERSISTENT, E_COMPILE_ERROR,
-    E_COMPILE_WARNING, E_CORE_ERROR, E_CORE_WARNING, E_DEPRECATED, E_ERROR, E_NOTICE, E_PARSE,
-    E_RECOVERABLE_ERROR, E_STRICT, E_USER_DEPRECATED, E_USER_ERROR, E_USER_NOTICE, E_USER_WARNING,
-    E_WARNING, GC_IMMUTABLE, IS_ARRAY, IS_CALLABLE, IS_CONSTANT_AST, IS_DOUBLE, IS_FALSE,
+    _IS_BOOL, BP_VAR_FUNC_ARG, BP_VAR_IS, BP_VAR_RW, BP_VAR_UNSET, BP_VAR_W, CONST_CS,
+    CONST_DEPRECATED, CONST_NO_FILE_CACHE, CONST_PERSISTENT, E_COMPILE_ERROR, E_COMPILE_WARNING,
+    E_CORE_ERROR, E_CORE_WARNING, E_DEPRECATED, E_ERROR, E_NOTICE, E_PARSE, E_RECOVERABLE_ERROR,
+    E_STRICT, E_USER_DEPRECATED, E_USER_ERROR, E_USER_NOTICE, E_USER_WARNING, E_WARNING,
+    GC_IMMUTABLE, GC_PERSISTENT, IS_ARRAY, IS_CALLABLE, IS_CONSTANT_AST, IS_DOUBLE, IS_FALSE,
     IS_INDIRECT, IS_ITERABLE, IS_LONG, IS_MIXED, IS_NULL, IS_OBJECT, IS_PTR, IS_REFERENCE,
     IS_RESOURCE, IS_STRING, IS_TRUE, IS_TYPE_COLLECTABLE, IS_TYPE_REFCOUNTED, IS_UNDEF, IS_VOID,
     PHP_INI_ALL, PHP_INI_PERDIR, PHP_INI_SYSTEM, PHP_INI_USER, Z_TYPE_FLAGS_SHIFT,
@@ -24,9 +23,20 @@ use crate::ffi::{
     ZEND_ACC_PROMOTED, ZEND_ACC_PROTECTED, ZEND_ACC_PUBLIC, ZEND_ACC_RESOLVED_INTERFACES,
     ZEND_ACC_RESOLVED_PARENT, ZEND_ACC_RETURN_REFERENCE, ZEND_ACC_STATIC, ZEND_ACC_STRICT_TYPES,
     ZEND_ACC_TOP_LEVEL, ZEND_ACC_TRAIT, ZEND_ACC_TRAIT_CLONE, ZEND_ACC_UNRESOLVED_VARIANCE,
-    ZEND_ACC_USE_GUARDS, ZEND_ACC_USES_THIS, ZEND_ACC_VARIADIC, ZEND_EVAL_CODE,
-    ZEND_HAS_STATIC_IN_METHODS, ZEND_INTERNAL_FUNCTION, ZEND_USER_FUNCTION,
+    ZEND_ACC_USE_GUARDS, ZEND_ACC_USES_THIS, ZEND_ACC_VARIADIC, ZEND_COMPILE_DELAYED_BINDING,
+    ZEND_COMPILE_EXTENDED_FCALL, ZEND_COMPILE_EXTENDED_STMT, ZEND_COMPILE_GUARDS,
+    ZEND_COMPILE_HANDLE_OP_ARRAY, ZEND_COMPILE_IGNORE_INTERNAL_CLASSES,
+    ZEND_COMPILE_IGNORE_INTERNAL_FUNCTIONS, ZEND_COMPILE_IGNORE_OTHER_FILES,
+    ZEND_COMPILE_IGNORE_USER_FUNCTIONS, ZEND_COMPILE_NO_BUILTINS,
+    ZEND_COMPILE_NO_CONSTANT_SUBSTITUTION, ZEND_COMPILE_NO_JUMPTABLES,
+    ZEND_COMPILE_NO_PERSISTENT_CONSTANT_SUBSTITUTION, ZEND_COMPILE_PRELOAD,
+    ZEND_COMPILE_PRELOAD_IN_CHILD, ZEND_COMPILE_WITH_FILE_CACHE, ZEND_COMPILE_WITHOUT_EXECUTION,
+    ZEND_EVAL_CODE, ZEND_HAS_STATIC_IN_METHODS, ZEND_INI_STAGE_ACTIVATE, ZEND_INI_STAGE_DEACTIVATE,
+    ZEND_INI_STAGE_HTACCESS, ZEND_INI_STAGE_RUNTIME, ZEND_INI_STAGE_SHUTDOWN,
+    ZEND_INI_STAGE_STARTUP, ZEND_INTERNAL_FUNCTION, ZEND_USER_FUNCTION,
 };
+#[cfg(php81)]
+use crate::ffi::{IS_NEVER, ZEND_ACC_ENUM};
 
 use std::{convert::TryFrom, fmt::Display};
 
@@ -91,6 +101,8 @@ bitflags! {
 
         /// Immutable (used for the shared empty array)
         const Immutable = GC_IMMUTABLE;
+        /// Persistent (used for values allocated with the persistent allocator)
+        const Persistent = GC_PERSISTENT;
     }
 }
 
@@ -296,6 +308,72 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Represents the stage at which a configuration setting is changed.
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+    pub struct IniStage: u32 {
+        /// The module is starting up, e.g. the value is read from `php.ini`
+        const Startup = ZEND_INI_STAGE_STARTUP;
+        /// The module is shutting down
+        const Shutdown = ZEND_INI_STAGE_SHUTDOWN;
+        /// A request is starting, e.g. the value is set per directory
+        const Activate = ZEND_INI_STAGE_ACTIVATE;
+        /// A request is ending and the value is restored
+        const Deactivate = ZEND_INI_STAGE_DEACTIVATE;
+        /// The value is changed at runtime, e.g. through `ini_set()`
+        const Runtime = ZEND_INI_STAGE_RUNTIME;
+        /// The value is set through a `.htaccess` file
+        const Htaccess = ZEND_INI_STAGE_HTACCESS;
+        /// Any stage during a request
+        const InRequest = ZEND_INI_STAGE_ACTIVATE | ZEND_INI_STAGE_DEACTIVATE | ZEND_INI_STAGE_RUNTIME | ZEND_INI_STAGE_HTACCESS;
+    }
+}
+
+bitflags! {
+    /// Options of the compiler, stored in `CG(compiler_options)`.
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+    pub struct CompilerOptions: u32 {
+        /// Emit `ZEND_EXT_STMT` opcodes, used by debuggers
+        const ExtendedStmt = ZEND_COMPILE_EXTENDED_STMT;
+        /// Emit `ZEND_EXT_FCALL_BEGIN` and `ZEND_EXT_FCALL_END` opcodes around calls
+        const ExtendedFcall = ZEND_COMPILE_EXTENDED_FCALL;
+        /// Run the `op_array_handler` of Zend extensions on compiled functions
+        const HandleOpArray = ZEND_COMPILE_HANDLE_OP_ARRAY;
+        /// Do not resolve calls to internal functions at compile time
+        const IgnoreInternalFunctions = ZEND_COMPILE_IGNORE_INTERNAL_FUNCTIONS;
+        /// Do not resolve internal classes at compile time
+        const IgnoreInternalClasses = ZEND_COMPILE_IGNORE_INTERNAL_CLASSES;
+        /// Delay the binding of classes and functions, used by opcode caches
+        const DelayedBinding = ZEND_COMPILE_DELAYED_BINDING;
+        /// Do not substitute constants at compile time
+        const NoConstantSubstitution = ZEND_COMPILE_NO_CONSTANT_SUBSTITUTION;
+        /// Do not substitute persistent constants at compile time
+        const NoPersistentConstantSubstitution = ZEND_COMPILE_NO_PERSISTENT_CONSTANT_SUBSTITUTION;
+        /// Do not resolve calls to user functions at compile time
+        const IgnoreUserFunctions = ZEND_COMPILE_IGNORE_USER_FUNCTIONS;
+        /// Force the use of property guards
+        const Guards = ZEND_COMPILE_GUARDS;
+        /// Do not compile calls to functions like `strlen()` into special opcodes
+        const NoBuiltins = ZEND_COMPILE_NO_BUILTINS;
+        /// The script is compiled for the file cache of OPcache
+        const WithFileCache = ZEND_COMPILE_WITH_FILE_CACHE;
+        /// Do not resolve classes and functions declared in other files
+        const IgnoreOtherFiles = ZEND_COMPILE_IGNORE_OTHER_FILES;
+        /// The script is compiled without being executed
+        const WithoutExecution = ZEND_COMPILE_WITHOUT_EXECUTION;
+        /// The script is compiled for preloading
+        const Preload = ZEND_COMPILE_PRELOAD;
+        /// Do not compile `switch` and `match` into jump tables
+        const NoJumptables = ZEND_COMPILE_NO_JUMPTABLES;
+        /// The script is preloaded in a child process
+        const PreloadInChild = ZEND_COMPILE_PRELOAD_IN_CHILD;
+        /// Emit all extended opcodes
+        const ExtendedInfo = ZEND_COMPILE_EXTENDED_STMT | ZEND_COMPILE_EXTENDED_FCALL;
+        /// Default options when compiling files
+        const Default = ZEND_COMPILE_HANDLE_OP_ARRAY;
+    }
+}
+
 bitflags! {
     /// Represents error types when used via php_error_docref for example.
     pub struct ErrorType: u32 {
@@ -356,6 +434,68 @@ impl From<u8> for FunctionType {
     }
 }
 
+/// The mode in which an element of an array or object is fetched, e.g. by a
+/// dimension handler (`BP_VAR_*`).
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum FetchMode {
+    /// The element is read, e.g. `$obj['key']`.
+    Read,
+    /// The element is fetched to be written to, e.g. `$obj['key'][] = 1`.
+    Write,
+    /// The element is fetched to be read and written to, e.g.
+    /// `$obj['key']['nested'] .= 'value'`.
+    ReadWrite,
+    /// The element is read without raising notices, e.g. `isset()` or `??`.
+    Isset,
+    /// The element is passed as an argument which may be taken by reference.
+    FuncArg,
+    /// The element is fetched to unset a nested element, e.g.
+    /// `unset($obj['key']['nested'])`.
+    Unset,
+}
+
+impl From<u32> for FetchMode {
+    /// Converts a `BP_VAR_*` value, treating unknown values as reads.
+    fn from(value: u32) -> Self {
+        match value {
+            BP_VAR_W => Self::Write,
+            BP_VAR_RW => Self::ReadWrite,
+            BP_VAR_IS => Self::Isset,
+            BP_VAR_FUNC_ARG => Self::FuncArg,
+            BP_VAR_UNSET => Self::Unset,
+            _ => Self::Read,
+        }
+    }
+}
+
+/// The check made by a property handler for whether a property is set
+/// (`ZEND_PROPERTY_*`).
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum PropertyCheck {
+    /// Whether the property exists and is not `null`, e.g. `isset()` or `??`.
+    Isset,
+    /// Whether the property exists and is truthy, i.e. the negation of
+    /// `empty()`.
+    NotEmpty,
+    /// Whether the property exists, including properties set to `null`, e.g.
+    /// `property_exists()` on an object.
+    Exists,
+}
+
+impl PropertyCheck {
+    /// Returns the check for the given `has_set_exists` value of the
+    /// `has_property` object handler, or `None` if it is invalid.
+    #[must_use]
+    pub fn from_raw(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Isset),
+            1 => Some(Self::NotEmpty),
+            2 => Some(Self::Exists),
+            _ => None,
+        }
+    }
+}
+
 /// Valid data types for PHP.
 #[repr(C, u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
@@ -399,6 +539,9 @@ pub enum DataType {
     Ptr,
     /// Indirect (internal)
     Indirect,
+    /// Never, only valid as the return type of functions which do not return
+    /// (PHP 8.1+)
+    Never,
 }
 
 impl DataType {
@@ -424,6 +567,12 @@ impl DataType {
             DataType::Bool => _IS_BOOL,
             DataType::Ptr => IS_PTR,
             DataType::Iterable => IS_ITERABLE,
+            #[cfg(php81)]
+            DataType::Never => IS_NEVER,
+            // `never` is not available before PHP 8.1, where the closest return
+            // type is `void`.
+            #[cfg(not(php81))]
+            DataType::Never => IS_VOID,
         }
     }
 }
@@ -531,6 +680,7 @@ impl Display for DataType {
             DataType::Ptr => write!(f, "Pointer"),
             DataType::Indirect => write!(f, "Indirect"),
             DataType::Iterable => write!(f, "Iterable"),
+            DataType::Never => write!(f, "Never"),
         }
     }
 }
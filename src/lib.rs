@@ -10,6 +10,7 @@
 
 pub mod alloc;
 pub mod args;
+pub mod async_;
 pub mod binary;
 pub mod binary_slice;
 pub mod builders;
@@ -35,6 +36,9 @@ pub mod enum_;
 pub mod internal;
 pub mod props;
 pub mod rc;
+#[cfg(feature = "serde")]
+#[cfg_attr(docs, doc(cfg(feature = "serde")))]
+pub mod serde;
 #[cfg(test)]
 pub mod test;
 pub mod types;
@@ -58,8 +62,8 @@ pub mod prelude {
     pub use crate::types::ZendCallable;
     pub use crate::zend::BailoutGuard;
     pub use crate::{
-        ZvalConvert, php_class, php_const, php_extern, php_function, php_impl, php_interface,
-        php_module, wrap_constant, wrap_function, zend_fastcall,
+        ZvalConvert, php_class, php_const, php_extern, php_extern_class, php_function, php_impl,
+        php_interface, php_module, wrap_constant, wrap_function, zend_fastcall,
     };
 }
 
@@ -75,6 +79,6 @@ pub const PHP_ZTS: bool = cfg!(php_zts);
 #[cfg(feature = "enum")]
 pub use ext_php_rs_derive::php_enum;
 pub use ext_php_rs_derive::{
-    ZvalConvert, php_class, php_const, php_extern, php_function, php_impl, php_interface,
-    php_module, wrap_constant, wrap_function, zend_fastcall,
+    ZvalConvert, php_class, php_const, php_extern, php_extern_class, php_function, php_impl,
+    php_interface, php_module, wrap_constant, wrap_function, zend_fastcall,
 };
@@ -335,7 +335,7 @@ macro_rules! class_derives {
 
 /// Derives `From<T> for Zval` and `IntoZval` for a given type.
 macro_rules! into_zval {
-    ($type: ty, $fn: ident, $dt: ident) => {
+    ($type: ty, $fn: ident, $dt: ident $(, packed: $into_ht: path)?) => {
         impl From<$type> for $crate::types::Zval {
             fn from(val: $type) -> Self {
                 let mut zv = Self::new();
@@ -352,13 +352,27 @@ macro_rules! into_zval {
                 zv.$fn(self);
                 Ok(())
             }
+
+            $(
+                fn vec_into_hashtable(
+                    vec: ::std::vec::Vec<Self>,
+                ) -> $crate::error::Result<$crate::boxed::ZBox<$crate::types::ZendHashTable>> {
+                    $into_ht(&vec)
+                }
+
+                fn slice_into_hashtable(
+                    slice: &[Self],
+                ) -> $crate::error::Result<$crate::boxed::ZBox<$crate::types::ZendHashTable>> {
+                    $into_ht(slice)
+                }
+            )?
         }
     };
 }
 
 /// Derives `TryFrom<Zval> for T` and `FromZval for T` on a given type.
 macro_rules! try_from_zval {
-    ($type: ty, $fn: ident, $dt: ident) => {
+    ($type: ty, $fn: ident, $dt: ident $(, packed: $from_ht: path)?) => {
         impl $crate::convert::FromZval<'_> for $type {
             const TYPE: $crate::flags::DataType = $crate::flags::DataType::$dt;
 
@@ -367,6 +381,14 @@ macro_rules! try_from_zval {
 
                 zval.$fn().and_then(|val| val.try_into().ok())
             }
+
+            $(
+                fn vec_from_hashtable(
+                    ht: &$crate::types::ZendHashTable,
+                ) -> $crate::error::Result<::std::vec::Vec<Self>> {
+                    $from_ht(ht)
+                }
+            )?
         }
 
         impl ::std::convert::TryFrom<$crate::types::Zval> for $type {
@@ -504,3 +526,28 @@ macro_rules! php_write {
 macro_rules! php_output {
     ($data: expr) => {{ $crate::zend::output_write($data) }};
 }
+
+/// Interns a string literal once per process, returning it as a
+/// [`ZBox<ZendStr>`](crate::types::ZendStr).
+///
+/// This avoids allocating the same string, e.g. a property name or an array
+/// key, on every call. The string is only interned once if it is first used
+/// during startup or PHP had already interned it permanently. Otherwise, it is
+/// interned again on every use, see
+/// [`LazyInterned`](crate::types::LazyInterned).
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::lazy_interned;
+///
+/// let key = lazy_interned!("id");
+/// assert_eq!(key.as_str().ok(), Some("id"));
+/// ```
+#[macro_export]
+macro_rules! lazy_interned {
+    ($str: literal) => {{
+        static INTERNED: $crate::types::LazyInterned = $crate::types::LazyInterned::new($str);
+        INTERNED.get()
+    }};
+}
@@ -3,8 +3,8 @@
 use std::fmt::Debug;
 
 use crate::{
-    ffi::{zend_refcounted_h, zend_string},
-    types::ZendObject,
+    ffi::{GC_FLAGS_MASK, GC_FLAGS_SHIFT, GC_IMMUTABLE, zend_refcounted_h, zend_string},
+    types::{ZendHashTable, ZendObject},
 };
 
 /// Object used to store Zend reference counter.
@@ -18,6 +18,18 @@ impl Debug for ZendRefcount {
     }
 }
 
+impl ZendRefcount {
+    /// Returns whether the value is immutable, e.g. an interned string or the
+    /// shared empty array. Immutable values are not reference counted.
+    #[must_use]
+    pub fn is_immutable(&self) -> bool {
+        // SAFETY: Type info is initialized by Zend when the value is created.
+        let type_info = unsafe { self.u.type_info };
+        let flags = (type_info >> GC_FLAGS_SHIFT) & (GC_FLAGS_MASK >> GC_FLAGS_SHIFT);
+        flags & GC_IMMUTABLE != 0
+    }
+}
+
 /// Implemented on refcounted types.
 pub trait PhpRc {
     /// Returns an immutable reference to the corresponding refcount object.
@@ -58,4 +70,4 @@ macro_rules! rc {
     };
 }
 
-rc!(ZendObject, zend_string);
+rc!(ZendObject, zend_string, ZendHashTable);
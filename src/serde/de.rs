@@ -0,0 +1,457 @@
+//! Deserialization of Rust values from zvals.
+
+use std::fmt::Display;
+
+use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::{
+    error::{Error, Result},
+    flags::DataType,
+    types::{ArrayKey, ZendHashTable, Zval, long_to_i64},
+};
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Serde(msg.to_string())
+    }
+}
+
+/// A deserializer which reads values from a [`Zval`], see
+/// [`from_zval`](super::from_zval).
+#[derive(Debug, Clone, Copy)]
+pub struct ZvalDeserializer<'de> {
+    zval: &'de Zval,
+}
+
+impl<'de> ZvalDeserializer<'de> {
+    /// Creates a deserializer reading from the given zval. References are
+    /// replaced by the value they refer to.
+    #[must_use]
+    pub fn new(zval: &'de Zval) -> Self {
+        Self {
+            zval: zval.dereference(),
+        }
+    }
+
+    /// Returns the table of the array, or of the properties of the object,
+    /// held by the zval.
+    fn table(self) -> Result<Option<&'de ZendHashTable>> {
+        if let Some(ht) = self.zval.array() {
+            return Ok(Some(ht));
+        }
+        match self.zval.object() {
+            Some(obj) => obj.get_properties().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn is_null(self) -> bool {
+        matches!(self.zval.get_type(), DataType::Null | DataType::Undef)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ZvalDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let zval = self.zval;
+        if self.is_null() {
+            return visitor.visit_unit();
+        }
+        if let Some(val) = zval.bool() {
+            return visitor.visit_bool(val);
+        }
+        if let Some(val) = zval.long() {
+            return visitor.visit_i64(long_to_i64(val));
+        }
+        if let Some(val) = zval.double() {
+            return visitor.visit_f64(val);
+        }
+        if let Some(val) = zval.zend_str() {
+            return match val.as_str() {
+                Ok(val) => visitor.visit_borrowed_str(val),
+                Err(_) => visitor.visit_borrowed_bytes(val.as_bytes()),
+            };
+        }
+        if let Some(ht) = zval.array()
+            && ht.has_sequential_keys()
+        {
+            return visitor.visit_seq(seq_deserializer(ht));
+        }
+        match self.table()? {
+            Some(ht) => visitor.visit_map(map_deserializer(ht)),
+            None => Err(Error::ZvalConversion(zval.get_type())),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.zval.zend_str() {
+            Some(val) => visitor.visit_borrowed_bytes(val.as_bytes()),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // The keys of arrays are ignored, so that e.g. filtered arrays can be
+        // read as lists.
+        match self.zval.array() {
+            Some(ht) => visitor.visit_seq(seq_deserializer(ht)),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.table()? {
+            Some(ht) => visitor.visit_map(map_deserializer(ht)),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(ht) = self.zval.array()
+            && ht.len() == 1
+            && let Some((key, val)) = ht.iter_buckets().next()
+        {
+            return visitor.visit_enum(EnumDeserializer {
+                key,
+                value: Some(val),
+            });
+        }
+        match self.zval.zend_str() {
+            Some(val) => visitor.visit_enum(EnumDeserializer {
+                key: match val.as_str() {
+                    Ok(val) => ArrayKey::Str(val),
+                    Err(_) => ArrayKey::Bytes(val.as_bytes()),
+                },
+                value: None,
+            }),
+            None => Err(Error::Serde(format!(
+                "Expected a string or an array with a single element for an enum, got {}",
+                self.zval.get_type()
+            ))),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct identifier
+    }
+}
+
+/// Reads the values of an array as a sequence.
+struct SeqDeserializer<I> {
+    iter: I,
+}
+
+fn seq_deserializer(ht: &ZendHashTable) -> SeqDeserializer<impl ExactSizeIterator<Item = &Zval>> {
+    SeqDeserializer { iter: ht.values() }
+}
+
+impl<'de, I> SeqAccess<'de> for SeqDeserializer<I>
+where
+    I: ExactSizeIterator<Item = &'de Zval>,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.iter
+            .next()
+            .map(|val| seed.deserialize(ZvalDeserializer::new(val)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Reads the keys and values of an array as a map.
+struct MapDeserializer<'de, I> {
+    iter: I,
+    value: Option<&'de Zval>,
+}
+
+fn map_deserializer(
+    ht: &ZendHashTable,
+) -> MapDeserializer<'_, impl Iterator<Item = (ArrayKey<'_>, &Zval)>> {
+    MapDeserializer {
+        // Uninitialized typed properties of objects are undefined.
+        iter: ht
+            .iter_buckets()
+            .filter(|(_, val)| val.dereference().get_type() != DataType::Undef),
+        value: None,
+    }
+}
+
+impl<'de, I> MapAccess<'de> for MapDeserializer<'de, I>
+where
+    I: Iterator<Item = (ArrayKey<'de>, &'de Zval)>,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, val)) => {
+                self.value = Some(val);
+                seed.deserialize(KeyDeserializer { key }).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let val = self
+            .value
+            .take()
+            .ok_or_else(|| Error::Serde("Map value deserialized before its key".to_owned()))?;
+        seed.deserialize(ZvalDeserializer::new(val))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.iter.size_hint().1
+    }
+}
+
+/// Reads the key of an array element.
+///
+/// Integer keys are also accepted where a string is expected, as PHP converts
+/// numeric string keys into integers.
+struct KeyDeserializer<'de> {
+    key: ArrayKey<'de>,
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.key {
+            ArrayKey::Long(key) => visitor.visit_i64(key),
+            ArrayKey::String(key) => visitor.visit_string(key),
+            ArrayKey::Str(key) => visitor.visit_borrowed_str(key),
+            ArrayKey::Binary(key) => visitor.visit_byte_buf(key),
+            ArrayKey::Bytes(key) => visitor.visit_borrowed_bytes(key),
+        }
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.key {
+            ArrayKey::Long(key) => visitor.visit_string(key.to_string()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(EnumDeserializer {
+            key: self.key,
+            value: None,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct ignored_any
+    }
+}
+
+/// Reads an enum variant from its name, and the value of the variant if it
+/// has one.
+struct EnumDeserializer<'de> {
+    key: ArrayKey<'de>,
+    value: Option<&'de Zval>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = Error;
+    type Variant = VariantDeserializer<'de>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(KeyDeserializer { key: self.key })?;
+        Ok((variant, VariantDeserializer { value: self.value }))
+    }
+}
+
+/// Reads the value of an enum variant.
+struct VariantDeserializer<'de> {
+    value: Option<&'de Zval>,
+}
+
+impl<'de> VariantDeserializer<'de> {
+    fn value(self, expected: &str) -> Result<ZvalDeserializer<'de>> {
+        self.value
+            .map(ZvalDeserializer::new)
+            .ok_or_else(|| Error::Serde(format!("Expected a {expected}, got a unit variant")))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.value.map(ZvalDeserializer::new) {
+            Some(value) if !value.is_null() => Err(Error::Serde(format!(
+                "Expected a unit variant, got {}",
+                value.zval.get_type()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.value("newtype variant")?)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.value("tuple variant")?, visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self.value("struct variant")?, visitor)
+    }
+}
@@ -0,0 +1,180 @@
+//! Conversions between PHP values and Rust types implementing serde's
+//! [`Serialize`] and [`Deserialize`] traits, without going through an
+//! intermediate format such as JSON.
+//!
+//! Values are mapped onto PHP values as follows:
+//!
+//! * Booleans, integers, floats and strings become their PHP counterparts.
+//!   Byte arrays become binary strings.
+//! * `None` and the unit type become `null`, `Some` becomes its value.
+//! * Sequences and tuples become lists.
+//! * Maps and structs become associative arrays. Map keys must serialize into
+//!   integers or strings, numeric string keys are converted into integers as
+//!   PHP does.
+//! * Unit variants become the name of the variant. Other variants become an
+//!   array with the name of the variant as its only key, e.g.
+//!   `['Line' => [1, 2]]`.
+//!
+//! When deserializing, objects are read like associative arrays of their
+//! properties, and references are replaced by the value they refer to.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # #![cfg_attr(windows, feature(abi_vectorcall))]
+//! use ext_php_rs::{prelude::*, types::Zval};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Point {
+//!     x: f64,
+//!     y: f64,
+//! }
+//!
+//! #[php_function]
+//! pub fn mirror(point: &Zval) -> PhpResult<Zval> {
+//!     let Point { x, y } = ext_php_rs::serde::from_zval(point)?;
+//!     Ok(ext_php_rs::serde::to_zval(&Point { x: y, y: x })?)
+//! }
+//! # fn main() {}
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Result, types::Zval};
+
+mod de;
+mod ser;
+
+pub use de::ZvalDeserializer;
+pub use ser::ZvalSerializer;
+
+/// Converts the given value into a [`Zval`].
+///
+/// # Errors
+///
+/// Returns an error if the value fails to serialize, or if it contains a map
+/// whose keys are neither integers nor strings.
+pub fn to_zval<T>(value: &T) -> Result<Zval>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(ZvalSerializer)
+}
+
+/// Converts the given [`Zval`] into a value of type `T`.
+///
+/// Strings are borrowed from the zval where possible.
+///
+/// # Errors
+///
+/// Returns an error if the zval does not match the structure of `T`.
+pub fn from_zval<'de, T>(zval: &'de Zval) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(ZvalDeserializer::new(zval))
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{embed::Embed, error::Error};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Line(i64, i64),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Drawing<'a> {
+        name: &'a str,
+        tags: Vec<String>,
+        shapes: Vec<Shape>,
+        scale: Option<f64>,
+        layers: BTreeMap<u32, bool>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Pair {
+        a: i64,
+        b: Vec<Option<bool>>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        Embed::run(|| {
+            let drawing = Drawing {
+                name: "plan",
+                tags: vec!["a".to_owned(), "b".to_owned()],
+                shapes: vec![
+                    Shape::Empty,
+                    Shape::Circle(1.5),
+                    Shape::Line(1, 2),
+                    Shape::Rect {
+                        width: 3,
+                        height: 4,
+                    },
+                ],
+                scale: None,
+                layers: BTreeMap::from([(1, true), (5, false)]),
+            };
+            let zval = to_zval(&drawing).unwrap();
+
+            let ht = zval.array().unwrap();
+            assert_eq!(ht.get("name").and_then(Zval::str), Some("plan"));
+            assert!(ht.get("scale").unwrap().is_null());
+            let shapes = ht.get("shapes").and_then(Zval::array).unwrap();
+            assert_eq!(shapes.get(0).and_then(Zval::str), Some("Empty"));
+            let rect = shapes.get_path("3.Rect.width").and_then(Zval::long);
+            assert_eq!(rect, Some(3));
+            let layers = ht.get("layers").and_then(Zval::array).unwrap();
+            assert_eq!(layers.get(5).and_then(Zval::bool), Some(false));
+
+            assert_eq!(from_zval::<Drawing<'_>>(&zval).unwrap(), drawing);
+        });
+    }
+
+    #[test]
+    fn test_from_php_values() {
+        Embed::run(|| {
+            let zval = Embed::eval("['name' => 'x', 'tags' => [2 => 'c'], 'shapes' => [['Circle' => 2]], 'scale' => 1, 'layers' => ['7' => true]];").unwrap();
+            let drawing: Drawing<'_> = from_zval(&zval).unwrap();
+            assert_eq!(drawing.tags, ["c"]);
+            assert_eq!(drawing.shapes, [Shape::Circle(2.0)]);
+            assert_eq!(drawing.scale, Some(1.0));
+            assert_eq!(drawing.layers, BTreeMap::from([(7, true)]));
+
+            let zval = Embed::eval("(object) ['a' => 1, 'b' => [true, null]];").unwrap();
+            let pair: Pair = from_zval(&zval).unwrap();
+            assert_eq!(
+                pair,
+                Pair {
+                    a: 1,
+                    b: vec![Some(true), None]
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_errors() {
+        Embed::run(|| {
+            let zval = Embed::eval("['name' => 1];").unwrap();
+            assert!(matches!(
+                from_zval::<Drawing<'_>>(&zval),
+                Err(Error::Serde(_))
+            ));
+
+            let map = BTreeMap::from([(vec![1], 1)]);
+            assert!(matches!(to_zval(&map), Err(Error::Serde(_))));
+        });
+    }
+}
@@ -0,0 +1,364 @@
+//! Serialization of Rust values into zvals.
+
+use std::fmt::Display;
+
+use serde::ser::{self, Serialize};
+
+use crate::{
+    boxed::ZBox,
+    convert::IntoZval,
+    error::{Error, Result},
+    types::{ArrayKey, ZendHashTable, Zval, long_to_i64},
+};
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Serde(msg.to_string())
+    }
+}
+
+/// A serializer which turns values into [`Zval`]s, see
+/// [`to_zval`](super::to_zval).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZvalSerializer;
+
+impl ser::Serializer for ZvalSerializer {
+    type Ok = Zval;
+    type Error = Error;
+
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeVariant<SerializeList>;
+    type SerializeMap = SerializeArray;
+    type SerializeStruct = SerializeArray;
+    type SerializeStructVariant = SerializeVariant<SerializeArray>;
+
+    fn serialize_bool(self, v: bool) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Zval> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Zval> {
+        let mut zv = Zval::new();
+        zv.set_binary(v.to_vec());
+        Ok(zv)
+    }
+
+    fn serialize_none(self) -> Result<Zval> {
+        Ok(Zval::null())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Zval>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Zval> {
+        Ok(Zval::null())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Zval> {
+        Ok(Zval::null())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Zval> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Zval>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Zval>
+    where
+        T: Serialize + ?Sized,
+    {
+        variant_zval(variant, value.serialize(self)?)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList> {
+        Ok(SerializeList { ht: new_array(len) })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeList> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SerializeList> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeList>> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeArray> {
+        Ok(SerializeArray {
+            ht: new_array(len),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeArray> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeArray>> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+/// Creates an array for the given number of elements, if known.
+fn new_array(len: Option<usize>) -> ZBox<ZendHashTable> {
+    len.and_then(|len| u32::try_from(len).ok())
+        .map_or_else(ZendHashTable::new, ZendHashTable::with_capacity)
+}
+
+/// Wraps the value of an enum variant into an array with the name of the
+/// variant as its key.
+fn variant_zval(variant: &'static str, value: Zval) -> Result<Zval> {
+    let mut ht = ZendHashTable::with_capacity(1);
+    ht.insert(variant, value)?;
+    ht.into_zval(false)
+}
+
+/// Converts a serialized map key into a key of an array.
+fn array_key(key: &Zval) -> Result<ArrayKey<'static>> {
+    if let Some(index) = key.long() {
+        return Ok(ArrayKey::Long(long_to_i64(index)));
+    }
+    match key.zend_str() {
+        Some(key) => Ok(match key.as_str() {
+            Ok(key) => ArrayKey::from(key.to_owned()),
+            Err(_) => ArrayKey::Binary(key.as_bytes().to_vec()),
+        }),
+        None => Err(Error::Serde(format!(
+            "Array keys must be integers or strings, got {}",
+            key.get_type()
+        ))),
+    }
+}
+
+/// Serializes sequences and tuples into a list.
+pub struct SerializeList {
+    ht: ZBox<ZendHashTable>,
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.ht.push(value.serialize(ZvalSerializer)?)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.ht.into_zval(false)
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Serializes maps and structs into an associative array.
+pub struct SerializeArray {
+    ht: ZBox<ZendHashTable>,
+    key: Option<ArrayKey<'static>>,
+}
+
+impl ser::SerializeMap for SerializeArray {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(array_key(&key.serialize(ZvalSerializer)?)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::Serde("Map value serialized before its key".to_owned()))?;
+        self.ht.insert(key, value.serialize(ZvalSerializer)?)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.ht.into_zval(false)
+    }
+}
+
+impl ser::SerializeStruct for SerializeArray {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.ht.insert(key, value.serialize(ZvalSerializer)?)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.ht.into_zval(false)
+    }
+}
+
+/// Serializes the fields of an enum variant, which are wrapped into an array
+/// with the name of the variant as its key.
+pub struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeList> {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        variant_zval(self.variant, ser::SerializeSeq::end(self.inner)?)
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeArray> {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        variant_zval(self.variant, ser::SerializeStruct::end(self.inner)?)
+    }
+}
@@ -1,9 +1,15 @@
-use crate::{convert::FromZval, error::Error, flags::DataType, types::Zval};
+use crate::{
+    convert::FromZval,
+    error::Error,
+    flags::DataType,
+    types::{Zval, long_to_i64},
+};
 use std::str::FromStr;
 use std::{convert::TryFrom, fmt::Display};
 
 /// Represents the key of a PHP array, which can be either a long or a string.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
 pub enum ArrayKey<'a> {
     /// A numerical key.
     /// In Zend API it's represented by `u64` (`zend_ulong`), so the value needs
@@ -13,6 +19,10 @@ pub enum ArrayKey<'a> {
     String(String),
     /// A string key by reference.
     Str(&'a str),
+    /// A string key which is not valid UTF-8.
+    Binary(Vec<u8>),
+    /// A string key by reference which is not valid UTF-8.
+    Bytes(&'a [u8]),
 }
 
 impl From<String> for ArrayKey<'_> {
@@ -37,6 +47,10 @@ impl TryFrom<ArrayKey<'_>> for String {
             ArrayKey::String(s) => Ok(s),
             ArrayKey::Str(s) => Ok(s.to_string()),
             ArrayKey::Long(l) => Ok(l.to_string()),
+            ArrayKey::Binary(b) => String::from_utf8(b).map_err(|_| Error::InvalidUtf8),
+            ArrayKey::Bytes(b) => std::str::from_utf8(b)
+                .map(str::to_owned)
+                .map_err(|_| Error::InvalidUtf8),
         }
     }
 }
@@ -47,12 +61,41 @@ impl TryFrom<ArrayKey<'_>> for i64 {
     fn try_from(value: ArrayKey<'_>) -> Result<Self, Self::Error> {
         match value {
             ArrayKey::Long(i) => Ok(i),
-            ArrayKey::String(s) => s.parse::<i64>().map_err(|_| Error::InvalidProperty),
-            ArrayKey::Str(s) => s.parse::<i64>().map_err(|_| Error::InvalidProperty),
+            key => parse_key(&key),
         }
     }
 }
 
+/// Implements conversions of keys into integer types other than `i64`. Keys
+/// which do not fit the type fail with [`Error::IntegerOverflow`], while
+/// string keys are parsed, as `u64` and `usize` keys too large for an `i64`
+/// are stored as strings.
+macro_rules! try_from_array_key_int {
+    ($($type: ty),*) => {
+        $(
+            impl TryFrom<ArrayKey<'_>> for $type {
+                type Error = Error;
+
+                fn try_from(value: ArrayKey<'_>) -> Result<Self, Self::Error> {
+                    match value {
+                        ArrayKey::Long(i) => i.try_into().map_err(|_| Error::IntegerOverflow),
+                        key => parse_key(&key),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+try_from_array_key_int!(i32, u32, u64, usize);
+
+/// Parses a string key into a number.
+fn parse_key<T: FromStr>(key: &ArrayKey<'_>) -> Result<T, Error> {
+    key.as_str()
+        .and_then(|key| key.parse().ok())
+        .ok_or(Error::InvalidProperty)
+}
+
 impl ArrayKey<'_> {
     /// Check if the key is an integer.
     ///
@@ -63,7 +106,104 @@ impl ArrayKey<'_> {
     pub fn is_long(&self) -> bool {
         match self {
             ArrayKey::Long(_) => true,
-            ArrayKey::String(_) | ArrayKey::Str(_) => false,
+            ArrayKey::String(_) | ArrayKey::Str(_) | ArrayKey::Binary(_) | ArrayKey::Bytes(_) => {
+                false
+            }
+        }
+    }
+
+    /// Returns the key if it is a string which is valid UTF-8, or [`None`] if
+    /// it is an integer or a binary string.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #![cfg_attr(windows, feature(abi_vectorcall))]
+    /// use ext_php_rs::types::ArrayKey;
+    ///
+    /// assert_eq!(ArrayKey::Str("name").as_str(), Some("name"));
+    /// assert_eq!(ArrayKey::Long(1).as_str(), None);
+    /// assert_eq!(ArrayKey::Bytes(b"\xff").as_str(), None);
+    /// ```
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ArrayKey::Long(_) => None,
+            ArrayKey::String(key) => Some(key),
+            ArrayKey::Str(key) => Some(key),
+            ArrayKey::Binary(key) => std::str::from_utf8(key).ok(),
+            ArrayKey::Bytes(key) => std::str::from_utf8(key).ok(),
+        }
+    }
+
+    /// Returns the bytes of the key if it is a string, including binary
+    /// strings, or [`None`] if it is an integer.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #![cfg_attr(windows, feature(abi_vectorcall))]
+    /// use ext_php_rs::types::ArrayKey;
+    ///
+    /// assert_eq!(ArrayKey::Str("name").as_bytes(), Some(&b"name"[..]));
+    /// assert_eq!(ArrayKey::Bytes(b"\xff").as_bytes(), Some(&b"\xff"[..]));
+    /// assert_eq!(ArrayKey::Long(1).as_bytes(), None);
+    /// ```
+    #[must_use]
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            ArrayKey::Long(_) => None,
+            ArrayKey::String(key) => Some(key.as_bytes()),
+            ArrayKey::Str(key) => Some(key.as_bytes()),
+            ArrayKey::Binary(key) => Some(key),
+            ArrayKey::Bytes(key) => Some(key),
+        }
+    }
+
+    /// Returns the key if it is an integer, or [`None`] if it is a string.
+    ///
+    /// String keys are never numeric, as PHP stores numeric string keys as
+    /// integers.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #![cfg_attr(windows, feature(abi_vectorcall))]
+    /// use ext_php_rs::types::ArrayKey;
+    ///
+    /// assert_eq!(ArrayKey::Long(1).as_long(), Some(1));
+    /// assert_eq!(ArrayKey::Str("name").as_long(), None);
+    /// ```
+    #[must_use]
+    pub fn as_long(&self) -> Option<i64> {
+        match self {
+            ArrayKey::Long(key) => Some(*key),
+            ArrayKey::String(_) | ArrayKey::Str(_) | ArrayKey::Binary(_) | ArrayKey::Bytes(_) => {
+                None
+            }
+        }
+    }
+
+    /// Converts the key into a key which does not borrow, copying borrowed
+    /// string and binary keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #![cfg_attr(windows, feature(abi_vectorcall))]
+    /// use ext_php_rs::types::ArrayKey;
+    ///
+    /// let key: ArrayKey<'static> = ArrayKey::Str("name").into_owned();
+    /// assert_eq!(key, ArrayKey::String("name".to_owned()));
+    /// ```
+    #[must_use]
+    pub fn into_owned(self) -> ArrayKey<'static> {
+        match self {
+            ArrayKey::Long(key) => ArrayKey::Long(key),
+            ArrayKey::String(key) => ArrayKey::String(key),
+            ArrayKey::Str(key) => ArrayKey::String(key.to_owned()),
+            ArrayKey::Binary(key) => ArrayKey::Binary(key),
+            ArrayKey::Bytes(key) => ArrayKey::Binary(key.to_owned()),
         }
     }
 }
@@ -74,6 +214,8 @@ impl Display for ArrayKey<'_> {
             ArrayKey::Long(key) => write!(f, "{key}"),
             ArrayKey::String(key) => write!(f, "{key}"),
             ArrayKey::Str(key) => write!(f, "{key}"),
+            ArrayKey::Binary(key) => write!(f, "{}", String::from_utf8_lossy(key)),
+            ArrayKey::Bytes(key) => write!(f, "{}", String::from_utf8_lossy(key)),
         }
     }
 }
@@ -92,12 +234,40 @@ impl<'a> From<&'a str> for ArrayKey<'a> {
     }
 }
 
+impl<'a> From<&'a [u8]> for ArrayKey<'a> {
+    /// Converts a byte string into a key, which is converted like a `&str` if
+    /// it is valid UTF-8.
+    fn from(value: &'a [u8]) -> ArrayKey<'a> {
+        match std::str::from_utf8(value) {
+            Ok(value) => value.into(),
+            Err(_) => ArrayKey::Bytes(value),
+        }
+    }
+}
+
+impl From<Vec<u8>> for ArrayKey<'_> {
+    /// Converts a byte string into a key, which is converted like a `String`
+    /// if it is valid UTF-8.
+    fn from(value: Vec<u8>) -> Self {
+        match String::from_utf8(value) {
+            Ok(value) => value.into(),
+            Err(err) => ArrayKey::Binary(err.into_bytes()),
+        }
+    }
+}
+
 impl<'a> From<i32> for ArrayKey<'a> {
     fn from(index: i32) -> ArrayKey<'a> {
         ArrayKey::Long(i64::from(index))
     }
 }
 
+impl<'a> From<u32> for ArrayKey<'a> {
+    fn from(index: u32) -> ArrayKey<'a> {
+        ArrayKey::Long(i64::from(index))
+    }
+}
+
 impl<'a> From<i64> for ArrayKey<'a> {
     fn from(index: i64) -> ArrayKey<'a> {
         ArrayKey::Long(index)
@@ -129,11 +299,14 @@ impl<'a> FromZval<'a> for ArrayKey<'_> {
 
     fn from_zval(zval: &'a Zval) -> Option<Self> {
         if let Some(key) = zval.long() {
-            return Some(ArrayKey::Long(key));
+            return Some(ArrayKey::Long(long_to_i64(key)));
         }
         if let Some(key) = zval.string() {
             return Some(ArrayKey::String(key));
         }
+        if let Some(key) = zval.zend_str() {
+            return Some(ArrayKey::Binary(key.as_bytes().to_vec()));
+        }
         None
     }
 }
@@ -195,6 +368,38 @@ mod tests {
         assert!(matches!(result.unwrap_err(), Error::InvalidProperty));
     }
 
+    #[test]
+    fn test_accessors() {
+        assert_eq!(ArrayKey::String("key".to_owned()).as_str(), Some("key"));
+        assert_eq!(ArrayKey::Str("key").as_long(), None);
+        assert_eq!(ArrayKey::Long(-5).as_long(), Some(-5));
+        assert_eq!(ArrayKey::Long(-5).into_owned(), ArrayKey::Long(-5));
+
+        let name = String::from("key");
+        let owned = ArrayKey::Str(&name).into_owned();
+        drop(name);
+        assert_eq!(owned.as_str(), Some("key"));
+    }
+
+    #[test]
+    fn test_binary_keys() {
+        let key: ArrayKey = b"\xffkey".as_slice().into();
+        assert_eq!(key, ArrayKey::Bytes(b"\xffkey"));
+        assert_eq!(key.as_str(), None);
+        assert_eq!(key.to_string(), "\u{fffd}key");
+        assert_eq!(
+            key.clone().into_owned(),
+            ArrayKey::Binary(b"\xffkey".to_vec())
+        );
+        assert!(matches!(String::try_from(key), Err(Error::InvalidUtf8)));
+
+        let key: ArrayKey = b"12".to_vec().into();
+        assert_eq!(key, ArrayKey::Long(12));
+        let key: ArrayKey = b"name".as_slice().into();
+        assert_eq!(key, ArrayKey::Str("name"));
+        assert!(i64::try_from(ArrayKey::Bytes(b"\xff")).is_err());
+    }
+
     #[test]
     fn test_from_str_with_leading_zeros() {
         let key: ArrayKey = "00".into();
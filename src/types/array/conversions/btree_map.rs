@@ -178,6 +178,31 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_btree_map_into_zval_is_ordered() {
+        Embed::run(|| {
+            let map = BTreeMap::from([("b", 1), ("c", 2), ("a", 3)]);
+
+            let zval = map.into_zval(false).unwrap();
+            let keys: Vec<_> = zval
+                .array()
+                .unwrap()
+                .keys()
+                .map(|k| k.to_string())
+                .collect();
+            assert_eq!(keys, ["a", "b", "c"]);
+        });
+    }
+
+    #[test]
+    fn test_btree_map_usize_keys_round_trip() {
+        Embed::run(|| {
+            let map = BTreeMap::from([(3_usize, 30_i64), (1, 10), (2, 20)]);
+            let zval = map.clone().into_zval(false).unwrap();
+            assert_eq!(BTreeMap::<usize, i64>::from_zval(&zval).unwrap(), map);
+        });
+    }
+
     #[test]
     fn test_btree_map_from_zval() {
         Embed::run(|| {
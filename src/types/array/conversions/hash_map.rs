@@ -268,6 +268,26 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_hashmap_integer_keys_round_trip() {
+        Embed::run(|| {
+            let map = HashMap::from([(u64::MAX, "max"), (7, "seven")]);
+            let zval = map.clone().into_zval(false).unwrap();
+            assert_eq!(zval.array().unwrap().get(7).unwrap().str(), Some("seven"));
+            assert_eq!(HashMap::<u64, &str>::from_zval(&zval).unwrap(), map);
+
+            let map = HashMap::from([(-1_i32, 1_i64), (2, 2)]);
+            let zval = map.clone().into_zval(false).unwrap();
+            assert_eq!(HashMap::<i32, i64>::from_zval(&zval).unwrap(), map);
+            assert!(HashMap::<u32, i64>::from_zval(&zval).is_none());
+
+            let mut ht = ZendHashTable::new();
+            ht.insert(i64::MAX, "big").unwrap();
+            let map: crate::error::Result<HashMap<i32, String>> = ht.as_ref().try_into();
+            assert!(matches!(map.unwrap_err(), Error::IntegerOverflow));
+        });
+    }
+
     #[test]
     fn test_hashmap_map_string_v_try_from_hash_table() {
         Embed::run(|| {
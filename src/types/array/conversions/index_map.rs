@@ -0,0 +1,170 @@
+use super::super::ZendHashTable;
+use crate::types::ArrayKey;
+use crate::{
+    boxed::ZBox,
+    convert::{FromZval, IntoZval},
+    error::{Error, Result},
+    flags::DataType,
+    types::Zval,
+};
+use indexmap::IndexMap;
+use std::convert::TryFrom;
+use std::hash::{BuildHasher, Hash};
+
+impl<'a, K, V, H> TryFrom<&'a ZendHashTable> for IndexMap<K, V, H>
+where
+    K: TryFrom<ArrayKey<'a>, Error = Error> + Eq + Hash,
+    V: FromZval<'a>,
+    H: BuildHasher + Default,
+{
+    type Error = Error;
+
+    fn try_from(value: &'a ZendHashTable) -> Result<Self> {
+        let mut map = Self::with_capacity_and_hasher(value.len(), H::default());
+
+        for (key, val) in value {
+            map.insert(
+                key.try_into()?,
+                V::from_zval(val).ok_or_else(|| Error::ZvalConversion(val.get_type()))?,
+            );
+        }
+
+        Ok(map)
+    }
+}
+
+impl<'a, V, H> TryFrom<&'a ZendHashTable> for IndexMap<ArrayKey<'a>, V, H>
+where
+    V: FromZval<'a>,
+    H: BuildHasher + Default,
+{
+    type Error = Error;
+
+    fn try_from(value: &'a ZendHashTable) -> Result<Self> {
+        let mut map = Self::with_capacity_and_hasher(value.len(), H::default());
+
+        for (key, val) in value {
+            map.insert(
+                key,
+                V::from_zval(val).ok_or_else(|| Error::ZvalConversion(val.get_type()))?,
+            );
+        }
+
+        Ok(map)
+    }
+}
+
+impl<'a, K, V, H> TryFrom<IndexMap<K, V, H>> for ZBox<ZendHashTable>
+where
+    K: Into<ArrayKey<'a>>,
+    V: IntoZval,
+    H: BuildHasher,
+{
+    type Error = Error;
+
+    fn try_from(value: IndexMap<K, V, H>) -> Result<Self> {
+        let mut ht = ZendHashTable::with_capacity(
+            value.len().try_into().map_err(|_| Error::IntegerOverflow)?,
+        );
+
+        for (k, v) in value {
+            ht.insert(k, v)?;
+        }
+
+        Ok(ht)
+    }
+}
+
+impl<'a, K, V, H> IntoZval for IndexMap<K, V, H>
+where
+    K: Into<ArrayKey<'a>>,
+    V: IntoZval,
+    H: BuildHasher,
+{
+    const TYPE: DataType = DataType::Array;
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        let arr = self.try_into()?;
+        zv.set_hashtable(arr);
+        Ok(())
+    }
+}
+
+impl<'a, K, V, H> FromZval<'a> for IndexMap<K, V, H>
+where
+    K: TryFrom<ArrayKey<'a>, Error = Error> + Eq + Hash,
+    V: FromZval<'a>,
+    H: BuildHasher + Default,
+{
+    const TYPE: DataType = DataType::Array;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.array().and_then(|arr| arr.try_into().ok())
+    }
+}
+
+impl<'a, V, H> FromZval<'a> for IndexMap<ArrayKey<'a>, V, H>
+where
+    V: FromZval<'a>,
+    H: BuildHasher + Default,
+{
+    const TYPE: DataType = DataType::Array;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.array().and_then(|arr| arr.try_into().ok())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use crate::convert::{FromZval, IntoZval};
+    use crate::embed::Embed;
+    use crate::types::{ArrayKey, ZendHashTable, Zval};
+
+    #[test]
+    fn test_index_map_keeps_order() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.insert("b", 1).unwrap();
+            ht.insert("a", 2).unwrap();
+            ht.insert("c", 3).unwrap();
+            let mut zval = Zval::new();
+            zval.set_hashtable(ht);
+
+            let map = IndexMap::<String, i64>::from_zval(&zval).unwrap();
+            assert_eq!(map.keys().collect::<Vec<_>>(), ["b", "a", "c"]);
+
+            let zval = map.into_zval(false).unwrap();
+            let keys: Vec<_> = zval
+                .array()
+                .unwrap()
+                .keys()
+                .map(|k| k.to_string())
+                .collect();
+            assert_eq!(keys, ["b", "a", "c"]);
+        });
+    }
+
+    #[test]
+    fn test_index_map_array_key_from_zval() {
+        Embed::run(|| {
+            let zval = Embed::eval("[5 => 'five', 'name' => 'value', 1 => 'one'];").unwrap();
+
+            let map = IndexMap::<ArrayKey, String>::from_zval(&zval).unwrap();
+            assert_eq!(
+                map.keys().cloned().collect::<Vec<_>>(),
+                [
+                    ArrayKey::Long(5),
+                    ArrayKey::String("name".to_owned()),
+                    ArrayKey::Long(1)
+                ]
+            );
+            assert!(IndexMap::<i64, String>::from_zval(&zval).is_none());
+        });
+    }
+}
@@ -10,10 +10,22 @@
 //! - `BTreeSet<V>` ↔ `ZendHashTable` (via `btree_set` module)
 //! - `HashMap<K, V>` ↔ `ZendHashTable` (via `hash_map` module)
 //! - `HashSet<V>` ↔ `ZendHashTable` (via `hash_set` module)
-//! - `Vec<T>` and `Vec<(K, V)>` ↔ `ZendHashTable` (via `vec` module)
+//! - `IndexMap<K, V>` ↔ `ZendHashTable` (via `index_map` module, with the
+//!   `indexmap` feature)
+//! - `Vec<T>`, `&[T]` and `Vec<(K, V)>` ↔ `ZendHashTable` (via `vec` module)
+//! - `VecDeque<T>` ↔ `ZendHashTable` (via `vec_deque` module)
+//! - `ZendHashTable` → `(A, B, ...)` and `[T; N]` (via `tuple` module)
+//!
+//! Vectors and slices of `ZendLong`, `f64` and `bool` are converted into packed
+//! arrays directly (via `packed` module).
 
 mod btree_map;
 mod btree_set;
 mod hash_map;
 mod hash_set;
+#[cfg(feature = "indexmap")]
+mod index_map;
+pub(crate) mod packed;
+mod tuple;
 mod vec;
+mod vec_deque;
@@ -0,0 +1,99 @@
+//! Fast paths converting slices of scalars into packed arrays and back.
+//!
+//! These write the values into the buckets of the array directly, rather than
+//! converting and inserting each value separately, and are used by the vector
+//! conversions of `ZendLong`, `f64` and `bool`. Other values are converted
+//! into zvals first, which are then moved into the array at once.
+
+use crate::{
+    boxed::ZBox,
+    error::{Error, Result},
+    ffi::{
+        HashTable, ext_php_rs_zend_hash_copy_bools, ext_php_rs_zend_hash_copy_doubles,
+        ext_php_rs_zend_hash_copy_longs, ext_php_rs_zend_new_packed_bool,
+        ext_php_rs_zend_new_packed_double, ext_php_rs_zend_new_packed_long,
+        ext_php_rs_zend_new_packed_zval,
+    },
+    flags::DataType,
+    types::{ZendHashTable, ZendLong, Zval},
+};
+
+/// Creates a packed array holding the given longs.
+pub(crate) fn longs_into_hashtable(values: &[ZendLong]) -> Result<ZBox<ZendHashTable>> {
+    new_packed(values, ext_php_rs_zend_new_packed_long)
+}
+
+/// Creates a packed array holding the given doubles.
+pub(crate) fn doubles_into_hashtable(values: &[f64]) -> Result<ZBox<ZendHashTable>> {
+    new_packed(values, ext_php_rs_zend_new_packed_double)
+}
+
+/// Creates a packed array holding the given bools.
+pub(crate) fn bools_into_hashtable(values: &[bool]) -> Result<ZBox<ZendHashTable>> {
+    new_packed(values, ext_php_rs_zend_new_packed_bool)
+}
+
+/// Creates a packed array holding the given zvals, which are moved into it.
+pub(crate) fn zvals_into_hashtable(mut values: Vec<Zval>) -> Result<ZBox<ZendHashTable>> {
+    let ht = new_packed(&values, ext_php_rs_zend_new_packed_zval)?;
+    // SAFETY: The values are owned by the array now, so they must not be
+    // released when the vector is dropped.
+    unsafe { values.set_len(0) };
+    Ok(ht)
+}
+
+/// Copies the values of an array which only holds longs.
+pub(crate) fn longs_from_hashtable(ht: &ZendHashTable) -> Result<Vec<ZendLong>> {
+    copy_values(ht, ext_php_rs_zend_hash_copy_longs, Zval::is_long)
+}
+
+/// Copies the values of an array which only holds doubles.
+pub(crate) fn doubles_from_hashtable(ht: &ZendHashTable) -> Result<Vec<f64>> {
+    copy_values(ht, ext_php_rs_zend_hash_copy_doubles, Zval::is_double)
+}
+
+/// Copies the values of an array which only holds bools.
+pub(crate) fn bools_from_hashtable(ht: &ZendHashTable) -> Result<Vec<bool>> {
+    copy_values(ht, ext_php_rs_zend_hash_copy_bools, Zval::is_bool)
+}
+
+fn new_packed<T>(
+    values: &[T],
+    new: unsafe extern "C" fn(*const T, u32) -> *mut HashTable,
+) -> Result<ZBox<ZendHashTable>> {
+    let len = values
+        .len()
+        .try_into()
+        .map_err(|_| Error::IntegerOverflow)?;
+    // SAFETY: The helper reads exactly `len` values and returns a new array.
+    unsafe {
+        let ptr = new(values.as_ptr(), len);
+        Ok(ZBox::from_raw(
+            ptr.as_mut()
+                .expect("Failed to allocate memory for hashtable"),
+        ))
+    }
+}
+
+fn copy_values<T>(
+    ht: &ZendHashTable,
+    copy: unsafe extern "C" fn(*const HashTable, *mut T) -> bool,
+    is_type: fn(&Zval) -> bool,
+) -> Result<Vec<T>> {
+    let len = ht.len();
+    let mut vec = Vec::with_capacity(len);
+
+    // SAFETY: The helper writes at most one value for each of the `len` elements
+    // of the array, and all of them if it succeeds.
+    unsafe {
+        if copy(ht, vec.as_mut_ptr()) {
+            vec.set_len(len);
+            return Ok(vec);
+        }
+    }
+
+    let invalid = ht.values().find(|val| !is_type(val));
+    Err(Error::ZvalConversion(
+        invalid.map_or(DataType::Undef, Zval::get_type),
+    ))
+}
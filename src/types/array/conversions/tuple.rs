@@ -0,0 +1,193 @@
+//! Conversions of list arrays into tuples and fixed-size arrays.
+//!
+//! The elements are looked up by their index, as PHP does when destructuring
+//! an array with `[$a, $b] = $array`, and the array must have exactly as many
+//! elements as the tuple or array.
+//!
+//! Tuples can also be converted from a zval with `TryFrom`, including pairs,
+//! e.g. to destructure a `[$status, $payload]` callback result.
+
+use std::convert::TryFrom;
+
+use super::super::ZendHashTable;
+use crate::{
+    convert::FromZval,
+    error::{Error, Result},
+    flags::DataType,
+    types::Zval,
+};
+
+/// Returns the element at the given index, converted into `T`.
+fn element<'a, T: FromZval<'a>>(ht: &'a ZendHashTable, index: i64) -> Result<T> {
+    let val = ht
+        .get_index(index)
+        .ok_or(Error::ZvalConversion(DataType::Array))?;
+    T::from_zval(val).ok_or_else(|| Error::ZvalConversion(val.get_type()))
+}
+
+/// Checks that the array holds the given number of elements.
+fn check_len(ht: &ZendHashTable, len: usize) -> Result<()> {
+    if ht.len() == len {
+        Ok(())
+    } else {
+        Err(Error::ZvalConversion(DataType::Array))
+    }
+}
+
+macro_rules! try_from_hashtable_tuple {
+    ($len: literal; $($ty: ident $index: literal),+) => {
+        impl<'a, $($ty),+> TryFrom<&'a ZendHashTable> for ($($ty,)+)
+        where
+            $($ty: FromZval<'a>),+
+        {
+            type Error = Error;
+
+            fn try_from(value: &'a ZendHashTable) -> Result<Self> {
+                check_len(value, $len)?;
+                Ok(($(element::<$ty>(value, $index)?,)+))
+            }
+        }
+
+        impl<'a, $($ty),+> TryFrom<&'a Zval> for ($($ty,)+)
+        where
+            $($ty: FromZval<'a>),+
+        {
+            type Error = Error;
+
+            fn try_from(value: &'a Zval) -> Result<Self> {
+                value
+                    .array()
+                    .ok_or_else(|| Error::ZvalConversion(value.get_type()))?
+                    .try_into()
+            }
+        }
+    };
+}
+
+/// Pairs are only converted with `TryFrom`, as `Vec<(K, V)>` converts arrays
+/// into key-value pairs.
+macro_rules! from_zval_tuple {
+    ($($ty: ident),+) => {
+        impl<'a, $($ty),+> FromZval<'a> for ($($ty,)+)
+        where
+            $($ty: FromZval<'a>),+
+        {
+            const TYPE: DataType = DataType::Array;
+
+            fn from_zval(zval: &'a Zval) -> Option<Self> {
+                zval.array().and_then(|arr| arr.try_into().ok())
+            }
+        }
+    };
+}
+
+try_from_hashtable_tuple!(2; A 0, B 1);
+try_from_hashtable_tuple!(3; A 0, B 1, C 2);
+try_from_hashtable_tuple!(4; A 0, B 1, C 2, D 3);
+try_from_hashtable_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+try_from_hashtable_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+try_from_hashtable_tuple!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+try_from_hashtable_tuple!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+from_zval_tuple!(A, B, C);
+from_zval_tuple!(A, B, C, D);
+from_zval_tuple!(A, B, C, D, E);
+from_zval_tuple!(A, B, C, D, E, F);
+from_zval_tuple!(A, B, C, D, E, F, G);
+from_zval_tuple!(A, B, C, D, E, F, G, H);
+
+impl<'a, T, const N: usize> TryFrom<&'a ZendHashTable> for [T; N]
+where
+    T: FromZval<'a>,
+{
+    type Error = Error;
+
+    fn try_from(value: &'a ZendHashTable) -> Result<Self> {
+        check_len(value, N)?;
+        let mut vec = Vec::with_capacity(N);
+        for index in 0..N {
+            let index = index.try_into().map_err(|_| Error::IntegerOverflow)?;
+            vec.push(element(value, index)?);
+        }
+        vec.try_into()
+            .map_err(|_| Error::ZvalConversion(DataType::Array))
+    }
+}
+
+impl<'a, T, const N: usize> FromZval<'a> for [T; N]
+where
+    T: FromZval<'a>,
+{
+    const TYPE: DataType = DataType::Array;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.array().and_then(|arr| arr.try_into().ok())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::convert::FromZval;
+    use crate::embed::Embed;
+    use crate::error::Result;
+    use crate::types::ZendHashTable;
+
+    #[test]
+    fn test_tuple_try_from_hash_table() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.push(200).unwrap();
+            ht.push("OK").unwrap();
+
+            let (status, message): (i64, String) = ht.as_ref().try_into().unwrap();
+            assert_eq!((status, message.as_str()), (200, "OK"));
+
+            let triple: Result<(i64, String, bool)> = ht.as_ref().try_into();
+            assert!(triple.is_err());
+            let swapped: Result<(String, i64)> = ht.as_ref().try_into();
+            assert!(swapped.is_err());
+        });
+    }
+
+    #[test]
+    fn test_pair_try_from_zval() {
+        Embed::run(|| {
+            let zval = Embed::eval("[404, 'Not Found'];").unwrap();
+            let (status, payload) = <(i64, String)>::try_from(&zval).unwrap();
+            assert_eq!((status, payload.as_str()), (404, "Not Found"));
+
+            let zval = Embed::eval("[404];").unwrap();
+            assert!(<(i64, String)>::try_from(&zval).is_err());
+            let zval = Embed::eval("'404';").unwrap();
+            assert!(<(i64, String)>::try_from(&zval).is_err());
+        });
+    }
+
+    #[test]
+    fn test_tuple_from_zval() {
+        Embed::run(|| {
+            let zval = Embed::eval("[2 => true, 0 => 1.5, 1 => 'y'];").unwrap();
+            let (x, y, z) = <(f64, String, bool)>::from_zval(&zval).unwrap();
+            assert_eq!((x, y.as_str(), z), (1.5, "y", true));
+
+            let zval = Embed::eval("['x' => 1.5, 'y' => 'y', 'z' => true];").unwrap();
+            assert!(<(f64, String, bool)>::from_zval(&zval).is_none());
+        });
+    }
+
+    #[test]
+    fn test_fixed_array_from_zval() {
+        Embed::run(|| {
+            let zval = Embed::eval("range(1, 3);").unwrap();
+            assert_eq!(<[i64; 3]>::from_zval(&zval), Some([1, 2, 3]));
+            assert_eq!(<[i64; 2]>::from_zval(&zval), None);
+            assert_eq!(<[String; 3]>::from_zval(&zval), None);
+
+            let ht = zval.array().unwrap();
+            let point: [i64; 3] = ht.try_into().unwrap();
+            assert_eq!(point, [1, 2, 3]);
+        });
+    }
+}
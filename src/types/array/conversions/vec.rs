@@ -124,13 +124,7 @@ where
     type Error = Error;
 
     fn try_from(value: &'a ZendHashTable) -> Result<Self> {
-        let mut vec = Vec::with_capacity(value.len());
-
-        for (_, val) in value {
-            vec.push(T::from_zval(val).ok_or_else(|| Error::ZvalConversion(val.get_type()))?);
-        }
-
-        Ok(vec)
+        T::vec_from_hashtable(value)
     }
 }
 
@@ -141,15 +135,7 @@ where
     type Error = Error;
 
     fn try_from(value: Vec<T>) -> Result<Self> {
-        let mut ht = ZendHashTable::with_capacity(
-            value.len().try_into().map_err(|_| Error::IntegerOverflow)?,
-        );
-
-        for val in value {
-            ht.push(val)?;
-        }
-
-        Ok(ht)
+        T::vec_into_hashtable(value)
     }
 }
 
@@ -178,6 +164,35 @@ where
     }
 }
 
+///////////////////////////////////////////
+// &[T] conversions
+///////////////////////////////////////////
+
+impl<T> TryFrom<&[T]> for ZBox<ZendHashTable>
+where
+    T: IntoZval + Clone,
+{
+    type Error = Error;
+
+    fn try_from(value: &[T]) -> Result<Self> {
+        T::slice_into_hashtable(value)
+    }
+}
+
+impl<T> IntoZval for &[T]
+where
+    T: IntoZval + Clone,
+{
+    const TYPE: DataType = DataType::Array;
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        let arr = self.try_into()?;
+        zv.set_hashtable(arr);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "embed")]
 #[allow(clippy::unwrap_used)]
@@ -367,4 +382,57 @@ mod tests {
             assert_eq!(vec[2].1, "value3");
         });
     }
+
+    #[test]
+    fn test_vec_numeric_into_packed_hash_table() {
+        Embed::run(|| {
+            let ht: ZBox<ZendHashTable> = vec![1_i64, -2, 3].try_into().unwrap();
+            assert!(ht.has_sequential_keys());
+            assert_eq!(ht.get(0).unwrap().long(), Some(1));
+            assert_eq!(ht.get(1).unwrap().long(), Some(-2));
+            assert_eq!(ht.get(2).unwrap().long(), Some(3));
+
+            let ht: ZBox<ZendHashTable> = [0.5_f64, 1.5][..].try_into().unwrap();
+            assert_eq!(ht.len(), 2);
+            assert_eq!(ht.get(1).unwrap().double(), Some(1.5));
+
+            let zval = [true, false].as_slice().into_zval(false).unwrap();
+            let ht = zval.array().unwrap();
+            assert_eq!(ht.get(0).unwrap().bool(), Some(true));
+            assert_eq!(ht.get(1).unwrap().bool(), Some(false));
+
+            let ht: ZBox<ZendHashTable> = Vec::<i64>::new().try_into().unwrap();
+            assert!(ht.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_vec_numeric_from_hash_table() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.insert("a", 1).unwrap();
+            ht.insert(5, 2).unwrap();
+            ht.insert("b", 3).unwrap();
+            let vec: Vec<i64> = ht.as_ref().try_into().unwrap();
+            assert_eq!(vec, [1, 2, 3]);
+
+            let values = vec![0.25_f64; 1000];
+            let ht: ZBox<ZendHashTable> = values.clone().try_into().unwrap();
+            let vec: Vec<f64> = ht.as_ref().try_into().unwrap();
+            assert_eq!(vec, values);
+
+            let ht: ZBox<ZendHashTable> = vec![true, false, true].try_into().unwrap();
+            let vec: Vec<bool> = ht.as_ref().try_into().unwrap();
+            assert_eq!(vec, [true, false, true]);
+
+            let mut ht = ZendHashTable::new();
+            ht.push(1).unwrap();
+            ht.push("two").unwrap();
+            let vec: crate::error::Result<Vec<i64>> = ht.as_ref().try_into();
+            assert!(matches!(
+                vec.unwrap_err(),
+                Error::ZvalConversion(crate::flags::DataType::String)
+            ));
+        });
+    }
 }
@@ -0,0 +1,96 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+use super::super::ZendHashTable;
+use crate::{
+    boxed::ZBox,
+    convert::{FromZval, IntoZval},
+    error::{Error, Result},
+    flags::DataType,
+    types::Zval,
+};
+
+// Deques are converted through vectors, which keeps the packed fast path of
+// numeric values.
+
+impl<'a, T> TryFrom<&'a ZendHashTable> for VecDeque<T>
+where
+    T: FromZval<'a>,
+{
+    type Error = Error;
+
+    fn try_from(value: &'a ZendHashTable) -> Result<Self> {
+        T::vec_from_hashtable(value).map(Self::from)
+    }
+}
+
+impl<T> TryFrom<VecDeque<T>> for ZBox<ZendHashTable>
+where
+    T: IntoZval,
+{
+    type Error = Error;
+
+    fn try_from(value: VecDeque<T>) -> Result<Self> {
+        T::vec_into_hashtable(value.into())
+    }
+}
+
+impl<T> IntoZval for VecDeque<T>
+where
+    T: IntoZval,
+{
+    const TYPE: DataType = DataType::Array;
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        let arr = self.try_into()?;
+        zv.set_hashtable(arr);
+        Ok(())
+    }
+}
+
+impl<'a, T> FromZval<'a> for VecDeque<T>
+where
+    T: FromZval<'a>,
+{
+    const TYPE: DataType = DataType::Array;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.array().and_then(|arr| arr.try_into().ok())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use crate::convert::{FromZval, IntoZval};
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_vec_deque_into_zval() {
+        Embed::run(|| {
+            let mut deque = VecDeque::from(["b", "c"]);
+            deque.push_front("a");
+            let zval = deque.into_zval(false).unwrap();
+            let ht = zval.array().unwrap();
+            let values: Vec<_> = ht.values().filter_map(|val| val.str()).collect();
+            assert_eq!(values, ["a", "b", "c"]);
+            assert!(ht.get_index(2).is_some());
+        });
+    }
+
+    #[test]
+    fn test_vec_deque_from_zval() {
+        Embed::run(|| {
+            let zval = Embed::eval("[1, 2, 3];").unwrap();
+            let deque = VecDeque::<i64>::from_zval(&zval).unwrap();
+            assert_eq!(deque, [1, 2, 3]);
+
+            let zval = Embed::eval("[1, 'two'];").unwrap();
+            assert!(VecDeque::<i64>::from_zval(&zval).is_none());
+        });
+    }
+}
@@ -1,31 +1,72 @@
-use cfg_if::cfg_if;
-use std::{
-    convert::TryInto,
-    iter::{DoubleEndedIterator, ExactSizeIterator, Iterator},
-    ptr,
-};
+use std::iter::{DoubleEndedIterator, ExactSizeIterator, Iterator};
 
 use super::{ArrayKey, ZendHashTable};
 use crate::boxed::ZBox;
 use crate::{
-    convert::FromZval,
-    ffi::{
-        HashPosition, zend_hash_get_current_data_ex, zend_hash_get_current_key_type_ex,
-        zend_hash_get_current_key_zval_ex, zend_hash_move_backwards_ex, zend_hash_move_forward_ex,
-    },
-    types::Zval,
+    convert::IntoZval,
+    ffi::zend_hash_get_current_key_zval_ex,
+    types::{Zval, ZvalOwned, long_from_i64},
 };
 
-#[cfg(php85)]
-use crate::ffi::zend_hash_key_type_HASH_KEY_NON_EXISTENT;
+/// Cursor over the used positions of a hashtable, shared by its iterators.
+///
+/// The front and back of the cursor delimit the positions which have not
+/// been visited, so elements are yielded once when both ends are advanced.
+/// Holes left by deleted elements are skipped, as are slots pointing to
+/// undefined values, which are not counted by [`ZendHashTable::len`] either.
+#[derive(Debug, Clone, Copy)]
+struct Cursor {
+    pos: u32,
+    end_pos: u32,
+    remaining: usize,
+}
+
+impl Cursor {
+    fn new(ht: &ZendHashTable) -> Self {
+        Self {
+            pos: 0,
+            end_pos: ht.nNumUsed,
+            remaining: ht.len(),
+        }
+    }
+
+    /// Advances the front of the cursor to the next element, returning its
+    /// position, key and value.
+    fn next<'a>(&mut self, ht: &'a ZendHashTable) -> Option<(u32, ArrayKey<'a>, *mut Zval)> {
+        while self.pos < self.end_pos {
+            let pos = self.pos;
+            self.pos += 1;
+            // SAFETY: The position is below `nNumUsed`.
+            if let Some((key, val)) = unsafe { ht.element(pos) } {
+                self.remaining -= 1;
+                return Some((pos, key, val));
+            }
+        }
+        None
+    }
+
+    /// Advances the back of the cursor to the previous element, returning its
+    /// position, key and value.
+    fn next_back<'a>(&mut self, ht: &'a ZendHashTable) -> Option<(u32, ArrayKey<'a>, *mut Zval)> {
+        while self.pos < self.end_pos {
+            self.end_pos -= 1;
+            // SAFETY: The position is below `nNumUsed`.
+            if let Some((key, val)) = unsafe { ht.element(self.end_pos) } {
+                self.remaining -= 1;
+                return Some((self.end_pos, key, val));
+            }
+        }
+        None
+    }
+}
 
 /// Immutable iterator upon a reference to a hashtable.
+///
+/// String keys are yielded as owned [`ArrayKey::String`]s, see
+/// [`ZendHashTable::iter_buckets`] to borrow them from the hashtable.
 pub struct Iter<'a> {
     ht: &'a ZendHashTable,
-    current_num: i64,
-    end_num: i64,
-    pos: HashPosition,
-    end_pos: HashPosition,
+    cursor: Cursor,
 }
 
 impl<'a> Iter<'a> {
@@ -35,80 +76,35 @@ impl<'a> Iter<'a> {
     ///
     /// * `ht` - The hashtable to iterate.
     pub fn new(ht: &'a ZendHashTable) -> Self {
-        let end_num: i64 = ht
-            .len()
-            .try_into()
-            .expect("Integer overflow in hashtable length");
-        let end_pos = if ht.nNumOfElements > 0 {
-            ht.nNumOfElements - 1
-        } else {
-            0
-        };
-
         Self {
             ht,
-            current_num: 0,
-            end_num,
-            pos: 0,
-            end_pos,
+            cursor: Cursor::new(ht),
         }
     }
 
+    /// Returns the next element of the hashtable, with its key as a zval.
     pub fn next_zval(&mut self) -> Option<(Zval, &'a Zval)> {
-        if self.current_num >= self.end_num {
-            return None;
-        }
-
-        let key_type = unsafe {
-            zend_hash_get_current_key_type_ex(ptr::from_ref(self.ht).cast_mut(), &raw mut self.pos)
-        };
-
-        // Key type `1` is string (HASH_KEY_IS_STRING)
-        // Key type `2` is long (HASH_KEY_IS_LONG)
-        // Key type `3` is null/non-existent (HASH_KEY_NON_EXISTENT)
-        // Pre-PHP 8.5 returns int, PHP 8.5+ returns enum u32
-        cfg_if! {
-            if #[cfg(php85)] {
-                if key_type == zend_hash_key_type_HASH_KEY_NON_EXISTENT {
-                    return None;
-                }
-            } else {
-                // Pre-PHP 8.5: function returns signed int (i32)
-                // Check for -1 (defensive) and 3 (HASH_KEY_NON_EXISTENT)
-                if key_type == -1 || key_type == 3 {
-                    return None;
-                }
-            }
-        }
-
-        let mut key = Zval::new();
-
-        unsafe {
-            zend_hash_get_current_key_zval_ex(
-                ptr::from_ref(self.ht).cast_mut(),
-                (&raw const key).cast_mut(),
-                &raw mut self.pos,
-            );
+        let (pos, key, val) = self.cursor.next(self.ht)?;
+        let mut zval = Zval::new();
+        match key {
+            ArrayKey::Long(index) => zval.set_long(long_from_i64(index).ok()?),
+            // The engine shares the string key with the zval.
+            _ => unsafe {
+                zend_hash_get_current_key_zval_ex(self.ht, &raw mut zval, &raw const pos);
+            },
         }
-        let value = unsafe {
-            let val_ptr =
-                zend_hash_get_current_data_ex(ptr::from_ref(self.ht).cast_mut(), &raw mut self.pos);
-
-            if val_ptr.is_null() {
-                return None;
-            }
-
-            &*val_ptr
-        };
-
-        if !key.is_long() && !key.is_string() {
-            key.set_long(self.current_num);
-        }
-
-        unsafe { zend_hash_move_forward_ex(ptr::from_ref(self.ht).cast_mut(), &raw mut self.pos) };
-        self.current_num += 1;
+        // SAFETY: The value lives as long as the borrow of the hashtable.
+        Some((zval, unsafe { &*val }))
+    }
+}
 
-        Some((key, value))
+/// Converts borrowed string and binary keys into owned ones, as yielded by
+/// [`Iter`].
+fn owned_key(key: ArrayKey<'_>) -> ArrayKey<'_> {
+    match key {
+        ArrayKey::Str(key) => ArrayKey::String(key.to_owned()),
+        ArrayKey::Bytes(key) => ArrayKey::Binary(key.to_owned()),
+        key => key,
     }
 }
 
@@ -143,75 +139,27 @@ impl<'a> Iterator for Iter<'a> {
     type Item = (ArrayKey<'a>, &'a Zval);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next_zval()
-            .map(|(k, v)| (ArrayKey::from_zval(&k).expect("Invalid array key!"), v))
+        let (_, key, val) = self.cursor.next(self.ht)?;
+        // SAFETY: The value lives as long as the borrow of the hashtable.
+        Some((owned_key(key), unsafe { &*val }))
     }
 
-    fn count(self) -> usize
-    where
-        Self: Sized,
-    {
-        self.ht.len()
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
     }
 }
 
 impl ExactSizeIterator for Iter<'_> {
     fn len(&self) -> usize {
-        self.ht.len()
+        self.cursor.remaining
     }
 }
 
 impl DoubleEndedIterator for Iter<'_> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.end_num <= self.current_num {
-            return None;
-        }
-
-        let key_type = unsafe {
-            zend_hash_get_current_key_type_ex(ptr::from_ref(self.ht).cast_mut(), &raw mut self.pos)
-        };
-
-        cfg_if! {
-            if #[cfg(php85)] {
-                if key_type == zend_hash_key_type_HASH_KEY_NON_EXISTENT {
-                    return None;
-                }
-            } else {
-                // Pre-PHP 8.5: function returns signed int (i32)
-                // Check for -1 (defensive) and 3 (HASH_KEY_NON_EXISTENT)
-                if key_type == -1 || key_type == 3 {
-                    return None;
-                }
-            }
-        }
-
-        let key = Zval::new();
-
-        unsafe {
-            zend_hash_get_current_key_zval_ex(
-                ptr::from_ref(self.ht).cast_mut(),
-                (&raw const key).cast_mut(),
-                &raw mut self.end_pos,
-            );
-        }
-        let value = unsafe {
-            &*zend_hash_get_current_data_ex(
-                ptr::from_ref(self.ht).cast_mut(),
-                &raw mut self.end_pos,
-            )
-        };
-
-        let key = match ArrayKey::from_zval(&key) {
-            Some(key) => key,
-            None => ArrayKey::Long(self.end_num),
-        };
-
-        unsafe {
-            zend_hash_move_backwards_ex(ptr::from_ref(self.ht).cast_mut(), &raw mut self.end_pos)
-        };
-        self.end_num -= 1;
-
-        Some((key, value))
+        let (_, key, val) = self.cursor.next_back(self.ht)?;
+        // SAFETY: The value lives as long as the borrow of the hashtable.
+        Some((owned_key(key), unsafe { &*val }))
     }
 }
 
@@ -237,11 +185,8 @@ impl<'a> Iterator for Values<'a> {
         self.0.next().map(|(_, zval)| zval)
     }
 
-    fn count(self) -> usize
-    where
-        Self: Sized,
-    {
-        self.0.count()
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
     }
 }
 
@@ -257,37 +202,293 @@ impl DoubleEndedIterator for Values<'_> {
     }
 }
 
-impl FromIterator<Zval> for ZBox<ZendHashTable> {
-    fn from_iter<T: IntoIterator<Item = Zval>>(iter: T) -> Self {
-        let mut ht = ZendHashTable::new();
-        for item in iter {
-            // Inserting a zval cannot fail, as `push` only returns `Err` if converting
-            // `val` to a zval fails.
-            let _ = ht.push(item);
+/// Immutable iterator upon a reference to a hashtable, which reads the
+/// buckets of the hashtable directly rather than going through the Zend API
+/// for each element, see [`ZendHashTable::iter_buckets`].
+///
+/// String keys are borrowed from the hashtable when they are valid UTF-8.
+pub struct Buckets<'a> {
+    ht: &'a ZendHashTable,
+    cursor: Cursor,
+}
+
+impl<'a> Buckets<'a> {
+    /// Creates a new iterator over the buckets of a hashtable.
+    ///
+    /// # Parameters
+    ///
+    /// * `ht` - The hashtable to iterate.
+    pub fn new(ht: &'a ZendHashTable) -> Self {
+        Self {
+            ht,
+            cursor: Cursor::new(ht),
         }
-        ht
     }
 }
 
-impl FromIterator<(i64, Zval)> for ZBox<ZendHashTable> {
-    fn from_iter<T: IntoIterator<Item = (i64, Zval)>>(iter: T) -> Self {
+impl<'a> Iterator for Buckets<'a> {
+    type Item = (ArrayKey<'a>, &'a Zval);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, key, val) = self.cursor.next(self.ht)?;
+        // SAFETY: The value lives as long as the borrow of the hashtable.
+        Some((key, unsafe { &*val }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl ExactSizeIterator for Buckets<'_> {
+    fn len(&self) -> usize {
+        self.cursor.remaining
+    }
+}
+
+impl DoubleEndedIterator for Buckets<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (_, key, val) = self.cursor.next_back(self.ht)?;
+        // SAFETY: The value lives as long as the borrow of the hashtable.
+        Some((key, unsafe { &*val }))
+    }
+}
+
+/// Immutable iterator which iterates over the keys of the hashtable, see
+/// [`ZendHashTable::keys`].
+pub struct Keys<'a>(Buckets<'a>);
+
+impl<'a> Keys<'a> {
+    /// Creates a new iterator over a hashtables keys.
+    ///
+    /// # Parameters
+    ///
+    /// * `ht` - The hashtable to iterate.
+    pub fn new(ht: &'a ZendHashTable) -> Self {
+        Self(Buckets::new(ht))
+    }
+}
+
+impl<'a> Iterator for Keys<'a> {
+    type Item = ArrayKey<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Keys<'_> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl DoubleEndedIterator for Keys<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(key, _)| key)
+    }
+}
+
+/// Mutable iterator upon a reference to a hashtable, see
+/// [`ZendHashTable::iter_mut`].
+pub struct IterMut<'a> {
+    // Only created from a mutable reference, which is held for `'a`.
+    ht: &'a ZendHashTable,
+    cursor: Cursor,
+}
+
+impl<'a> IterMut<'a> {
+    /// Creates a new mutable iterator over a hashtable.
+    ///
+    /// # Parameters
+    ///
+    /// * `ht` - The hashtable to iterate.
+    pub fn new(ht: &'a mut ZendHashTable) -> Self {
+        Self {
+            cursor: Cursor::new(ht),
+            ht,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a mut ZendHashTable {
+    type Item = (ArrayKey<'a>, &'a mut Zval);
+    type IntoIter = IterMut<'a>;
+
+    /// Returns a mutable iterator over the key(s) and value contained inside
+    /// the hashtable.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IterMut::new(self)
+    }
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = (ArrayKey<'a>, &'a mut Zval);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, key, val) = self.cursor.next(self.ht)?;
+        // SAFETY: Each position is only visited once, so the values are only
+        // borrowed mutably once, and the hashtable is borrowed mutably for `'a`.
+        Some((key, unsafe { &mut *val }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl ExactSizeIterator for IterMut<'_> {
+    fn len(&self) -> usize {
+        self.cursor.remaining
+    }
+}
+
+impl DoubleEndedIterator for IterMut<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (_, key, val) = self.cursor.next_back(self.ht)?;
+        // SAFETY: See `next`.
+        Some((key, unsafe { &mut *val }))
+    }
+}
+
+/// Mutable iterator which iterates over the values of the hashtable, see
+/// [`ZendHashTable::values_mut`].
+pub struct ValuesMut<'a>(IterMut<'a>);
+
+impl<'a> ValuesMut<'a> {
+    /// Creates a new mutable iterator over a hashtables values.
+    ///
+    /// # Parameters
+    ///
+    /// * `ht` - The hashtable to iterate.
+    pub fn new(ht: &'a mut ZendHashTable) -> Self {
+        Self(IterMut::new(ht))
+    }
+}
+
+impl<'a> Iterator for ValuesMut<'a> {
+    type Item = &'a mut Zval;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, zval)| zval)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ValuesMut<'_> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl DoubleEndedIterator for ValuesMut<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(_, zval)| zval)
+    }
+}
+
+/// Iterator which removes the elements of a hashtable and yields them, see
+/// [`ZendHashTable::drain`].
+///
+/// The hashtable is empty once the iterator is dropped, even if it was not
+/// fully consumed.
+pub struct Drain<'a> {
+    ht: &'a mut ZendHashTable,
+    pos: u32,
+}
+
+impl<'a> Drain<'a> {
+    /// Creates a new iterator draining a hashtable.
+    ///
+    /// # Parameters
+    ///
+    /// * `ht` - The hashtable to drain.
+    pub fn new(ht: &'a mut ZendHashTable) -> Self {
+        Self { ht, pos: 0 }
+    }
+}
+
+impl Iterator for Drain<'_> {
+    type Item = (ArrayKey<'static>, Zval);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.ht.nNumUsed {
+            let pos = self.pos;
+            self.pos += 1;
+
+            // SAFETY: The position is below `nNumUsed`.
+            let Some((key, slot)) = (unsafe { self.ht.element(pos) }) else {
+                continue;
+            };
+            let key = key.into_owned();
+            // The value is moved out and replaced with null, which the
+            // destructor of the hashtable ignores when the element is deleted.
+            // SAFETY: The value belongs to an element of the hashtable.
+            let val = unsafe { ZvalOwned::take_from(slot) }.into_inner();
+            unsafe { self.ht.delete_element(slot) };
+            return Some((key, val));
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ht.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Drain<'_> {}
+
+impl Drop for Drain<'_> {
+    fn drop(&mut self) {
+        self.ht.clear();
+    }
+}
+
+/// Collects values into a list array.
+///
+/// # Panics
+///
+/// Panics if a value cannot be converted into a [`Zval`], or if the next
+/// index of the array overflows, see [`ZendHashTable::push`].
+impl<V> FromIterator<V> for ZBox<ZendHashTable>
+where
+    V: IntoZval,
+{
+    fn from_iter<T: IntoIterator<Item = V>>(iter: T) -> Self {
         let mut ht = ZendHashTable::new();
-        for (key, val) in iter {
-            // Inserting a zval cannot fail, as `push` only returns `Err` if converting
-            // `val` to a zval fails.
-            let _ = ht.insert_at_index(key, val);
+        for val in iter {
+            ht.push(val).expect("Failed to push value into hashtable");
         }
         ht
     }
 }
 
-impl<'a> FromIterator<(&'a str, Zval)> for ZBox<ZendHashTable> {
-    fn from_iter<T: IntoIterator<Item = (&'a str, Zval)>>(iter: T) -> Self {
+/// Collects key-value pairs into an array, where later values overwrite
+/// earlier ones with the same key.
+///
+/// # Panics
+///
+/// Panics if a value cannot be converted into a [`Zval`], see
+/// [`ZendHashTable::insert`].
+impl<'a, K, V> FromIterator<(K, V)> for ZBox<ZendHashTable>
+where
+    K: Into<ArrayKey<'a>>,
+    V: IntoZval,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
         let mut ht = ZendHashTable::new();
         for (key, val) in iter {
-            // Inserting a zval cannot fail, as `push` only returns `Err` if converting
-            // `val` to a zval fails.
-            let _ = ht.insert(key, val);
+            ht.insert(key, val)
+                .expect("Failed to insert value into hashtable");
         }
         ht
     }
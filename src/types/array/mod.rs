@@ -1,29 +1,45 @@
 //! Represents an array in PHP. As all arrays in PHP are associative arrays,
 //! they are represented by hash tables.
 
-use std::{convert::TryFrom, ffi::CString, fmt::Debug, ptr};
+use std::{convert::TryFrom, fmt::Debug, mem, ptr};
 
+use cfg_if::cfg_if;
+
+#[cfg(php82)]
+use crate::ffi::zend_hash_packed_del_val;
 use crate::{
     boxed::{ZBox, ZBoxable},
     convert::{FromZval, FromZvalMut, IntoZval},
-    error::Result,
-    ffi::zend_ulong,
+    error::{Error, Result},
     ffi::{
-        _zend_new_array, GC_FLAGS_MASK, GC_FLAGS_SHIFT, HT_MIN_SIZE, zend_array_count,
-        zend_array_destroy, zend_array_dup, zend_empty_array, zend_hash_clean, zend_hash_index_del,
-        zend_hash_index_find, zend_hash_index_update, zend_hash_next_index_insert,
-        zend_hash_str_del, zend_hash_str_find, zend_hash_str_update,
+        _zend_new_array, Bucket, GC_FLAGS_MASK, GC_FLAGS_SHIFT, HASH_FLAG_PACKED,
+        HASH_FLAG_UNINITIALIZED, HT_MIN_SIZE, ext_php_rs_zend_new_persistent_array,
+        ext_php_rs_zend_persistent_array_destroy, zend_array_count, zend_array_destroy,
+        zend_array_dup, zend_empty_array, zend_hash_clean, zend_hash_del_bucket, zend_hash_extend,
+        zend_hash_index_add, zend_hash_index_del, zend_hash_index_find, zend_hash_index_update,
+        zend_hash_merge, zend_hash_next_index_insert, zend_hash_rehash, zend_hash_str_add,
+        zend_hash_str_del, zend_hash_str_find, zend_hash_str_update, zval_add_ref,
     },
     flags::{DataType, ZvalTypeFlags},
-    types::Zval,
+    rc::PhpRc,
+    types::{ZendLong, ZendStr, Zval, hash_to_index, index_to_hash},
 };
 
 mod array_key;
 mod conversions;
 mod iterators;
+#[cfg(feature = "rayon")]
+mod par_iter;
+mod set_ops;
+mod sort;
+mod walk;
 
 pub use array_key::ArrayKey;
-pub use iterators::{Iter, Values};
+pub(crate) use conversions::packed;
+pub use iterators::{Buckets, Drain, Iter, IterMut, Keys, Values, ValuesMut};
+#[cfg(feature = "rayon")]
+pub use par_iter::SharedZval;
+pub use walk::WalkAction;
 
 /// A PHP hashtable.
 ///
@@ -105,6 +121,97 @@ impl ZendHashTable {
         }
     }
 
+    /// Creates a new, empty, PHP hashtable which is allocated with the
+    /// persistent allocator, returned inside a [`ZBox`].
+    ///
+    /// Persistent hashtables are not freed at the end of the request, so they
+    /// can be created during module startup and kept between requests, e.g.
+    /// for caches. Strings inserted into them are allocated persistently as
+    /// well. Values which are freed at the end of the request, e.g. objects,
+    /// resources, references, arrays which are not immutable or strings of
+    /// the request, are rejected with [`Error::NotPersistent`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut cache = ZendHashTable::new_persistent();
+    /// cache.insert("greeting", "Hello");
+    /// assert!(cache.is_persistent());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if memory for the hashtable could not be allocated.
+    #[must_use]
+    pub fn new_persistent() -> ZBox<Self> {
+        unsafe {
+            // SAFETY: The wrapper allocates and initializes the array.
+            let ptr = ext_php_rs_zend_new_persistent_array(HT_MIN_SIZE);
+
+            // SAFETY: `as_mut()` checks if the pointer is null, and panics if it is not.
+            ZBox::from_raw(
+                ptr.as_mut()
+                    .expect("Failed to allocate memory for hashtable"),
+            )
+        }
+    }
+
+    /// Creates a new packed PHP hashtable holding the values of the given
+    /// slice, returned inside a [`ZBox`].
+    ///
+    /// The hashtable is allocated with room for all values, which are written
+    /// into it at once. Slices of integers, floats and booleans are copied
+    /// directly, without converting each value into a zval first.
+    ///
+    /// See [`try_from_slice`](Self::try_from_slice) for values whose
+    /// conversion may fail.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let ht = ZendHashTable::from_slice(&[1, 2, 3]);
+    /// assert_eq!(ht.len(), 3);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value could not be converted into a zval, or if memory for
+    /// the hashtable could not be allocated.
+    #[must_use]
+    pub fn from_slice<T>(values: &[T]) -> ZBox<Self>
+    where
+        T: IntoZval + Clone,
+    {
+        Self::try_from_slice(values).expect("Failed to convert slice into hashtable")
+    }
+
+    /// Creates a new packed PHP hashtable holding the values of the given
+    /// slice, returned inside a [`ZBox`], see [`from_slice`](Self::from_slice).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let ht = ZendHashTable::try_from_slice(&["a", "b"]).unwrap();
+    /// assert_eq!(ht.get_index(1).and_then(|zv| zv.str()), Some("b"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slice is longer than a hashtable can hold, or
+    /// if a value could not be converted into a zval.
+    pub fn try_from_slice<T>(values: &[T]) -> Result<ZBox<Self>>
+    where
+        T: IntoZval + Clone,
+    {
+        T::slice_into_hashtable(values)
+    }
+
     /// Returns the current number of elements in the array.
     ///
     /// # Example
@@ -164,6 +271,64 @@ impl ZendHashTable {
         unsafe { zend_hash_clean(self) }
     }
 
+    /// Reserves room for at least `additional` more elements to be appended
+    /// to the hashtable, so it does not grow repeatedly while it is filled.
+    ///
+    /// An empty hashtable is prepared as a list, which is converted when a
+    /// string key is inserted.
+    ///
+    /// # Parameters
+    ///
+    /// * `additional` - The number of elements to reserve room for.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// ht.reserve(1000);
+    /// for i in 0..1000 {
+    ///     ht.push(i);
+    /// }
+    /// ```
+    pub fn reserve(&mut self, additional: u32) {
+        // SAFETY: The flags are initialized by Zend on array init.
+        let uninitialized = u32::from(unsafe { self.u.v.flags }) & HASH_FLAG_UNINITIALIZED != 0;
+        let packed = uninitialized || self.is_packed();
+        // Elements are appended after the holes left by removed elements.
+        let size = self.nNumUsed.saturating_add(additional);
+        unsafe { zend_hash_extend(self, size, packed) }
+    }
+
+    /// Reclaims the space of the elements removed from the hashtable, which
+    /// are left as holes until the hashtable grows.
+    ///
+    /// The order of the elements and their keys are kept. The holes of lists
+    /// are kept, as their positions are the keys of the elements.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// for i in 0..100 {
+    ///     ht.insert(format!("key{i}").as_str(), i);
+    /// }
+    /// ht.retain(|_, val| val.long() == Some(0));
+    /// ht.compact();
+    /// assert_eq!(ht.len(), 1);
+    /// ```
+    pub fn compact(&mut self) {
+        if self.is_packed() || self.nNumUsed == self.nNumOfElements {
+            return;
+        }
+        unsafe { zend_hash_rehash(self) }
+    }
+
     /// Attempts to retrieve a value from the hash table with a string key.
     ///
     /// # Parameters
@@ -193,20 +358,12 @@ impl ZendHashTable {
     {
         match key.into() {
             ArrayKey::Long(index) => unsafe {
-                #[allow(clippy::cast_sign_loss)]
-                zend_hash_index_find(self, index as zend_ulong).as_ref()
-            },
-            ArrayKey::String(key) => unsafe {
-                zend_hash_str_find(
-                    self,
-                    CString::new(key.as_str()).ok()?.as_ptr(),
-                    key.len() as _,
-                )
-                .as_ref()
-            },
-            ArrayKey::Str(key) => unsafe {
-                zend_hash_str_find(self, CString::new(key).ok()?.as_ptr(), key.len() as _).as_ref()
+                zend_hash_index_find(self, index_to_hash(index).ok()?).as_ref()
             },
+            ArrayKey::String(key) => unsafe { self.find_str(key.as_bytes()).as_ref() },
+            ArrayKey::Str(key) => unsafe { self.find_str(key.as_bytes()).as_ref() },
+            ArrayKey::Binary(key) => unsafe { self.find_str(&key).as_ref() },
+            ArrayKey::Bytes(key) => unsafe { self.find_str(key).as_ref() },
         }
     }
 
@@ -242,23 +399,77 @@ impl ZendHashTable {
     {
         match key.into() {
             ArrayKey::Long(index) => unsafe {
-                #[allow(clippy::cast_sign_loss)]
-                zend_hash_index_find(self, index as zend_ulong).as_mut()
-            },
-            ArrayKey::String(key) => unsafe {
-                zend_hash_str_find(
-                    self,
-                    CString::new(key.as_str()).ok()?.as_ptr(),
-                    key.len() as _,
-                )
-                .as_mut()
-            },
-            ArrayKey::Str(key) => unsafe {
-                zend_hash_str_find(self, CString::new(key).ok()?.as_ptr(), key.len() as _).as_mut()
+                zend_hash_index_find(self, index_to_hash(index).ok()?).as_mut()
             },
+            ArrayKey::String(key) => unsafe { self.find_str(key.as_bytes()).as_mut() },
+            ArrayKey::Str(key) => unsafe { self.find_str(key.as_bytes()).as_mut() },
+            ArrayKey::Binary(key) => unsafe { self.find_str(&key).as_mut() },
+            ArrayKey::Bytes(key) => unsafe { self.find_str(key).as_mut() },
         }
     }
 
+    /// Retrieves a value from nested arrays and objects, following the keys of
+    /// the given path separated by dots.
+    ///
+    /// Each segment of the path is used as an array key, so numeric segments
+    /// look up integer keys, or as the name of a public property of an object.
+    /// References are followed. See [`get_path_as`](Self::get_path_as) to
+    /// find out which segment is missing.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut server = ZendHashTable::new();
+    /// server.insert("host", "localhost");
+    /// let mut servers = ZendHashTable::new();
+    /// servers.push(server);
+    /// let mut config = ZendHashTable::new();
+    /// config.insert("servers", servers);
+    ///
+    /// let host = config.get_path("servers.0.host").and_then(|zv| zv.str());
+    /// assert_eq!(host, Some("localhost"));
+    /// assert!(config.get_path("servers.1.host").is_none());
+    /// ```
+    #[must_use]
+    pub fn get_path(&self, path: &str) -> Option<&Zval> {
+        self.lookup_path(path).ok()
+    }
+
+    /// Retrieves a value from nested arrays and objects like
+    /// [`get_path`](Self::get_path), converted into `T`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::PathNotFound`] - If there is no value at a segment of the
+    ///   path, with the path up to that segment.
+    /// * [`Error::ZvalConversion`] - If the value could not be converted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::{error::Error, types::ZendHashTable};
+    ///
+    /// let mut db = ZendHashTable::new();
+    /// db.insert("port", 5432);
+    /// let mut config = ZendHashTable::new();
+    /// config.insert("db", db);
+    ///
+    /// assert_eq!(config.get_path_as::<u16>("db.port").ok(), Some(5432));
+    /// assert!(matches!(
+    ///     config.get_path_as::<String>("db.user.name"),
+    ///     Err(Error::PathNotFound(path)) if path == "db.user"
+    /// ));
+    /// ```
+    pub fn get_path_as<'a, T>(&'a self, path: &str) -> Result<T>
+    where
+        T: FromZval<'a>,
+    {
+        let val = self.lookup_path(path)?;
+        T::from_zval(val).ok_or_else(|| Error::ZvalConversion(val.get_type()))
+    }
+
     /// Attempts to retrieve a value from the hash table with an index.
     ///
     /// # Parameters
@@ -283,10 +494,7 @@ impl ZendHashTable {
     /// ```
     #[must_use]
     pub fn get_index(&self, key: i64) -> Option<&Zval> {
-        #[allow(clippy::cast_sign_loss)]
-        unsafe {
-            zend_hash_index_find(self, key as zend_ulong).as_ref()
-        }
+        unsafe { zend_hash_index_find(self, index_to_hash(key).ok()?).as_ref() }
     }
 
     /// Attempts to retrieve a value from the hash table with an index.
@@ -316,10 +524,7 @@ impl ZendHashTable {
     #[allow(clippy::mut_from_ref)]
     #[must_use]
     pub fn get_index_mut(&self, key: i64) -> Option<&mut Zval> {
-        unsafe {
-            #[allow(clippy::cast_sign_loss)]
-            zend_hash_index_find(self, key as zend_ulong).as_mut()
-        }
+        unsafe { zend_hash_index_find(self, index_to_hash(key).ok()?).as_mut() }
     }
 
     /// Attempts to remove a value from the hash table with a string key.
@@ -352,18 +557,19 @@ impl ZendHashTable {
     {
         let result = match key.into() {
             ArrayKey::Long(index) => unsafe {
-                #[allow(clippy::cast_sign_loss)]
-                zend_hash_index_del(self, index as zend_ulong)
+                zend_hash_index_del(self, index_to_hash(index).ok()?)
             },
             ArrayKey::String(key) => unsafe {
-                zend_hash_str_del(
-                    self,
-                    CString::new(key.as_str()).ok()?.as_ptr(),
-                    key.len() as _,
-                )
+                zend_hash_str_del(self, key.as_ptr().cast(), key.len())
             },
             ArrayKey::Str(key) => unsafe {
-                zend_hash_str_del(self, CString::new(key).ok()?.as_ptr(), key.len() as _)
+                zend_hash_str_del(self, key.as_ptr().cast(), key.len())
+            },
+            ArrayKey::Binary(key) => unsafe {
+                zend_hash_str_del(self, key.as_ptr().cast(), key.len())
+            },
+            ArrayKey::Bytes(key) => unsafe {
+                zend_hash_str_del(self, key.as_ptr().cast(), key.len())
             },
         };
 
@@ -395,14 +601,61 @@ impl ZendHashTable {
     /// assert_eq!(ht.len(), 0);
     /// ```
     pub fn remove_index(&mut self, key: i64) -> Option<()> {
-        let result = unsafe {
-            #[allow(clippy::cast_sign_loss)]
-            zend_hash_index_del(self, key as zend_ulong)
-        };
+        let result = unsafe { zend_hash_index_del(self, index_to_hash(key).ok()?) };
 
         if result < 0 { None } else { Some(()) }
     }
 
+    /// Attempts to retrieve a value from the hash table with a byte string
+    /// key, which may contain NUL bytes or be invalid UTF-8.
+    ///
+    /// Keys which are valid UTF-8 are looked up as with [`get`](Self::get),
+    /// so numeric keys like `"5"` find the element at index 5.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// ht.insert_bytes(b"\0*\0data\xff", "hello world");
+    /// assert_eq!(
+    ///     ht.get_bytes(b"\0*\0data\xff").and_then(|zv| zv.str()),
+    ///     Some("hello world")
+    /// );
+    /// ```
+    #[must_use]
+    pub fn get_bytes(&self, key: &[u8]) -> Option<&Zval> {
+        self.get(key)
+    }
+
+    /// Attempts to remove a value from the hash table with a byte string key,
+    /// see [`get_bytes`](Self::get_bytes).
+    ///
+    /// # Returns
+    ///
+    /// * `Some(())` - Key was successfully removed.
+    /// * `None` - No key was removed, did not exist.
+    pub fn remove_bytes(&mut self, key: &[u8]) -> Option<()> {
+        self.remove(key)
+    }
+
+    /// Attempts to insert an item into the hash table with a byte string key,
+    /// or update if the key already exists, see [`get_bytes`](Self::get_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if converting the value into a [`Zval`] failed, or
+    /// [`Error::NotPersistent`] if the hashtable is persistent and the value
+    /// is freed at the end of the request.
+    pub fn insert_bytes<V>(&mut self, key: &[u8], val: V) -> Result<()>
+    where
+        V: IntoZval,
+    {
+        self.insert(key, val)
+    }
+
     /// Attempts to insert an item into the hash table, or update if the key
     /// already exists. Returns nothing in a result if successful.
     ///
@@ -417,8 +670,9 @@ impl ZendHashTable {
     ///
     /// # Errors
     ///
-    /// Returns an error if the key could not be converted into a [`CString`],
-    /// or converting the value into a [`Zval`] failed.
+    /// Returns an error if converting the value into a [`Zval`] failed, or
+    /// [`Error::NotPersistent`] if the hashtable is persistent and the value
+    /// is freed at the end of the request.
     ///
     /// # Example
     ///
@@ -437,13 +691,10 @@ impl ZendHashTable {
         K: Into<ArrayKey<'a>>,
         V: IntoZval,
     {
-        let mut val = val.into_zval(false)?;
+        let mut val = self.value_into_zval(val)?;
         match key.into() {
             ArrayKey::Long(index) => {
-                unsafe {
-                    #[allow(clippy::cast_sign_loss)]
-                    zend_hash_index_update(self, index as zend_ulong, &raw mut val)
-                };
+                unsafe { zend_hash_index_update(self, index_to_hash(index)?, &raw mut val) };
             }
             ArrayKey::String(key) => {
                 unsafe {
@@ -464,11 +715,119 @@ impl ZendHashTable {
                     zend_hash_str_update(self, key.as_ptr().cast(), key.len(), &raw mut val)
                 };
             }
+            ArrayKey::Binary(key) => {
+                unsafe { zend_hash_str_update(self, key.as_ptr().cast(), key.len(), &raw mut val) };
+            }
+            ArrayKey::Bytes(key) => {
+                unsafe { zend_hash_str_update(self, key.as_ptr().cast(), key.len(), &raw mut val) };
+            }
         }
         val.release();
         Ok(())
     }
 
+    /// Inserts an item into the hash table only if the key does not exist yet,
+    /// leaving the existing value untouched otherwise.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key to insert the value at in the hash table.
+    /// * `value` - The value to insert into the hash table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The value was inserted.
+    /// * `Err(Zval)` - The key already exists. The rejected value is given
+    ///   back, converted into a [`Zval`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if converting the value into a [`Zval`] failed, or
+    /// [`Error::NotPersistent`] if the hashtable is persistent and the value
+    /// is freed at the end of the request.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// assert!(ht.try_insert("owner", "first").unwrap().is_ok());
+    /// let rejected = ht.try_insert("owner", "second").unwrap().unwrap_err();
+    /// assert_eq!(rejected.str(), Some("second"));
+    /// assert_eq!(ht.get("owner").and_then(|zv| zv.str()), Some("first"));
+    /// ```
+    pub fn try_insert<'a, K, V>(&mut self, key: K, val: V) -> Result<Result<(), Zval>>
+    where
+        K: Into<ArrayKey<'a>>,
+        V: IntoZval,
+    {
+        let mut val = self.value_into_zval(val)?;
+        let key = key.into();
+        let inserted = match &key {
+            ArrayKey::Long(index) => unsafe {
+                zend_hash_index_add(self, index_to_hash(*index)?, &raw mut val)
+            },
+            _ => {
+                let key = key.as_bytes().unwrap_or_default();
+                unsafe { zend_hash_str_add(self, key.as_ptr().cast(), key.len(), &raw mut val) }
+            }
+        };
+        if inserted.is_null() {
+            // The hash table did not take the value, so it is still owned here.
+            return Ok(Err(val));
+        }
+        val.release();
+        Ok(Ok(()))
+    }
+
+    /// Inserts an item into the hash table like [`insert`](Self::insert),
+    /// returning the value which was previously stored at the key.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key to insert the value at in the hash table.
+    /// * `value` - The value to insert into the hash table.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Zval)` - The previous value, which is no longer part of the
+    ///   hash table.
+    /// * `None` - The key did not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if converting the value into a [`Zval`] failed, or
+    /// [`Error::NotPersistent`] if the hashtable is persistent and the value
+    /// is freed at the end of the request.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// assert!(ht.replace("hits", 1).unwrap().is_none());
+    /// let previous = ht.replace("hits", 2).unwrap();
+    /// assert_eq!(previous.and_then(|zv| zv.long()), Some(1));
+    /// ```
+    pub fn replace<'a, K, V>(&mut self, key: K, val: V) -> Result<Option<Zval>>
+    where
+        K: Into<ArrayKey<'a>>,
+        V: IntoZval,
+    {
+        let key = key.into();
+        let val = self.value_into_zval(val)?;
+        match self.get_mut(key.clone()) {
+            // The previous value is moved out of the hash table, so it is
+            // released when the returned zval is dropped.
+            Some(slot) => Ok(Some(mem::replace(slot, val))),
+            None => self.insert(key, val).map(|()| None),
+        }
+    }
+
     /// Inserts an item into the hash table at a specified index, or updates if
     /// the key already exists. Returns nothing in a result if successful.
     ///
@@ -483,7 +842,9 @@ impl ZendHashTable {
     ///
     /// # Errors
     ///
-    /// Returns an error if converting the value into a [`Zval`] failed.
+    /// Returns an error if converting the value into a [`Zval`] failed, or
+    /// [`Error::NotPersistent`] if the hashtable is persistent and the value
+    /// is freed at the end of the request.
     ///
     /// # Example
     ///
@@ -501,11 +862,9 @@ impl ZendHashTable {
     where
         V: IntoZval,
     {
-        let mut val = val.into_zval(false)?;
-        unsafe {
-            #[allow(clippy::cast_sign_loss)]
-            zend_hash_index_update(self, key as zend_ulong, &raw mut val)
-        };
+        let hash = index_to_hash(key)?;
+        let mut val = self.value_into_zval(val)?;
+        unsafe { zend_hash_index_update(self, hash, &raw mut val) };
         val.release();
         Ok(())
     }
@@ -523,7 +882,10 @@ impl ZendHashTable {
     ///
     /// # Errors
     ///
-    /// Returns an error if converting the value into a [`Zval`] failed.
+    /// Returns an error if converting the value into a [`Zval`] failed,
+    /// [`Error::NotPersistent`] if the hashtable is persistent and the value
+    /// is freed at the end of the request, or [`Error::IntegerOverflow`] if
+    /// the next index of the hashtable overflows.
     ///
     /// # Example
     ///
@@ -541,18 +903,22 @@ impl ZendHashTable {
     where
         V: IntoZval,
     {
-        let mut val = val.into_zval(false)?;
-        unsafe { zend_hash_next_index_insert(self, &raw mut val) };
+        let mut val = self.value_into_zval(val)?;
+        // The engine fails to insert the value if the next index overflows.
+        if unsafe { zend_hash_next_index_insert(self, &raw mut val) }.is_null() {
+            return Err(Error::IntegerOverflow);
+        }
         val.release();
 
         Ok(())
     }
 
-    /// Checks if the hashtable only contains numerical keys.
-    ///
-    /// # Returns
+    /// Returns the index which the next element pushed onto the hash table
+    /// will be inserted at.
     ///
-    /// True if all keys on the hashtable are numerical.
+    /// As in PHP, it is one more than the largest integer key which has been
+    /// inserted, even if that element has been removed since, and `0` if no
+    /// integer key was inserted.
     ///
     /// # Example
     ///
@@ -560,30 +926,33 @@ impl ZendHashTable {
     /// use ext_php_rs::types::ZendHashTable;
     ///
     /// let mut ht = ZendHashTable::new();
+    /// assert_eq!(ht.next_index(), 0);
     ///
-    /// ht.push(0);
-    /// ht.push(3);
-    /// ht.push(9);
-    /// assert!(ht.has_numerical_keys());
-    ///
-    /// ht.insert("obviously not numerical", 10);
-    /// assert!(!ht.has_numerical_keys());
+    /// ht.insert(5, "five");
+    /// ht.remove(5);
+    /// assert_eq!(ht.next_index(), 6);
     /// ```
     #[must_use]
-    pub fn has_numerical_keys(&self) -> bool {
-        !self.into_iter().any(|(k, _)| !k.is_long())
+    pub fn next_index(&self) -> ZendLong {
+        // The engine marks tables without integer keys with the minimum value.
+        if self.nNextFreeElement == ZendLong::MIN {
+            0
+        } else {
+            self.nNextFreeElement
+        }
     }
 
-    /// Checks if the hashtable has numerical, sequential keys.
+    /// Sets the index which the next element pushed onto the hash table will
+    /// be inserted at, see [`next_index`](Self::next_index).
     ///
-    /// # Returns
-    ///
-    /// True if all keys on the hashtable are numerical and are in sequential
-    /// order (i.e. starting at 0 and not skipping any keys).
+    /// This allows reproducing the appends of an array whose elements were
+    /// removed, e.g. when rebuilding it. Pushing fails if the index is already
+    /// used by an element, and an index lower than the current one is raised
+    /// when a larger integer key is inserted.
     ///
-    /// # Panics
+    /// # Parameters
     ///
-    /// Panics if the number of elements in the hashtable exceeds `i64::MAX`.
+    /// * `index` - The index of the next pushed element.
     ///
     /// # Example
     ///
@@ -592,20 +961,167 @@ impl ZendHashTable {
     ///
     /// let mut ht = ZendHashTable::new();
     ///
-    /// ht.push(0);
-    /// ht.push(3);
-    /// ht.push(9);
-    /// assert!(ht.has_sequential_keys());
-    ///
-    /// ht.insert_at_index(90, 10);
-    /// assert!(!ht.has_sequential_keys());
+    /// ht.set_next_index(10);
+    /// ht.push("ten");
+    /// assert!(ht.get_index(10).is_some());
     /// ```
-    #[must_use]
-    pub fn has_sequential_keys(&self) -> bool {
-        !self
-            .into_iter()
-            .enumerate()
-            .any(|(i, (k, _))| ArrayKey::Long(i64::try_from(i).expect("Integer overflow")) != k)
+    pub fn set_next_index(&mut self, index: ZendLong) {
+        self.nNextFreeElement = index;
+    }
+
+    /// Copies all elements of another hashtable into this one.
+    ///
+    /// Keys, including integer keys, are kept. Elements whose key already
+    /// exists in this hashtable replace the existing elements if `overwrite`
+    /// is `true`, like `array_replace()`, and are skipped otherwise, like the
+    /// `+` operator. Values are shared with the other hashtable rather than
+    /// copied.
+    ///
+    /// To insert elements from an iterator, use the [`Extend`]
+    /// implementation, e.g. with `Extend::extend(&mut *ht, iter)`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.insert("a", 1);
+    ///
+    /// let mut other = ZendHashTable::new();
+    /// other.insert("a", 2);
+    /// other.insert("b", 3);
+    ///
+    /// ht.extend(&other, false);
+    /// assert_eq!(ht.get("a").and_then(|zv| zv.long()), Some(1));
+    /// assert_eq!(ht.len(), 2);
+    /// ```
+    pub fn extend(&mut self, other: &ZendHashTable, overwrite: bool) {
+        // SAFETY: `zval_add_ref` adds a reference to each copied value.
+        unsafe { zend_hash_merge(self, other, Some(zval_add_ref), overwrite) };
+    }
+
+    /// Retains only the elements for which the given predicate returns
+    /// `true`, removing all others.
+    ///
+    /// The predicate is passed the key and a mutable reference to the value
+    /// of each element, in order. Elements are removed through the Zend API,
+    /// which keeps the internal pointer and iterators of the hashtable valid.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// ht.push(1);
+    /// ht.push(2);
+    /// ht.push(3);
+    /// ht.retain(|_, val| val.long().is_some_and(|val| val % 2 == 1));
+    /// assert_eq!(ht.len(), 2);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(ArrayKey<'_>, &mut Zval) -> bool,
+    {
+        let mut pos = 0;
+        // Removing the last elements shrinks `nNumUsed`, but never moves other
+        // elements.
+        while pos < self.nNumUsed {
+            // SAFETY: The position is below `nNumUsed`.
+            let removed = unsafe { self.element(pos) }
+                .and_then(|(key, val)| (!f(key, unsafe { &mut *val })).then_some(val));
+            if let Some(val) = removed {
+                // SAFETY: The value belongs to an element of this hashtable.
+                unsafe { self.delete_element(val) };
+            }
+            pos += 1;
+        }
+    }
+
+    /// Returns an iterator which removes the elements of the hashtable and
+    /// yields their keys and values, leaving the hashtable empty.
+    ///
+    /// The values are moved out of the hashtable without being copied. The
+    /// hashtable is emptied once the iterator is dropped, even if it was not
+    /// fully consumed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// ht.insert("a", "A");
+    /// ht.insert("b", "B");
+    /// let values: Vec<_> = ht.drain().filter_map(|(_, val)| val.string()).collect();
+    /// assert_eq!(values, ["A", "B"]);
+    /// assert!(ht.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_> {
+        Drain::new(self)
+    }
+
+    /// Checks if the hashtable only contains numerical keys.
+    ///
+    /// # Returns
+    ///
+    /// True if all keys on the hashtable are numerical.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// ht.push(0);
+    /// ht.push(3);
+    /// ht.push(9);
+    /// assert!(ht.has_numerical_keys());
+    ///
+    /// ht.insert("obviously not numerical", 10);
+    /// assert!(!ht.has_numerical_keys());
+    /// ```
+    #[must_use]
+    pub fn has_numerical_keys(&self) -> bool {
+        !self.into_iter().any(|(k, _)| !k.is_long())
+    }
+
+    /// Checks if the hashtable has numerical, sequential keys.
+    ///
+    /// # Returns
+    ///
+    /// True if all keys on the hashtable are numerical and are in sequential
+    /// order (i.e. starting at 0 and not skipping any keys).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of elements in the hashtable exceeds `i64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// ht.push(0);
+    /// ht.push(3);
+    /// ht.push(9);
+    /// assert!(ht.has_sequential_keys());
+    ///
+    /// ht.insert_at_index(90, 10);
+    /// assert!(!ht.has_sequential_keys());
+    /// ```
+    #[must_use]
+    pub fn has_sequential_keys(&self) -> bool {
+        !self
+            .into_iter()
+            .enumerate()
+            .any(|(i, (k, _))| ArrayKey::Long(i64::try_from(i).expect("Integer overflow")) != k)
     }
 
     /// Returns an iterator over the values contained inside the hashtable, as
@@ -645,6 +1161,11 @@ impl ZendHashTable {
     ///         }
     ///         ArrayKey::Str(key) => {
     ///         }
+    ///         ArrayKey::Binary(key) => {
+    ///         }
+    ///         ArrayKey::Bytes(key) => {
+    ///         }
+    ///         _ => {}
     ///     }
     ///     dbg!(key, val);
     /// }
@@ -654,6 +1175,134 @@ impl ZendHashTable {
         self.into_iter()
     }
 
+    /// Returns an iterator over the keys of the hashtable, without reading
+    /// the values.
+    ///
+    /// Like [`iter_buckets`](Self::iter_buckets), the buckets of the
+    /// hashtable are read directly and string keys are borrowed when they are
+    /// valid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.push("a");
+    /// ht.insert("name", "b");
+    ///
+    /// let keys: Vec<_> = ht.keys().map(|key| key.to_string()).collect();
+    /// assert_eq!(keys, ["0", "name"]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn keys(&self) -> Keys<'_> {
+        Keys::new(self)
+    }
+
+    /// Returns an iterator over the values contained inside the hashtable,
+    /// which allows modifying each value in place.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.push(1);
+    /// ht.push(2);
+    ///
+    /// for val in ht.values_mut() {
+    ///     val.set_long(val.long().unwrap_or_default() * 10);
+    /// }
+    /// assert_eq!(ht.get_index(1).and_then(|zv| zv.long()), Some(20));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_> {
+        ValuesMut::new(self)
+    }
+
+    /// Returns an iterator over the key(s) and value contained inside the
+    /// hashtable, which allows modifying each value in place.
+    ///
+    /// Like [`iter_buckets`](Self::iter_buckets), the buckets of the
+    /// hashtable are read directly. Elements cannot be added or removed
+    /// while iterating, see [`retain`](Self::retain) for removing elements.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.insert("name", "value");
+    ///
+    /// for (_, val) in ht.iter_mut() {
+    ///     if let Some(upper) = val.str().map(str::to_uppercase) {
+    ///         let _ = val.set_string(&upper, false);
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut::new(self)
+    }
+
+    /// Returns an iterator over the key(s) and value contained inside the
+    /// hashtable, which reads the buckets of the hashtable directly.
+    ///
+    /// This yields the same elements as [`iter`](Self::iter), but borrows
+    /// string keys instead of copying them, which makes iterating large
+    /// arrays considerably faster. Holes left by deleted elements are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::{ArrayKey, ZendHashTable};
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.insert("name", "value").unwrap();
+    ///
+    /// for (key, val) in ht.iter_buckets() {
+    ///     if let ArrayKey::Str(key) = key {
+    ///         dbg!(key, val);
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter_buckets(&self) -> Buckets<'_> {
+        Buckets::new(self)
+    }
+
+    /// Determines whether this hashtable is packed.
+    ///
+    /// Packed hashtables are lists whose keys are increasing integer indexes,
+    /// e.g. the arrays created by pushing values, which PHP stores without
+    /// hashing the keys. Iterating over a packed hashtable reads its values
+    /// directly rather than going through the Zend API for each element.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.push(1);
+    /// ht.push(2);
+    /// assert!(ht.is_packed());
+    ///
+    /// ht.insert("name", 3);
+    /// assert!(!ht.is_packed());
+    /// ```
+    #[must_use]
+    pub fn is_packed(&self) -> bool {
+        // SAFETY: The flags are initialized by Zend on array init.
+        u32::from(unsafe { self.u.v.flags }) & HASH_FLAG_PACKED != 0
+    }
+
     /// Determines whether this hashtable is immutable.
     ///
     /// Immutable hashtables are shared and cannot be modified. The primary
@@ -676,6 +1325,273 @@ impl ZendHashTable {
 
         gc_flags & ZvalTypeFlags::Immutable.bits() != 0
     }
+
+    /// Returns whether this hashtable was allocated with the persistent
+    /// allocator, see [`new_persistent`](Self::new_persistent).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let ht = ZendHashTable::new();
+    /// assert!(!ht.is_persistent());
+    /// ```
+    #[must_use]
+    pub fn is_persistent(&self) -> bool {
+        // SAFETY: Type info is initialized by Zend on array init.
+        let gc_type_info = unsafe { self.gc.u.type_info };
+        let gc_flags = (gc_type_info >> GC_FLAGS_SHIFT) & (GC_FLAGS_MASK >> GC_FLAGS_SHIFT);
+
+        gc_flags & ZvalTypeFlags::Persistent.bits() != 0
+    }
+
+    /// Converts a value into a zval to be inserted into the hashtable,
+    /// rejecting values which are freed at the end of the request if the
+    /// hashtable is persistent.
+    fn value_into_zval<V: IntoZval>(&self, val: V) -> Result<Zval> {
+        let persistent = self.is_persistent();
+        let val = val.into_zval(persistent)?;
+        if persistent && !outlives_request(&val) {
+            return Err(Error::NotPersistent(val.get_type()));
+        }
+        Ok(val)
+    }
+
+    /// Returns the array held by the given zval, which can be modified without
+    /// affecting other values, or [`None`] if the zval is not an array.
+    ///
+    /// Arrays are shared between zvals until one of them is modified, so an
+    /// array which is shared or immutable is duplicated first, as PHP's
+    /// `SEPARATE_ARRAY()` macro does. Arrays received by value must be
+    /// separated before they are modified, or the caller would observe the
+    /// changes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::{ZendHashTable, Zval};
+    ///
+    /// fn append_total(zv: &mut Zval) {
+    ///     let Some(ht) = ZendHashTable::make_writable(zv) else {
+    ///         return;
+    ///     };
+    ///     let total: i64 = ht.values().filter_map(Zval::long).sum();
+    ///     ht.insert("total", total);
+    /// }
+    /// ```
+    pub fn make_writable(zv: &mut Zval) -> Option<&mut Self> {
+        if !zv.is_array() {
+            return None;
+        }
+        unsafe {
+            let arr = zv.value.arr;
+            if (*arr).gc.refcount > 1 || (*arr).is_immutable() {
+                // The other zvals keep the original array, immutable arrays
+                // are not reference counted.
+                if !(*arr).is_immutable() {
+                    (*arr).gc.refcount -= 1;
+                }
+                zv.value.arr = zend_array_dup(arr);
+                zv.u1.type_info = ZvalTypeFlags::ArrayEx.bits();
+            }
+            zv.value.arr.as_mut()
+        }
+    }
+
+    /// Takes the array held by the given zval, which is copied unless the zval
+    /// holds the only reference to it, or returns [`None`] if the zval is not
+    /// an array.
+    pub(crate) fn take_from(mut zv: Zval) -> Option<ZBox<Self>> {
+        let flags = ZvalTypeFlags::from_bits_retain(unsafe { zv.u1.type_info });
+        let ht = zv.array()?;
+        // Zvals which are not reference counted do not own their array, e.g.
+        // the immutable shared empty array.
+        if !flags.contains(ZvalTypeFlags::RefCounted) || ht.gc.refcount > 1 {
+            return Some(ht.to_owned());
+        }
+        let ht = unsafe { zv.value.arr };
+        // The zval no longer owns the array.
+        zv.u1.type_info = ZvalTypeFlags::Null.bits();
+        // SAFETY: The array is only referenced by the zval.
+        Some(unsafe { ZBox::from_raw(&mut *ht) })
+    }
+}
+
+impl ZendHashTable {
+    /// Follows the given path through nested arrays and objects.
+    fn lookup_path(&self, path: &str) -> Result<&Zval> {
+        let mut ht = Some(self);
+        let mut found = None;
+        let mut end = 0;
+        for (i, segment) in path.split('.').enumerate() {
+            end += segment.len() + usize::from(i > 0);
+            let val = ht
+                .and_then(|ht| ht.get(segment))
+                .map(Zval::dereference)
+                .ok_or_else(|| Error::PathNotFound(path[..end].to_owned()))?;
+            ht = match val.object() {
+                Some(object) => object.get_properties().ok(),
+                None => val.array(),
+            };
+            found = Some(val);
+        }
+        found.ok_or_else(|| Error::PathNotFound(path.to_owned()))
+    }
+
+    /// Returns a pointer to the value with the given string key, which is
+    /// null if there is none.
+    fn find_str(&self, key: &[u8]) -> *mut Zval {
+        unsafe { zend_hash_str_find(self, key.as_ptr().cast(), key.len()) }
+    }
+
+    /// Returns the key of the element at the given position of the bucket
+    /// array and a pointer to its value, or `None` if the position is a hole
+    /// left by a deleted element.
+    ///
+    /// # Safety
+    ///
+    /// The position must be below `nNumUsed`.
+    unsafe fn element(&self, pos: u32) -> Option<(ArrayKey<'_>, *mut Zval)> {
+        unsafe { self.element_with(pos, ZendStr::as_str) }
+    }
+
+    /// Returns the element at the given position like
+    /// [`element`](Self::element), without modifying the string key to cache
+    /// whether it is valid UTF-8, so it can be called from other threads.
+    ///
+    /// # Safety
+    ///
+    /// The position must be below `nNumUsed`.
+    #[cfg(feature = "rayon")]
+    unsafe fn shared_element(&self, pos: u32) -> Option<(ArrayKey<'_>, *mut Zval)> {
+        unsafe { self.element_with(pos, ZendStr::as_str_uncached) }
+    }
+
+    /// Returns the element at the given position, converting string keys with
+    /// `as_str`.
+    ///
+    /// # Safety
+    ///
+    /// The position must be below `nNumUsed`.
+    unsafe fn element_with(
+        &self,
+        pos: u32,
+        as_str: fn(&ZendStr) -> Result<&str>,
+    ) -> Option<(ArrayKey<'_>, *mut Zval)> {
+        let pos = pos as usize;
+
+        cfg_if! {
+            if #[cfg(php82)] {
+                // Packed arrays only store the values since PHP 8.2.
+                if self.is_packed() {
+                    let val = unsafe { self.__bindgen_anon_1.arPacked.add(pos) };
+                    if is_empty_slot(unsafe { &*val }) {
+                        return None;
+                    }
+                    return Some((ArrayKey::Long(pos.try_into().ok()?), val));
+                }
+                let bucket = unsafe { self.__bindgen_anon_1.arData.add(pos) };
+            } else {
+                let bucket = unsafe { self.arData.add(pos) };
+            }
+        }
+
+        let val = unsafe { &raw mut (*bucket).val };
+        if is_empty_slot(unsafe { &*val }) {
+            return None;
+        }
+        Some((bucket_key(unsafe { &*bucket }, as_str), val))
+    }
+
+    /// Returns the key of the element with the given value, as returned by
+    /// [`element`](Self::element).
+    ///
+    /// # Safety
+    ///
+    /// The value must belong to an element of this hashtable.
+    unsafe fn value_key(&self, val: *const Zval) -> ArrayKey<'_> {
+        #[cfg(php82)]
+        if self.is_packed() {
+            let pos = unsafe { val.offset_from(self.__bindgen_anon_1.arPacked) };
+            return ArrayKey::Long(pos.try_into().expect("Integer overflow"));
+        }
+        // The value is the first field of its bucket.
+        bucket_key(unsafe { &*val.cast::<Bucket>() }, ZendStr::as_str)
+    }
+
+    /// Deletes the element with the given value, as returned by
+    /// [`element`](Self::element).
+    ///
+    /// # Safety
+    ///
+    /// The value must belong to an element of this hashtable.
+    unsafe fn delete_element(&mut self, val: *mut Zval) {
+        #[cfg(php82)]
+        if self.is_packed() {
+            unsafe { zend_hash_packed_del_val(self, val) };
+            return;
+        }
+        // The value is the first field of its bucket.
+        unsafe { zend_hash_del_bucket(self, val.cast::<Bucket>()) };
+    }
+}
+
+/// Returns whether the value is not freed at the end of the request, so it
+/// can be stored in a persistent hashtable.
+fn outlives_request(val: &Zval) -> bool {
+    match val.get_type() {
+        DataType::Undef
+        | DataType::Null
+        | DataType::False
+        | DataType::True
+        | DataType::Bool
+        | DataType::Long
+        | DataType::Double => true,
+        DataType::String => val
+            .zend_str()
+            .is_some_and(|s| s.is_persistent() || s.is_permanent()),
+        // Releasing a nested array frees it from the request heap, so only
+        // immutable arrays, which are never released, can be stored.
+        DataType::Array => val.array().is_some_and(ZendHashTable::is_immutable),
+        _ => false,
+    }
+}
+
+/// Returns whether the slot of a hashtable holds no element, i.e. is undefined
+/// or points to an undefined value, like the slots of uninitialized typed
+/// properties and unset variables. Such slots are not counted by
+/// [`ZendHashTable::len`].
+fn is_empty_slot(val: &Zval) -> bool {
+    val.indirect().unwrap_or(val).get_type() == DataType::Undef
+}
+
+/// Returns the key of the given bucket, which borrows string keys if they are
+/// valid UTF-8, as checked by `as_str`.
+fn bucket_key(bucket: &Bucket, as_str: fn(&ZendStr) -> Result<&str>) -> ArrayKey<'_> {
+    match unsafe { bucket.key.as_ref() } {
+        Some(key) => match as_str(key) {
+            Ok(key) => ArrayKey::Str(key),
+            Err(_) => ArrayKey::Bytes(key.as_bytes()),
+        },
+        None => ArrayKey::Long(hash_to_index(bucket.h)),
+    }
+}
+
+/// Inserts the key-value pairs into the hashtable, overwriting values with the
+/// same key, see [`ZendHashTable::insert`].
+///
+/// # Panics
+///
+/// Panics if a value cannot be inserted, i.e. if the hashtable is persistent
+/// and the value is freed at the end of the request.
+impl<'a> Extend<(ArrayKey<'a>, Zval)> for ZendHashTable {
+    fn extend<T: IntoIterator<Item = (ArrayKey<'a>, Zval)>>(&mut self, iter: T) {
+        for (key, val) in iter {
+            self.insert(key, val)
+                .expect("Failed to insert value into hashtable");
+        }
+    }
 }
 
 unsafe impl ZBoxable for ZendHashTable {
@@ -684,8 +1600,17 @@ unsafe impl ZBoxable for ZendHashTable {
         if self.is_immutable() {
             return;
         }
+        // Other references may exist if the box was shared, see `ZBox::share`.
+        if self.get_count() > 1 {
+            self.dec_count();
+            return;
+        }
         // SAFETY: ZBox has immutable access to `self`.
-        unsafe { zend_array_destroy(self) }
+        if self.is_persistent() {
+            unsafe { ext_php_rs_zend_persistent_array_destroy(self) }
+        } else {
+            unsafe { zend_array_destroy(self) }
+        }
     }
 }
 
@@ -817,3 +1742,529 @@ impl IntoZval for ZendEmptyArray {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_retain() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            for i in 0..10 {
+                ht.push(i).unwrap();
+            }
+            ht.insert("name", "value").unwrap();
+
+            ht.retain(|key, val| key.is_long() && val.long().unwrap() % 3 == 0);
+            let kept: Vec<_> = ht.iter().map(|(key, val)| (key, val.long())).collect();
+            assert_eq!(
+                kept,
+                [
+                    (ArrayKey::Long(0), Some(0)),
+                    (ArrayKey::Long(3), Some(3)),
+                    (ArrayKey::Long(6), Some(6)),
+                    (ArrayKey::Long(9), Some(9)),
+                ]
+            );
+
+            ht.retain(|_, val| {
+                val.set_long(val.long().unwrap() * 2);
+                true
+            });
+            assert_eq!(ht.get_index(9).and_then(Zval::long), Some(18));
+
+            ht.retain(|_, _| false);
+            assert!(ht.is_empty());
+            ht.push(1).unwrap();
+            assert_eq!(ht.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_extend() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.insert("a", 1).unwrap();
+            ht.insert(5, "five").unwrap();
+
+            let mut other = ZendHashTable::new();
+            other.insert("a", 2).unwrap();
+            other.insert("b", vec![1, 2, 3]).unwrap();
+            other.insert(5, "other five").unwrap();
+
+            ht.extend(&other, false);
+            assert_eq!(ht.len(), 3);
+            assert_eq!(ht.get("a").and_then(Zval::long), Some(1));
+            assert_eq!(ht.get_index(5).and_then(Zval::str), Some("five"));
+            let shared = ht.get("b").and_then(Zval::array).unwrap();
+            assert_eq!(shared.get_count(), 2);
+
+            ht.extend(&other, true);
+            assert_eq!(ht.get("a").and_then(Zval::long), Some(2));
+            assert_eq!(ht.get_index(5).and_then(Zval::str), Some("other five"));
+            drop(other);
+            assert_eq!(
+                ht.get("b").and_then(Zval::array).map(ZendHashTable::len),
+                Some(3)
+            );
+
+            Extend::extend(
+                &mut *ht,
+                [
+                    (ArrayKey::Str("c"), 3_i64.into_zval(false).unwrap()),
+                    (ArrayKey::Long(7), Zval::new()),
+                ],
+            );
+            assert_eq!(ht.len(), 5);
+            assert_eq!(ht.get("c").and_then(Zval::long), Some(3));
+            assert!(ht.get_index(7).is_some_and(Zval::is_null));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to insert value into hashtable")]
+    fn test_extend_persistent_panics() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new_persistent();
+            let request_str = Embed::eval("str_repeat('a', 3);").unwrap();
+            Extend::extend(&mut *ht, [(ArrayKey::Str("a"), request_str)]);
+        });
+    }
+
+    #[test]
+    fn test_get_path() {
+        Embed::run(|| {
+            let config = Embed::eval(
+                "['db' => [['host' => 'primary', 'port' => 5432]], 'cache' => (object) ['ttl' => 60]];",
+            )
+            .unwrap();
+            let config = config.array().unwrap();
+
+            assert_eq!(
+                config.get_path("db.0.host").and_then(Zval::str),
+                Some("primary")
+            );
+            assert_eq!(config.get_path_as::<i64>("cache.ttl").unwrap(), 60);
+            assert!(config.get_path("db.1.host").is_none());
+            assert!(matches!(
+                config.get_path_as::<i64>("db.0.port.value"),
+                Err(Error::PathNotFound(path)) if path == "db.0.port.value"
+            ));
+            assert!(matches!(
+                config.get_path_as::<i64>("db.0.host"),
+                Err(Error::ZvalConversion(DataType::String))
+            ));
+        });
+    }
+
+    #[test]
+    fn test_make_writable() {
+        Embed::run(|| {
+            let mut array = Embed::eval("[];").unwrap();
+            assert!(array.array().unwrap().is_immutable());
+            let ht = ZendHashTable::make_writable(&mut array).unwrap();
+            assert!(!ht.is_immutable());
+            ht.push(1).unwrap();
+            assert!(Embed::eval("[];").unwrap().array().unwrap().is_empty());
+
+            let mut shared = array.shallow_clone();
+            ZendHashTable::make_writable(&mut shared)
+                .unwrap()
+                .push(2)
+                .unwrap();
+            assert_eq!(array.array().unwrap().len(), 1);
+            assert_eq!(shared.array().unwrap().len(), 2);
+            assert_eq!(array.array().unwrap().gc.refcount, 1);
+
+            assert!(ZendHashTable::make_writable(&mut Zval::new()).is_none());
+        });
+    }
+
+    #[test]
+    fn test_new_persistent() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new_persistent();
+            assert!(ht.is_persistent());
+            ht.insert("greeting", "Hello").unwrap();
+            ht.push(42).unwrap();
+
+            assert_eq!(ht.get("greeting").and_then(Zval::str), Some("Hello"));
+            assert_eq!(ht.get_index(0).and_then(Zval::long), Some(42));
+            assert!(ht.remove("greeting").is_some());
+            assert!(!ZendHashTable::new().is_persistent());
+
+            let request_str = Embed::eval("str_repeat('a', 3);").unwrap();
+            assert!(matches!(
+                ht.insert("request", request_str),
+                Err(Error::NotPersistent(DataType::String))
+            ));
+            let object = Embed::eval("new stdClass;").unwrap();
+            assert!(matches!(
+                ht.push(object),
+                Err(Error::NotPersistent(DataType::Object(_)))
+            ));
+            assert!(matches!(
+                ht.replace("list", ZendHashTable::new()),
+                Err(Error::NotPersistent(DataType::Array))
+            ));
+            assert_eq!(ht.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_reserve_and_compact() {
+        Embed::run(|| {
+            let mut list = ZendHashTable::new();
+            list.reserve(1000);
+            assert!(list.is_packed());
+            assert!(list.nTableSize >= 1000);
+            list.push(1).unwrap();
+
+            let mut ht = ZendHashTable::new();
+            ht.insert("a", 1).unwrap();
+            ht.reserve(100);
+            assert!(!ht.is_packed());
+            assert!(ht.nTableSize >= 101);
+
+            for i in 0..50 {
+                ht.insert(format!("key{i}").as_str(), i).unwrap();
+            }
+            ht.retain(|_, val| val.long().is_some_and(|n| n % 10 == 1));
+            assert!(ht.nNumUsed > ht.nNumOfElements);
+            ht.compact();
+            assert_eq!(ht.nNumUsed, ht.nNumOfElements);
+            let keys: Vec<_> = ht.keys().map(|key| key.to_string()).collect();
+            assert_eq!(keys, ["a", "key1", "key11", "key21", "key31", "key41"]);
+        });
+    }
+
+    #[test]
+    fn test_next_index() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            assert_eq!(ht.next_index(), 0);
+            ht.insert("a", "string").unwrap();
+            assert_eq!(ht.next_index(), 0);
+            ht.push(1).unwrap();
+            assert_eq!(ht.next_index(), 1);
+
+            ht.insert(7, "seven").unwrap();
+            assert!(ht.remove(7).is_some());
+            assert_eq!(ht.next_index(), 8);
+
+            ht.set_next_index(3);
+            ht.push(3).unwrap();
+            assert_eq!(ht.get_index(3).and_then(Zval::long), Some(3));
+            assert_eq!(ht.next_index(), 4);
+        });
+    }
+
+    #[test]
+    fn test_replace() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            assert!(ht.replace("key", "first").unwrap().is_none());
+            assert!(ht.replace(3, 3).unwrap().is_none());
+
+            let previous = ht.replace("key", "second").unwrap().unwrap();
+            assert_eq!(previous.str(), Some("first"));
+            assert_eq!(ht.get("key").and_then(Zval::str), Some("second"));
+            let previous = ht.replace(3, 4).unwrap().unwrap();
+            assert_eq!(previous.long(), Some(3));
+            assert_eq!(ht.len(), 2);
+        });
+    }
+
+    #[test]
+    fn test_iter_both_ends() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            for i in 0..6 {
+                ht.insert(format!("key{i}").as_str(), i).unwrap();
+            }
+            ht.remove("key1");
+            ht.remove("key4");
+
+            let mut iter = ht.iter();
+            assert_eq!(iter.size_hint(), (4, Some(4)));
+            let mut keys = vec![];
+            while let Some((key, _)) = iter.next() {
+                keys.push(key.to_string());
+                assert_eq!(iter.len(), 4 - keys.len());
+                if let Some((key, _)) = iter.next_back() {
+                    keys.push(key.to_string());
+                }
+            }
+            assert_eq!(keys, ["key0", "key5", "key2", "key3"]);
+            assert!(iter.next_back().is_none());
+
+            let mut list = Embed::eval("range(0, 4);").unwrap();
+            let list = list.array_mut().unwrap();
+            assert!(list.remove(2).is_some());
+            let mut iter = list.values();
+            assert_eq!(iter.next_back().and_then(Zval::long), Some(4));
+            assert_eq!(iter.next().and_then(Zval::long), Some(0));
+            assert_eq!(iter.next_back().and_then(Zval::long), Some(3));
+            assert_eq!(iter.next().and_then(Zval::long), Some(1));
+            assert_eq!(iter.len(), 0);
+            assert!(iter.next().is_none());
+
+            let (key, _) = ht.iter().next_zval().unwrap();
+            assert_eq!(key.str(), Some("key0"));
+        });
+    }
+
+    #[test]
+    fn test_iter_empty_indirect_slots() {
+        Embed::run(|| {
+            Embed::eval(
+                "eval('class IterEmptySlots { public $a = 1; public ?int $b; public $c = 3; }');",
+            )
+            .unwrap();
+            let object = Embed::eval("new IterEmptySlots;").unwrap();
+            let properties = object.object().unwrap().get_properties().unwrap();
+            assert_eq!(properties.len(), 2);
+
+            let mut iter = properties.iter_buckets();
+            assert_eq!(iter.len(), 2);
+            let keys: Vec<_> = iter.by_ref().map(|(key, _)| key.to_string()).collect();
+            assert_eq!(keys, ["a", "c"]);
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+            assert_eq!(properties.iter().rev().count(), 2);
+        });
+    }
+
+    #[test]
+    fn test_try_insert() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            assert!(ht.try_insert("a", 1).unwrap().is_ok());
+            assert!(ht.try_insert(3, "three").unwrap().is_ok());
+
+            let rejected = ht.try_insert("a", 2).unwrap().unwrap_err();
+            assert_eq!(rejected.long(), Some(2));
+            let rejected = ht.try_insert(3, "other").unwrap().unwrap_err();
+            assert_eq!(rejected.str(), Some("other"));
+
+            assert_eq!(ht.len(), 2);
+            assert_eq!(ht.get("a").and_then(Zval::long), Some(1));
+            assert_eq!(ht.get(3).and_then(Zval::str), Some("three"));
+        });
+    }
+
+    #[test]
+    fn test_collect() {
+        Embed::run(|| {
+            let ht: ZBox<ZendHashTable> = (1..=3).collect();
+            let values: Vec<_> = ht.values().filter_map(Zval::long).collect();
+            assert_eq!(values, [1, 2, 3]);
+
+            let ht: ZBox<ZendHashTable> = ["a", "b", "a"]
+                .into_iter()
+                .enumerate()
+                .map(|(i, key)| (key.to_owned(), i))
+                .collect();
+            assert_eq!(ht.len(), 2);
+            assert_eq!(ht.get("a").and_then(Zval::long), Some(2));
+
+            let ht: ZBox<ZendHashTable> = vec![(5, Some("five")), (-1, None)].into_iter().collect();
+            assert_eq!(ht.get(5).and_then(Zval::str), Some("five"));
+            assert!(ht.get(-1).unwrap().is_null());
+        });
+    }
+
+    #[test]
+    fn test_push_overflow() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.insert(ZendLong::MAX, 1).unwrap();
+            assert!(matches!(ht.push(2), Err(Error::IntegerOverflow)));
+            assert_eq!(ht.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_binary_keys() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.insert("a\0b", 1).unwrap();
+            assert_eq!(ht.get("a\0b").and_then(Zval::long), Some(1));
+            assert!(ht.get("a").is_none());
+
+            ht.insert_bytes(b"\xff\0key", 2).unwrap();
+            ht.insert_bytes(b"7", 3).unwrap();
+            assert_eq!(ht.get_bytes(b"\xff\0key").and_then(Zval::long), Some(2));
+            assert_eq!(ht.get_bytes(b"a\0b").and_then(Zval::long), Some(1));
+            assert_eq!(ht.get_index(7).and_then(Zval::long), Some(3));
+            assert_eq!(ht.len(), 3);
+
+            let keys: Vec<_> = ht.keys().collect();
+            assert_eq!(keys[1], ArrayKey::Bytes(b"\xff\0key"));
+            let (key, _) = ht.iter().nth(1).unwrap();
+            assert_eq!(key, ArrayKey::Binary(b"\xff\0key".to_vec()));
+            assert_eq!(ht.get(key).and_then(Zval::long), Some(2));
+
+            assert!(ht.remove("a\0b").is_some());
+            assert!(ht.remove_bytes(b"\xff\0key").is_some());
+            assert!(ht.remove_bytes(b"\xff\0key").is_none());
+            assert_eq!(ht.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_from_slice() {
+        Embed::run(|| {
+            let longs: Vec<i64> = (0..1000).collect();
+            let ht = ZendHashTable::from_slice(&longs);
+            assert!(ht.is_packed());
+            assert_eq!(ht.len(), 1000);
+            assert_eq!(ht.get_index(999).and_then(Zval::long), Some(999));
+
+            let strings = ["a".to_owned(), "b".to_owned()];
+            let ht = ZendHashTable::try_from_slice(&strings).unwrap();
+            assert!(ht.is_packed());
+            let values: Vec<_> = ht.values().filter_map(Zval::string).collect();
+            assert_eq!(values, strings);
+
+            let nested = [vec![1, 2], vec![3]];
+            let ht = ZendHashTable::from_slice(&nested);
+            assert_eq!(
+                ht.get_index(1)
+                    .and_then(Zval::array)
+                    .map(ZendHashTable::len),
+                Some(1)
+            );
+
+            assert!(ZendHashTable::from_slice::<i64>(&[]).is_empty());
+        });
+    }
+
+    #[test]
+    fn test_packed_iteration() {
+        Embed::run(|| {
+            let mut array = Embed::eval("range(1, 6);").unwrap();
+            let ht = array.array_mut().unwrap();
+            assert!(ht.is_packed());
+
+            ht.retain(|_, val| val.long() != Some(3));
+            assert!(ht.is_packed());
+            let entries: Vec<_> = ht
+                .iter()
+                .map(|(key, val)| (key.to_string(), val.long()))
+                .collect();
+            assert_eq!(
+                entries,
+                [
+                    ("0".to_owned(), Some(1)),
+                    ("1".to_owned(), Some(2)),
+                    ("3".to_owned(), Some(4)),
+                    ("4".to_owned(), Some(5)),
+                    ("5".to_owned(), Some(6)),
+                ]
+            );
+            let rev: Vec<_> = ht.values().rev().filter_map(Zval::long).collect();
+            assert_eq!(rev, [6, 5, 4, 2, 1]);
+            let (key, val) = ht.iter().next_zval().unwrap();
+            assert_eq!((key.long(), val.long()), (Some(0), Some(1)));
+
+            ht.insert("name", "value").unwrap();
+            assert!(!ht.is_packed());
+            assert_eq!(ht.iter().count(), 6);
+        });
+    }
+
+    #[test]
+    fn test_keys() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.push("a").unwrap();
+            ht.insert("name", "b").unwrap();
+            ht.insert(-3, "c").unwrap();
+
+            let keys: Vec<_> = ht.keys().collect();
+            assert_eq!(
+                keys,
+                [ArrayKey::Long(0), ArrayKey::Str("name"), ArrayKey::Long(-3)]
+            );
+            assert_eq!(ht.keys().len(), 3);
+            assert_eq!(ht.keys().filter_map(|key| key.as_long()).sum::<i64>(), -3);
+            assert_eq!(
+                ht.keys().rev().find_map(|key| key.as_str().map(str::len)),
+                Some(4)
+            );
+        });
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.push(1).unwrap();
+            ht.insert("two", 2).unwrap();
+            ht.push(3).unwrap();
+            ht.remove_index(0);
+
+            let mut keys = Vec::new();
+            for (key, val) in ht.iter_mut() {
+                keys.push(key.to_string());
+                val.set_long(val.long().unwrap() * 10);
+            }
+            assert_eq!(keys, ["two", "1"]);
+            assert_eq!(ht.get("two").and_then(Zval::long), Some(20));
+
+            for val in ht.values_mut().rev() {
+                val.set_bool(true);
+            }
+            assert!(ht.values().all(|val| val.bool() == Some(true)));
+            assert_eq!(ht.values_mut().len(), 2);
+
+            let mut array = Embed::eval("range(1, 3);").unwrap();
+            let ht = array.array_mut().unwrap();
+            for val in &mut *ht {
+                val.1.set_null();
+            }
+            assert!(ht.values().all(Zval::is_null));
+        });
+    }
+
+    #[test]
+    fn test_drain() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.push("first").unwrap();
+            ht.insert("key", vec![1, 2]).unwrap();
+            ht.push("last").unwrap();
+
+            let drained: Vec<_> = ht.drain().collect();
+            assert!(ht.is_empty());
+            assert_eq!(drained.len(), 3);
+            assert_eq!(drained[0].0, ArrayKey::Long(0));
+            assert_eq!(drained[0].1.str(), Some("first"));
+            assert_eq!(drained[1].0, ArrayKey::String("key".to_owned()));
+            assert_eq!(drained[1].1.array().map(ZendHashTable::len), Some(2));
+            assert_eq!(drained[2].0, ArrayKey::Long(1));
+
+            let mut array = Embed::eval("range(1, 4);").unwrap();
+            let ht = array.array_mut().unwrap();
+            let first = ht.drain().next().map(|(_, val)| val.long());
+            assert_eq!(first, Some(Some(1)));
+            assert!(ht.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_retain_packed_array() {
+        Embed::run(|| {
+            let mut array = Embed::eval("range(1, 6);").unwrap();
+            let ht = array.array_mut().unwrap();
+            ht.retain(|_, val| val.long().unwrap() > 3);
+            let kept: Vec<_> = ht.values().filter_map(Zval::long).collect();
+            assert_eq!(kept, [4, 5, 6]);
+            assert_eq!(ht.get_index(3).and_then(Zval::long), Some(4));
+        });
+    }
+}
@@ -0,0 +1,197 @@
+//! Parallel iteration over hashtables with rayon.
+
+use std::ops::Deref;
+
+use rayon::prelude::*;
+
+use super::{ArrayKey, ZendHashTable};
+use crate::types::Zval;
+
+/// A value of a hashtable which is shared with the threads of a parallel
+/// iteration, returned by [`ZendHashTable::par_iter`] and
+/// [`ZendHashTable::par_values`].
+///
+/// It dereferences to the [`Zval`], which must only be read as described by
+/// [`ZendHashTable::par_iter`].
+#[derive(Debug, Clone, Copy)]
+pub struct SharedZval<'a>(&'a Zval);
+
+// SAFETY: Shared values are only created by `par_iter` and `par_values`, whose
+// callers guarantee that they are only read.
+unsafe impl Send for SharedZval<'_> {}
+unsafe impl Sync for SharedZval<'_> {}
+
+impl<'a> SharedZval<'a> {
+    /// Returns the value if it is a string which is valid UTF-8, like
+    /// [`Zval::str`].
+    ///
+    /// Unlike [`Zval::str`], the result of the UTF-8 check is not cached in
+    /// the string, which would modify it while it is shared with other
+    /// threads.
+    #[must_use]
+    pub fn str(&self) -> Option<&'a str> {
+        self.0.zend_str()?.as_str_uncached().ok()
+    }
+}
+
+impl Deref for SharedZval<'_> {
+    type Target = Zval;
+
+    fn deref(&self) -> &Zval {
+        self.0
+    }
+}
+
+/// A hashtable which is shared with the threads of a parallel iteration.
+#[derive(Clone, Copy)]
+struct SharedTable<'a>(&'a ZendHashTable);
+
+// SAFETY: See `SharedZval`.
+unsafe impl Send for SharedTable<'_> {}
+unsafe impl Sync for SharedTable<'_> {}
+
+impl<'a> SharedTable<'a> {
+    // Closures capture the whole table through this method, rather than the
+    // reference it holds.
+    fn get(self) -> &'a ZendHashTable {
+        self.0
+    }
+}
+
+impl ZendHashTable {
+    /// Returns a parallel iterator over the keys and values of the hashtable,
+    /// which splits the elements into chunks processed by the threads of
+    /// rayon.
+    ///
+    /// The elements are collected in order, e.g. by
+    /// [`collect`](ParallelIterator::collect) into a [`Vec`].
+    ///
+    /// # Safety
+    ///
+    /// The Zend engine is single threaded, and the values of the hashtable are
+    /// shared without synchronization. While the iterator is in use:
+    ///
+    /// * The hashtable and its values must not be modified.
+    /// * The values must only be read, e.g. with [`Zval::long`],
+    ///   [`Zval::zend_str`], [`Zval::array`] or [`FromZval`] for scalars.
+    ///   Nested arrays are subject to the same rules.
+    /// * Strings must be read with [`SharedZval::str`] or as bytes.
+    ///   [`Zval::str`], [`ZendStr::as_str`] and [`FromZval`] for strings
+    ///   cache the result of the UTF-8 check in the string, which modifies
+    ///   it. Keys are checked without caching.
+    /// * The values must not be passed to the engine, e.g. by calling
+    ///   callables or methods of objects, and must not be cloned, which would
+    ///   change their reference count.
+    ///
+    /// The engine must not be used from the threads of rayon in any other way
+    /// either, as it is only initialized on the thread running PHP.
+    ///
+    /// [`FromZval`]: crate::convert::FromZval
+    /// [`ZendStr::as_str`]: crate::types::ZendStr::as_str
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.insert("a", "apple");
+    /// ht.insert("b", "banana");
+    ///
+    /// // SAFETY: The values are only read with `SharedZval::str`.
+    /// let lengths: Vec<_> = unsafe { ht.par_iter() }
+    ///     .map(|(key, val)| (key.to_string(), val.str().map_or(0, str::len)))
+    ///     .collect();
+    /// assert_eq!(lengths, [("a".to_owned(), 5), ("b".to_owned(), 6)]);
+    /// ```
+    pub unsafe fn par_iter(&self) -> impl ParallelIterator<Item = (ArrayKey<'_>, SharedZval<'_>)> {
+        let ht = SharedTable(self);
+        (0..self.nNumUsed).into_par_iter().filter_map(move |pos| {
+            // SAFETY: The position is within the used elements of the table.
+            let (key, val) = unsafe { ht.get().shared_element(pos) }?;
+            Some((key, SharedZval(unsafe { &*val })))
+        })
+    }
+
+    /// Returns a parallel iterator over the values of the hashtable, see
+    /// [`par_iter`](Self::par_iter).
+    ///
+    /// # Safety
+    ///
+    /// See [`par_iter`](Self::par_iter).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    /// use rayon::prelude::*;
+    ///
+    /// let ht = ZendHashTable::from_slice(&[1, 2, 3]);
+    ///
+    /// // SAFETY: The values are only read as integers.
+    /// let sum: i64 = unsafe { ht.par_values() }.filter_map(|val| val.long()).sum();
+    /// assert_eq!(sum, 6);
+    /// ```
+    pub unsafe fn par_values(&self) -> impl ParallelIterator<Item = SharedZval<'_>> {
+        unsafe { self.par_iter() }.map(|(_, val)| val)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_par_values() {
+        Embed::run(|| {
+            let zval = Embed::eval("range(1, 10000);").unwrap();
+            let ht = zval.array().unwrap();
+
+            let sum: i64 = unsafe { ht.par_values() }
+                .filter_map(|val| val.long())
+                .sum();
+            assert_eq!(sum, 50_005_000);
+        });
+    }
+
+    #[test]
+    fn test_par_iter() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            for i in 0..100 {
+                ht.insert(format!("key{i}").as_str(), i).unwrap();
+            }
+            assert!(ht.remove("key50").is_some());
+
+            let entries: Vec<_> = unsafe { ht.par_iter() }
+                .map(|(key, val)| (key.to_string(), val.long().unwrap()))
+                .collect();
+            assert_eq!(entries.len(), 99);
+            assert_eq!(entries[0], ("key0".to_owned(), 0));
+            assert_eq!(entries[50], ("key51".to_owned(), 51));
+        });
+    }
+
+    #[test]
+    fn test_par_iter_strings() {
+        Embed::run(|| {
+            let zval = Embed::eval("['a' => 'apple', \"\\xff\" => \"\\xfe\"];").unwrap();
+            let ht = zval.array().unwrap();
+
+            let entries: Vec<_> = unsafe { ht.par_iter() }
+                .map(|(key, val)| (key.into_owned(), val.str()))
+                .collect();
+            assert_eq!(
+                entries,
+                [
+                    (ArrayKey::String("a".to_owned()), Some("apple")),
+                    (ArrayKey::Binary(vec![0xff]), None),
+                ]
+            );
+        });
+    }
+}
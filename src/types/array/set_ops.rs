@@ -0,0 +1,178 @@
+//! Set operations on hashtables through PHP's array functions.
+
+use std::ptr;
+
+use super::ZendHashTable;
+use crate::{
+    boxed::ZBox,
+    convert::IntoZvalDyn,
+    error::{Error, Result},
+    flags::ZvalTypeFlags,
+    types::{ZendCallable, Zval},
+};
+
+impl ZendHashTable {
+    /// Returns a new hashtable with the elements of this hashtable whose
+    /// values are not present in the other hashtable, as PHP's `array_diff()`
+    /// function does.
+    ///
+    /// Values are compared by their string representation and keys are
+    /// preserved.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Callable`] - If the function could not be called.
+    /// * If the function threw an exception.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let a = ZendHashTable::from_slice(&[1, 2, 3]);
+    /// let b = ZendHashTable::from_slice(&[2]);
+    ///
+    /// let diff = a.diff(&b).unwrap();
+    /// assert_eq!(diff.len(), 2);
+    /// assert!(diff.get_index(1).is_none());
+    /// ```
+    pub fn diff(&self, other: &ZendHashTable) -> Result<ZBox<ZendHashTable>> {
+        call_array_function("array_diff", &[self, other])
+    }
+
+    /// Returns a new hashtable with the elements of this hashtable whose
+    /// values are present in the other hashtable, as PHP's
+    /// `array_intersect()` function does.
+    ///
+    /// Values are compared by their string representation and keys are
+    /// preserved.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Callable`] - If the function could not be called.
+    /// * If the function threw an exception.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let a = ZendHashTable::from_slice(&[1, 2, 3]);
+    /// let b = ZendHashTable::from_slice(&[3, 2]);
+    ///
+    /// let common = a.intersect(&b).unwrap();
+    /// assert_eq!(common.len(), 2);
+    /// assert!(common.get_index(0).is_none());
+    /// ```
+    pub fn intersect(&self, other: &ZendHashTable) -> Result<ZBox<ZendHashTable>> {
+        call_array_function("array_intersect", &[self, other])
+    }
+
+    /// Returns a new hashtable with the first element of each distinct value
+    /// of this hashtable, as PHP's `array_unique()` function does.
+    ///
+    /// Values are compared by their string representation and keys are
+    /// preserved.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Callable`] - If the function could not be called.
+    /// * If the function threw an exception.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let ht = ZendHashTable::from_slice(&["a", "b", "a"]);
+    ///
+    /// let unique = ht.unique().unwrap();
+    /// assert_eq!(unique.len(), 2);
+    /// ```
+    pub fn unique(&self) -> Result<ZBox<ZendHashTable>> {
+        call_array_function("array_unique", &[self])
+    }
+}
+
+/// Calls the given PHP function with the hashtables, returning the array it
+/// returned.
+fn call_array_function(name: &str, args: &[&ZendHashTable]) -> Result<ZBox<ZendHashTable>> {
+    let function = ZendCallable::try_from_name(name)?;
+    let args: Vec<_> = args.iter().map(|ht| borrowed_zval(ht)).collect();
+    let result = function.try_call(args.iter().map(|arg| arg as &dyn IntoZvalDyn).collect())?;
+    let ty = result.get_type();
+    // The result may be an argument, the immutable shared empty array or
+    // shared with other values, none of which are owned by the result.
+    ZendHashTable::take_from(result).ok_or(Error::ZvalConversion(ty))
+}
+
+/// Returns a zval pointing to the given hashtable, which passes it to PHP
+/// functions without copying it.
+fn borrowed_zval(ht: &ZendHashTable) -> Zval {
+    let mut zval = Zval::new();
+    // The array is not marked as refcounted, so the engine does not release
+    // it, and it is treated as immutable.
+    zval.value.arr = ptr::from_ref(ht).cast_mut();
+    zval.u1.type_info = ZvalTypeFlags::Array.bits();
+    zval
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    fn entries(ht: &ZendHashTable) -> Vec<(String, String)> {
+        ht.iter()
+            .map(|(key, val)| (key.to_string(), val.string().unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_and_intersect() {
+        Embed::run(|| {
+            let a = Embed::eval("['a' => 'x', 'b' => 'y', 5 => 'z', 6 => '1'];").unwrap();
+            let b = Embed::eval("['y', 1];").unwrap();
+            let (a, b) = (a.array().unwrap(), b.array().unwrap());
+
+            let diff = a.diff(b).unwrap();
+            assert_eq!(
+                entries(&diff),
+                [
+                    ("a".to_owned(), "x".to_owned()),
+                    ("5".to_owned(), "z".to_owned())
+                ]
+            );
+            let common = a.intersect(b).unwrap();
+            assert_eq!(
+                entries(&common),
+                [
+                    ("b".to_owned(), "y".to_owned()),
+                    ("6".to_owned(), "1".to_owned())
+                ]
+            );
+            assert_eq!(a.len(), 4);
+
+            let mut empty = a.diff(a).unwrap();
+            assert!(empty.is_empty());
+            empty.push(1).unwrap();
+        });
+    }
+
+    #[test]
+    fn test_unique() {
+        Embed::run(|| {
+            let ht = Embed::eval("[3, '3', 'a', 4, 'a'];").unwrap();
+            let unique = ht.array().unwrap().unique().unwrap();
+            let keys: Vec<_> = unique.keys().map(|key| key.to_string()).collect();
+            assert_eq!(keys, ["0", "2", "3"]);
+
+            let single = ZendHashTable::from_slice(&[1]);
+            let mut unique = single.unique().unwrap();
+            unique.push(2).unwrap();
+            assert_eq!(single.len(), 1);
+        });
+    }
+}
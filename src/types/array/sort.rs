@@ -0,0 +1,214 @@
+//! In-place sorting of hashtables through `zend_hash_sort_ex`.
+
+use std::{cell::Cell, cmp::Ordering, ffi::c_int, mem};
+
+use super::ZendHashTable;
+use crate::{
+    ffi::{Bucket, zend_hash_sort_ex, zend_sort},
+    flags::ZvalTypeFlags,
+    types::{Zval, hash_to_index},
+};
+
+/// The comparison function of the sort running on the current thread.
+struct Compare<'a>(&'a mut dyn FnMut(&Bucket, &Bucket) -> Ordering);
+
+thread_local! {
+    /// The comparison of the innermost sort running on this thread. The
+    /// lifetime is erased, [`ZendHashTable::sort_buckets`] removes it before
+    /// it ends.
+    static COMPARE: Cell<Option<Compare<'static>>> = const { Cell::new(None) };
+}
+
+impl ZendHashTable {
+    /// Sorts the hashtable by its values, which are compared like PHP's
+    /// `sort()` and `asort()` functions do.
+    ///
+    /// The sort is stable. If `renumber` is `true`, the keys are replaced
+    /// with sequential indexes as with `sort()`, otherwise the keys are kept
+    /// as with `asort()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// ht.push(3);
+    /// ht.push(1);
+    /// ht.push(2);
+    /// ht.sort_by_values(true);
+    /// assert_eq!(ht.get_index(0).and_then(|zv| zv.long()), Some(1));
+    /// ```
+    pub fn sort_by_values(&mut self, renumber: bool) {
+        self.sort_buckets(renumber, |a, b| a.val.compare(&b.val));
+    }
+
+    /// Sorts the hashtable by its keys, which are compared like PHP's
+    /// `ksort()` function does. The sort is stable.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// ht.insert("b", 1);
+    /// ht.insert("a", 2);
+    /// ht.sort_by_keys();
+    /// assert_eq!(ht.iter().next().map(|(key, _)| key.to_string()), Some("a".to_owned()));
+    /// ```
+    pub fn sort_by_keys(&mut self) {
+        self.sort_buckets(false, |a, b| key_zval(a).compare(&key_zval(b)));
+    }
+
+    /// Sorts the hashtable by its values with the given comparison function.
+    ///
+    /// The sort is stable. If `renumber` is `true`, the keys are replaced
+    /// with sequential indexes as with `usort()`, otherwise the keys are kept
+    /// as with `uasort()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new();
+    ///
+    /// ht.push("ccc");
+    /// ht.push("a");
+    /// ht.push("bb");
+    /// ht.sort_by(true, |a, b| a.str().map(str::len).cmp(&b.str().map(str::len)));
+    /// assert_eq!(ht.get_index(0).and_then(|zv| zv.str()), Some("a"));
+    /// ```
+    pub fn sort_by<F>(&mut self, renumber: bool, mut compare: F)
+    where
+        F: FnMut(&Zval, &Zval) -> Ordering,
+    {
+        self.sort_buckets(renumber, |a, b| compare(&a.val, &b.val));
+    }
+
+    /// Sorts the buckets of the hashtable with the given comparison function,
+    /// falling back to the original order of the buckets for equal ones.
+    fn sort_buckets<F>(&mut self, renumber: bool, mut compare: F)
+    where
+        F: FnMut(&Bucket, &Bucket) -> Ordering,
+    {
+        let compare = Compare(&mut compare);
+        // SAFETY: The comparison is removed before this function returns, which
+        // happens before its lifetime ends.
+        let compare = unsafe { mem::transmute::<Compare<'_>, Compare<'static>>(compare) };
+        let previous = COMPARE.replace(Some(compare));
+        unsafe { zend_hash_sort_ex(self, Some(zend_sort), Some(compare_buckets), renumber) };
+        COMPARE.set(previous);
+    }
+}
+
+/// Returns the key of the bucket as a zval, which borrows string keys.
+fn key_zval(bucket: &Bucket) -> Zval {
+    let mut zval = Zval::new();
+    if bucket.key.is_null() {
+        zval.set_long(hash_to_index(bucket.h));
+    } else {
+        // The string is not marked as refcounted, so dropping the zval does
+        // not release it.
+        zval.value.str_ = bucket.key;
+        zval.u1.type_info = ZvalTypeFlags::String.bits();
+    }
+    zval
+}
+
+unsafe extern "C" fn compare_buckets(a: *mut Bucket, b: *mut Bucket) -> c_int {
+    let Some(compare) = COMPARE.take() else {
+        return 0;
+    };
+    // SAFETY: The buckets are passed by `zend_hash_sort_ex`.
+    let (a, b) = unsafe { (&*a, &*b) };
+    // `zend_hash_sort_ex` stores the original position of the buckets in the
+    // extra space of their values to allow stable sorting.
+    let ordering = (compare.0)(a, b).then_with(|| unsafe { a.val.u2.extra.cmp(&b.val.u2.extra) });
+    COMPARE.set(Some(compare));
+    match ordering {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{embed::Embed, types::ArrayKey};
+
+    fn entries(ht: &ZendHashTable) -> Vec<(String, Option<i64>)> {
+        ht.iter()
+            .map(|(key, val)| {
+                (
+                    key.to_string(),
+                    val.long().or_else(|| val.str()?.parse().ok()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_by_values() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.insert("c", 3).unwrap();
+            ht.insert("a", "10").unwrap();
+            ht.insert("b", 2).unwrap();
+
+            ht.sort_by_values(false);
+            assert_eq!(
+                entries(&ht),
+                [
+                    ("b".to_owned(), Some(2)),
+                    ("c".to_owned(), Some(3)),
+                    ("a".to_owned(), Some(10)),
+                ]
+            );
+
+            ht.sort_by_values(true);
+            let keys: Vec<_> = ht.iter().map(|(key, _)| key).collect();
+            assert_eq!(
+                keys,
+                [ArrayKey::Long(0), ArrayKey::Long(1), ArrayKey::Long(2)]
+            );
+        });
+    }
+
+    #[test]
+    fn test_sort_by_keys() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.insert(10, "ten").unwrap();
+            ht.insert("b", "bee").unwrap();
+            ht.insert(2, "two").unwrap();
+            ht.insert("a", "ay").unwrap();
+
+            ht.sort_by_keys();
+            let keys: Vec<_> = ht.iter().map(|(key, _)| key.to_string()).collect();
+            assert_eq!(keys, ["2", "10", "a", "b"]);
+        });
+    }
+
+    #[test]
+    fn test_sort_by_is_stable() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            for word in ["bb", "a", "cc", "d", "ee"] {
+                ht.push(word).unwrap();
+            }
+
+            ht.sort_by(true, |a, b| {
+                a.str().map(str::len).cmp(&b.str().map(str::len))
+            });
+            let words: Vec<_> = ht.values().filter_map(Zval::string).collect();
+            assert_eq!(words, ["a", "d", "bb", "cc", "ee"]);
+        });
+    }
+}
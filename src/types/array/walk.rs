@@ -0,0 +1,186 @@
+//! Traversal of hashtables through `zend_hash_apply_with_argument`.
+
+use std::ffi::{c_int, c_void};
+
+use super::{ArrayKey, ZendHashTable};
+use crate::{
+    ffi::{
+        ZEND_HASH_APPLY_KEEP, ZEND_HASH_APPLY_REMOVE, ZEND_HASH_APPLY_STOP,
+        zend_hash_apply_with_argument,
+    },
+    types::Zval,
+};
+
+/// What to do with an element visited by [`ZendHashTable::walk`] or
+/// [`ZendHashTable::walk_with_key`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WalkAction {
+    /// Keeps the element and continues with the next one.
+    #[default]
+    Keep,
+    /// Removes the element and continues with the next one.
+    Remove,
+    /// Keeps the element and stops the walk.
+    Stop,
+    /// Removes the element and stops the walk.
+    RemoveAndStop,
+}
+
+impl WalkAction {
+    /// Returns the `ZEND_HASH_APPLY_*` flags of the action.
+    #[allow(clippy::cast_possible_wrap)]
+    fn flags(self) -> c_int {
+        let flags = match self {
+            Self::Keep => ZEND_HASH_APPLY_KEEP,
+            Self::Remove => ZEND_HASH_APPLY_REMOVE,
+            Self::Stop => ZEND_HASH_APPLY_STOP,
+            Self::RemoveAndStop => ZEND_HASH_APPLY_REMOVE | ZEND_HASH_APPLY_STOP,
+        };
+        flags as c_int
+    }
+}
+
+/// The closure visiting the elements of a walk.
+type Visit<'a> = &'a mut dyn FnMut(*mut Zval) -> WalkAction;
+
+impl ZendHashTable {
+    /// Visits the values of the hashtable in order with the given closure,
+    /// which decides whether to remove each value and whether to continue.
+    ///
+    /// The elements are traversed by the engine, in the same way as by PHP's
+    /// own array functions, so elements may be removed during the walk.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::{WalkAction, ZendHashTable};
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.push(1);
+    /// ht.push(-2);
+    /// ht.push(3);
+    ///
+    /// ht.walk(|val| match val.long() {
+    ///     Some(n) if n < 0 => WalkAction::Remove,
+    ///     _ => WalkAction::Keep,
+    /// });
+    /// assert_eq!(ht.len(), 2);
+    /// ```
+    pub fn walk<F>(&mut self, mut visit: F)
+    where
+        F: FnMut(&mut Zval) -> WalkAction,
+    {
+        // SAFETY: The engine passes the values of the hashtable.
+        self.apply(&mut |val| visit(unsafe { &mut *val }));
+    }
+
+    /// Visits the keys and values of the hashtable in order with the given
+    /// closure, see [`walk`](Self::walk).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::{WalkAction, ZendHashTable};
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.insert("a", 1);
+    /// ht.insert("stop", 2);
+    /// ht.insert("b", 3);
+    ///
+    /// let mut seen = vec![];
+    /// ht.walk_with_key(|key, _| {
+    ///     seen.push(key.to_string());
+    ///     if key.as_str() == Some("stop") {
+    ///         WalkAction::Stop
+    ///     } else {
+    ///         WalkAction::Keep
+    ///     }
+    /// });
+    /// assert_eq!(seen, ["a", "stop"]);
+    /// ```
+    pub fn walk_with_key<F>(&mut self, mut visit: F)
+    where
+        F: FnMut(ArrayKey<'_>, &mut Zval) -> WalkAction,
+    {
+        let ht: *const Self = self;
+        self.apply(&mut |val| {
+            // SAFETY: The engine passes the values of the hashtable, and the key
+            // is not modified while the closure runs.
+            let key = unsafe { (*ht).value_key(val) };
+            visit(key, unsafe { &mut *val })
+        });
+    }
+
+    fn apply(&mut self, mut visit: Visit<'_>) {
+        unsafe {
+            zend_hash_apply_with_argument(self, Some(apply_element), (&raw mut visit).cast());
+        }
+    }
+}
+
+unsafe extern "C" fn apply_element(val: *mut Zval, argument: *mut c_void) -> c_int {
+    // SAFETY: The argument is the closure passed by `ZendHashTable::apply`.
+    let visit = unsafe { &mut *argument.cast::<Visit<'_>>() };
+    visit(val).flags()
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_walk() {
+        Embed::run(|| {
+            let mut array = Embed::eval("range(1, 6);").unwrap();
+            let ht = array.array_mut().unwrap();
+
+            ht.walk(|val| {
+                let n = val.long().unwrap();
+                val.set_long(n * 10);
+                if n % 2 == 0 {
+                    WalkAction::Remove
+                } else {
+                    WalkAction::Keep
+                }
+            });
+            let values: Vec<_> = ht.values().filter_map(Zval::long).collect();
+            assert_eq!(values, [10, 30, 50]);
+
+            let mut visited = 0;
+            ht.walk(|_| {
+                visited += 1;
+                WalkAction::RemoveAndStop
+            });
+            assert_eq!(visited, 1);
+            assert_eq!(ht.len(), 2);
+        });
+    }
+
+    #[test]
+    fn test_walk_with_key() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.insert("a", 1).unwrap();
+            ht.insert(7, 2).unwrap();
+            ht.insert("b", 3).unwrap();
+            ht.insert("c", 4).unwrap();
+
+            let mut keys = vec![];
+            ht.walk_with_key(|key, _| {
+                let action = match key {
+                    ArrayKey::Long(_) => WalkAction::Remove,
+                    _ if key.as_str() == Some("b") => WalkAction::Stop,
+                    _ => WalkAction::Keep,
+                };
+                keys.push(key.to_string());
+                action
+            });
+            assert_eq!(keys, ["a", "7", "b"]);
+            assert_eq!(ht.len(), 3);
+            assert!(ht.get(7).is_none());
+        });
+    }
+}
@@ -0,0 +1,450 @@
+//! Conversions of integers which may not fit into a [`ZendLong`], such as
+//! `i128` and `u128`.
+//!
+//! Integers which fit are converted into PHP integers. Otherwise, they are
+//! converted according to an [`OverflowPolicy`], by default into a `GMP`
+//! object if ext/gmp is loaded, and into a numeric string otherwise. When
+//! converting from a zval, integers, numeric strings and `GMP` objects are
+//! accepted.
+//!
+//! A `u64` is converted with [`Error::IntegerOverflow`] if it does not fit.
+//! Wrapping it into a [`PhpU64`] selects another policy through one of the
+//! strategies in [`overflow`].
+
+use std::{fmt::Display, marker::PhantomData};
+
+use crate::{
+    class::ExternClassEntry,
+    convert::{FromZval, IntoZval},
+    error::{Error, Result},
+    flags::DataType,
+    types::{ZendCallable, ZendLong, Zval},
+};
+
+static GMP: ExternClassEntry = ExternClassEntry::new("GMP");
+
+/// How integers which do not fit into a [`ZendLong`] are converted into PHP
+/// values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// A `GMP` object if ext/gmp is loaded, otherwise a numeric string.
+    #[default]
+    Auto,
+    /// A `GMP` object, failing if ext/gmp is not loaded.
+    Gmp,
+    /// A numeric string.
+    String,
+    /// A float, losing precision like integer overflows in PHP do.
+    Double,
+    /// The closest integer, i.e. [`ZendLong::MAX`] or [`ZendLong::MIN`].
+    Saturate,
+    /// No conversion, failing with [`Error::IntegerOverflow`].
+    Error,
+}
+
+impl OverflowPolicy {
+    /// Sets the zval to the integer, converting it according to the policy if
+    /// it does not fit into a [`ZendLong`].
+    fn set_zval<T>(self, zv: &mut Zval, val: T, persistent: bool) -> Result<()>
+    where
+        T: Display + TryInto<ZendLong> + Copy,
+    {
+        if let Ok(long) = val.try_into() {
+            zv.set_long(long);
+            return Ok(());
+        }
+        set_overflowing(zv, &val.to_string(), self, persistent)
+    }
+}
+
+/// Sets the zval to an integer given as decimal digits which does not fit
+/// into a [`ZendLong`].
+fn set_overflowing(
+    zv: &mut Zval,
+    digits: &str,
+    policy: OverflowPolicy,
+    persistent: bool,
+) -> Result<()> {
+    match policy {
+        OverflowPolicy::Auto => match ZendCallable::try_from_name("gmp_init") {
+            Ok(gmp_init) => *zv = gmp_init.try_call(vec![&digits])?,
+            Err(_) => zv.set_string(digits, persistent)?,
+        },
+        OverflowPolicy::Gmp => {
+            *zv = ZendCallable::try_from_name("gmp_init")?.try_call(vec![&digits])?;
+        }
+        OverflowPolicy::String => zv.set_string(digits, persistent)?,
+        OverflowPolicy::Double => {
+            zv.set_double(digits.parse::<f64>().map_err(|_| Error::IntegerOverflow)?);
+        }
+        OverflowPolicy::Saturate => zv.set_long(if digits.starts_with('-') {
+            ZendLong::MIN
+        } else {
+            ZendLong::MAX
+        }),
+        OverflowPolicy::Error => return Err(Error::IntegerOverflow),
+    }
+    Ok(())
+}
+
+/// Returns the decimal digits of an integer, numeric string or `GMP` object.
+fn digits(zval: &Zval) -> Option<String> {
+    let zval = zval.dereference();
+    if let Some(long) = zval.long() {
+        return Some(long.to_string());
+    }
+    if let Some(str) = zval.str() {
+        return Some(
+            str.trim_matches(|c: char| c.is_ascii_whitespace())
+                .to_owned(),
+        );
+    }
+    let obj = zval.object()?;
+    if !obj.instance_of(GMP.get().ok()?) {
+        return None;
+    }
+    zval.cast_to_string().ok()?.as_str().ok().map(str::to_owned)
+}
+
+impl Zval {
+    /// Sets the value of the zval as an `i128`, converting it according to
+    /// the given policy if it does not fit into a [`ZendLong`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the policy fails to convert the integer.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::{OverflowPolicy, Zval};
+    ///
+    /// let mut zv = Zval::new();
+    /// zv.set_i128(i128::MAX, OverflowPolicy::String).unwrap();
+    /// assert_eq!(zv.str(), Some("170141183460469231731687303715884105727"));
+    /// ```
+    pub fn set_i128(&mut self, val: i128, policy: OverflowPolicy) -> Result<()> {
+        policy.set_zval(self, val, false)
+    }
+
+    /// Sets the value of the zval as an `u128`, converting it according to
+    /// the given policy if it does not fit into a [`ZendLong`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the policy fails to convert the integer.
+    pub fn set_u128(&mut self, val: u128, policy: OverflowPolicy) -> Result<()> {
+        policy.set_zval(self, val, false)
+    }
+}
+
+/// Strategies selecting the [`OverflowPolicy`] of a [`PhpU64`].
+pub mod overflow {
+    use super::OverflowPolicy;
+
+    /// Selects the [`OverflowPolicy`] used to convert a
+    /// [`PhpU64`](super::PhpU64) into a zval.
+    pub trait OverflowStrategy {
+        /// The policy used by the strategy.
+        const POLICY: OverflowPolicy;
+    }
+
+    macro_rules! strategy {
+        ($(#[$attr: meta])* $name: ident) => {
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct $name;
+
+            impl OverflowStrategy for $name {
+                const POLICY: OverflowPolicy = OverflowPolicy::$name;
+            }
+        };
+    }
+
+    strategy!(
+        /// Uses [`OverflowPolicy::Auto`].
+        Auto
+    );
+    strategy!(
+        /// Uses [`OverflowPolicy::Gmp`].
+        Gmp
+    );
+    strategy!(
+        /// Uses [`OverflowPolicy::String`].
+        String
+    );
+    strategy!(
+        /// Uses [`OverflowPolicy::Double`].
+        Double
+    );
+    strategy!(
+        /// Uses [`OverflowPolicy::Saturate`].
+        Saturate
+    );
+    strategy!(
+        /// Uses [`OverflowPolicy::Error`], like a plain `u64`.
+        Error
+    );
+}
+
+use overflow::OverflowStrategy;
+
+/// A `u64` which is converted into a zval according to the given
+/// [`overflow`] strategy if it does not fit into a [`ZendLong`], e.g. 64-bit
+/// identifiers which should become strings instead of failing.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::{
+///     convert::IntoZval,
+///     types::{PhpU64, overflow},
+/// };
+///
+/// let id = PhpU64::<overflow::String>::new(u64::MAX);
+/// let zv = id.into_zval(false).unwrap();
+/// assert_eq!(zv.str(), Some("18446744073709551615"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PhpU64<S = overflow::Error> {
+    value: u64,
+    strategy: PhantomData<S>,
+}
+
+impl<S> PhpU64<S> {
+    /// Wraps the given integer.
+    #[must_use]
+    pub const fn new(value: u64) -> Self {
+        Self {
+            value,
+            strategy: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped integer.
+    #[must_use]
+    pub const fn get(self) -> u64 {
+        self.value
+    }
+}
+
+impl<S> From<u64> for PhpU64<S> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<S> From<usize> for PhpU64<S> {
+    fn from(value: usize) -> Self {
+        Self::new(value as u64)
+    }
+}
+
+impl<S> From<PhpU64<S>> for u64 {
+    fn from(value: PhpU64<S>) -> Self {
+        value.value
+    }
+}
+
+impl<S: OverflowStrategy> IntoZval for PhpU64<S> {
+    const TYPE: DataType = match S::POLICY {
+        OverflowPolicy::Saturate | OverflowPolicy::Error => DataType::Long,
+        _ => DataType::Mixed,
+    };
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        S::POLICY.set_zval(zv, self.value, persistent)
+    }
+}
+
+impl<S: OverflowStrategy> FromZval<'_> for PhpU64<S> {
+    const TYPE: DataType = <Self as IntoZval>::TYPE;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        digits(zval)?.parse().ok().map(Self::new)
+    }
+}
+
+macro_rules! big_integer {
+    ($type: ty) => {
+        impl IntoZval for $type {
+            const TYPE: DataType = DataType::Mixed;
+            const NULLABLE: bool = false;
+
+            fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+                OverflowPolicy::default().set_zval(zv, self, persistent)
+            }
+        }
+
+        impl FromZval<'_> for $type {
+            const TYPE: DataType = DataType::Mixed;
+
+            fn from_zval(zval: &Zval) -> Option<Self> {
+                digits(zval)?.parse().ok()
+            }
+        }
+    };
+}
+
+big_integer!(i128);
+big_integer!(u128);
+
+#[cfg(feature = "num-bigint")]
+mod num_bigint_impls {
+    use num_bigint::{BigInt, BigUint};
+
+    use super::{OverflowPolicy, digits, set_overflowing};
+    use crate::{
+        convert::{FromZval, IntoZval},
+        error::Result,
+        flags::DataType,
+        types::{ZendLong, Zval},
+    };
+
+    impl Zval {
+        /// Sets the value of the zval as a [`BigInt`], converting it according
+        /// to the given policy if it does not fit into a [`ZendLong`].
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the policy fails to convert the integer.
+        #[cfg_attr(docs, doc(cfg(feature = "num-bigint")))]
+        pub fn set_big_int(&mut self, val: &BigInt, policy: OverflowPolicy) -> Result<()> {
+            set_big_int(self, val, policy, false)
+        }
+    }
+
+    fn set_big_int(
+        zv: &mut Zval,
+        val: &BigInt,
+        policy: OverflowPolicy,
+        persistent: bool,
+    ) -> Result<()> {
+        if let Ok(long) = ZendLong::try_from(val) {
+            zv.set_long(long);
+            return Ok(());
+        }
+        set_overflowing(zv, &val.to_string(), policy, persistent)
+    }
+
+    impl IntoZval for BigInt {
+        const TYPE: DataType = DataType::Mixed;
+        const NULLABLE: bool = false;
+
+        fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+            set_big_int(zv, &self, OverflowPolicy::default(), persistent)
+        }
+    }
+
+    impl IntoZval for BigUint {
+        const TYPE: DataType = DataType::Mixed;
+        const NULLABLE: bool = false;
+
+        fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+            set_big_int(zv, &self.into(), OverflowPolicy::default(), persistent)
+        }
+    }
+
+    impl FromZval<'_> for BigInt {
+        const TYPE: DataType = DataType::Mixed;
+
+        fn from_zval(zval: &Zval) -> Option<Self> {
+            digits(zval)?.parse().ok()
+        }
+    }
+
+    impl FromZval<'_> for BigUint {
+        const TYPE: DataType = DataType::Mixed;
+
+        fn from_zval(zval: &Zval) -> Option<Self> {
+            digits(zval)?.parse().ok()
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    const BIG: &str = "170141183460469231731687303715884105727";
+
+    #[test]
+    fn test_into_zval() {
+        Embed::run(|| {
+            let zval = 42i128.into_zval(false).unwrap();
+            assert_eq!(zval.long(), Some(42));
+
+            let mut zval = Zval::new();
+            zval.set_i128(i128::MAX, OverflowPolicy::String).unwrap();
+            assert_eq!(zval.str(), Some(BIG));
+            zval.set_u128(u128::MAX, OverflowPolicy::Double).unwrap();
+            assert_eq!(zval.double(), Some(3.402_823_669_209_385e38));
+            assert!(matches!(
+                zval.set_i128(i128::MIN, OverflowPolicy::Error),
+                Err(Error::IntegerOverflow)
+            ));
+
+            let zval = i128::MAX.into_zval(false).unwrap();
+            if ZendCallable::try_from_name("gmp_init").is_ok() {
+                assert_eq!(zval.object().unwrap().get_class_name().unwrap(), "GMP");
+            } else {
+                assert_eq!(zval.str(), Some(BIG));
+            }
+            assert_eq!(i128::from_zval(&zval), Some(i128::MAX));
+        });
+    }
+
+    #[test]
+    fn test_php_u64() {
+        Embed::run(|| {
+            let zval = PhpU64::<overflow::String>::new(u64::MAX)
+                .into_zval(false)
+                .unwrap();
+            assert_eq!(zval.str(), Some("18446744073709551615"));
+            assert_eq!(
+                PhpU64::<overflow::String>::from_zval(&zval).map(PhpU64::get),
+                Some(u64::MAX)
+            );
+
+            let zval = PhpU64::<overflow::Saturate>::new(u64::MAX)
+                .into_zval(false)
+                .unwrap();
+            assert_eq!(zval.long(), Some(ZendLong::MAX));
+            let zval = PhpU64::<overflow::Double>::new(u64::MAX)
+                .into_zval(false)
+                .unwrap();
+            assert_eq!(zval.double(), Some(1.844_674_407_370_955_2e19));
+            assert!(matches!(
+                PhpU64::<overflow::Error>::new(u64::MAX).into_zval(false),
+                Err(Error::IntegerOverflow)
+            ));
+
+            let zval = PhpU64::<overflow::Error>::from(5u64)
+                .into_zval(false)
+                .unwrap();
+            assert_eq!(zval.long(), Some(5));
+        });
+    }
+
+    #[test]
+    fn test_from_zval() {
+        Embed::run(|| {
+            let zval = Embed::eval("-5;").unwrap();
+            assert_eq!(i128::from_zval(&zval), Some(-5));
+            assert_eq!(u128::from_zval(&zval), None);
+
+            let zval = Embed::eval("' 340282366920938463463374607431768211455';").unwrap();
+            assert_eq!(u128::from_zval(&zval), Some(u128::MAX));
+            assert_eq!(i128::from_zval(&zval), None);
+
+            let zval = Embed::eval("'1.5';").unwrap();
+            assert_eq!(i128::from_zval(&zval), None);
+            let zval = Embed::eval("new stdClass;").unwrap();
+            assert_eq!(i128::from_zval(&zval), None);
+        });
+    }
+}
@@ -1,16 +1,23 @@
 //! Types related to callables in PHP (anonymous functions, functions, etc).
 
-use std::{convert::TryFrom, ops::Deref, ptr};
+use std::{
+    convert::TryFrom,
+    mem::ManuallyDrop,
+    ops::Deref,
+    ptr,
+    thread::{self, ThreadId},
+};
 
 use crate::{
-    convert::{FromZval, IntoZvalDyn},
+    boxed::ZBox,
+    convert::{FromZval, IntoZval, IntoZvalDyn},
     error::{Error, Result},
-    ffi::_call_user_function_impl,
+    ffi::{_call_user_function_impl, HashTable},
     flags::DataType,
-    zend::ExecutorGlobals,
+    zend::{ExecutorGlobals, request},
 };
 
-use super::Zval;
+use super::{ArrayKey, ZendHashTable, Zval};
 
 /// Acts as a wrapper around a callable [`Zval`]. Allows the owner to call the
 /// [`Zval`] as if it was a PHP function through the [`try_call`] method.
@@ -116,6 +123,46 @@ impl<'a> ZendCallable<'a> {
     #[allow(clippy::inline_always)]
     #[inline(always)]
     pub fn try_call(&self, params: Vec<&dyn IntoZvalDyn>) -> Result<Zval> {
+        self.call(params, ptr::null_mut())
+    }
+
+    /// Attempts to call the callable with a list of named arguments, as if
+    /// it was called as `f(name: $value)` in PHP. Parameters which are not
+    /// passed receive their default value.
+    ///
+    /// # Parameters
+    ///
+    /// * `params` - A list of parameter names and values to call the function
+    ///   with.
+    ///
+    /// # Returns
+    ///
+    /// Returns the result wrapped in [`Ok`] upon success.
+    ///
+    /// # Errors
+    ///
+    /// * If calling the callable fails, or an exception is thrown, an [`Err`]
+    ///   is returned. This includes passing an unknown parameter name, or
+    ///   omitting a parameter without a default value.
+    /// * If a parameter could not be converted to a zval.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendCallable;
+    ///
+    /// let str_pad = ZendCallable::try_from_name("str_pad").unwrap();
+    /// let result = str_pad
+    ///     .try_call_named(&[("string", &"5"), ("length", &3), ("pad_string", &"0")])
+    ///     .unwrap();
+    /// assert_eq!(result.str(), Some("500"));
+    /// ```
+    pub fn try_call_named(&self, params: &[(&str, &dyn IntoZvalDyn)]) -> Result<Zval> {
+        let mut named = named_params(params)?;
+        self.call(vec![], &raw mut *named)
+    }
+
+    fn call(&self, params: Vec<&dyn IntoZvalDyn>, named: *mut HashTable) -> Result<Zval> {
         if !self.0.is_callable() {
             return Err(Error::Callable);
         }
@@ -136,7 +183,7 @@ impl<'a> ZendCallable<'a> {
                 &raw mut retval,
                 len.try_into()?,
                 packed.as_ptr().cast_mut(),
-                ptr::null_mut(),
+                named,
             )
         };
 
@@ -150,6 +197,16 @@ impl<'a> ZendCallable<'a> {
     }
 }
 
+/// Builds the table of named arguments passed to the engine when calling a
+/// function.
+pub(crate) fn named_params(params: &[(&str, &dyn IntoZvalDyn)]) -> Result<ZBox<ZendHashTable>> {
+    let mut ht = ZendHashTable::with_capacity(params.len().try_into()?);
+    for (name, val) in params {
+        ht.insert(ArrayKey::Str(name), val.as_zval(false)?)?;
+    }
+    Ok(ht)
+}
+
 impl<'a> FromZval<'a> for ZendCallable<'a> {
     const TYPE: DataType = DataType::Callable;
 
@@ -158,6 +215,19 @@ impl<'a> FromZval<'a> for ZendCallable<'a> {
     }
 }
 
+impl IntoZval for ZendCallable<'_> {
+    const TYPE: DataType = DataType::Callable;
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        *zv = match self.0 {
+            OwnedZval::Reference(callable) => callable.shallow_clone(),
+            OwnedZval::Owned(callable) => callable,
+        };
+        Ok(())
+    }
+}
+
 impl TryFrom<Zval> for ZendCallable<'_> {
     type Error = Error;
 
@@ -166,6 +236,153 @@ impl TryFrom<Zval> for ZendCallable<'_> {
     }
 }
 
+/// An owned callable which is not bound to a lifetime, so it can be stored in
+/// Rust structs or globals and called after the function it was passed to
+/// has returned.
+///
+/// The callable holds a reference to the underlying zval. PHP values belong
+/// to the thread and request which created them, so the callable can only be
+/// called on that thread while the request is running. Otherwise
+/// [`Error::StaleCallable`] is returned. Dropping the callable on another
+/// thread or after the request has ended forgets the reference instead of
+/// releasing it, as PHP frees all values of a request once it ends.
+///
+/// The callable is [`Send`], allowing it to be moved through queues of other
+/// threads, as long as it is called back on the thread which created it.
+///
+/// Requests are only tracked for extensions built with a
+/// [`ModuleBuilder`](crate::builders::ModuleBuilder), e.g. through
+/// `#[php_module]`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use std::cell::RefCell;
+/// use ext_php_rs::{prelude::*, types::PersistentCallable};
+///
+/// thread_local! {
+///     static LISTENERS: RefCell<Vec<PersistentCallable>> = const { RefCell::new(Vec::new()) };
+/// }
+///
+/// #[php_function]
+/// pub fn on_event(listener: PersistentCallable) {
+///     LISTENERS.with_borrow_mut(|listeners| listeners.push(listener));
+/// }
+///
+/// #[php_function]
+/// pub fn emit_event(name: &str) -> PhpResult<()> {
+///     LISTENERS.with_borrow(|listeners| {
+///         for listener in listeners {
+///             listener.try_call(vec![&name])?;
+///         }
+///         Ok(())
+///     })
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct PersistentCallable {
+    callable: ManuallyDrop<Zval>,
+    thread: ThreadId,
+    request: u64,
+}
+
+// SAFETY: The zval is only accessed on the thread which created it.
+unsafe impl Send for PersistentCallable {}
+
+impl PersistentCallable {
+    /// Attempts to create a new [`PersistentCallable`], adding a reference to
+    /// the given zval.
+    ///
+    /// # Parameters
+    ///
+    /// * `callable` - The underlying [`Zval`] that is callable.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Callable`] - If the zval was not callable.
+    pub fn new(callable: &Zval) -> Result<Self> {
+        if !callable.is_callable() {
+            return Err(Error::Callable);
+        }
+        Ok(Self {
+            callable: ManuallyDrop::new(callable.shallow_clone()),
+            thread: thread::current().id(),
+            request: request::current_request(),
+        })
+    }
+
+    /// Returns whether the callable can be used, which is the case on the
+    /// thread which created it while the request which created it is running.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.thread == thread::current().id() && self.request == request::current_request()
+    }
+
+    /// Returns a [`ZendCallable`] referencing the underlying zval.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::StaleCallable`] - If the callable is not valid anymore.
+    /// * [`Error::Callable`] - If the zval is not callable anymore.
+    pub fn callable(&self) -> Result<ZendCallable<'_>> {
+        if !self.is_valid() {
+            return Err(Error::StaleCallable);
+        }
+        ZendCallable::new(&self.callable)
+    }
+
+    /// Attempts to call the callable with a list of arguments to pass to the
+    /// function. See [`ZendCallable::try_call`].
+    ///
+    /// # Parameters
+    ///
+    /// * `params` - A list of parameters to call the function with.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::StaleCallable`] - If the callable is not valid anymore.
+    /// * If calling the callable fails, or an exception is thrown, an [`Err`]
+    ///   is returned.
+    pub fn try_call(&self, params: Vec<&dyn IntoZvalDyn>) -> Result<Zval> {
+        self.callable()?.try_call(params)
+    }
+}
+
+impl Drop for PersistentCallable {
+    fn drop(&mut self) {
+        if self.is_valid() {
+            // SAFETY: The zval is not used after being dropped.
+            unsafe { ManuallyDrop::drop(&mut self.callable) };
+        }
+    }
+}
+
+impl FromZval<'_> for PersistentCallable {
+    const TYPE: DataType = DataType::Callable;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        Self::new(zval).ok()
+    }
+}
+
+impl IntoZval for PersistentCallable {
+    const TYPE: DataType = DataType::Callable;
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        if !self.is_valid() {
+            return Err(Error::StaleCallable);
+        }
+        // The reference held by `self` is moved into the zval.
+        let mut this = ManuallyDrop::new(self);
+        // SAFETY: `this` is not dropped, so the zval is only moved out once.
+        *zv = unsafe { ManuallyDrop::take(&mut this.callable) };
+        Ok(())
+    }
+}
+
 /// A container for a zval. Either contains a reference to a zval or an owned
 /// zval.
 #[derive(Debug)]
@@ -190,3 +407,54 @@ impl Deref for OwnedZval<'_> {
         self.as_ref()
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_persistent_callable() {
+        let callable = Embed::run(|| {
+            let closure = Embed::eval("fn ($a, $b) => $a + $b;").unwrap();
+            let callable = PersistentCallable::new(&closure).unwrap();
+            drop(closure);
+
+            assert!(callable.is_valid());
+            let result = callable.try_call(vec![&1, &2]).unwrap();
+            assert_eq!(result.long(), Some(3));
+
+            assert!(PersistentCallable::new(&Zval::new()).is_err());
+            Some(callable)
+        })
+        .unwrap();
+
+        assert!(!callable.is_valid());
+        assert!(matches!(
+            callable.try_call(vec![]),
+            Err(Error::StaleCallable)
+        ));
+    }
+
+    #[test]
+    fn test_try_call_named() {
+        Embed::run(|| {
+            let str_pad = ZendCallable::try_from_name("str_pad").unwrap();
+            let result = str_pad
+                .try_call_named(&[("pad_type", &0), ("length", &4), ("string", &"ab")])
+                .unwrap();
+            assert_eq!(result.str(), Some("  ab"));
+
+            let obj = Embed::eval(
+                "new class { function join($a, $b = 'b', $c = 'c') { return $a . $b . $c; } };",
+            )
+            .unwrap();
+            let result = obj
+                .try_call_method_named("JOIN", &[("c", &"z"), ("a", &"x")])
+                .unwrap();
+            assert_eq!(result.str(), Some("xbz"));
+        });
+    }
+}
@@ -0,0 +1,158 @@
+//! Conversions between chrono's date and time types and PHP's
+//! `DateTimeImmutable`.
+//!
+//! Dates are exchanged with PHP with a precision of microseconds. Dates with
+//! a time zone keep their UTC offset, while a [`NaiveDateTime`] is read and
+//! written as the wall-clock time of the object, which is in the default time
+//! zone of PHP when converting into a zval.
+
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+
+use crate::{
+    class::ExternClassEntry,
+    convert::{FromZval, IntoZval},
+    error::Result,
+    flags::DataType,
+    types::Zval,
+};
+
+static DATE_TIME_IMMUTABLE: ExternClassEntry = ExternClassEntry::new("DateTimeImmutable");
+static DATE_TIME_INTERFACE: ExternClassEntry = ExternClassEntry::new("DateTimeInterface");
+
+/// The format of `DateTimeInterface::format()` matching RFC 3339 with
+/// microseconds.
+const RFC3339_FORMAT: &str = "Y-m-d\\TH:i:s.uP";
+/// The format of `DateTimeInterface::format()` for the wall-clock time.
+const NAIVE_FORMAT: &str = "Y-m-d\\TH:i:s.u";
+
+/// Creates a `DateTimeImmutable` from a string understood by its constructor.
+fn immutable(time: &str, zv: &mut Zval, persistent: bool) -> Result<()> {
+    DATE_TIME_IMMUTABLE
+        .instantiate(vec![&time])?
+        .set_zval(zv, persistent)
+}
+
+/// Formats an instance of `DateTimeInterface` with the given format.
+fn format_object(zval: &Zval, format: &str) -> Option<String> {
+    let obj = zval.object()?;
+    let ce = DATE_TIME_INTERFACE.get().ok()?;
+    if !obj.instance_of(ce) {
+        return None;
+    }
+    obj.try_call_method("format", vec![&format]).ok()?.string()
+}
+
+impl<Tz: TimeZone> IntoZval for DateTime<Tz>
+where
+    Tz::Offset: Display,
+{
+    const TYPE: DataType = DataType::Object(Some("DateTimeImmutable"));
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        immutable(
+            &self.to_rfc3339_opts(SecondsFormat::Micros, false),
+            zv,
+            persistent,
+        )
+    }
+}
+
+impl IntoZval for NaiveDateTime {
+    const TYPE: DataType = DataType::Object(Some("DateTimeImmutable"));
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        immutable(
+            &self.format("%Y-%m-%dT%H:%M:%S%.6f").to_string(),
+            zv,
+            persistent,
+        )
+    }
+}
+
+impl FromZval<'_> for DateTime<FixedOffset> {
+    const TYPE: DataType = DataType::Object(Some("DateTimeInterface"));
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        DateTime::parse_from_rfc3339(&format_object(zval, RFC3339_FORMAT)?).ok()
+    }
+}
+
+impl FromZval<'_> for DateTime<Utc> {
+    const TYPE: DataType = DataType::Object(Some("DateTimeInterface"));
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        DateTime::<FixedOffset>::from_zval(zval).map(|time| time.with_timezone(&Utc))
+    }
+}
+
+impl FromZval<'_> for NaiveDateTime {
+    const TYPE: DataType = DataType::Object(Some("DateTimeInterface"));
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        NaiveDateTime::parse_from_str(&format_object(zval, NAIVE_FORMAT)?, "%Y-%m-%dT%H:%M:%S%.f")
+            .ok()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use chrono::{NaiveDate, Timelike};
+
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_into_zval() {
+        Embed::run(|| {
+            let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+            let time = offset
+                .with_ymd_and_hms(2024, 2, 29, 13, 30, 15)
+                .unwrap()
+                .with_nanosecond(123_456_789)
+                .unwrap();
+            let zval = time.into_zval(false).unwrap();
+
+            let obj = zval.object().unwrap();
+            assert_eq!(obj.get_class_name().unwrap(), "DateTimeImmutable");
+            let formatted = obj.try_call_method("format", vec![&"c u"]).unwrap();
+            assert_eq!(formatted.str(), Some("2024-02-29T13:30:15+02:00 123456"));
+
+            let back = DateTime::<FixedOffset>::from_zval(&zval).unwrap();
+            assert_eq!(back, time.with_nanosecond(123_456_000).unwrap());
+            assert_eq!(back.offset(), &offset);
+        });
+    }
+
+    #[test]
+    fn test_from_zval() {
+        Embed::run(|| {
+            let zval = Embed::eval(
+                "new DateTime('2001-09-09 01:46:40.5', new DateTimeZone('America/New_York'));",
+            )
+            .unwrap();
+
+            let time = DateTime::<FixedOffset>::from_zval(&zval).unwrap();
+            assert_eq!(time.offset().local_minus_utc(), -4 * 3600);
+            let utc = DateTime::<Utc>::from_zval(&zval).unwrap();
+            assert_eq!(utc.timestamp_millis(), 1_000_014_400_500);
+            let naive = NaiveDateTime::from_zval(&zval).unwrap();
+            let expected = NaiveDate::from_ymd_opt(2001, 9, 9)
+                .unwrap()
+                .and_hms_milli_opt(1, 46, 40, 500)
+                .unwrap();
+            assert_eq!(naive, expected);
+
+            let zval = naive.into_zval(false).unwrap();
+            assert_eq!(NaiveDateTime::from_zval(&zval), Some(expected));
+
+            let zval = Embed::eval("new stdClass;").unwrap();
+            assert!(DateTime::<Utc>::from_zval(&zval).is_none());
+        });
+    }
+}
@@ -0,0 +1,146 @@
+//! Bridges to the character encoding conversion of ext/mbstring, as used by
+//! `mb_convert_encoding()` and `mb_detect_encoding()`.
+//!
+//! The functions are looked up when they are called, so extensions using them
+//! don't depend on ext/mbstring being loaded. Use
+//! [`Requirement::Extension`](crate::builders::Requirement::Extension) to check
+//! whether it is available.
+
+use std::mem;
+
+use crate::{
+    boxed::ZBox,
+    convert::IntoZvalDyn,
+    error::{Error, Result},
+    types::{ZendStr, Zval},
+    zend::{ExecutorGlobals, Function},
+};
+
+/// Calls the given function of ext/mbstring with the string as the first
+/// argument.
+fn call_mbstring(name: &str, str: &ZendStr, args: Vec<&dyn IntoZvalDyn>) -> Result<Zval> {
+    let func = Function::try_from_function(name).ok_or(Error::Callable)?;
+    let mut zv = Zval::new();
+    zv.set_zend_string(str.to_owned());
+    let mut params: Vec<&dyn IntoZvalDyn> = vec![&zv];
+    params.extend(args);
+    let result = func.try_call(params)?;
+    if let Some(e) = ExecutorGlobals::take_exception() {
+        return Err(Error::Exception(e));
+    }
+    Ok(result)
+}
+
+impl ZendStr {
+    /// Converts the string from one character encoding into another, like
+    /// `mb_convert_encoding()`.
+    ///
+    /// The encodings are named as in PHP, e.g. `"UTF-8"` or `"ISO-8859-1"`.
+    /// Characters which can't be represented in the target encoding are
+    /// replaced by `mb_substitute_character()`.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Callable` - If ext/mbstring is not loaded.
+    /// * `Error::Exception` - If an encoding is not supported.
+    /// * `Error::ZvalConversion` - If the conversion failed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let latin1 = ZendStr::new(b"caf\xe9", false);
+    /// let utf8 = latin1.convert_encoding("ISO-8859-1", "UTF-8").unwrap();
+    /// assert_eq!(utf8.as_str().unwrap(), "café");
+    /// ```
+    pub fn convert_encoding(&self, from: &str, to: &str) -> Result<ZBox<ZendStr>> {
+        let result = call_mbstring("mb_convert_encoding", self, vec![&to, &from])?;
+        if !result.is_string() {
+            return Err(Error::ZvalConversion(result.get_type()));
+        }
+        // SAFETY: The reference to the string held by the zval is moved into
+        // the box.
+        let str = unsafe { ZBox::from_raw(result.value.str_) };
+        mem::forget(result);
+        Ok(str)
+    }
+
+    /// Detects the character encoding of the string among the given encodings,
+    /// like `mb_detect_encoding()` in strict mode.
+    ///
+    /// Returns [`None`] if the string is not valid in any of the encodings.
+    /// Otherwise, the choice among the valid encodings depends on the PHP
+    /// version: PHP 8.0 returns the first valid encoding, while PHP 8.1 and
+    /// later score the valid encodings heuristically, e.g. penalizing rare
+    /// characters, and return the one with the best score, preferring earlier
+    /// encodings on ties.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Callable` - If ext/mbstring is not loaded.
+    /// * `Error::Exception` - If an encoding is not supported.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let s = ZendStr::new(b"caf\xe9", false);
+    /// let encoding = s.detect_encoding(&["UTF-8", "ISO-8859-1"]).unwrap();
+    /// assert_eq!(encoding.as_deref(), Some("ISO-8859-1"));
+    /// ```
+    pub fn detect_encoding(&self, encodings: &[&str]) -> Result<Option<String>> {
+        let encodings = encodings.join(",");
+        let result = call_mbstring("mb_detect_encoding", self, vec![&encodings, &true])?;
+        Ok(result.string())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{builders::Requirement, embed::Embed};
+
+    #[test]
+    fn test_encoding() {
+        Embed::run(|| {
+            let latin1 = ZendStr::new(b"caf\xe9", false);
+            if !Requirement::Extension("mbstring").is_met() {
+                assert!(matches!(
+                    latin1.convert_encoding("ISO-8859-1", "UTF-8"),
+                    Err(Error::Callable)
+                ));
+                return;
+            }
+
+            let utf8 = latin1.convert_encoding("ISO-8859-1", "UTF-8").unwrap();
+            assert_eq!(utf8.as_str().unwrap(), "café");
+            let back = utf8.convert_encoding("UTF-8", "ISO-8859-1").unwrap();
+            assert_eq!(back.as_bytes(), b"caf\xe9");
+            assert!(matches!(
+                utf8.convert_encoding("UTF-8", "not-an-encoding"),
+                Err(Error::Exception(_))
+            ));
+
+            // Only one of the encodings is valid for each string, so the result
+            // does not depend on the heuristics of PHP 8.1 and later.
+            assert_eq!(
+                utf8.detect_encoding(&["ASCII", "UTF-8"])
+                    .unwrap()
+                    .as_deref(),
+                Some("UTF-8")
+            );
+            assert_eq!(
+                latin1
+                    .detect_encoding(&["UTF-8", "ISO-8859-1"])
+                    .unwrap()
+                    .as_deref(),
+                Some("ISO-8859-1")
+            );
+            assert_eq!(latin1.detect_encoding(&["UTF-8"]).unwrap(), None);
+        });
+    }
+}
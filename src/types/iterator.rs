@@ -1,7 +1,7 @@
 use crate::convert::FromZvalMut;
 use crate::ffi::{ZEND_RESULT_CODE_SUCCESS, zend_object_iterator};
 use crate::flags::DataType;
-use crate::types::Zval;
+use crate::types::{ZendLong, Zval};
 use crate::zend::ExecutorGlobals;
 use std::fmt::{Debug, Formatter};
 
@@ -166,7 +166,7 @@ impl<'a> Iterator for Iter<'a> {
 
         self.zi.index += 1;
 
-        let real_index = i64::try_from(self.zi.index - 1).expect("index out of bounds");
+        let real_index = ZendLong::try_from(self.zi.index - 1).expect("index out of bounds");
 
         let key = match self.zi.get_current_key() {
             None => {
@@ -5,28 +5,86 @@
 use crate::{
     convert::IntoZval,
     error::{Error, Result},
-    ffi::zend_long,
+    ffi::{zend_long, zend_ulong},
     flags::DataType,
     types::Zval,
 };
 
+use super::array::packed;
+
 use std::convert::{TryFrom, TryInto};
 
 /// A PHP long.
 ///
 /// The type size depends on the system architecture. On 32-bit systems, it is
 /// 32-bits, while on a 64-bit system, it is 64-bits.
+///
+/// This is an alias rather than a newtype, so code mixing it with `i64`
+/// fails to compile on 32-bit systems instead of truncating values. Wider
+/// integers are converted with [`TryFrom`], or through [`IntoZval`], which
+/// returns [`Error::IntegerOverflow`] for values which do not fit.
 pub type ZendLong = zend_long;
 
+/// Converts an `i64` into a [`ZendLong`].
+///
+/// # Errors
+///
+/// Returns [`Error::IntegerOverflow`] if the value does not fit, which is only
+/// possible on 32-bit systems.
+#[cfg(target_pointer_width = "64")]
+#[allow(clippy::unnecessary_wraps)]
+pub(crate) fn long_from_i64(val: i64) -> Result<ZendLong> {
+    Ok(val)
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+pub(crate) fn long_from_i64(val: i64) -> Result<ZendLong> {
+    val.try_into().map_err(|_| Error::IntegerOverflow)
+}
+
+/// Converts a [`ZendLong`] into an `i64`, which is lossless on all systems.
+#[cfg(target_pointer_width = "64")]
+pub(crate) fn long_to_i64(val: ZendLong) -> i64 {
+    val
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+pub(crate) fn long_to_i64(val: ZendLong) -> i64 {
+    i64::from(val)
+}
+
+/// Converts an integer array key into the hash of the bucket storing it.
+/// Negative keys are stored as their two's complement.
+///
+/// # Errors
+///
+/// Returns [`Error::IntegerOverflow`] if the key does not fit into a
+/// [`ZendLong`], rather than truncating it to another key.
+pub(crate) fn index_to_hash(index: i64) -> Result<zend_ulong> {
+    #[allow(clippy::cast_sign_loss)]
+    Ok(long_from_i64(index)? as zend_ulong)
+}
+
+/// Converts the hash of a bucket with an integer key back into the key.
+pub(crate) fn hash_to_index(h: zend_ulong) -> i64 {
+    #[allow(clippy::cast_possible_wrap)]
+    long_to_i64(h as ZendLong)
+}
+
+// Vectors of the integer type matching `ZendLong` are converted into packed
+// arrays directly.
 into_zval!(i8, set_long, Long);
 into_zval!(i16, set_long, Long);
+#[cfg(target_pointer_width = "64")]
 into_zval!(i32, set_long, Long);
+#[cfg(not(target_pointer_width = "64"))]
+into_zval!(i32, set_long, Long, packed: packed::longs_into_hashtable);
 
 into_zval!(u8, set_long, Long);
 into_zval!(u16, set_long, Long);
 
 macro_rules! try_into_zval_int {
-    ($type: ty) => {
+    ($type: ty $(, packed: $into_ht: path)?) => {
         impl TryFrom<$type> for Zval {
             type Error = Error;
 
@@ -47,10 +105,23 @@ macro_rules! try_into_zval_int {
                 zv.set_long(val);
                 Ok(())
             }
+
+            $(
+                fn vec_into_hashtable(vec: Vec<Self>) -> Result<crate::boxed::ZBox<crate::types::ZendHashTable>> {
+                    $into_ht(&vec)
+                }
+
+                fn slice_into_hashtable(slice: &[Self]) -> Result<crate::boxed::ZBox<crate::types::ZendHashTable>> {
+                    $into_ht(slice)
+                }
+            )?
         }
     };
 }
 
+#[cfg(target_pointer_width = "64")]
+try_into_zval_int!(i64, packed: packed::longs_into_hashtable);
+#[cfg(not(target_pointer_width = "64"))]
 try_into_zval_int!(i64);
 try_into_zval_int!(u32);
 try_into_zval_int!(u64);
@@ -60,7 +131,13 @@ try_into_zval_int!(usize);
 
 try_from_zval!(i8, long, Long);
 try_from_zval!(i16, long, Long);
+#[cfg(target_pointer_width = "64")]
 try_from_zval!(i32, long, Long);
+#[cfg(not(target_pointer_width = "64"))]
+try_from_zval!(i32, long, Long, packed: packed::longs_from_hashtable);
+#[cfg(target_pointer_width = "64")]
+try_from_zval!(i64, long, Long, packed: packed::longs_from_hashtable);
+#[cfg(not(target_pointer_width = "64"))]
 try_from_zval!(i64, long, Long);
 
 try_from_zval!(u8, long, Long);
@@ -70,3 +147,36 @@ try_from_zval!(u64, long, Long);
 
 try_from_zval!(usize, long, Long);
 try_from_zval!(isize, long, Long);
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_index_hash_round_trip() {
+        for index in [
+            0,
+            1,
+            -1,
+            42,
+            long_to_i64(ZendLong::MAX),
+            long_to_i64(ZendLong::MIN),
+        ] {
+            assert_eq!(hash_to_index(index_to_hash(index).unwrap()), index);
+        }
+    }
+
+    #[cfg(not(target_pointer_width = "64"))]
+    #[test]
+    fn test_index_overflow() {
+        assert!(matches!(
+            index_to_hash(long_to_i64(ZendLong::MAX) + 1),
+            Err(Error::IntegerOverflow)
+        ));
+        assert!(matches!(
+            long_from_i64(i64::MIN),
+            Err(Error::IntegerOverflow)
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+//! A wrapper distinguishing omitted arguments from arguments which were
+//! passed as `null`.
+
+use crate::{convert::FromZval, flags::DataType, types::Zval};
+
+/// A value which may be undefined, such as a parameter which was not passed
+/// to a function.
+///
+/// Unlike [`Option`], which is [`None`] both when a parameter is omitted and
+/// when `null` is passed, `MaybeUndef<Option<T>>` separates the two cases.
+/// This is useful for update-style functions, which must only change the
+/// fields mentioned by the caller.
+///
+/// When used as a parameter of a function, the parameter is optional. It is
+/// nullable if `T` is an [`Option`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{prelude::*, types::MaybeUndef};
+///
+/// #[php_function]
+/// pub fn update_name(name: MaybeUndef<Option<String>>) -> String {
+///     match name {
+///         MaybeUndef::Undef => "unchanged".into(),
+///         MaybeUndef::Value(None) => "cleared".into(),
+///         MaybeUndef::Value(Some(name)) => format!("renamed to {name}"),
+///     }
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MaybeUndef<T> {
+    /// No value was given.
+    #[default]
+    Undef,
+    /// A value was given.
+    Value(T),
+}
+
+impl<T> MaybeUndef<T> {
+    /// Returns true if no value was given.
+    #[must_use]
+    pub fn is_undef(&self) -> bool {
+        matches!(self, Self::Undef)
+    }
+
+    /// Returns the value, or [`None`] if no value was given.
+    #[must_use]
+    pub fn value(self) -> Option<T> {
+        self.into()
+    }
+
+    /// Returns a reference to the value, or [`None`] if no value was given.
+    #[must_use]
+    pub fn as_ref(&self) -> MaybeUndef<&T> {
+        match self {
+            Self::Undef => MaybeUndef::Undef,
+            Self::Value(val) => MaybeUndef::Value(val),
+        }
+    }
+}
+
+impl<T> From<MaybeUndef<T>> for Option<T> {
+    fn from(value: MaybeUndef<T>) -> Self {
+        match value {
+            MaybeUndef::Undef => None,
+            MaybeUndef::Value(val) => Some(val),
+        }
+    }
+}
+
+impl<'a, T> FromZval<'a> for MaybeUndef<T>
+where
+    T: FromZval<'a>,
+{
+    const TYPE: DataType = T::TYPE;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        if zval.is_undef() {
+            Some(Self::Undef)
+        } else {
+            T::from_zval(zval).map(Self::Value)
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_from_zval() {
+        Embed::run(|| {
+            let mut undef = Zval::new();
+            undef.u1.type_info = DataType::Undef.as_u32();
+            assert!(undef.is_undef());
+            assert_eq!(
+                MaybeUndef::<Option<i64>>::from_zval(&undef),
+                Some(MaybeUndef::Undef)
+            );
+
+            let null = Zval::new();
+            assert!(!null.is_undef());
+            assert_eq!(
+                MaybeUndef::<Option<i64>>::from_zval(&null),
+                Some(MaybeUndef::Value(None))
+            );
+            assert_eq!(MaybeUndef::<i64>::from_zval(&null), None);
+
+            let long = Embed::eval("5;").unwrap();
+            assert_eq!(
+                MaybeUndef::<i64>::from_zval(&long).and_then(MaybeUndef::value),
+                Some(5)
+            );
+        });
+    }
+}
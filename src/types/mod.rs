@@ -4,33 +4,56 @@
 //! these PHP types when required.
 
 mod array;
+mod big_integer;
 mod callable;
 mod class_object;
+#[cfg(feature = "chrono")]
+mod date_time;
+mod encoding;
 mod iterable;
 mod iterator;
 mod long;
+mod maybe_undef;
 mod object;
+mod operators;
+mod php_value;
+mod resource;
+mod serialize;
 mod string;
+mod string_builder;
 mod zval;
+mod zval_ownership;
 
-pub use array::{ArrayKey, ZendEmptyArray, ZendHashTable};
-pub use callable::ZendCallable;
+#[cfg(feature = "rayon")]
+#[cfg_attr(docs, doc(cfg(feature = "rayon")))]
+pub use array::SharedZval;
+pub(crate) use array::packed;
+pub use array::{ArrayKey, WalkAction, ZendEmptyArray, ZendHashTable};
+pub use big_integer::{OverflowPolicy, PhpU64, overflow};
+pub use callable::{PersistentCallable, ZendCallable};
 pub use class_object::ZendClassObject;
 pub use iterable::Iterable;
 pub use iterator::ZendIterator;
 pub use long::ZendLong;
-pub use object::{PropertyQuery, ZendObject};
-pub use string::ZendStr;
+pub(crate) use long::{hash_to_index, index_to_hash, long_from_i64, long_to_i64};
+pub use maybe_undef::MaybeUndef;
+pub use object::{Properties, PropertyQuery, ZendObject};
+pub use php_value::PhpValue;
+pub use resource::PhpResource;
+pub use serialize::AllowedClasses;
+pub use string::{LazyInterned, ZendStr};
+pub use string_builder::ZendStrBuilder;
 pub use zval::Zval;
+pub use zval_ownership::{ZvalOwned, ZvalRef};
 
 use crate::{convert::FromZval, flags::DataType};
 
 into_zval!(f32, set_double, Double);
-into_zval!(f64, set_double, Double);
-into_zval!(bool, set_bool, Bool);
+into_zval!(f64, set_double, Double, packed: array::packed::doubles_into_hashtable);
+into_zval!(bool, set_bool, Bool, packed: array::packed::bools_into_hashtable);
 
-try_from_zval!(f64, double, Double);
-try_from_zval!(bool, bool, Bool);
+try_from_zval!(f64, double, Double, packed: array::packed::doubles_from_hashtable);
+try_from_zval!(bool, bool, Bool, packed: array::packed::bools_from_hashtable);
 
 impl FromZval<'_> for f32 {
     const TYPE: DataType = DataType::Double;
@@ -13,9 +13,9 @@ use crate::{
         ext_php_rs_zend_object_release, object_properties_init, zend_call_known_function,
         zend_function, zend_hash_str_find_ptr_lc, zend_object, zend_objects_new,
     },
-    flags::DataType,
+    flags::{DataType, PropertyFlags},
     rc::PhpRc,
-    types::{ZendClassObject, ZendStr, Zval},
+    types::{ArrayKey, ZendClassObject, ZendStr, Zval, array::Buckets, callable::named_params},
     zend::{ClassEntry, ExecutorGlobals, ZendObjectHandlers, ce},
 };
 
@@ -178,19 +178,8 @@ impl ZendObject {
         let packed = params.into_boxed_slice();
 
         unsafe {
-            let res = zend_hash_str_find_ptr_lc(
-                &raw const (*self.ce).function_table,
-                name.as_ptr().cast::<c_char>(),
-                name.len(),
-            )
-            .cast::<zend_function>();
-
-            if res.is_null() {
-                return Err(Error::Callable);
-            }
-
             zend_call_known_function(
-                res,
+                self.find_method(name)?,
                 ptr::from_ref(self).cast_mut(),
                 self.ce,
                 &raw mut retval,
@@ -203,6 +192,66 @@ impl ZendObject {
         Ok(retval)
     }
 
+    /// Tries to call a method on the object with a list of named arguments,
+    /// as if it was called as `$obj->name(param: $value)` in PHP. Parameters
+    /// which are not passed receive their default value.
+    ///
+    /// # Returns
+    ///
+    /// Returns the return value of the method, or an error if the method
+    /// could not be found or called.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Callable` - If the method could not be found.
+    /// * `Error::Exception` - If the method threw an exception. This includes
+    ///   passing an unknown parameter name, or omitting a parameter without a
+    ///   default value.
+    /// * If a parameter could not be converted to a zval.
+    pub fn try_call_method_named(
+        &self,
+        name: &str,
+        params: &[(&str, &dyn IntoZvalDyn)],
+    ) -> Result<Zval> {
+        let mut retval = Zval::new();
+        let mut named = named_params(params)?;
+
+        unsafe {
+            zend_call_known_function(
+                self.find_method(name)?,
+                ptr::from_ref(self).cast_mut(),
+                self.ce,
+                &raw mut retval,
+                0,
+                ptr::null_mut(),
+                &raw mut *named,
+            );
+        };
+
+        if let Some(e) = ExecutorGlobals::take_exception() {
+            return Err(Error::Exception(e));
+        }
+        Ok(retval)
+    }
+
+    /// Looks up a method of the object's class by its case-insensitive name.
+    fn find_method(&self, name: &str) -> Result<*mut zend_function> {
+        let res = unsafe {
+            zend_hash_str_find_ptr_lc(
+                &raw const (*self.ce).function_table,
+                name.as_ptr().cast::<c_char>(),
+                name.len(),
+            )
+        }
+        .cast::<zend_function>();
+
+        if res.is_null() {
+            Err(Error::Callable)
+        } else {
+            Ok(res)
+        }
+    }
+
     /// Attempts to read a property from the Object. Returns a result containing
     /// the value of the property if it exists and can be read, and an
     /// [`Error`] otherwise.
@@ -312,6 +361,38 @@ impl ZendObject {
         }
     }
 
+    /// Returns an iterator over the properties of the object, yielding the
+    /// name, value and visibility of each property.
+    ///
+    /// The properties are retrieved through the `get_properties` handler of
+    /// the object, so dynamic properties and properties exposed by custom
+    /// handlers are included. The names of private and protected properties
+    /// are unmangled, and declared properties which have been unset are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::InvalidScope` - If the object handlers or the properties
+    ///   cannot be retrieved.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendObject;
+    ///
+    /// let mut obj = ZendObject::new_stdclass();
+    /// obj.set_property("hello", "world");
+    ///
+    /// for (name, val, vis) in obj.properties().unwrap() {
+    ///     dbg!(name, val, vis);
+    /// }
+    /// ```
+    pub fn properties(&self) -> Result<Properties<'_>> {
+        Ok(Properties {
+            buckets: self.get_properties()?.iter_buckets(),
+        })
+    }
+
     /// Extracts some type from a Zend object.
     ///
     /// This is a wrapper function around `FromZendObject::extract()`.
@@ -486,3 +567,143 @@ pub enum PropertyQuery {
     /// Property exists.
     Exists = ZEND_PROPERTY_EXISTS,
 }
+
+/// Iterator over the properties of an object, see
+/// [`ZendObject::properties`].
+pub struct Properties<'a> {
+    buckets: Buckets<'a>,
+}
+
+impl<'a> Iterator for Properties<'a> {
+    type Item = (ArrayKey<'a>, &'a Zval, PropertyFlags);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, val) = self.buckets.next()?;
+        // Declared properties are stored as indirect zvals pointing into the
+        // properties table of the object. Uninitialized ones are skipped by
+        // the buckets.
+        let val = val.indirect().unwrap_or(val);
+        let (name, vis) = match key {
+            ArrayKey::Long(index) => (ArrayKey::Long(index), PropertyFlags::Public),
+            ArrayKey::Str(name) => borrowed_property_name(name.as_bytes()),
+            ArrayKey::Bytes(name) => borrowed_property_name(name),
+            ArrayKey::String(name) => owned_property_name(name.as_bytes()),
+            ArrayKey::Binary(name) => owned_property_name(&name),
+        };
+        Some((name, val, vis))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.buckets.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Properties<'_> {
+    fn len(&self) -> usize {
+        self.buckets.len()
+    }
+}
+
+/// Splits a property name mangled by PHP into the name of the property and
+/// its visibility. Protected properties are mangled as `"\0*\0name"` and
+/// private properties as `"\0Class\0name"`.
+fn unmangle_property_name(name: &[u8]) -> (&[u8], PropertyFlags) {
+    let Some(mangled) = name.strip_prefix(b"\0") else {
+        return (name, PropertyFlags::Public);
+    };
+    let Some(end) = mangled.iter().position(|&b| b == 0) else {
+        return (name, PropertyFlags::Public);
+    };
+    let vis = if &mangled[..end] == b"*" {
+        PropertyFlags::Protected
+    } else {
+        PropertyFlags::Private
+    };
+    (&mangled[end + 1..], vis)
+}
+
+/// Unmangles a property name borrowed from the properties table.
+fn borrowed_property_name(name: &[u8]) -> (ArrayKey<'_>, PropertyFlags) {
+    let (name, vis) = unmangle_property_name(name);
+    let name = match std::str::from_utf8(name) {
+        Ok(name) => ArrayKey::Str(name),
+        Err(_) => ArrayKey::Bytes(name),
+    };
+    (name, vis)
+}
+
+/// Unmangles a property name which was copied out of the properties table.
+fn owned_property_name(name: &[u8]) -> (ArrayKey<'static>, PropertyFlags) {
+    let (name, vis) = unmangle_property_name(name);
+    let name = match String::from_utf8(name.to_vec()) {
+        Ok(name) => ArrayKey::String(name),
+        Err(e) => ArrayKey::Binary(e.into_bytes()),
+    };
+    (name, vis)
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_properties() {
+        Embed::run(|| {
+            Embed::eval(
+                "eval('#[AllowDynamicProperties] class ObjectPropertiesTest {
+                    public $a = 1; protected $b = 2; private $c = 3; public ?int $e;
+                }');",
+            )
+            .unwrap();
+            Embed::eval("$o = new ObjectPropertiesTest;").unwrap();
+            Embed::eval("$o->d = 4;").unwrap();
+            let zval = Embed::eval("$o;").unwrap();
+            let properties = zval.object().unwrap().properties().unwrap();
+            assert_eq!(properties.len(), 4);
+            let props: Vec<_> = properties
+                .map(|(name, val, vis)| (name.to_string(), val.long().unwrap(), vis))
+                .collect();
+            assert_eq!(
+                props,
+                [
+                    ("a".to_owned(), 1, PropertyFlags::Public),
+                    ("b".to_owned(), 2, PropertyFlags::Protected),
+                    ("c".to_owned(), 3, PropertyFlags::Private),
+                    ("d".to_owned(), 4, PropertyFlags::Public),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_properties_binary_names() {
+        Embed::run(|| {
+            Embed::eval(
+                "eval('#[AllowDynamicProperties] class ObjectPropertiesBinaryTest { private $p = 1; }');",
+            )
+            .unwrap();
+            Embed::eval("$o = new ObjectPropertiesBinaryTest;").unwrap();
+            Embed::eval("$o->{\"\\xff\"} = 2;").unwrap();
+            let zval = Embed::eval("$o;").unwrap();
+            let props: Vec<_> = zval
+                .object()
+                .unwrap()
+                .properties()
+                .unwrap()
+                .map(|(name, val, vis)| {
+                    (name.as_bytes().unwrap().to_vec(), val.long().unwrap(), vis)
+                })
+                .collect();
+            assert_eq!(
+                props,
+                [
+                    (b"p".to_vec(), 1, PropertyFlags::Private),
+                    (b"\xff".to_vec(), 2, PropertyFlags::Public),
+                ]
+            );
+        });
+    }
+}
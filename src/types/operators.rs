@@ -0,0 +1,564 @@
+//! Arithmetic, concatenation, bitwise, comparison and cast operators of PHP
+//! on zvals.
+
+use std::{cmp::Ordering, ffi::c_int, ptr};
+
+use crate::{
+    boxed::ZBox,
+    error::{Error, Result},
+    ffi::{
+        _convert_to_string, IS_STRING, ZEND_RESULT_CODE_SUCCESS, add_function,
+        bitwise_and_function, bitwise_or_function, bitwise_xor_function, concat_function,
+        convert_to_array, convert_to_boolean, convert_to_double, convert_to_long, div_function,
+        mod_function, mul_function, pow_function, shift_left_function, shift_right_function,
+        sub_function, zend_compare, zend_is_true, zend_result, zval,
+    },
+    flags::{DataType, ZvalTypeFlags},
+    types::{ZendHashTable, ZendLong, ZendStr, Zval},
+    zend::ExecutorGlobals,
+};
+
+macro_rules! binary_op {
+    ($(#[$attr: meta])* $name: ident, $op: ident, $php: literal) => {
+        #[doc = concat!("Applies PHP's `", $php, "` operator to the zval and the given one.")]
+        ///
+        /// The operands are converted as PHP does, e.g. numeric strings are
+        /// converted into numbers, and objects may overload the operator.
+        ///
+        /// # Errors
+        ///
+        /// * [`Error::Exception`] - If the engine threw an exception, e.g. a
+        ///   `TypeError` for unsupported operand types or a
+        ///   `DivisionByZeroError`.
+        $(#[$attr])*
+        pub fn $name(&self, other: &Zval) -> Result<Zval> {
+            // SAFETY: The operator is called with valid zvals.
+            self.binary_op(other, |result, op1, op2| unsafe { $op(result, op1, op2) })
+        }
+    };
+}
+
+impl Zval {
+    binary_op!(
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ext_php_rs::{convert::IntoZval, types::Zval};
+        ///
+        /// let a = "40".into_zval(false).unwrap();
+        /// let b = 2.into_zval(false).unwrap();
+        /// assert_eq!(a.try_add(&b).unwrap().long(), Some(42));
+        /// ```
+        try_add, add_function, "+"
+    );
+    binary_op!(try_sub, sub_function, "-");
+    binary_op!(try_mul, mul_function, "*");
+    binary_op!(try_div, div_function, "/");
+    binary_op!(try_mod, mod_function, "%");
+    binary_op!(try_pow, pow_function, "**");
+    binary_op!(
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ext_php_rs::{convert::IntoZval, types::Zval};
+        ///
+        /// let a = "answer: ".into_zval(false).unwrap();
+        /// let b = 42.into_zval(false).unwrap();
+        /// assert_eq!(a.try_concat(&b).unwrap().str(), Some("answer: 42"));
+        /// ```
+        try_concat, concat_function, "."
+    );
+    binary_op!(try_bitwise_and, bitwise_and_function, "&");
+    binary_op!(try_bitwise_or, bitwise_or_function, "|");
+    binary_op!(try_bitwise_xor, bitwise_xor_function, "^");
+    binary_op!(try_shift_left, shift_left_function, "<<");
+    binary_op!(try_shift_right, shift_right_function, ">>");
+
+    /// Compares the zval with the given one like PHP's `<=>` operator does,
+    /// e.g. to sort values in the same order as PHP's `sort()` function.
+    ///
+    /// Values which cannot be compared, such as objects of different classes
+    /// or `NAN`, compare as [`Ordering::Greater`]. So do values whose
+    /// comparison threw an exception, e.g. in the `__toString()` method of an
+    /// object compared with a string; the exception is discarded. Use
+    /// [`try_compare`](Self::try_compare) to handle it instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::cmp::Ordering;
+    ///
+    /// use ext_php_rs::convert::IntoZval;
+    ///
+    /// let a = "10".into_zval(false).unwrap();
+    /// let b = 9.into_zval(false).unwrap();
+    /// assert_eq!(a.compare(&b), Ordering::Greater);
+    /// ```
+    #[must_use]
+    pub fn compare(&self, other: &Zval) -> Ordering {
+        self.try_compare(other).unwrap_or(Ordering::Greater)
+    }
+
+    /// Compares the zval with the given one like PHP's `<=>` operator does,
+    /// see [`compare`](Self::compare).
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`] - If the engine threw an exception while
+    ///   comparing the values, e.g. in the `__toString()` method of an object
+    ///   compared with a string.
+    pub fn try_compare(&self, other: &Zval) -> Result<Ordering> {
+        // SAFETY: `zend_compare` does not modify the values.
+        let result = unsafe {
+            zend_compare(
+                ptr::from_ref(self).cast_mut(),
+                ptr::from_ref(other).cast_mut(),
+            )
+        };
+        if let Some(e) = ExecutorGlobals::take_exception() {
+            return Err(Error::Exception(e));
+        }
+        Ok(result.cmp(&0))
+    }
+
+    /// Checks if the zval is equal to the given one like PHP's `==` operator
+    /// does, see [`is_identical`](Self::is_identical) for `===`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::convert::IntoZval;
+    ///
+    /// let a = "1e1".into_zval(false).unwrap();
+    /// let b = 10.into_zval(false).unwrap();
+    /// assert!(a.loose_equals(&b));
+    /// assert!(!a.is_identical(&b));
+    /// ```
+    #[must_use]
+    pub fn loose_equals(&self, other: &Zval) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+
+    /// Converts the zval into an integer like PHP's `(int)` cast does.
+    ///
+    /// The engine emits the same warnings as for the cast, e.g. for objects
+    /// which cannot be converted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::convert::IntoZval;
+    ///
+    /// let zv = "12abc".into_zval(false).unwrap();
+    /// assert_eq!(zv.cast_to_long(), 12);
+    /// ```
+    #[must_use]
+    pub fn cast_to_long(&self) -> ZendLong {
+        // SAFETY: The copy is converted in place.
+        let copy = self.converted(|zv| unsafe { convert_to_long(zv) });
+        copy.long().unwrap_or_default()
+    }
+
+    /// Converts the zval into a float like PHP's `(float)` cast does, see
+    /// [`cast_to_long`](Self::cast_to_long).
+    #[must_use]
+    pub fn cast_to_double(&self) -> f64 {
+        // SAFETY: The copy is converted in place.
+        let copy = self.converted(|zv| unsafe { convert_to_double(zv) });
+        copy.double().unwrap_or_default()
+    }
+
+    /// Converts the zval into a boolean like PHP's `(bool)` cast does.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::convert::IntoZval;
+    ///
+    /// assert!(!"0".into_zval(false).unwrap().cast_to_bool());
+    /// assert!("0.0".into_zval(false).unwrap().cast_to_bool());
+    /// ```
+    #[must_use]
+    pub fn cast_to_bool(&self) -> bool {
+        // SAFETY: The copy is converted in place.
+        let copy = self.converted(|zv| unsafe { convert_to_boolean(zv) });
+        copy.is_true()
+    }
+
+    /// Returns whether the zval is truthy, i.e. whether conditions like
+    /// `if ($val)` consider it `true`.
+    ///
+    /// The engine's rules are applied: `null`, `false`, `0`, `0.0`, `""`,
+    /// `"0"` and empty arrays are falsy, while e.g. `"0.0"`, `" "` and `[0]`
+    /// are truthy. Objects are truthy unless their class overrides the cast,
+    /// like empty `SimpleXMLElement` objects.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::convert::IntoZval;
+    ///
+    /// assert!(!"0".into_zval(false).unwrap().is_truthy());
+    /// assert!("0.0".into_zval(false).unwrap().is_truthy());
+    /// ```
+    #[must_use]
+    pub fn is_truthy(&self) -> bool {
+        let ptr = ptr::from_ref(self).cast_mut();
+        cfg_if::cfg_if! {
+            if #[cfg(php84)] {
+                // SAFETY: The zval is only read by the engine.
+                unsafe { zend_is_true(ptr) }
+            } else {
+                // SAFETY: The zval is only read by the engine.
+                unsafe { zend_is_true(ptr) == 1 }
+            }
+        }
+    }
+
+    /// Returns whether the zval is falsy, the opposite of
+    /// [`Zval::is_truthy`].
+    #[must_use]
+    pub fn is_falsy(&self) -> bool {
+        !self.is_truthy()
+    }
+
+    /// Converts the zval into a string like PHP's `(string)` cast does,
+    /// calling the `__toString()` method of objects.
+    ///
+    /// The engine emits the same warnings as for the cast, e.g. for arrays.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`] - If the engine threw an exception, e.g. for an
+    ///   object which cannot be converted into a string.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::convert::IntoZval;
+    ///
+    /// let zv = 1.5.into_zval(false).unwrap();
+    /// assert_eq!(zv.cast_to_string().unwrap().as_str().ok(), Some("1.5"));
+    /// ```
+    pub fn cast_to_string(&self) -> Result<ZBox<ZendStr>> {
+        // SAFETY: The copy is converted in place.
+        let copy = self.converted(|zv| unsafe { _convert_to_string(zv) });
+        if let Some(e) = ExecutorGlobals::take_exception() {
+            return Err(Error::Exception(e));
+        }
+        take_string(copy).ok_or_else(|| Error::ZvalConversion(self.get_type()))
+    }
+
+    /// Converts the zval into a string like PHP does for `string` parameters
+    /// in coercive typing mode, i.e. without `strict_types`.
+    ///
+    /// Integers, floats and booleans are cast, and objects are converted by
+    /// the cast handler of their class, which calls the `__toString()` method
+    /// of userland classes. Unlike [`Zval::cast_to_string`], other values are
+    /// rejected, including `null`, arrays and objects which cannot be
+    /// converted.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::TypeMismatch`] - If the value cannot be coerced into a
+    ///   string.
+    /// * [`Error::Exception`] - If the cast handler threw an exception.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::{convert::IntoZval, types::Zval};
+    ///
+    /// let zv = 5.into_zval(false).unwrap();
+    /// assert_eq!(zv.coerce_string().unwrap().as_str().ok(), Some("5"));
+    /// assert!(Zval::null().coerce_string().is_err());
+    /// ```
+    pub fn coerce_string(&self) -> Result<ZBox<ZendStr>> {
+        let zv = self.dereference();
+        let mismatch = || Error::TypeMismatch {
+            expected: DataType::String,
+            actual: zv.get_type(),
+        };
+        match zv.get_type() {
+            DataType::String
+            | DataType::Long
+            | DataType::Double
+            | DataType::True
+            | DataType::False => zv.cast_to_string(),
+            DataType::Object(_) => {
+                let obj = zv.object().ok_or_else(mismatch)?;
+                let cast =
+                    unsafe { obj.handlers.as_ref() }.and_then(|handlers| handlers.cast_object);
+                let Some(cast) = cast else {
+                    return Err(mismatch());
+                };
+                let mut rv = Zval::new();
+                // SAFETY: The handler only reads the object and writes the result
+                // into `rv`.
+                #[allow(clippy::cast_possible_wrap)]
+                let status = unsafe {
+                    cast(
+                        ptr::from_ref(obj).cast_mut(),
+                        &raw mut rv,
+                        IS_STRING as c_int,
+                    )
+                };
+                if let Some(e) = ExecutorGlobals::take_exception() {
+                    return Err(Error::Exception(e));
+                }
+                if status != ZEND_RESULT_CODE_SUCCESS {
+                    return Err(mismatch());
+                }
+                take_string(rv).ok_or_else(mismatch)
+            }
+            _ => Err(mismatch()),
+        }
+    }
+
+    /// Converts the zval into an array like PHP's `(array)` cast does.
+    ///
+    /// Scalars become an array with a single element, `null` becomes an empty
+    /// array and objects become an array of their properties.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::convert::IntoZval;
+    ///
+    /// let zv = "a".into_zval(false).unwrap();
+    /// let ht = zv.cast_to_array();
+    /// assert_eq!(ht.get_index(0).and_then(|zv| zv.str()), Some("a"));
+    /// ```
+    #[must_use]
+    pub fn cast_to_array(&self) -> ZBox<ZendHashTable> {
+        // SAFETY: The copy is converted in place.
+        let copy = self.converted(|zv| unsafe { convert_to_array(zv) });
+        ZendHashTable::take_from(copy).unwrap_or_else(ZendHashTable::new)
+    }
+
+    /// Returns a copy of the zval converted with an engine function, e.g.
+    /// `convert_to_long`. References are replaced by their value first.
+    fn converted<F>(&self, convert: F) -> Zval
+    where
+        F: FnOnce(*mut zval),
+    {
+        let mut copy = self.dereference().shallow_clone();
+        convert(&raw mut copy);
+        copy
+    }
+
+    /// Applies an operator of the engine, e.g. `add_function`, to the zval and
+    /// the given one.
+    fn binary_op<F>(&self, other: &Zval, op: F) -> Result<Zval>
+    where
+        F: FnOnce(*mut zval, *mut zval, *mut zval) -> zend_result,
+    {
+        let mut result = Zval::new();
+        // The operators do not modify their operands unless the result is one
+        // of them, which it is not.
+        let status = op(
+            &raw mut result,
+            ptr::from_ref(self).cast_mut(),
+            ptr::from_ref(other).cast_mut(),
+        );
+        if let Some(e) = ExecutorGlobals::take_exception() {
+            return Err(Error::Exception(e));
+        }
+        if status == ZEND_RESULT_CODE_SUCCESS {
+            Ok(result)
+        } else {
+            Err(Error::ZvalConversion(self.get_type()))
+        }
+    }
+}
+
+/// Moves the string held by the zval into a box, or returns [`None`] if the
+/// zval is not a string.
+fn take_string(mut zv: Zval) -> Option<ZBox<ZendStr>> {
+    if !zv.is_string() {
+        return None;
+    }
+    let str = unsafe { zv.value.str_ };
+    // The zval holds a reference to the string, which is moved into the box.
+    // Interned strings are not released by the box.
+    zv.u1.type_info = ZvalTypeFlags::Null.bits();
+    // SAFETY: The zval was checked to hold a string above.
+    Some(unsafe { ZBox::from_raw(&mut *str) })
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::{convert::IntoZval, embed::Embed, error::Error, types::Zval};
+
+    fn zval<T: IntoZval>(val: T) -> Zval {
+        val.into_zval(false).unwrap()
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        Embed::run(|| {
+            assert_eq!(zval(40).try_add(&zval("2")).unwrap().long(), Some(42));
+            assert_eq!(zval(1.5).try_sub(&zval(1)).unwrap().double(), Some(0.5));
+            assert_eq!(zval("3").try_mul(&zval("4")).unwrap().long(), Some(12));
+            assert_eq!(zval(7).try_div(&zval(2)).unwrap().double(), Some(3.5));
+            assert_eq!(zval(6).try_div(&zval(2)).unwrap().long(), Some(3));
+            assert_eq!(zval(7).try_mod(&zval(3)).unwrap().long(), Some(1));
+            assert_eq!(zval(2).try_pow(&zval(10)).unwrap().long(), Some(1024));
+            let overflow = zval(i64::MAX).try_add(&zval(1)).unwrap();
+            assert!(overflow.is_double());
+        });
+    }
+
+    #[test]
+    fn test_concat_and_bitwise() {
+        Embed::run(|| {
+            let concat = zval("a").try_concat(&zval(1.5)).unwrap();
+            assert_eq!(concat.str(), Some("a1.5"));
+            assert_eq!(zval(6).try_bitwise_and(&zval(3)).unwrap().long(), Some(2));
+            assert_eq!(zval(6).try_bitwise_or(&zval(3)).unwrap().long(), Some(7));
+            assert_eq!(zval(6).try_bitwise_xor(&zval(3)).unwrap().long(), Some(5));
+            assert_eq!(zval(1).try_shift_left(&zval(4)).unwrap().long(), Some(16));
+            assert_eq!(zval(16).try_shift_right(&zval(2)).unwrap().long(), Some(4));
+
+            let left = zval("ab").try_bitwise_or(&zval("  ")).unwrap();
+            assert_eq!(left.str(), Some("ab"));
+        });
+    }
+
+    #[test]
+    fn test_compare() {
+        Embed::run(|| {
+            assert_eq!(zval(1).compare(&zval(2)), Ordering::Less);
+            assert_eq!(zval("abc").compare(&zval("abd")), Ordering::Less);
+            assert_eq!(zval(1.5).compare(&zval("1.5")), Ordering::Equal);
+            assert_eq!(zval(f64::NAN).compare(&zval(0)), Ordering::Greater);
+
+            let a = Embed::eval("[1, 2, 3];").unwrap();
+            let b = Embed::eval("[1, 2, 4];").unwrap();
+            assert_eq!(a.compare(&b), Ordering::Less);
+
+            let object = Embed::eval(
+                "new class { public function __toString(): string { throw new Exception(); } };",
+            )
+            .unwrap();
+            assert!(matches!(
+                object.try_compare(&zval("a")),
+                Err(Error::Exception(_))
+            ));
+            assert_eq!(object.compare(&zval("a")), Ordering::Greater);
+            assert_eq!(zval(1).try_compare(&zval(1)).unwrap(), Ordering::Equal);
+        });
+    }
+
+    #[test]
+    fn test_loose_equals() {
+        Embed::run(|| {
+            assert!(zval("1e1").loose_equals(&zval(10)));
+            assert!(zval(0).loose_equals(&zval(false)));
+            assert!(Zval::null().loose_equals(&zval("")));
+            assert!(!zval("abc").loose_equals(&zval(0)));
+            assert!(!zval("1e1").is_identical(&zval(10)));
+
+            let a = Embed::eval("['a' => 1, 'b' => 2];").unwrap();
+            let b = Embed::eval("['b' => '2', 'a' => 1];").unwrap();
+            assert!(a.loose_equals(&b));
+        });
+    }
+
+    #[test]
+    fn test_scalar_casts() {
+        Embed::run(|| {
+            assert_eq!(zval("12abc").cast_to_long(), 12);
+            assert_eq!(zval(3.99).cast_to_long(), 3);
+            assert_eq!(zval(true).cast_to_long(), 1);
+            assert_eq!(Zval::null().cast_to_long(), 0);
+            assert_eq!(zval("1e3").cast_to_double(), 1000.0);
+
+            assert!(!zval("0").cast_to_bool());
+            assert!(!zval("").cast_to_bool());
+            assert!(zval("0.0").cast_to_bool());
+            assert!(!Embed::eval("[];").unwrap().cast_to_bool());
+
+            let original = zval("5");
+            assert_eq!(original.cast_to_long(), 5);
+            assert_eq!(original.str(), Some("5"));
+        });
+    }
+
+    #[test]
+    fn test_string_and_array_casts() {
+        Embed::run(|| {
+            let str = zval(1.5).cast_to_string().unwrap();
+            assert_eq!(str.as_str().unwrap(), "1.5");
+            let str = zval(false).cast_to_string().unwrap();
+            assert_eq!(str.as_str().unwrap(), "");
+
+            let ht = zval(7).cast_to_array();
+            assert_eq!(ht.get_index(0).and_then(Zval::long), Some(7));
+            assert!(Zval::null().cast_to_array().is_empty());
+
+            let object = Embed::eval("(object) ['a' => 1];").unwrap();
+            let ht = object.cast_to_array();
+            assert_eq!(ht.get("a").and_then(Zval::long), Some(1));
+
+            let array = Embed::eval("[1, 2];").unwrap();
+            let mut copy = array.cast_to_array();
+            copy.push(3).unwrap();
+            assert_eq!(array.array().unwrap().len(), 2);
+        });
+    }
+
+    #[test]
+    fn test_coerce_string() {
+        Embed::run(|| {
+            let str = zval(42).coerce_string().unwrap();
+            assert_eq!(str.as_str().unwrap(), "42");
+            let str = zval(true).coerce_string().unwrap();
+            assert_eq!(str.as_str().unwrap(), "1");
+            let str = zval("a").coerce_string().unwrap();
+            assert_eq!(str.as_str().unwrap(), "a");
+
+            let object =
+                Embed::eval("new class { public function __toString(): string { return 'b'; } };")
+                    .unwrap();
+            assert_eq!(object.coerce_string().unwrap().as_str().unwrap(), "b");
+
+            assert!(matches!(
+                Zval::null().coerce_string(),
+                Err(Error::TypeMismatch { .. })
+            ));
+            let object = Embed::eval("new stdClass;").unwrap();
+            assert!(matches!(
+                object.coerce_string(),
+                Err(Error::TypeMismatch { .. })
+            ));
+            let array = Embed::eval("[1];").unwrap();
+            assert!(matches!(
+                array.coerce_string(),
+                Err(Error::TypeMismatch { .. })
+            ));
+        });
+    }
+
+    #[test]
+    fn test_truthiness() {
+        Embed::run(|| {
+            let falsy = Embed::eval("[null, false, 0, 0.0, -0.0, '', '0', []];").unwrap();
+            for zv in falsy.array().unwrap().values() {
+                assert!(zv.is_falsy(), "{zv:?} should be falsy");
+            }
+
+            let truthy =
+                Embed::eval("[true, -1, 0.1, NAN, '0.0', ' ', '00', 'false', [0], new stdClass];")
+                    .unwrap();
+            for zv in truthy.array().unwrap().values() {
+                assert!(zv.is_truthy(), "{zv:?} should be truthy");
+                assert_eq!(zv.is_truthy(), zv.cast_to_bool());
+            }
+        });
+    }
+}
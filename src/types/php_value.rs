@@ -0,0 +1,273 @@
+//! An owned representation of PHP values, which copies nested arrays and
+//! objects into Rust.
+
+use std::ptr;
+
+use crate::{
+    boxed::ZBox,
+    convert::{FromZval, IntoZval},
+    error::{Error, Result},
+    ffi::object_properties_load,
+    flags::DataType,
+    types::{ArrayKey, ZendHashTable, ZendLong, ZendObject, Zval},
+    zend::ClassEntry,
+};
+
+/// A PHP value which is fully owned by Rust.
+///
+/// Converting a zval into a [`PhpValue`] copies the value, including the
+/// elements of arrays and the properties of objects, so it does not keep any
+/// references to values of the engine. Converting it back creates new values.
+///
+/// References are replaced by the value they refer to. Converting a zval
+/// fails if it holds a resource or another value which cannot be copied, or
+/// if an array or object contains itself.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{prelude::*, types::PhpValue};
+///
+/// #[php_function]
+/// pub fn count_leaves(value: PhpValue) -> usize {
+///     match value {
+///         PhpValue::Array(entries) => entries.into_iter().map(|(_, v)| count_leaves(v)).sum(),
+///         PhpValue::Object { properties, .. } => {
+///             properties.into_iter().map(|(_, v)| count_leaves(v)).sum()
+///         }
+///         _ => 1,
+///     }
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PhpValue {
+    /// `null`.
+    #[default]
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// An integer.
+    Long(ZendLong),
+    /// A floating point number.
+    Double(f64),
+    /// A string which is valid UTF-8.
+    String(String),
+    /// A string which is not valid UTF-8.
+    Bytes(Vec<u8>),
+    /// An array, with its elements in order.
+    Array(Vec<(ArrayKey<'static>, PhpValue)>),
+    /// An object.
+    Object {
+        /// The name of the class of the object.
+        class: String,
+        /// The properties of the object, in order. The names of private and
+        /// protected properties are mangled as in PHP, e.g. `"\0Foo\0bar"`
+        /// for the private property `bar` of the class `Foo` and `"\0*\0bar"`
+        /// for a protected property.
+        properties: Vec<(ArrayKey<'static>, PhpValue)>,
+    },
+}
+
+impl PhpValue {
+    /// Copies the given zval, keeping track of the arrays and objects which
+    /// are being copied to detect recursion.
+    fn copy(zval: &Zval, parents: &mut Vec<*const ZendHashTable>) -> Option<Self> {
+        let zval = Self::value_of(zval);
+        Some(match zval.get_type() {
+            DataType::Null => Self::Null,
+            DataType::False => Self::Bool(false),
+            DataType::True => Self::Bool(true),
+            DataType::Long => Self::Long(zval.long()?),
+            DataType::Double => Self::Double(zval.double()?),
+            DataType::String => {
+                let str = zval.zend_str()?;
+                match str.as_str() {
+                    Ok(str) => Self::String(str.to_owned()),
+                    Err(_) => Self::Bytes(str.as_bytes().to_vec()),
+                }
+            }
+            DataType::Array => Self::Array(Self::copy_table(zval.array()?, parents)?),
+            DataType::Object(_) => {
+                let object = zval.object()?;
+                Self::Object {
+                    class: object.get_class_name().ok()?,
+                    properties: Self::copy_table(object.get_properties().ok()?, parents)?,
+                }
+            }
+            _ => return None,
+        })
+    }
+
+    fn copy_table(
+        ht: &ZendHashTable,
+        parents: &mut Vec<*const ZendHashTable>,
+    ) -> Option<Vec<(ArrayKey<'static>, Self)>> {
+        let ptr = ptr::from_ref(ht);
+        if parents.contains(&ptr) {
+            return None;
+        }
+        parents.push(ptr);
+        // Uninitialized typed properties are undefined.
+        let entries = ht
+            .iter_buckets()
+            .filter(|(_, val)| Self::value_of(val).get_type() != DataType::Undef)
+            .map(|(key, val)| Some((key.into_owned(), Self::copy(val, parents)?)))
+            .collect();
+        parents.pop();
+        entries
+    }
+
+    /// Returns the value held by the zval. The property tables of objects hold
+    /// indirect zvals pointing to the properties, which may in turn be
+    /// references, so both are followed.
+    fn value_of(zval: &Zval) -> &Zval {
+        let zval = zval.indirect().unwrap_or(zval);
+        zval.reference().unwrap_or(zval)
+    }
+
+    fn into_table(entries: Vec<(ArrayKey<'static>, Self)>) -> Result<ZBox<ZendHashTable>> {
+        let mut ht = ZendHashTable::with_capacity(
+            entries
+                .len()
+                .try_into()
+                .map_err(|_| Error::IntegerOverflow)?,
+        );
+        for (key, val) in entries {
+            ht.insert(key, val)?;
+        }
+        Ok(ht)
+    }
+}
+
+impl FromZval<'_> for PhpValue {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        Self::copy(zval, &mut Vec::new())
+    }
+}
+
+impl IntoZval for PhpValue {
+    const TYPE: DataType = DataType::Mixed;
+    const NULLABLE: bool = true;
+
+    /// Sets the zval to a new value.
+    ///
+    /// Objects are created without calling their constructor, and their
+    /// properties are set as `unserialize()` does.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnknownClass`] - If the class of an object does not exist.
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        match self {
+            Self::Null => zv.set_null(),
+            Self::Bool(val) => zv.set_bool(val),
+            Self::Long(val) => zv.set_long(val),
+            Self::Double(val) => zv.set_double(val),
+            Self::String(val) => zv.set_string(&val, persistent)?,
+            Self::Bytes(val) => zv.set_binary(val),
+            Self::Array(entries) => zv.set_hashtable(Self::into_table(entries)?),
+            Self::Object { class, properties } => {
+                let ce = ClassEntry::try_find(&class).ok_or(Error::UnknownClass(class))?;
+                let mut properties = Self::into_table(properties)?;
+                let mut object = ZendObject::new(ce);
+                // SAFETY: The properties are copied into the object, which
+                // adds references to their values.
+                unsafe { object_properties_load(&raw mut *object, &raw mut *properties) };
+                object.set_zval(zv, persistent)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_nested_round_trip() {
+        Embed::run(|| {
+            let zval =
+                Embed::eval("['a' => [1, 2.5, null], 5 => true, 'b' => \"\\xff\"];").unwrap();
+            let value = PhpValue::from_zval(&zval).unwrap();
+            assert_eq!(
+                value,
+                PhpValue::Array(vec![
+                    (
+                        ArrayKey::String("a".to_owned()),
+                        PhpValue::Array(vec![
+                            (ArrayKey::Long(0), PhpValue::Long(1)),
+                            (ArrayKey::Long(1), PhpValue::Double(2.5)),
+                            (ArrayKey::Long(2), PhpValue::Null),
+                        ])
+                    ),
+                    (ArrayKey::Long(5), PhpValue::Bool(true)),
+                    (
+                        ArrayKey::String("b".to_owned()),
+                        PhpValue::Bytes(vec![0xff])
+                    ),
+                ])
+            );
+            drop(zval);
+
+            let zval = value.clone().into_zval(false).unwrap();
+            assert_eq!(PhpValue::from_zval(&zval).unwrap(), value);
+        });
+    }
+
+    #[test]
+    fn test_objects() {
+        Embed::run(|| {
+            Embed::eval(
+                "eval('#[AllowDynamicProperties] class PhpValueTest {
+                    public $a = 1; protected $b = [2]; private ?int $c;
+                }');",
+            )
+            .unwrap();
+            Embed::eval("$o = new PhpValueTest;").unwrap();
+            Embed::eval("$o->d = 'x';").unwrap();
+            Embed::eval("$x = 3; $o->a = &$x;").unwrap();
+            let zval = Embed::eval("$o;").unwrap();
+            let value = PhpValue::from_zval(&zval).unwrap();
+            let PhpValue::Object { class, properties } = &value else {
+                panic!("Expected an object, got {value:?}");
+            };
+            assert_eq!(class, "PhpValueTest");
+            let names: Vec<_> = properties.iter().map(|(key, _)| key.to_string()).collect();
+            assert_eq!(names, ["a", "\0*\0b", "d"]);
+            assert_eq!(properties[0].1, PhpValue::Long(3));
+
+            let copy = value.clone().into_zval(false).unwrap();
+            let object = copy.object().unwrap();
+            assert_eq!(object.get_class_name().unwrap(), "PhpValueTest");
+            assert_eq!(object.get_property::<String>("d").unwrap(), "x");
+            assert_eq!(PhpValue::from_zval(&copy).unwrap(), value);
+
+            let missing = PhpValue::Object {
+                class: "PhpValueMissing".to_owned(),
+                properties: vec![],
+            };
+            assert!(matches!(
+                missing.into_zval(false),
+                Err(Error::UnknownClass(_))
+            ));
+        });
+    }
+
+    #[test]
+    fn test_recursion() {
+        Embed::run(|| {
+            Embed::eval("$a = [1];").unwrap();
+            Embed::eval("$a[] = &$a;").unwrap();
+            let zval = Embed::eval("$a;").unwrap();
+            assert!(PhpValue::from_zval(&zval).is_none());
+        });
+    }
+}
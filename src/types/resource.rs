@@ -0,0 +1,357 @@
+//! Resources holding Rust values, passed to PHP as `resource`s.
+
+use std::{
+    any::{TypeId, type_name},
+    collections::HashMap,
+    ffi::{CString, c_int, c_void},
+    fmt::{self, Debug},
+    marker::PhantomData,
+    ptr::NonNull,
+    sync::LazyLock,
+};
+
+use parking_lot::RwLock;
+
+use crate::{
+    PHP_ZTS,
+    convert::{FromZval, IntoZval},
+    error::Result,
+    ffi::{
+        zend_list_close, zend_list_delete, zend_register_list_destructors_ex,
+        zend_register_resource, zend_resource,
+    },
+    flags::DataType,
+    types::{ZendLong, Zval},
+    zend::engine,
+};
+
+/// Resource type numbers (`le_*` in C extensions) registered for Rust types,
+/// along with the run of the engine they were registered in. The numbers are
+/// only valid until the engine is shut down.
+static RESOURCE_TYPES: LazyLock<RwLock<HashMap<TypeId, (u64, c_int)>>> =
+    LazyLock::new(RwLock::default);
+
+/// A reference counted PHP resource holding a value of type `T`.
+///
+/// The resource type of `T` should be registered at module startup with
+/// [`ModuleBuilder::resource`], with a destructor dropping the value once PHP
+/// releases the resource. Otherwise, the type is registered the first time it
+/// is required, which is not possible in thread safe builds of PHP. Cloning a
+/// `PhpResource` adds a reference to the same resource.
+///
+/// Resources can be returned to PHP and accepted as parameters of functions,
+/// where passing a resource of another type is rejected as a type error:
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use std::cell::RefCell;
+/// use ext_php_rs::{prelude::*, types::PhpResource};
+///
+/// pub struct Connection {
+///     queries: RefCell<Vec<String>>,
+/// }
+///
+/// #[php_function]
+/// pub fn connection_open() -> PhpResource<Connection> {
+///     PhpResource::new(Connection {
+///         queries: RefCell::default(),
+///     })
+/// }
+///
+/// #[php_function]
+/// pub fn connection_query(conn: PhpResource<Connection>, query: String) -> PhpResult<()> {
+///     let conn = conn.get().ok_or("Connection was closed")?;
+///     conn.queries.borrow_mut().push(query);
+///     Ok(())
+/// }
+/// ```
+///
+/// [`ModuleBuilder::resource`]: crate::builders::ModuleBuilder::resource
+pub struct PhpResource<T: 'static> {
+    res: NonNull<zend_resource>,
+    _value: PhantomData<T>,
+}
+
+impl<T: 'static> PhpResource<T> {
+    /// Creates a new resource holding the given value, registering the
+    /// resource type of `T` if it was not registered yet.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if PHP fails to create the resource.
+    /// * Panics in thread safe builds of PHP if the resource type of `T` was
+    ///   not registered at module startup.
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        let type_id = Self::type_id_or_register();
+        let ptr = Box::into_raw(Box::new(value)).cast::<c_void>();
+        let res = unsafe { zend_register_resource(ptr, type_id) };
+        Self {
+            res: NonNull::new(res).expect("Failed to register resource"),
+            _value: PhantomData,
+        }
+    }
+
+    /// Registers the resource type of `T` with the given name, which is
+    /// returned by `get_resource_type()` in PHP. Returns the resource type
+    /// number.
+    ///
+    /// This should be called during module startup, before any resource of
+    /// the type is created, which is done by
+    /// [`ModuleBuilder::resource`](crate::builders::ModuleBuilder::resource).
+    /// Registering a type again while the engine is running returns the
+    /// existing number.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the resource type.
+    /// * `module_number` - The module number passed to the startup function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name contains NUL bytes.
+    pub fn register(name: &str, module_number: i32) -> i32 {
+        let engine = engine::current_engine();
+        let mut types = RESOURCE_TYPES.write();
+        match types.get(&TypeId::of::<T>()) {
+            Some(&(registered_in, le_id)) if registered_in == engine => le_id,
+            // Types registered before the engine was restarted are gone.
+            _ => {
+                // PHP keeps the pointer to the name, so it must live forever.
+                let name = CString::new(name).expect("Resource type name contains NUL bytes");
+                let le_id = unsafe {
+                    zend_register_list_destructors_ex(
+                        Some(resource_dtor::<T>),
+                        None,
+                        name.into_raw(),
+                        module_number,
+                    )
+                };
+                types.insert(TypeId::of::<T>(), (engine, le_id));
+                le_id
+            }
+        }
+    }
+
+    /// Returns the resource type number (`le_id`) of `T`, if it has been
+    /// registered since the engine was started.
+    #[must_use]
+    pub fn le_id() -> Option<i32> {
+        match RESOURCE_TYPES.read().get(&TypeId::of::<T>()) {
+            Some(&(registered_in, le_id)) if registered_in == engine::current_engine() => {
+                Some(le_id)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the handle of the resource, which is shown by `var_dump()` and
+    /// returned by `get_resource_id()` in PHP.
+    #[must_use]
+    pub fn handle(&self) -> ZendLong {
+        self.as_ref().handle
+    }
+
+    /// Returns the number of references to the resource.
+    #[must_use]
+    pub fn refcount(&self) -> u32 {
+        self.as_ref().gc.refcount
+    }
+
+    /// Returns the value held by the resource, or `None` if the resource was
+    /// closed.
+    ///
+    /// Resources may be shared by multiple references, so the value can only
+    /// be borrowed immutably. Use interior mutability for values which need
+    /// to change.
+    #[must_use]
+    pub fn get(&self) -> Option<&T> {
+        let res = self.as_ref();
+        if Self::le_id() != Some(res.type_) {
+            return None;
+        }
+        unsafe { res.ptr.cast::<T>().as_ref() }
+    }
+
+    /// Closes the resource, dropping the value held by it even if there are
+    /// other references to the resource. Closed resources show up as
+    /// `resource (closed)` in PHP.
+    pub fn close(&self) {
+        unsafe { zend_list_close(self.res.as_ptr()) };
+    }
+
+    /// Returns whether the resource was closed.
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.get().is_none()
+    }
+
+    /// Attempts to create a new reference to the given resource, returning
+    /// `None` if it is not an open resource holding a value of type `T`.
+    fn from_resource(res: *mut zend_resource) -> Option<Self> {
+        let mut res = NonNull::new(res)?;
+        let le_id = Self::le_id()?;
+        // Closed resources have their type set to -1.
+        let type_ = unsafe { res.as_ref() }.type_;
+        if type_ != le_id {
+            return None;
+        }
+        unsafe { res.as_mut() }.gc.refcount += 1;
+        Some(Self {
+            res,
+            _value: PhantomData,
+        })
+    }
+
+    fn type_id_or_register() -> c_int {
+        Self::le_id().unwrap_or_else(|| {
+            // The list of resource types is shared by all threads, which may only
+            // modify it during module startup.
+            assert!(
+                !PHP_ZTS,
+                "Resource type `{}` must be registered at module startup with `ModuleBuilder::resource()`",
+                type_name::<T>()
+            );
+            Self::register(type_name::<T>(), 0)
+        })
+    }
+
+    fn as_ref(&self) -> &zend_resource {
+        // SAFETY: The resource is kept alive by the reference held by `self`.
+        unsafe { self.res.as_ref() }
+    }
+}
+
+impl<T: 'static> Clone for PhpResource<T> {
+    fn clone(&self) -> Self {
+        let mut res = self.res;
+        unsafe { res.as_mut() }.gc.refcount += 1;
+        Self {
+            res,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static> Drop for PhpResource<T> {
+    fn drop(&mut self) {
+        // Releases the value once the last reference is gone.
+        unsafe { zend_list_delete(self.res.as_ptr()) };
+    }
+}
+
+impl<T: 'static + Debug> Debug for PhpResource<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PhpResource")
+            .field("handle", &self.handle())
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
+impl<T: 'static> IntoZval for PhpResource<T> {
+    const TYPE: DataType = DataType::Resource;
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        // The reference held by `self` is moved into the zval.
+        zv.set_resource(self.res.as_ptr());
+        std::mem::forget(self);
+        Ok(())
+    }
+}
+
+impl<T: 'static> FromZval<'_> for PhpResource<T> {
+    const TYPE: DataType = DataType::Resource;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        Self::from_resource(zval.resource()?)
+    }
+}
+
+unsafe extern "C" fn resource_dtor<T>(res: *mut zend_resource) {
+    let Some(res) = (unsafe { res.as_mut() }) else {
+        return;
+    };
+    if !res.ptr.is_null() {
+        // SAFETY: Resources of this type only hold values boxed by `PhpResource::new`.
+        drop(unsafe { Box::from_raw(res.ptr.cast::<T>()) });
+        res.ptr = std::ptr::null_mut();
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+    use crate::embed::Embed;
+
+    struct DropCounter(Rc<Cell<u32>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_resource_drop() {
+        Embed::run(|| {
+            let drops = Rc::new(Cell::new(0));
+            let res = PhpResource::new(DropCounter(drops.clone()));
+            assert!(PhpResource::<DropCounter>::le_id().is_some());
+            assert_eq!(res.refcount(), 1);
+
+            let clone = res.clone();
+            assert_eq!(res.refcount(), 2);
+            drop(clone);
+            assert_eq!(drops.get(), 0);
+
+            drop(res);
+            assert_eq!(drops.get(), 1);
+        });
+    }
+
+    #[test]
+    fn test_resource_zval() {
+        Embed::run(|| {
+            let res = PhpResource::new(42_i32);
+            let zv = res.clone().into_zval(false).unwrap();
+            assert!(zv.is_resource());
+            assert_eq!(res.refcount(), 2);
+
+            let from_zval = PhpResource::<i32>::from_zval(&zv).unwrap();
+            assert_eq!(from_zval.get(), Some(&42));
+            assert_eq!(from_zval.handle(), res.handle());
+            assert!(PhpResource::<u8>::from_zval(&zv).is_none());
+
+            res.close();
+            assert!(from_zval.is_closed());
+        });
+    }
+
+    #[test]
+    fn test_resource_engine_restart() {
+        struct Restarted(u32);
+
+        for run in 0..2 {
+            Embed::run(move || {
+                let drops = Rc::new(Cell::new(0));
+                let res = PhpResource::new((Restarted(run), DropCounter(drops.clone())));
+                let le_id = PhpResource::<(Restarted, DropCounter)>::le_id();
+                assert_eq!(le_id, Some(res.as_ref().type_));
+                assert_eq!(res.get().unwrap().0.0, run);
+
+                let zv = res.clone().into_zval(false).unwrap();
+                let from_zval = PhpResource::<(Restarted, DropCounter)>::from_zval(&zv).unwrap();
+                assert_eq!(from_zval.handle(), res.handle());
+
+                drop((res, from_zval, zv));
+                assert_eq!(drops.get(), 1);
+            });
+        }
+    }
+}
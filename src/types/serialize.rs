@@ -0,0 +1,173 @@
+//! Bridges to PHP's native serialization format, as used by `serialize()` and
+//! `unserialize()`.
+
+use std::{os::raw::c_char, ptr};
+
+use crate::{
+    boxed::ZBox,
+    error::{Error, Result},
+    ffi::{ext_php_rs_php_var_serialize, ext_php_rs_php_var_unserialize},
+    types::{ZendHashTable, ZendStr, Zval},
+    zend::ExecutorGlobals,
+};
+
+/// The classes which may be instantiated when unserializing a value with
+/// [`Zval::php_unserialize`], like the `allowed_classes` option of
+/// `unserialize()`.
+///
+/// Objects of other classes are unserialized as `__PHP_Incomplete_Class`.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum AllowedClasses<'a> {
+    /// All classes may be instantiated.
+    #[default]
+    All,
+    /// No classes may be instantiated.
+    None,
+    /// Only the given classes may be instantiated. The names are case
+    /// insensitive.
+    Only(&'a [&'a str]),
+}
+
+impl Zval {
+    /// Serializes the zval into PHP's native serialization format, like
+    /// `serialize()`.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Exception` - If an exception was thrown while serializing,
+    ///   e.g. by an object which can't be serialized.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::Zval;
+    ///
+    /// let mut zv = Zval::new();
+    /// zv.set_long(5);
+    /// assert_eq!(zv.php_serialize().unwrap().as_bytes(), b"i:5;");
+    /// ```
+    pub fn php_serialize(&self) -> Result<ZBox<ZendStr>> {
+        let ptr = unsafe { ext_php_rs_php_var_serialize(ptr::from_ref(self).cast_mut()) };
+        if let Some(e) = ExecutorGlobals::take_exception() {
+            return Err(Error::Exception(e));
+        }
+        if ptr.is_null() {
+            return Err(Error::ZvalConversion(self.get_type()));
+        }
+        Ok(unsafe { ZBox::from_raw(ptr) })
+    }
+
+    /// Unserializes a value from PHP's native serialization format, like
+    /// `unserialize()`.
+    ///
+    /// # Parameters
+    ///
+    /// * `data` - The serialized value.
+    /// * `allowed_classes` - The classes which may be instantiated.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::InvalidSerializedData` - If the data is not a valid serialized
+    ///   value.
+    /// * `Error::Exception` - If an exception was thrown while unserializing,
+    ///   e.g. by `__unserialize()` or `__wakeup()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::{AllowedClasses, Zval};
+    ///
+    /// let zv = Zval::php_unserialize(b"a:1:{i:0;s:1:\"a\";}", AllowedClasses::None).unwrap();
+    /// assert_eq!(zv.array().and_then(|ht| ht.get(0)).and_then(Zval::str), Some("a"));
+    /// ```
+    pub fn php_unserialize(data: &[u8], allowed_classes: AllowedClasses<'_>) -> Result<Zval> {
+        let mut classes = match allowed_classes {
+            AllowedClasses::All => None,
+            AllowedClasses::None => Some(ZendHashTable::new()),
+            AllowedClasses::Only(names) => {
+                let mut ht = ZendHashTable::with_capacity(names.len().try_into()?);
+                for name in names {
+                    ht.insert(name.to_ascii_lowercase().as_str(), ())?;
+                }
+                Some(ht)
+            }
+        };
+        let classes = classes.as_mut().map_or(ptr::null_mut(), |ht| &raw mut **ht);
+
+        let mut zv = Zval::new();
+        let mut offset = 0;
+        let success = unsafe {
+            ext_php_rs_php_var_unserialize(
+                &raw mut zv,
+                data.as_ptr().cast::<c_char>(),
+                data.len(),
+                classes,
+                &raw mut offset,
+            )
+        };
+        if let Some(e) = ExecutorGlobals::take_exception() {
+            return Err(Error::Exception(e));
+        }
+        if !success {
+            return Err(Error::InvalidSerializedData(offset));
+        }
+        Ok(zv)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    fn class_name(zv: &Zval) -> String {
+        zv.object().unwrap().get_class_name().unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        Embed::run(|| {
+            let zval = Embed::eval("[1, 'a' => [true, 1.5, null], 'b' => 'str'];").unwrap();
+            let data = zval.php_serialize().unwrap();
+            assert_eq!(
+                data.as_bytes(),
+                b"a:3:{i:0;i:1;s:1:\"a\";a:3:{i:0;b:1;i:1;d:1.5;i:2;N;}s:1:\"b\";s:3:\"str\";}"
+            );
+
+            let copy = Zval::php_unserialize(data.as_bytes(), AllowedClasses::All).unwrap();
+            assert!(copy.loose_equals(&zval));
+        });
+    }
+
+    #[test]
+    fn test_allowed_classes() {
+        Embed::run(|| {
+            let data = b"O:8:\"stdClass\":1:{s:1:\"a\";i:1;}";
+
+            let zval = Zval::php_unserialize(data, AllowedClasses::All).unwrap();
+            assert_eq!(class_name(&zval), "stdClass");
+            let zval = Zval::php_unserialize(data, AllowedClasses::None).unwrap();
+            assert_eq!(class_name(&zval), "__PHP_Incomplete_Class");
+            let zval = Zval::php_unserialize(data, AllowedClasses::Only(&["STDCLASS"])).unwrap();
+            assert_eq!(class_name(&zval), "stdClass");
+            let zval = Zval::php_unserialize(data, AllowedClasses::Only(&["Foo"])).unwrap();
+            assert_eq!(class_name(&zval), "__PHP_Incomplete_Class");
+        });
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        Embed::run(|| {
+            assert!(matches!(
+                Zval::php_unserialize(b"a:1:{i:0;", AllowedClasses::All),
+                Err(Error::InvalidSerializedData(_))
+            ));
+            assert!(matches!(
+                Zval::php_unserialize(b"", AllowedClasses::All),
+                Err(Error::InvalidSerializedData(0))
+            ));
+        });
+    }
+}
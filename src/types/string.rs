@@ -6,7 +6,15 @@ use std::{
     convert::TryFrom,
     ffi::{CStr, CString},
     fmt::Debug,
-    ptr, slice,
+    iter,
+    ops::{Bound, RangeBounds},
+    ptr,
+    rc::Rc,
+    slice,
+    sync::{
+        Arc,
+        atomic::{AtomicPtr, AtomicU64, Ordering},
+    },
 };
 
 use parking_lot::{Mutex, const_mutex};
@@ -16,12 +24,15 @@ use crate::{
     convert::{FromZval, IntoZval},
     error::{Error, Result},
     ffi::{
-        ext_php_rs_is_known_valid_utf8, ext_php_rs_set_known_valid_utf8,
-        ext_php_rs_zend_string_init, ext_php_rs_zend_string_release, zend_string,
-        zend_string_init_interned,
+        GC_FLAGS_SHIFT, GC_PERSISTENT, IS_STR_PERMANENT, ext_php_rs_is_known_valid_utf8,
+        ext_php_rs_set_known_valid_utf8, ext_php_rs_zend_string_init,
+        ext_php_rs_zend_string_release, zend_hash_func, zend_interned_string_find_permanent,
+        zend_new_interned_string, zend_string, zend_string_init_interned, zend_string_tolower_ex,
+        zend_ulong,
     },
     flags::DataType,
     types::Zval,
+    zend::engine,
 };
 
 /// A borrowed Zend string.
@@ -87,6 +98,30 @@ impl ZendStr {
         }
     }
 
+    /// Creates a new persistent Zend string from a slice of bytes.
+    ///
+    /// Persistent strings are allocated outside of the request heap, so they
+    /// can be created during startup, e.g. for class names or cached
+    /// templates, and used in any request. They are released when the
+    /// [`ZBox`] is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function was unable to allocate memory for the Zend
+    /// string.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let s = ZendStr::new_persistent("Hello, world!");
+    /// assert!(s.is_persistent());
+    /// ```
+    pub fn new_persistent(str: impl AsRef<[u8]>) -> ZBox<Self> {
+        Self::new(str, true)
+    }
+
     /// Creates a new Zend string from a [`CStr`].
     ///
     /// # Parameters
@@ -240,6 +275,209 @@ impl ZendStr {
         }
     }
 
+    /// Interns the given Zend string, returning the interned string with the
+    /// same content. Strings which are already interned are returned as is.
+    ///
+    /// During startup, the string is interned for the lifetime of the process.
+    /// Afterwards, it is interned for the current request, unless a string
+    /// with the same content was interned at startup.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function used to intern strings has not been set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let s = ZendStr::intern(ZendStr::new("key", false));
+    /// assert!(s.is_interned());
+    /// ```
+    #[must_use]
+    pub fn intern(str: ZBox<Self>) -> ZBox<Self> {
+        if str.is_interned() {
+            return str;
+        }
+        let _lock = INTERNED_LOCK.lock();
+        unsafe {
+            let intern = zend_new_interned_string.expect("`zend_new_interned_string` not ready");
+            // The reference held by the box is released by the engine.
+            let ptr = intern(str.into_raw())
+                .as_mut()
+                .expect("Failed to intern Zend string");
+            ZBox::from_raw(ptr)
+        }
+    }
+
+    /// Looks up a string interned for the lifetime of the process, e.g. the
+    /// names of functions and classes or strings interned by extensions
+    /// during startup.
+    ///
+    /// Returns [`None`] if no string with the given content was interned at
+    /// startup.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// assert!(ZendStr::find_interned("strlen").is_some());
+    /// ```
+    #[must_use]
+    pub fn find_interned(str: impl AsRef<[u8]>) -> Option<&'static Self> {
+        let mut key = Self::new(str, false);
+        // SAFETY: The key is a valid string, and permanent interned strings
+        // live until the engine shuts down.
+        unsafe { zend_interned_string_find_permanent(&raw mut *key).as_ref() }
+    }
+
+    /// Returns the hash of the string used by the engine, e.g. for array
+    /// keys.
+    ///
+    /// The hash is computed by the engine when the string is first used as a
+    /// key, and is always set for interned strings. Otherwise, it is computed
+    /// without being stored, as the string is borrowed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let a = ZendStr::new("key", false);
+    /// assert_eq!(a.zend_hash(), ZendStr::new_interned("key", false).zend_hash());
+    /// ```
+    #[must_use]
+    pub fn zend_hash(&self) -> zend_ulong {
+        if self.h != 0 {
+            return self.h;
+        }
+        // SAFETY: The pointer and length describe the content of the string.
+        unsafe { zend_hash_func(self.val.as_ptr(), self.len) }
+    }
+
+    /// Returns whether the strings have the same content, like `==`.
+    ///
+    /// Unlike comparing the bytes, strings which are the same in memory, e.g.
+    /// interned strings, are equal without comparing their content, and
+    /// strings whose hashes were computed by the engine and differ are not
+    /// equal without comparing their content.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let a = ZendStr::new_interned("key", false);
+    /// assert!(a.fast_eq(&ZendStr::new("key", false)));
+    /// assert!(!a.fast_eq(&ZendStr::new("other", false)));
+    /// ```
+    #[must_use]
+    pub fn fast_eq(&self, other: &ZendStr) -> bool {
+        if ptr::eq(self, other) {
+            return true;
+        }
+        if self.len != other.len || (self.h != 0 && other.h != 0 && self.h != other.h) {
+            return false;
+        }
+        self.as_bytes() == other.as_bytes()
+    }
+
+    /// Returns a copy of the string with ASCII letters converted into lower
+    /// case, like `strtolower()`.
+    ///
+    /// Strings which are already in lower case are returned as another
+    /// reference to the same string, so interned strings stay interned.
+    /// Otherwise, the copy is allocated the same way as the string.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let s = ZendStr::new("Hello, World!", false);
+    /// assert_eq!(s.to_lowercase().as_bytes(), b"hello, world!");
+    /// ```
+    #[must_use]
+    pub fn to_lowercase(&self) -> ZBox<Self> {
+        // SAFETY: The engine returns a new reference to either the string or a
+        // new string, which is owned by the box.
+        unsafe {
+            ZBox::from_raw(zend_string_tolower_ex(
+                self.as_ptr().cast_mut(),
+                self.is_persistent(),
+            ))
+        }
+    }
+
+    /// Returns a copy of the string with ASCII letters converted into upper
+    /// case, like `strtoupper()`.
+    ///
+    /// Strings which are already in upper case are returned as another
+    /// reference to the same string on PHP 8.2 and later, so interned strings
+    /// stay interned. Otherwise, the copy is allocated the same way as the
+    /// string.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let s = ZendStr::new("Hello, World!", false);
+    /// assert_eq!(s.to_uppercase().as_bytes(), b"HELLO, WORLD!");
+    /// ```
+    #[must_use]
+    pub fn to_uppercase(&self) -> ZBox<Self> {
+        cfg_if::cfg_if! {
+            if #[cfg(php82)] {
+                // SAFETY: The engine returns a new reference to either the
+                // string or a new string, which is owned by the box.
+                unsafe {
+                    ZBox::from_raw(crate::ffi::zend_string_toupper_ex(
+                        self.as_ptr().cast_mut(),
+                        self.is_persistent(),
+                    ))
+                }
+            } else {
+                let upper = self.as_bytes().to_ascii_uppercase();
+                if self.is_persistent() {
+                    Self::new_persistent(upper)
+                } else {
+                    Self::new(upper, false)
+                }
+            }
+        }
+    }
+
+    /// Returns whether the string is allocated outside of the request heap,
+    /// i.e. whether it outlives the current request.
+    #[must_use]
+    pub fn is_persistent(&self) -> bool {
+        // SAFETY: Type info is initialized by Zend when the string is created.
+        let type_info = unsafe { self.gc.u.type_info };
+        (type_info >> GC_FLAGS_SHIFT) & GC_PERSISTENT != 0
+    }
+
+    /// Returns a persistent copy of the string, e.g. to cache a string of the
+    /// current request for later requests.
+    #[must_use]
+    pub fn to_persistent(&self) -> ZBox<Self> {
+        Self::new_persistent(self.as_bytes())
+    }
+
+    /// Returns whether the string is interned.
+    #[must_use]
+    pub fn is_interned(&self) -> bool {
+        self.gc.is_immutable()
+    }
+
+    /// Returns whether the string is interned for the lifetime of the process.
+    pub(crate) fn is_permanent(&self) -> bool {
+        // SAFETY: Type info is initialized by Zend when the string is created.
+        let type_info = unsafe { self.gc.u.type_info };
+        (type_info >> GC_FLAGS_SHIFT) & IS_STR_PERMANENT != 0
+    }
+
     /// Returns the length of the string.
     ///
     /// # Example
@@ -309,12 +547,107 @@ impl ZendStr {
         Ok(str)
     }
 
+    /// Attempts to return a reference to the underlying bytes inside the Zend
+    /// string, like [`as_str`](Self::as_str), without caching the result of
+    /// the UTF-8 check in the string. The string is not modified, so it can be
+    /// read by multiple threads at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidUtf8`] variant if the [`str`] contains
+    /// non-UTF-8 characters.
+    pub(crate) fn as_str_uncached(&self) -> Result<&str> {
+        if unsafe { ext_php_rs_is_known_valid_utf8(self.as_ptr()) } {
+            let str = unsafe { std::str::from_utf8_unchecked(self.as_bytes()) };
+            return Ok(str);
+        }
+        std::str::from_utf8(self.as_bytes()).map_err(|_| Error::InvalidUtf8)
+    }
+
     /// Returns a reference to the underlying bytes inside the Zend string.
     #[must_use]
     pub fn as_bytes(&self) -> &[u8] {
         unsafe { slice::from_raw_parts(self.val.as_ptr().cast(), self.len()) }
     }
 
+    /// Returns a reference to the bytes in the given range of the Zend string,
+    /// or [`None`] if the range is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let s = ZendStr::new("hello, world!", false);
+    /// assert_eq!(s.slice(7..), Some(&b"world!"[..]));
+    /// assert_eq!(s.slice(..20), None);
+    /// ```
+    #[must_use]
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Option<&[u8]> {
+        self.as_bytes().get(bounds(&range))
+    }
+
+    /// Attempts to return a reference to the bytes in the given range of the
+    /// Zend string as a [`str`]. Only the bytes in the range are validated.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::OutOfBounds`] - If the range is out of bounds.
+    /// * [`Error::InvalidUtf8`] - If the bytes in the range are not valid
+    ///   UTF-8, e.g. if the range splits a multi-byte character.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let s = ZendStr::new("hello, world!", false);
+    /// assert_eq!(s.try_substr(..5).unwrap(), "hello");
+    /// ```
+    pub fn try_substr(&self, range: impl RangeBounds<usize>) -> Result<&str> {
+        let bytes = self.slice(range).ok_or(Error::OutOfBounds(self.len()))?;
+        std::str::from_utf8(bytes).map_err(|_| Error::InvalidUtf8)
+    }
+
+    /// Returns an iterator over the parts of the Zend string separated by the
+    /// given separator, like PHP's `explode()`. The parts borrow from the
+    /// string, so nothing is copied.
+    ///
+    /// An empty separator yields the whole string.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendStr;
+    ///
+    /// let s = ZendStr::new("a,b,,c", false);
+    /// let parts: Vec<&[u8]> = s.split(b",").collect();
+    /// assert_eq!(parts, [&b"a"[..], b"b", b"", b"c"]);
+    /// ```
+    pub fn split<'a>(&'a self, separator: &'a [u8]) -> impl Iterator<Item = &'a [u8]> + 'a {
+        let mut rest = Some(self.as_bytes());
+        iter::from_fn(move || {
+            let bytes = rest?;
+            let found = if separator.is_empty() {
+                None
+            } else {
+                bytes
+                    .windows(separator.len())
+                    .position(|window| window == separator)
+            };
+            match found {
+                Some(i) => {
+                    rest = Some(&bytes[i + separator.len()..]);
+                    Some(&bytes[..i])
+                }
+                None => {
+                    rest = None;
+                    Some(bytes)
+                }
+            }
+        })
+    }
+
     /// Returns a raw pointer to this object
     #[must_use]
     pub fn as_ptr(&self) -> *const ZendStr {
@@ -327,6 +660,67 @@ impl ZendStr {
     }
 }
 
+/// Converts range bounds into a pair of bounds usable to index a slice.
+fn bounds(range: &impl RangeBounds<usize>) -> (Bound<usize>, Bound<usize>) {
+    (range.start_bound().cloned(), range.end_bound().cloned())
+}
+
+/// A string literal which is interned once per process, created by the
+/// [`lazy_interned!`](crate::lazy_interned) macro.
+///
+/// The string is interned when it is first used. It is only cached if it was
+/// interned permanently, i.e. if it was first used during startup or if PHP
+/// had already interned the same string permanently. When it is first used
+/// during a request, it is interned for that request only and is not cached,
+/// so it is interned again on every use.
+///
+/// The cached string is released when the engine is shut down, and interned
+/// again the next time the engine runs.
+#[derive(Debug)]
+pub struct LazyInterned {
+    value: &'static str,
+    ptr: AtomicPtr<ZendStr>,
+    /// The run of the engine in which the string was cached, plus one, or
+    /// zero if it was not cached.
+    engine: AtomicU64,
+}
+
+impl LazyInterned {
+    /// Creates a lazily interned string with the given content.
+    #[must_use]
+    pub const fn new(value: &'static str) -> Self {
+        Self {
+            value,
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            engine: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the interned string, interning it on first use.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function used to create interned strings has not been
+    /// set.
+    #[must_use]
+    pub fn get(&self) -> ZBox<ZendStr> {
+        let engine = engine::current_engine() + 1;
+        if self.engine.load(Ordering::Acquire) == engine {
+            // SAFETY: Cached strings are interned until the engine is shut
+            // down, and are not released by the box.
+            return unsafe { ZBox::from_raw(self.ptr.load(Ordering::Acquire)) };
+        }
+        let str = ZendStr::new_interned(self.value, true);
+        if str.is_permanent() {
+            // Permanent strings are only interned once per run of the engine, so
+            // concurrent calls store the same pointer.
+            self.ptr.store(str.as_ptr().cast_mut(), Ordering::Release);
+            self.engine.store(engine, Ordering::Release);
+        }
+        str
+    }
+}
+
 unsafe impl ZBoxable for ZendStr {
     fn free(&mut self) {
         unsafe { ext_php_rs_zend_string_release(self) };
@@ -349,8 +743,13 @@ impl<T> PartialEq<T> for ZendStr
 where
     T: AsRef<[u8]>,
 {
+    /// Compares the bytes of the strings. The same bytes in memory, e.g. of
+    /// the same interned string, are equal without comparing them. Unlike
+    /// [`ZendStr::fast_eq`], hashes are not compared, as `other` is not known
+    /// to be a [`ZendStr`].
     fn eq(&self, other: &T) -> bool {
-        self.as_ref() == other.as_ref()
+        let (bytes, other) = (self.as_bytes(), other.as_ref());
+        ptr::eq(bytes, other) || bytes == other
     }
 }
 
@@ -453,6 +852,34 @@ macro_rules! try_into_zval_str {
 
 try_into_zval_str!(String);
 try_into_zval_str!(&str);
+try_into_zval_str!(Cow<'_, str>);
+try_into_zval_str!(Box<str>);
+try_into_zval_str!(Rc<str>);
+try_into_zval_str!(Arc<str>);
+
+impl From<ZBox<ZendStr>> for Zval {
+    fn from(value: ZBox<ZendStr>) -> Self {
+        let mut zv = Self::new();
+        zv.set_zend_string(value);
+        zv
+    }
+}
+
+/// Owned Zend strings are moved into the zval without copying their
+/// contents, which makes them the cheapest way to return large strings.
+///
+/// Persistent strings are copied when `persistent` is `false`, see
+/// [`Zval::set_zend_string`].
+impl IntoZval for ZBox<ZendStr> {
+    const TYPE: DataType = DataType::String;
+    const NULLABLE: bool = false;
+
+    #[inline]
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        zv.set_zend_string_in(self, persistent);
+        Ok(())
+    }
+}
 try_from_zval!(String, string, String);
 
 impl<'a> FromZval<'a> for &'a str {
@@ -463,10 +890,61 @@ impl<'a> FromZval<'a> for &'a str {
     }
 }
 
+/// Borrows the content of a string which is valid UTF-8. Otherwise, the
+/// content is copied with invalid sequences replaced by
+/// [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+impl<'a> FromZval<'a> for Cow<'a, str> {
+    const TYPE: DataType = DataType::String;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        let str = zval.zend_str()?;
+        Some(
+            str.as_str()
+                .map_or_else(|_| String::from_utf8_lossy(str.as_bytes()), Cow::Borrowed),
+        )
+    }
+}
+
+macro_rules! from_zval_shared_str {
+    ($type: ty) => {
+        impl FromZval<'_> for $type {
+            const TYPE: DataType = DataType::String;
+
+            fn from_zval(zval: &Zval) -> Option<Self> {
+                zval.str().map(Into::into)
+            }
+        }
+    };
+}
+
+from_zval_shared_str!(Box<str>);
+from_zval_shared_str!(Rc<str>);
+from_zval_shared_str!(Arc<str>);
+
 #[cfg(test)]
 #[cfg(feature = "embed")]
 mod tests {
+    use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+    use super::ZendStr;
+    use crate::boxed::ZBox;
+    use crate::convert::{FromZval, IntoZval};
     use crate::embed::Embed;
+    use crate::error::Error;
+    use crate::types::Zval;
+
+    #[test]
+    fn test_zend_string_into_zval() {
+        Embed::run(|| {
+            let s = ZendStr::new("Hello, world!", false);
+            let ptr = s.as_ptr();
+
+            let zval = s.into_zval(false).expect("Failed to convert string");
+            let zend_str = zval.zend_str().expect("Zval is not a string");
+            assert_eq!(zend_str.as_ptr(), ptr);
+            assert_eq!(zend_str.as_str().expect("Invalid UTF-8"), "Hello, world!");
+        });
+    }
 
     #[test]
     fn test_string() {
@@ -481,4 +959,174 @@ mod tests {
             assert_eq!(zval.string(), Some("foo".to_string()));
         });
     }
+
+    #[test]
+    fn test_shared_strings() {
+        Embed::run(|| {
+            let zval = Cow::<str>::Owned("a".to_owned())
+                .into_zval(false)
+                .expect("Failed to convert string");
+            assert_eq!(zval.str(), Some("a"));
+            let zval = Box::<str>::from("b")
+                .into_zval(false)
+                .expect("Failed to convert string");
+            assert_eq!(zval.str(), Some("b"));
+            let zval = Rc::<str>::from("c")
+                .into_zval(false)
+                .expect("Failed to convert string");
+            assert_eq!(zval.str(), Some("c"));
+            let zval = Arc::<str>::from("d")
+                .into_zval(false)
+                .expect("Failed to convert string");
+            assert_eq!(zval.str(), Some("d"));
+
+            assert!(matches!(
+                Cow::<str>::from_zval(&zval),
+                Some(Cow::Borrowed("d"))
+            ));
+            assert_eq!(Box::<str>::from_zval(&zval).as_deref(), Some("d"));
+            assert_eq!(Rc::<str>::from_zval(&zval).as_deref(), Some("d"));
+            assert_eq!(Arc::<str>::from_zval(&zval).as_deref(), Some("d"));
+            assert!(
+                Arc::<str>::from_zval(&5.into_zval(false).expect("Failed to convert string"))
+                    .is_none()
+            );
+        });
+    }
+
+    #[test]
+    fn test_interned_strings() {
+        Embed::run(|| {
+            let s = ZendStr::intern(ZendStr::new("ext-php-rs interned", false));
+            assert!(s.is_interned());
+            let again = ZendStr::intern(ZendStr::new("ext-php-rs interned", false));
+            assert_eq!(s.as_ptr(), again.as_ptr());
+            assert!(!ZendStr::new("ext-php-rs interned", false).is_interned());
+
+            let found = ZendStr::find_interned("strlen").expect("Function names are interned");
+            assert_eq!(found.as_bytes(), b"strlen");
+            assert!(ZendStr::find_interned("ext-php-rs not interned").is_none());
+
+            let get = || crate::lazy_interned!("strlen");
+            assert_eq!(get().as_ptr(), found.as_ptr());
+            assert_eq!(get().as_ptr(), get().as_ptr());
+        });
+    }
+
+    #[test]
+    fn test_lazy_interned_engine_restart() {
+        fn get() -> ZBox<ZendStr> {
+            crate::lazy_interned!("strrev")
+        }
+
+        for _ in 0..2 {
+            Embed::run(|| {
+                let found = ZendStr::find_interned("strrev").expect("Function names are interned");
+                assert_eq!(get().as_ptr(), found.as_ptr());
+                assert_eq!(get().as_bytes(), b"strrev");
+            });
+        }
+    }
+
+    #[test]
+    fn test_persistent_strings() {
+        Embed::run(|| {
+            let s = ZendStr::new_persistent("cached");
+            assert!(s.is_persistent());
+            assert!(!ZendStr::new("cached", false).is_persistent());
+            let copy = ZendStr::new("cached", false).to_persistent();
+            assert!(copy.is_persistent());
+            assert_eq!(copy.as_bytes(), b"cached");
+
+            let ptr = s.as_ptr();
+            let zval = s.into_zval(false).expect("Failed to convert string");
+            let zend_str = zval.zend_str().expect("Zval is not a string");
+            assert!(!zend_str.is_persistent());
+            assert_ne!(zend_str.as_ptr(), ptr);
+            assert_eq!(zend_str.as_bytes(), b"cached");
+
+            let zval = Zval::from(ZendStr::new_persistent("from"));
+            let zend_str = zval.zend_str().expect("Zval is not a string");
+            assert!(!zend_str.is_persistent());
+            assert_eq!(zend_str.as_bytes(), b"from");
+
+            let zval = ZendStr::new_persistent("kept")
+                .into_zval(true)
+                .expect("Failed to convert string");
+            assert!(zval.zend_str().is_some_and(ZendStr::is_persistent));
+        });
+    }
+
+    #[test]
+    fn test_hash_and_fast_eq() {
+        Embed::run(|| {
+            let interned = ZendStr::new_interned("ext-php-rs key", false);
+            let copy = ZendStr::new("ext-php-rs key", false);
+            assert_eq!(interned.zend_hash(), copy.zend_hash());
+            assert_ne!(
+                interned.zend_hash(),
+                ZendStr::new("other", false).zend_hash()
+            );
+
+            assert!(interned.fast_eq(&interned));
+            assert!(interned.fast_eq(&copy));
+            assert!(copy.fast_eq(&interned));
+            assert!(!interned.fast_eq(&ZendStr::new("ext-php-rs kex", false)));
+            assert!(!interned.fast_eq(&ZendStr::new("ext-php-rs", false)));
+
+            assert!(*interned == interned.as_bytes());
+            assert!(*interned == *copy);
+            assert!(*interned != "ext-php-rs kex");
+        });
+    }
+
+    #[test]
+    fn test_slices() {
+        Embed::run(|| {
+            let s = ZendStr::new("id,name\n1,caf\u{e9}\n", false);
+            assert_eq!(s.slice(3..7), Some(&b"name"[..]));
+            assert_eq!(s.slice(..), Some(s.as_bytes()));
+            assert_eq!(s.slice(5..100), None);
+
+            assert_eq!(s.try_substr(..2).expect("Invalid substring"), "id");
+            assert!(matches!(s.try_substr(..15), Err(Error::InvalidUtf8)));
+            assert!(matches!(s.try_substr(20..), Err(Error::OutOfBounds(16))));
+
+            let lines: Vec<&[u8]> = s.split(b"\n").collect();
+            assert_eq!(lines, [&b"id,name"[..], b"1,caf\xc3\xa9", b""]);
+            let fields: Vec<&[u8]> = s.split(b",").collect();
+            assert_eq!(fields.len(), 3);
+            assert_eq!(s.split(b"").collect::<Vec<_>>(), [s.as_bytes()]);
+            assert_eq!(fields[0].as_ptr(), s.as_bytes().as_ptr());
+        });
+    }
+
+    #[test]
+    fn test_case_conversion() {
+        Embed::run(|| {
+            let s = ZendStr::new("Caf\u{e9} LATTE", false);
+            assert_eq!(s.to_lowercase().as_bytes(), "caf\u{e9} latte".as_bytes());
+            assert_eq!(s.to_uppercase().as_bytes(), "CAF\u{e9} LATTE".as_bytes());
+
+            let interned = ZendStr::new_interned("ext-php-rs lower", false);
+            let lower = interned.to_lowercase();
+            assert_eq!(lower.as_ptr(), interned.as_ptr());
+            assert!(lower.is_interned());
+            assert!(!interned.to_uppercase().is_interned());
+        });
+    }
+
+    #[test]
+    fn test_lossy_cow() {
+        Embed::run(|| {
+            let zval = ZendStr::new(b"caf\xe9", false)
+                .into_zval(false)
+                .expect("Failed to convert string");
+            assert!(matches!(
+                Cow::<str>::from_zval(&zval),
+                Some(Cow::Owned(s)) if s == "caf\u{fffd}"
+            ));
+            assert!(zval.str().is_none());
+        });
+    }
 }
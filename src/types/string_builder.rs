@@ -0,0 +1,195 @@
+//! A builder for Zend strings backed by the engine's `smart_str` buffer.
+
+use std::{fmt, ptr};
+
+use crate::{
+    boxed::ZBox,
+    error::Result,
+    ffi::{
+        ext_php_rs_smart_str_alloc, ext_php_rs_smart_str_appendl, ext_php_rs_smart_str_extract,
+        ext_php_rs_smart_str_free, smart_str,
+    },
+    types::{ZendStr, Zval},
+};
+
+/// Builds a [`ZendStr`] incrementally, like PHP's `smart_str`.
+///
+/// The content is appended into a growing Zend string, which is returned by
+/// [`ZendStrBuilder::finish`] without copying it. The string is allocated on
+/// the request heap.
+///
+/// The builder implements [`fmt::Write`], so Rust formatting can write into
+/// it with [`write!`] without allocating intermediate strings.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::types::ZendStrBuilder;
+///
+/// let mut builder = ZendStrBuilder::with_capacity(16);
+/// builder.push_str("Hello, ");
+/// builder.push_bytes(b"world!");
+/// assert_eq!(builder.finish().as_bytes(), b"Hello, world!");
+/// ```
+///
+/// ```no_run
+/// use std::fmt::Write;
+///
+/// use ext_php_rs::types::ZendStrBuilder;
+///
+/// let mut builder = ZendStrBuilder::new();
+/// write!(builder, "{} + {} = {}", 1, 2, 1 + 2).unwrap();
+/// assert_eq!(builder.finish().as_bytes(), b"1 + 2 = 3");
+/// ```
+#[derive(Debug)]
+pub struct ZendStrBuilder {
+    buf: smart_str,
+}
+
+impl ZendStrBuilder {
+    /// Creates an empty builder. No memory is allocated until content is
+    /// appended.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buf: smart_str {
+                s: ptr::null_mut(),
+                a: 0,
+            },
+        }
+    }
+
+    /// Creates an empty builder with room for at least the given number of
+    /// bytes.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut builder = Self::new();
+        builder.reserve(capacity);
+        builder
+    }
+
+    /// Reserves room for at least the given number of additional bytes.
+    pub fn reserve(&mut self, additional: usize) {
+        // SAFETY: The buffer is valid and owned by the builder.
+        unsafe { ext_php_rs_smart_str_alloc(&raw mut self.buf, additional) };
+    }
+
+    /// Appends the given bytes.
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        // SAFETY: The buffer is valid and owned by the builder.
+        unsafe {
+            ext_php_rs_smart_str_appendl(&raw mut self.buf, bytes.as_ptr().cast(), bytes.len());
+        }
+    }
+
+    /// Appends the given string.
+    pub fn push_str(&mut self, str: &str) {
+        self.push_bytes(str.as_bytes());
+    }
+
+    /// Appends the content of the given Zend string.
+    pub fn push_zend_str(&mut self, str: &ZendStr) {
+        self.push_bytes(str.as_bytes());
+    }
+
+    /// Appends the given zval converted into a string like PHP's `(string)`
+    /// cast does, calling the `__toString()` method of objects.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`](crate::error::Error::Exception) - If the engine
+    ///   threw an exception, e.g. for an object which cannot be converted into
+    ///   a string. Nothing is appended in this case.
+    pub fn push_zval(&mut self, zval: &Zval) -> Result<()> {
+        if let Some(str) = zval.dereference().zend_str() {
+            self.push_zend_str(str);
+        } else {
+            self.push_zend_str(&zval.cast_to_string()?);
+        }
+        Ok(())
+    }
+
+    /// Returns the number of bytes appended so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        // SAFETY: The string is either null or valid and owned by the builder.
+        unsafe { self.buf.s.as_ref() }.map_or(0, ZendStr::len)
+    }
+
+    /// Returns whether nothing has been appended so far.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the built string.
+    #[must_use]
+    pub fn finish(mut self) -> ZBox<ZendStr> {
+        // SAFETY: The buffer is valid and left empty, and the returned string
+        // is owned by the caller.
+        unsafe { ZBox::from_raw(ext_php_rs_smart_str_extract(&raw mut self.buf)) }
+    }
+}
+
+impl Default for ZendStrBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Write for ZendStrBuilder {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+impl Drop for ZendStrBuilder {
+    fn drop(&mut self) {
+        // SAFETY: The buffer is valid and owned by the builder.
+        unsafe { ext_php_rs_smart_str_free(&raw mut self.buf) };
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{convert::IntoZval, embed::Embed};
+
+    #[test]
+    fn test_builder() {
+        Embed::run(|| {
+            let mut builder = ZendStrBuilder::new();
+            assert!(builder.is_empty());
+            builder.push_str("a=");
+            builder.push_zval(&1.5.into_zval(false).unwrap()).unwrap();
+            builder.push_bytes(b"\0");
+            builder.push_zval(&"z".into_zval(false).unwrap()).unwrap();
+            assert_eq!(builder.len(), 6);
+            assert_eq!(builder.finish().as_bytes(), b"a=1.5\0z");
+
+            assert_eq!(ZendStrBuilder::new().finish().as_bytes(), b"");
+
+            let mut builder = ZendStrBuilder::with_capacity(1 << 16);
+            for _ in 0..1000 {
+                builder.push_str("0123456789");
+            }
+            assert_eq!(builder.finish().len(), 10_000);
+        });
+    }
+
+    #[test]
+    fn test_write() {
+        use std::fmt::Write;
+
+        Embed::run(|| {
+            let mut builder = ZendStrBuilder::new();
+            builder.push_str("<li>");
+            write!(builder, "{:>3}|{:.2}|{}", 7, 1.0 / 3.0, 'é').unwrap();
+            builder.write_char('!').unwrap();
+            assert_eq!(builder.finish().as_bytes(), "<li>  7|0.33|é!".as_bytes());
+        });
+    }
+}
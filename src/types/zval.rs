@@ -13,14 +13,15 @@ use crate::{
     convert::{FromZval, FromZvalMut, IntoZval, IntoZvalDyn},
     error::{Error, Result},
     ffi::{
-        _zval_struct__bindgen_ty_1, _zval_struct__bindgen_ty_2, ext_php_rs_zend_string_release,
-        zend_array_dup, zend_is_callable, zend_is_identical, zend_is_iterable, zend_resource,
-        zend_value, zval, zval_ptr_dtor,
+        _zval_struct__bindgen_ty_1, _zval_struct__bindgen_ty_2, ext_php_rs_set_known_valid_utf8,
+        ext_php_rs_zend_string_release, php_var_dump, php_var_export, zend_is_callable,
+        zend_is_identical, zend_is_iterable, zend_resource, zend_value, zval, zval_ptr_dtor,
     },
     flags::DataType,
     flags::ZvalTypeFlags,
     rc::PhpRc,
     types::{ZendCallable, ZendHashTable, ZendLong, ZendObject, ZendStr},
+    zend::capture_output,
 };
 
 /// A zend value. This is the primary storage container used throughout the Zend
@@ -232,25 +233,9 @@ impl Zval {
     /// if so, creates a private copy. This is equivalent to PHP's
     /// `SEPARATE_ARRAY()` macro and prevents the "Assertion failed:
     /// `zend_gc_refcount` == 1" error that occurs when modifying shared arrays.
+    /// See [`ZendHashTable::make_writable`].
     pub fn array_mut(&mut self) -> Option<&mut ZendHashTable> {
-        if self.is_array() {
-            unsafe {
-                let arr = self.value.arr;
-                // Check if the array is shared (refcount > 1)
-                // If so, we need to separate it (copy-on-write)
-                if (*arr).gc.refcount > 1 {
-                    // Decrement the refcount of the original array
-                    (*arr).gc.refcount -= 1;
-                    // Duplicate the array to get our own private copy
-                    let new_arr = zend_array_dup(arr);
-                    // Update the zval to point to the new array
-                    self.value.arr = new_arr;
-                }
-                self.value.arr.as_mut()
-            }
-        } else {
-            None
-        }
+        ZendHashTable::make_writable(self)
     }
 
     /// Returns the value of the zval if it is an object.
@@ -273,6 +258,120 @@ impl Zval {
         }
     }
 
+    /// Returns the value of the zval if it is a long, see [`Zval::long`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeMismatch`] if the zval is not a long.
+    pub fn try_long(&self) -> Result<ZendLong> {
+        self.long().ok_or_else(|| self.mismatch(DataType::Long))
+    }
+
+    /// Returns the value of the zval if it is a bool, see [`Zval::bool`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeMismatch`] if the zval is not a bool.
+    pub fn try_bool(&self) -> Result<bool> {
+        self.bool().ok_or_else(|| self.mismatch(DataType::Bool))
+    }
+
+    /// Returns the value of the zval if it is a double, see [`Zval::double`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeMismatch`] if the zval is not a double.
+    pub fn try_double(&self) -> Result<f64> {
+        self.double().ok_or_else(|| self.mismatch(DataType::Double))
+    }
+
+    /// Returns the value of the zval as a zend string if it is a string, see
+    /// [`Zval::zend_str`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeMismatch`] if the zval is not a string.
+    pub fn try_zend_str(&self) -> Result<&ZendStr> {
+        self.zend_str()
+            .ok_or_else(|| self.mismatch(DataType::String))
+    }
+
+    /// Returns the value of the zval if it is a string, see [`Zval::str`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::TypeMismatch`] - If the zval is not a string.
+    /// * [`Error::InvalidUtf8`] - If the string is not valid UTF-8.
+    pub fn try_str(&self) -> Result<&str> {
+        self.try_zend_str()?.as_str()
+    }
+
+    /// Returns the value of the zval if it is a string, see [`Zval::string`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::TypeMismatch`] - If the zval is not a string.
+    /// * [`Error::InvalidUtf8`] - If the string is not valid UTF-8.
+    pub fn try_string(&self) -> Result<String> {
+        self.try_str().map(ToString::to_string)
+    }
+
+    /// Returns the underlying hashtable if the zval contains an array, see
+    /// [`Zval::array`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeMismatch`] if the zval is not an array.
+    pub fn try_array(&self) -> Result<&ZendHashTable> {
+        self.array().ok_or_else(|| self.mismatch(DataType::Array))
+    }
+
+    /// Returns a mutable reference to the underlying hashtable if the zval
+    /// contains an array, separating it if it is shared, see
+    /// [`Zval::array_mut`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeMismatch`] if the zval is not an array.
+    pub fn try_array_mut(&mut self) -> Result<&mut ZendHashTable> {
+        let actual = self.get_type();
+        self.array_mut().ok_or(Error::TypeMismatch {
+            expected: DataType::Array,
+            actual,
+        })
+    }
+
+    /// Returns the object contained in the zval, see [`Zval::object`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeMismatch`] if the zval is not an object.
+    pub fn try_object(&self) -> Result<&ZendObject> {
+        self.object()
+            .ok_or_else(|| self.mismatch(DataType::Object(None)))
+    }
+
+    /// Returns a mutable reference to the object contained in the zval, see
+    /// [`Zval::object_mut`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeMismatch`] if the zval is not an object.
+    pub fn try_object_mut(&mut self) -> Result<&mut ZendObject> {
+        let actual = self.get_type();
+        self.object_mut().ok_or(Error::TypeMismatch {
+            expected: DataType::Object(None),
+            actual,
+        })
+    }
+
+    fn mismatch(&self, expected: DataType) -> Error {
+        Error::TypeMismatch {
+            expected,
+            actual: self.get_type(),
+        }
+    }
+
     /// Attempts to call a method on the object contained in the zval.
     ///
     /// # Errors
@@ -287,6 +386,22 @@ impl Zval {
             .try_call_method(name, params)
     }
 
+    /// Attempts to call a method on the object contained in the zval with a
+    /// list of named arguments, see [`ZendObject::try_call_method_named`].
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error if the [`Zval`] is not an object.
+    pub fn try_call_method_named(
+        &self,
+        name: &str,
+        params: &[(&str, &dyn IntoZvalDyn)],
+    ) -> Result<Zval> {
+        self.object()
+            .ok_or(Error::Object)?
+            .try_call_method_named(name, params)
+    }
+
     /// Returns the value of the zval if it is an internal indirect reference.
     #[must_use]
     pub fn indirect(&self) -> Option<&Zval> {
@@ -409,6 +524,15 @@ impl Zval {
         self.get_type() == DataType::Long
     }
 
+    /// Returns true if the zval is undefined, false otherwise.
+    ///
+    /// Undefined zvals hold no value, e.g. unset properties or the slots of
+    /// parameters which were not passed. PHP code reading them sees `null`.
+    #[must_use]
+    pub fn is_undef(&self) -> bool {
+        self.get_type() == DataType::Undef
+    }
+
     /// Returns true if the zval is null, false otherwise.
     #[must_use]
     pub fn is_null(&self) -> bool {
@@ -558,20 +682,42 @@ impl Zval {
     /// Never returns an error.
     // TODO: Check if we can drop the result here.
     pub fn set_string(&mut self, val: &str, persistent: bool) -> Result<()> {
-        self.set_zend_string(ZendStr::new(val, persistent));
+        let mut zend_str = ZendStr::new(val, persistent);
+        // Rust strings are valid UTF-8, which saves validating the contents when
+        // the string is read back.
+        unsafe { ext_php_rs_set_known_valid_utf8(zend_str.as_mut_ptr()) };
+        self.set_zend_string_in(zend_str, persistent);
         Ok(())
     }
 
     /// Sets the value of the zval as a Zend string.
     ///
     /// The Zval takes ownership of the string. When the Zval is dropped,
-    /// the string will be released.
+    /// the string will be released. Persistent strings are copied into the
+    /// request heap, as the zval belongs to the current request. Interned
+    /// strings are always moved.
     ///
     /// # Parameters
     ///
     /// * `val` - String content.
     pub fn set_zend_string(&mut self, val: ZBox<ZendStr>) {
-        self.change_type(ZvalTypeFlags::StringEx);
+        self.set_zend_string_in(val, false);
+    }
+
+    /// Sets the value of the zval as a Zend string, which is copied into the
+    /// request heap if it is persistent and the zval is not.
+    pub(crate) fn set_zend_string_in(&mut self, val: ZBox<ZendStr>, persistent: bool) {
+        let val = if !persistent && val.is_persistent() && !val.is_interned() {
+            ZendStr::new(val.as_bytes(), false)
+        } else {
+            val
+        };
+        // Interned strings, e.g. returned by casts, are not reference counted.
+        self.change_type(if val.get_rc().is_immutable() {
+            ZvalTypeFlags::InternedStringEx
+        } else {
+            ZvalTypeFlags::StringEx
+        });
         self.value.str_ = val.into_raw();
     }
 
@@ -787,6 +933,8 @@ impl Zval {
     /// For example, if the zval contains a long, it will simply copy the value.
     /// However, if the zval contains an object, the new zval will point to the
     /// same object, and the objects reference counter will be incremented.
+    /// Use [`ZBox::share`] to share values which are held in a [`ZBox`], and
+    /// [`Zval::deep_clone`] to copy arrays and strings instead.
     ///
     /// # Returns
     ///
@@ -811,6 +959,185 @@ impl Zval {
 
         new
     }
+
+    /// Creates a deep clone of the [`Zval`], which shares no arrays or strings
+    /// with the original.
+    ///
+    /// Arrays are duplicated recursively and strings are copied, so the clone
+    /// can be modified without separating it first. Objects and resources are
+    /// shared as by [`Zval::shallow_clone`], as are PHP references inside of
+    /// arrays. A reference holding the zval itself is replaced by a clone of
+    /// its value.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::{convert::IntoZval, types::ZendHashTable};
+    ///
+    /// let mut inner = ZendHashTable::new();
+    /// inner.push("a").unwrap();
+    /// let mut outer = ZendHashTable::new();
+    /// outer.push(inner).unwrap();
+    /// let original = outer.into_zval(false).unwrap();
+    ///
+    /// let copy = original.deep_clone();
+    /// assert_eq!(copy.array().and_then(|ht| ht.get(0)).unwrap().refcount(), Some(1));
+    /// ```
+    #[must_use]
+    pub fn deep_clone(&self) -> Zval {
+        let zv = self.dereference();
+        if let Some(ht) = zv.array() {
+            let mut copy = ht.to_owned();
+            for val in copy.values_mut() {
+                if !val.is_reference() {
+                    let inner = val.deep_clone();
+                    *val = inner;
+                }
+            }
+            let mut new = Zval::new();
+            new.set_hashtable(copy);
+            new
+        } else if let Some(str) = zv.zend_str() {
+            let mut new = Zval::new();
+            new.set_zend_string(ZendStr::new(str.as_bytes(), false));
+            new
+        } else {
+            zv.shallow_clone()
+        }
+    }
+
+    /// Returns whether the value of the zval is reference counted, which is
+    /// the case for objects, resources and references, and for strings and
+    /// arrays which are not immutable.
+    #[must_use]
+    pub fn is_refcounted(&self) -> bool {
+        // SAFETY: `u1` union is only used for easier bitmasking. It is valid to read
+        // from either of the variants.
+        let flags = ZvalTypeFlags::from_bits_retain(unsafe { self.u1.type_info });
+        flags.contains(ZvalTypeFlags::RefCounted)
+    }
+
+    /// Returns the number of references to the value of the zval, or [`None`]
+    /// if the value is not reference counted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::Zval;
+    ///
+    /// let zv = Zval::new_array();
+    /// assert_eq!(zv.refcount(), Some(1));
+    /// let copy = zv.shallow_clone();
+    /// assert_eq!(zv.refcount(), Some(2));
+    /// ```
+    #[must_use]
+    pub fn refcount(&self) -> Option<u32> {
+        // SAFETY: Reference counted values start with their reference counter.
+        self.is_refcounted()
+            .then(|| unsafe { (*self.value.counted).gc.refcount })
+    }
+
+    /// Increments the reference counter of the value of the zval, if it is
+    /// reference counted.
+    ///
+    /// The reference must be released with [`delref`](Self::delref), or the
+    /// value is leaked. Use [`shallow_clone`](Self::shallow_clone) to create
+    /// another zval holding the value instead.
+    pub fn addref(&self) {
+        if self.is_refcounted() {
+            // SAFETY: Reference counted values start with their reference counter.
+            unsafe { (*self.value.counted).gc.refcount += 1 };
+        }
+    }
+
+    /// Decrements the reference counter of the value of the zval, if it is
+    /// reference counted. The value is not released if the counter reaches
+    /// zero.
+    ///
+    /// # Safety
+    ///
+    /// The caller must release a reference which was added with
+    /// [`addref`](Self::addref), and the counter must not reach zero while
+    /// the zval is still in use.
+    pub unsafe fn delref(&self) {
+        if self.is_refcounted() {
+            // SAFETY: Reference counted values start with their reference counter.
+            unsafe { (*self.value.counted).gc.refcount -= 1 };
+        }
+    }
+
+    /// Separates the value of the zval from the other zvals sharing it, so that
+    /// it can be modified without affecting them, as PHP's `SEPARATE_ZVAL()`
+    /// macro does.
+    ///
+    /// Arrays and strings which are shared or immutable are duplicated.
+    /// Objects and resources are handles, which are never duplicated.
+    /// References are followed, so the value they refer to is separated.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::Zval;
+    ///
+    /// let original = Zval::new_array();
+    /// let mut copy = original.shallow_clone();
+    /// copy.separate();
+    /// copy.array_mut().unwrap().push(1);
+    /// assert_eq!(original.array().map(|ht| ht.len()), Some(0));
+    /// ```
+    pub fn separate(&mut self) {
+        let zv = self.dereference_mut();
+        if zv.is_array() {
+            ZendHashTable::make_writable(zv);
+        } else if zv.is_string() && zv.refcount().is_none_or(|count| count > 1) {
+            let copy = zv.zend_str().map(|str| ZendStr::new(str.as_bytes(), false));
+            if let Some(copy) = copy {
+                zv.set_zend_string(copy);
+            }
+        }
+    }
+
+    /// Returns the output of PHP's `var_dump()` for the zval.
+    ///
+    /// Returns an empty string outside of a request, where the output can't
+    /// be captured.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::Zval;
+    ///
+    /// let mut zv = Zval::new();
+    /// zv.set_long(5);
+    /// assert_eq!(zv.dump(), "int(5)\n");
+    /// ```
+    #[must_use]
+    pub fn dump(&self) -> String {
+        let output = capture_output(|| unsafe { php_var_dump(ptr::from_ref(self).cast_mut(), 1) });
+        String::from_utf8_lossy(&output.unwrap_or_default()).into_owned()
+    }
+
+    /// Returns the output of PHP's `var_export()` for the zval, which is a
+    /// PHP expression evaluating to the value.
+    ///
+    /// Returns an empty string outside of a request, where the output can't
+    /// be captured.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::Zval;
+    ///
+    /// let mut zv = Zval::new();
+    /// zv.set_string("it's", false).unwrap();
+    /// assert_eq!(zv.export(), r"'it\'s'");
+    /// ```
+    #[must_use]
+    pub fn export(&self) -> String {
+        let output =
+            capture_output(|| unsafe { php_var_export(ptr::from_ref(self).cast_mut(), 1) });
+        String::from_utf8_lossy(&output.unwrap_or_default()).into_owned()
+    }
 }
 
 impl Debug for Zval {
@@ -826,7 +1153,11 @@ impl Debug for Zval {
         }
 
         match ty {
-            DataType::Undef | DataType::Null | DataType::ConstantExpression | DataType::Void => {
+            DataType::Undef
+            | DataType::Null
+            | DataType::ConstantExpression
+            | DataType::Void
+            | DataType::Never => {
                 field!(Option::<()>::None)
             }
             DataType::False => field!(false),
@@ -935,4 +1266,131 @@ mod tests {
             assert!(!zval_array.is_scalar());
         });
     }
+
+    #[test]
+    fn test_refcount() {
+        Embed::run(|| {
+            let mut zval = Zval::new();
+            zval.set_long(1);
+            assert!(!zval.is_refcounted());
+            assert_eq!(zval.refcount(), None);
+
+            let zval = Zval::new_array();
+            assert!(zval.is_refcounted());
+            assert_eq!(zval.refcount(), Some(1));
+            zval.addref();
+            assert_eq!(zval.refcount(), Some(2));
+            unsafe { zval.delref() };
+            assert_eq!(zval.refcount(), Some(1));
+        });
+    }
+
+    #[test]
+    fn test_separate() {
+        Embed::run(|| {
+            let original = Embed::eval("[1, 2];").unwrap();
+            let mut copy = original.shallow_clone();
+            copy.separate();
+            assert_eq!(copy.refcount(), Some(1));
+            copy.array_mut().unwrap().push(3).unwrap();
+            assert_eq!(original.array().unwrap().len(), 2);
+            assert_eq!(copy.array().unwrap().len(), 3);
+
+            let original = Embed::eval("str_repeat('a', 3);").unwrap();
+            let mut copy = original.shallow_clone();
+            assert_eq!(original.refcount(), Some(2));
+            copy.separate();
+            assert_eq!(original.refcount(), Some(1));
+            assert_eq!(copy.refcount(), Some(1));
+            assert_eq!(copy.str(), Some("aaa"));
+
+            let mut object = Embed::eval("new stdClass;").unwrap();
+            let copy = object.shallow_clone();
+            object.separate();
+            assert!(object.is_identical(&copy));
+        });
+    }
+
+    #[test]
+    fn test_try_getters() {
+        Embed::run(|| {
+            let zval = Embed::eval("'abc';").unwrap();
+            assert_eq!(zval.try_str().unwrap(), "abc");
+            assert_eq!(zval.try_zend_str().unwrap().len(), 3);
+            assert!(matches!(
+                zval.try_long(),
+                Err(Error::TypeMismatch {
+                    expected: DataType::Long,
+                    actual: DataType::String,
+                })
+            ));
+            assert_eq!(
+                zval.try_array().unwrap_err().to_string(),
+                "Expected Array, got String."
+            );
+
+            let mut zval = Embed::eval("[1];").unwrap();
+            assert_eq!(zval.try_array_mut().unwrap().len(), 1);
+            assert!(matches!(
+                zval.try_object_mut(),
+                Err(Error::TypeMismatch {
+                    actual: DataType::Array,
+                    ..
+                })
+            ));
+
+            let mut zval = Zval::new();
+            zval.set_binary(vec![0xffu8]);
+            assert!(matches!(zval.try_str(), Err(Error::InvalidUtf8)));
+        });
+    }
+
+    #[test]
+    fn test_deep_clone() {
+        Embed::run(|| {
+            let original = Embed::eval("[[1, 'a' => str_repeat('b', 3)], 'c' => 5];").unwrap();
+            let copy = original.deep_clone();
+            assert!(copy.loose_equals(&original));
+
+            let inner = original.array().and_then(|ht| ht.get(0)).unwrap();
+            let inner_copy = copy.array().and_then(|ht| ht.get(0)).unwrap();
+            assert!(!ptr::eq(
+                inner.array().unwrap(),
+                inner_copy.array().unwrap()
+            ));
+            assert_eq!(inner_copy.refcount(), Some(1));
+
+            let str = inner.array().and_then(|ht| ht.get("a")).unwrap();
+            let str_copy = inner_copy.array().and_then(|ht| ht.get("a")).unwrap();
+            assert!(!ptr::eq(
+                str.zend_str().unwrap(),
+                str_copy.zend_str().unwrap()
+            ));
+
+            let object = Embed::eval("new stdClass;").unwrap();
+            assert!(object.deep_clone().is_identical(&object));
+        });
+    }
+
+    #[test]
+    fn test_dump_export() {
+        Embed::run(|| {
+            let zval = Embed::eval("[1, 'a' => [true, null]];").unwrap();
+            assert_eq!(
+                zval.dump(),
+                "array(2) {\n  [0]=>\n  int(1)\n  [\"a\"]=>\n  array(2) {\n    [0]=>\n    bool(true)\n    [1]=>\n    NULL\n  }\n}\n"
+            );
+            assert_eq!(
+                zval.export(),
+                "array (\n  0 => 1,\n  'a' => \n  array (\n    0 => true,\n    1 => NULL,\n  ),\n)"
+            );
+
+            // The output of the script is left untouched.
+            Embed::eval("ob_start();").unwrap();
+            Embed::eval("print 'x';").unwrap();
+            assert!(zval.export().starts_with("array ("));
+            let output = Embed::eval("ob_get_clean();").unwrap();
+            assert_eq!(output.str(), Some("x"));
+        });
+    }
 }
@@ -0,0 +1,279 @@
+//! Types making the ownership of zvals explicit.
+//!
+//! A [`Zval`] releases the value it holds when it is dropped, while zvals
+//! owned by the engine, e.g. parameters, array elements or properties, are
+//! only ever borrowed as `&Zval`. Both look alike in signatures and raw
+//! pointers, which makes it easy to release a value twice or not at all.
+//!
+//! [`ZvalRef`] is a borrowed zval owned by the engine, which is never
+//! released by Rust, and [`ZvalOwned`] is a zval owned by Rust, which holds a
+//! reference to its value and releases it when dropped. Converting between
+//! them adds a reference with [`ZvalRef::share`], or borrows with
+//! [`ZvalOwned::as_zval_ref`].
+//!
+//! Both types can be used as parameters and return values of exported
+//! functions. The dimension handlers of classes and the draining iterator of
+//! hashtables use them where zvals cross the boundary with the engine: values
+//! passed in by the engine are borrowed as [`ZvalRef`], and values handed back
+//! are moved out of a [`ZvalOwned`]. The remaining APIs still take `&Zval`
+//! and return `Zval`, which are borrowed and owned respectively in the same
+//! way, and are migrated gradually.
+
+use std::{
+    fmt::{self, Debug},
+    mem,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    convert::{FromZval, IntoZval},
+    error::Result,
+    flags::DataType,
+};
+
+use super::Zval;
+
+/// A borrowed zval owned by the engine, or by another Rust value.
+///
+/// The value is never released through a [`ZvalRef`]. Use
+/// [`ZvalRef::share`] to keep the value beyond the lifetime of the borrow.
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{prelude::*, types::{ZvalOwned, ZvalRef}};
+///
+/// #[php_function]
+/// pub fn first_truthy(a: ZvalRef, b: ZvalRef) -> ZvalOwned {
+///     if a.bool().unwrap_or(false) { a.share() } else { b.share() }
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct ZvalRef<'a>(&'a Zval);
+
+impl<'a> ZvalRef<'a> {
+    /// Borrows the given zval.
+    #[must_use]
+    pub fn new(zval: &'a Zval) -> Self {
+        Self(zval)
+    }
+
+    /// Borrows the zval the given pointer points to, returning [`None`] if
+    /// the pointer is null.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be null or point to an initialized zval which lives
+    /// and is not modified for the lifetime `'a`.
+    #[must_use]
+    pub unsafe fn from_ptr(ptr: *const Zval) -> Option<Self> {
+        unsafe { ptr.as_ref() }.map(Self)
+    }
+
+    /// Returns the borrowed zval.
+    #[must_use]
+    pub fn get(self) -> &'a Zval {
+        self.0
+    }
+
+    /// Returns a pointer to the borrowed zval.
+    #[must_use]
+    pub fn as_ptr(self) -> *const Zval {
+        self.0
+    }
+
+    /// Returns an owned zval holding a new reference to the value, see
+    /// [`Zval::shallow_clone`].
+    #[must_use]
+    pub fn share(self) -> ZvalOwned {
+        ZvalOwned(self.0.shallow_clone())
+    }
+}
+
+impl Deref for ZvalRef<'_> {
+    type Target = Zval;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl Debug for ZvalRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ZvalRef").field(self.0).finish()
+    }
+}
+
+impl<'a> From<&'a Zval> for ZvalRef<'a> {
+    fn from(zval: &'a Zval) -> Self {
+        Self(zval)
+    }
+}
+
+impl<'a> FromZval<'a> for ZvalRef<'a> {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        Some(Self(zval))
+    }
+}
+
+impl IntoZval for ZvalRef<'_> {
+    const TYPE: DataType = DataType::Mixed;
+    const NULLABLE: bool = true;
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        self.share().set_zval(zv, persistent)
+    }
+}
+
+/// A zval owned by Rust, which holds a reference to its value and releases it
+/// when dropped.
+///
+/// Ownership of the value can be handed to the engine with
+/// [`ZvalOwned::write_to`], and taken from it with [`ZvalOwned::take_from`].
+#[derive(Default)]
+#[repr(transparent)]
+pub struct ZvalOwned(Zval);
+
+impl ZvalOwned {
+    /// Creates an owned null zval.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Zval::new())
+    }
+
+    /// Takes the value out of the zval the given pointer points to, leaving
+    /// null in its place.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to an initialized zval which may be modified.
+    #[must_use]
+    pub unsafe fn take_from(ptr: *mut Zval) -> Self {
+        Self(mem::take(unsafe { &mut *ptr }))
+    }
+
+    /// Moves the value into the zval the given pointer points to, handing
+    /// ownership of the value to whoever owns that zval. The previous contents
+    /// of the zval are overwritten without being released.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to writable memory for a zval, which is either
+    /// uninitialized or holds a value which does not need to be released.
+    pub unsafe fn write_to(self, ptr: *mut Zval) {
+        unsafe { ptr.write(self.0) };
+    }
+
+    /// Borrows the zval.
+    #[must_use]
+    pub fn as_zval_ref(&self) -> ZvalRef<'_> {
+        ZvalRef(&self.0)
+    }
+
+    /// Returns the inner zval, which releases the value when dropped.
+    #[must_use]
+    pub fn into_inner(self) -> Zval {
+        self.0
+    }
+}
+
+impl Deref for ZvalOwned {
+    type Target = Zval;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ZvalOwned {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Clone for ZvalOwned {
+    /// Adds a reference to the value, see [`Zval::shallow_clone`].
+    fn clone(&self) -> Self {
+        self.as_zval_ref().share()
+    }
+}
+
+impl Debug for ZvalOwned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ZvalOwned").field(&self.0).finish()
+    }
+}
+
+impl From<Zval> for ZvalOwned {
+    fn from(zval: Zval) -> Self {
+        Self(zval)
+    }
+}
+
+impl From<ZvalOwned> for Zval {
+    fn from(zval: ZvalOwned) -> Self {
+        zval.0
+    }
+}
+
+impl FromZval<'_> for ZvalOwned {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        Some(ZvalRef(zval).share())
+    }
+}
+
+impl IntoZval for ZvalOwned {
+    const TYPE: DataType = DataType::Mixed;
+    const NULLABLE: bool = true;
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        self.0.set_zval(zv, persistent)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::{embed::Embed, rc::PhpRc};
+
+    #[test]
+    fn test_share_and_release() {
+        Embed::run(|| {
+            let array = Embed::eval("range(1, 3);").unwrap();
+            let borrowed = ZvalRef::new(&array);
+
+            let owned = borrowed.share();
+            assert_eq!(array.array().unwrap().get_count(), 2);
+            let clone = owned.clone();
+            assert_eq!(array.array().unwrap().get_count(), 3);
+            drop(owned);
+            drop(clone);
+            assert_eq!(array.array().unwrap().get_count(), 1);
+            assert_eq!(borrowed.array().unwrap().len(), 3);
+        });
+    }
+
+    #[test]
+    fn test_take_and_write() {
+        Embed::run(|| {
+            let mut slot = Embed::eval("'value';").unwrap();
+            let owned = unsafe { ZvalOwned::take_from(&raw mut slot) };
+            assert!(slot.is_null());
+            assert_eq!(owned.str(), Some("value"));
+
+            let mut target = std::mem::MaybeUninit::<Zval>::uninit();
+            unsafe {
+                owned.write_to(target.as_mut_ptr());
+                let target = target.assume_init();
+                assert_eq!(target.str(), Some("value"));
+            }
+        });
+    }
+}
@@ -111,3 +111,154 @@ bool ext_php_rs_zend_first_try_catch(void* (*callback)(void *), void *ctx, void
 void ext_php_rs_zend_bailout() {
   zend_bailout();
 }
+
+// Packed arrays of scalars are filled by writing the buckets directly, rather
+// than inserting each value separately.
+#define EXT_PHP_RS_NEW_PACKED(name, type, set)                                  \
+  HashTable *ext_php_rs_zend_new_packed_##name(const type *values, uint32_t len) { \
+    HashTable *ht = zend_new_array(len);                                       \
+    zend_hash_real_init_packed(ht);                                            \
+    ZEND_HASH_FILL_PACKED(ht) {                                                \
+      for (uint32_t i = 0; i < len; i++) {                                     \
+        zval tmp;                                                              \
+        set(&tmp, values[i]);                                                  \
+        ZEND_HASH_FILL_SET(&tmp);                                              \
+        ZEND_HASH_FILL_NEXT();                                                 \
+      }                                                                        \
+    } ZEND_HASH_FILL_END();                                                    \
+    return ht;                                                                 \
+  }
+
+EXT_PHP_RS_NEW_PACKED(long, zend_long, ZVAL_LONG)
+EXT_PHP_RS_NEW_PACKED(double, double, ZVAL_DOUBLE)
+EXT_PHP_RS_NEW_PACKED(bool, bool, ZVAL_BOOL)
+
+// The zvals are moved into the array, the caller must not release them.
+#define EXT_PHP_RS_ZVAL_MOVE(z, v) ZVAL_COPY_VALUE(z, &(v))
+EXT_PHP_RS_NEW_PACKED(zval, zval, EXT_PHP_RS_ZVAL_MOVE)
+
+bool ext_php_rs_zend_hash_copy_longs(const HashTable *ht, zend_long *out) {
+  zval *val;
+  ZEND_HASH_FOREACH_VAL((HashTable *)ht, val) {
+    if (Z_TYPE_P(val) != IS_LONG) {
+      return false;
+    }
+    *out++ = Z_LVAL_P(val);
+  } ZEND_HASH_FOREACH_END();
+  return true;
+}
+
+bool ext_php_rs_zend_hash_copy_doubles(const HashTable *ht, double *out) {
+  zval *val;
+  ZEND_HASH_FOREACH_VAL((HashTable *)ht, val) {
+    if (Z_TYPE_P(val) != IS_DOUBLE) {
+      return false;
+    }
+    *out++ = Z_DVAL_P(val);
+  } ZEND_HASH_FOREACH_END();
+  return true;
+}
+
+// Persistent arrays are allocated with `malloc()` and release their values
+// with the destructor of internal zvals, which frees persistent strings.
+HashTable *ext_php_rs_zend_new_persistent_array(uint32_t size) {
+  HashTable *ht = pemalloc(sizeof(HashTable), 1);
+  zend_hash_init(ht, size, NULL, ZVAL_INTERNAL_PTR_DTOR, 1);
+  return ht;
+}
+
+void ext_php_rs_zend_persistent_array_destroy(HashTable *ht) {
+  zend_hash_destroy(ht);
+  pefree(ht, 1);
+}
+
+bool ext_php_rs_zend_hash_copy_bools(const HashTable *ht, bool *out) {
+  zval *val;
+  ZEND_HASH_FOREACH_VAL((HashTable *)ht, val) {
+    if (Z_TYPE_P(val) == IS_TRUE) {
+      *out++ = true;
+    } else if (Z_TYPE_P(val) == IS_FALSE) {
+      *out++ = false;
+    } else {
+      return false;
+    }
+  } ZEND_HASH_FOREACH_END();
+  return true;
+}
+
+// Serializes the value like `serialize()`, returning `NULL` if an exception
+// was thrown, e.g. by `__serialize()`.
+zend_string *ext_php_rs_php_var_serialize(zval *val) {
+  smart_str buf = {0};
+  php_serialize_data_t var_hash;
+
+  PHP_VAR_SERIALIZE_INIT(var_hash);
+  php_var_serialize(&buf, val, &var_hash);
+  PHP_VAR_SERIALIZE_DESTROY(var_hash);
+
+  if (EG(exception)) {
+    smart_str_free(&buf);
+    return NULL;
+  }
+  smart_str_0(&buf);
+  return buf.s ? buf.s : ZSTR_EMPTY_ALLOC();
+}
+
+// Unserializes the value like `unserialize()`. `allowed_classes` holds the
+// lowercase names of the classes which may be instantiated, or is `NULL` to
+// allow all classes. On failure, `offset` is set to the offset of the byte
+// where unserializing stopped.
+bool ext_php_rs_php_var_unserialize(zval *rval, const char *buf, size_t len,
+                                    HashTable *allowed_classes, size_t *offset) {
+  const unsigned char *p = (const unsigned char *)buf;
+  php_unserialize_data_t var_hash;
+  zval *tmp;
+  bool success;
+
+  PHP_VAR_UNSERIALIZE_INIT(var_hash);
+  if (allowed_classes) {
+    php_var_unserialize_set_allowed_classes(var_hash, allowed_classes);
+  }
+  tmp = var_tmp_var(&var_hash);
+  success = php_var_unserialize(tmp, &p, p + len, &var_hash);
+  *offset = (size_t)(p - (const unsigned char *)buf);
+  if (success) {
+    ZVAL_COPY_DEREF(rval, tmp);
+  }
+  // Calls `__wakeup()` and `__unserialize()` of the created objects.
+  PHP_VAR_UNSERIALIZE_DESTROY(var_hash);
+
+  if (success && EG(exception)) {
+    zval_ptr_dtor(rval);
+    ZVAL_UNDEF(rval);
+    return false;
+  }
+  return success;
+}
+
+void ext_php_rs_smart_str_appendl(smart_str *dest, const char *str, size_t len) {
+  smart_str_appendl(dest, str, len);
+}
+
+// Ensures the buffer has room for `len` more bytes.
+void ext_php_rs_smart_str_alloc(smart_str *dest, size_t len) {
+  smart_str_alloc(dest, len, false);
+}
+
+// Takes the string out of the buffer, leaving it empty.
+zend_string *ext_php_rs_smart_str_extract(smart_str *dest) {
+  zend_string *str;
+
+  if (!dest->s) {
+    return ZSTR_EMPTY_ALLOC();
+  }
+  smart_str_0(dest);
+  str = dest->s;
+  dest->s = NULL;
+  dest->a = 0;
+  return str;
+}
+
+void ext_php_rs_smart_str_free(smart_str *dest) {
+  smart_str_free(dest);
+}
@@ -38,6 +38,7 @@
 #include "zend_interfaces.h"
 #include "php_variables.h"
 #include "zend_ini.h"
+#include "zend_smart_str.h"
 #include "main/SAPI.h"
 
 zend_string *ext_php_rs_zend_string_init(const char *str, size_t len, bool persistent);
@@ -56,3 +57,18 @@ sapi_module_struct *ext_php_rs_sapi_module();
 bool ext_php_rs_zend_try_catch(void* (*callback)(void *), void *ctx, void **result);
 bool ext_php_rs_zend_first_try_catch(void* (*callback)(void *), void *ctx, void **result);
 void ext_php_rs_zend_bailout();
+HashTable *ext_php_rs_zend_new_packed_long(const zend_long *values, uint32_t len);
+HashTable *ext_php_rs_zend_new_packed_double(const double *values, uint32_t len);
+HashTable *ext_php_rs_zend_new_packed_bool(const bool *values, uint32_t len);
+HashTable *ext_php_rs_zend_new_packed_zval(const zval *values, uint32_t len);
+HashTable *ext_php_rs_zend_new_persistent_array(uint32_t size);
+void ext_php_rs_zend_persistent_array_destroy(HashTable *ht);
+bool ext_php_rs_zend_hash_copy_longs(const HashTable *ht, zend_long *out);
+bool ext_php_rs_zend_hash_copy_doubles(const HashTable *ht, double *out);
+bool ext_php_rs_zend_hash_copy_bools(const HashTable *ht, bool *out);
+zend_string *ext_php_rs_php_var_serialize(zval *val);
+bool ext_php_rs_php_var_unserialize(zval *rval, const char *buf, size_t len, HashTable *allowed_classes, size_t *offset);
+void ext_php_rs_smart_str_appendl(smart_str *dest, const char *str, size_t len);
+void ext_php_rs_smart_str_alloc(smart_str *dest, size_t len);
+zend_string *ext_php_rs_smart_str_extract(smart_str *dest);
+void ext_php_rs_smart_str_free(smart_str *dest);
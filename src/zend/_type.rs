@@ -2,8 +2,8 @@ use std::{ffi::c_void, ptr};
 
 use crate::{
     ffi::{
-        _IS_BOOL, _ZEND_IS_VARIADIC_BIT, _ZEND_SEND_MODE_SHIFT, _ZEND_TYPE_NULLABLE_BIT, IS_MIXED,
-        MAY_BE_ANY, MAY_BE_BOOL, zend_type,
+        _IS_BOOL, _ZEND_IS_VARIADIC_BIT, _ZEND_SEND_MODE_SHIFT, _ZEND_TYPE_NULLABLE_BIT,
+        IS_ITERABLE, IS_MIXED, MAY_BE_ANY, MAY_BE_BOOL, zend_type,
     },
     flags::DataType,
 };
@@ -144,6 +144,19 @@ impl ZendType {
             })
     }
 
+    /// Returns the type mask of the `iterable` type. Since PHP 8.2, `iterable`
+    /// is an alias of `Traversable|array` which is marked by a separate bit
+    /// instead of a type code.
+    fn iterable_mask() -> u32 {
+        cfg_if::cfg_if! {
+            if #[cfg(php82)] {
+                crate::ffi::_ZEND_TYPE_ITERABLE_BIT
+            } else {
+                1 << IS_ITERABLE
+            }
+        }
+    }
+
     /// Calculates the internal flags of the type.
     /// Translation of the `ZEND_TYPE_INIT_CODE` macro from `zend_API.h:163`.
     ///
@@ -163,6 +176,8 @@ impl ZendType {
 
         (if type_ == _IS_BOOL {
             MAY_BE_BOOL
+        } else if type_ == IS_ITERABLE {
+            Self::iterable_mask()
         } else if type_ == IS_MIXED {
             MAY_BE_ANY
         } else {
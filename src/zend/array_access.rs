@@ -0,0 +1,348 @@
+//! Implementation of the `ArrayAccess` interface for Rust classes.
+
+use std::{os::raw::c_int, ptr};
+
+use crate::{
+    args::Arg,
+    builders::FunctionBuilder,
+    class::RegisteredClass,
+    exception::{PhpException, PhpResult},
+    ffi::{
+        zend_std_has_dimension, zend_std_read_dimension, zend_std_unset_dimension,
+        zend_std_write_dimension,
+    },
+    flags::{DataType, FetchMode, MethodFlags},
+    types::{ZendObject, Zval, ZvalOwned, ZvalRef},
+    zend::ExecuteData,
+    zend_fastcall,
+};
+
+use super::handlers::class_object;
+
+/// Implements the [`ArrayAccess`] interface for a class, allowing objects of
+/// the class to be used as arrays, e.g. `$obj['key']`.
+///
+/// The implementation is installed by adding the `#[php(array_access)]`
+/// attribute to the class, which declares the `offsetExists()`,
+/// `offsetGet()`, `offsetSet()` and `offsetUnset()` methods of the interface.
+/// Array syntax on the object calls the implementation directly through the
+/// dimension handlers of the class, without calling the methods. Objects of
+/// PHP subclasses, which may override the methods, call the methods instead.
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use std::collections::HashMap;
+/// use ext_php_rs::{
+///     exception::PhpResult, prelude::*, types::Zval, zend::PhpArrayAccess,
+/// };
+///
+/// #[php_class]
+/// #[php(array_access)]
+/// #[derive(Default)]
+/// pub struct Bag {
+///     items: HashMap<String, Zval>,
+/// }
+///
+/// impl PhpArrayAccess for Bag {
+///     fn offset_exists(&mut self, offset: &Zval) -> PhpResult<bool> {
+///         Ok(offset.string().is_some_and(|key| self.items.contains_key(&key)))
+///     }
+///
+///     fn offset_get(&mut self, offset: &Zval) -> PhpResult<Option<Zval>> {
+///         let key = offset.string().ok_or("Invalid key")?;
+///         Ok(self.items.get(&key).map(Zval::shallow_clone))
+///     }
+///
+///     fn offset_set(&mut self, offset: &Zval, value: &Zval) -> PhpResult<()> {
+///         let key = offset.string().ok_or("Cannot append to a bag")?;
+///         self.items.insert(key, value.shallow_clone());
+///         Ok(())
+///     }
+///
+///     fn offset_unset(&mut self, offset: &Zval) -> PhpResult<()> {
+///         if let Some(key) = offset.string() {
+///             self.items.remove(&key);
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// [`ArrayAccess`]: https://www.php.net/manual/en/class.arrayaccess.php
+pub trait PhpArrayAccess: RegisteredClass {
+    /// Returns whether the element at the given offset exists, as checked by
+    /// `isset($obj[$offset])`. `empty($obj[$offset])` additionally reads the
+    /// element to check whether it is truthy.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn offset_exists(&mut self, offset: &Zval) -> PhpResult<bool>;
+
+    /// Reads the element at the given offset, as done by `$obj[$offset]`.
+    /// Returning `None` reads `null`.
+    ///
+    /// # Parameters
+    ///
+    /// * `offset` - The offset of the element, which is `null` when an
+    ///   appended element is fetched, e.g. `$obj[][] = 1`.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn offset_get(&mut self, offset: &Zval) -> PhpResult<Option<Zval>>;
+
+    /// Writes the element at the given offset, as done by
+    /// `$obj[$offset] = $value`.
+    ///
+    /// # Parameters
+    ///
+    /// * `offset` - The offset of the element, which is `null` when the
+    ///   element is appended, e.g. `$obj[] = $value`.
+    /// * `value` - The value to write.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn offset_set(&mut self, offset: &Zval, value: &Zval) -> PhpResult<()>;
+
+    /// Removes the element at the given offset, as done by
+    /// `unset($obj[$offset])`.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn offset_unset(&mut self, offset: &Zval) -> PhpResult<()>;
+}
+
+/// The `ArrayAccess` methods of a class implementing [`PhpArrayAccess`], see
+/// [`RegisteredClass::ARRAY_ACCESS`].
+#[derive(Clone, Copy)]
+pub struct ArrayAccessMethods(fn() -> Vec<(FunctionBuilder<'static>, MethodFlags)>);
+
+impl ArrayAccessMethods {
+    /// Returns the methods calling the [`PhpArrayAccess`] implementation of
+    /// `T`.
+    #[must_use]
+    pub const fn of<T: PhpArrayAccess>() -> Self {
+        Self(method_builders::<T>)
+    }
+
+    /// Returns the builders of the methods, which are added to the class.
+    pub(crate) fn method_builders(self) -> Vec<(FunctionBuilder<'static>, MethodFlags)> {
+        (self.0)()
+    }
+}
+
+fn method_builders<T: PhpArrayAccess>() -> Vec<(FunctionBuilder<'static>, MethodFlags)> {
+    let offset = || Arg::new("offset", DataType::Mixed);
+    vec![
+        (
+            FunctionBuilder::new("offsetExists", offset_exists::<T>)
+                .arg(offset())
+                .returns(DataType::Bool, false, false),
+            MethodFlags::Public,
+        ),
+        (
+            FunctionBuilder::new("offsetGet", offset_get::<T>)
+                .arg(offset())
+                .returns(DataType::Mixed, false, true),
+            MethodFlags::Public,
+        ),
+        (
+            FunctionBuilder::new("offsetSet", offset_set::<T>)
+                .arg(offset())
+                .arg(Arg::new("value", DataType::Mixed))
+                .returns(DataType::Void, false, false),
+            MethodFlags::Public,
+        ),
+        (
+            FunctionBuilder::new("offsetUnset", offset_unset::<T>)
+                .arg(offset())
+                .returns(DataType::Void, false, false),
+            MethodFlags::Public,
+        ),
+    ]
+}
+
+/// Parses the arguments of an `ArrayAccess` method and calls `f` with the
+/// Rust object and the arguments, throwing returned errors.
+fn call_method<'a, T: RegisteredClass, const N: usize>(
+    ex: &'a mut ExecuteData,
+    mut args: [Arg<'a>; N],
+    f: impl FnOnce(&mut T, [&Zval; N]) -> PhpResult<()>,
+) {
+    let (mut parser, this) = ex.parser_method::<T>();
+    let Some(this) = this else {
+        PhpException::default("Failed to retrieve reference to `$this`".into())
+            .throw()
+            .expect("Failed to throw exception");
+        return;
+    };
+    for arg in &mut args {
+        parser = parser.arg(arg);
+    }
+    if parser.parse().is_err() {
+        return;
+    }
+
+    let null = Zval::new();
+    let args = args
+        .each_mut()
+        .map(|arg| arg.zval().map_or(&null, |zv| &**zv));
+    if let Err(e) = f(&mut **this, args) {
+        let _ = e.throw();
+    }
+}
+
+zend_fastcall! {
+    extern "C" fn offset_exists<T: PhpArrayAccess>(ex: &mut ExecuteData, retval: &mut Zval) {
+        call_method::<T, 1>(ex, [Arg::new("offset", DataType::Mixed)], |obj, [offset]| {
+            retval.set_bool(obj.offset_exists(offset)?);
+            Ok(())
+        });
+    }
+}
+
+zend_fastcall! {
+    extern "C" fn offset_get<T: PhpArrayAccess>(ex: &mut ExecuteData, retval: &mut Zval) {
+        call_method::<T, 1>(ex, [Arg::new("offset", DataType::Mixed)], |obj, [offset]| {
+            match obj.offset_get(offset)? {
+                Some(val) => *retval = val,
+                None => retval.set_null(),
+            }
+            Ok(())
+        });
+    }
+}
+
+zend_fastcall! {
+    extern "C" fn offset_set<T: PhpArrayAccess>(ex: &mut ExecuteData, _: &mut Zval) {
+        let args = [
+            Arg::new("offset", DataType::Mixed),
+            Arg::new("value", DataType::Mixed),
+        ];
+        call_method::<T, 2>(ex, args, |obj, [offset, value]| obj.offset_set(offset, value));
+    }
+}
+
+zend_fastcall! {
+    extern "C" fn offset_unset<T: PhpArrayAccess>(ex: &mut ExecuteData, _: &mut Zval) {
+        call_method::<T, 1>(ex, [Arg::new("offset", DataType::Mixed)], |obj, [offset]| {
+            obj.offset_unset(offset)
+        });
+    }
+}
+
+/// Returns whether the object is an instance of a PHP subclass of `T`, which
+/// may override the `ArrayAccess` methods. The standard dimension handlers
+/// call the methods of such objects.
+unsafe fn is_subclass<T: RegisteredClass>(object: *mut ZendObject) -> bool {
+    unsafe { object.as_ref() }
+        .is_some_and(|obj| !ptr::eq(obj.ce.cast_const(), T::get_metadata().ce()))
+}
+
+pub(super) unsafe extern "C" fn read_dimension<T: PhpArrayAccess>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+    type_: c_int,
+    rv: *mut Zval,
+) -> *mut Zval {
+    if unsafe { is_subclass::<T>(object) } {
+        return unsafe { zend_std_read_dimension(object, offset, type_, rv) };
+    }
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let null = Zval::new();
+        let offset = unsafe { ZvalRef::from_ptr(offset) }.map_or(&null, ZvalRef::get);
+        // Elements which do not exist are not read by `isset()` and `??`.
+        let mode = FetchMode::from(u32::try_from(type_).unwrap_or_default());
+        if mode == FetchMode::Isset && !obj.offset_exists(offset)? {
+            return Ok(None);
+        }
+        obj.offset_get(offset)
+    });
+
+    if rv.is_null() {
+        return rv;
+    }
+    let value = match result {
+        Ok(val) => val.map(ZvalOwned::from).unwrap_or_default(),
+        Err(e) => {
+            let _ = e.throw();
+            ZvalOwned::new()
+        }
+    };
+    // SAFETY: `rv` is uninitialized, and the engine releases the value.
+    unsafe { value.write_to(rv) };
+    rv
+}
+
+pub(super) unsafe extern "C" fn write_dimension<T: PhpArrayAccess>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+    value: *mut Zval,
+) {
+    if unsafe { is_subclass::<T>(object) } {
+        unsafe { zend_std_write_dimension(object, offset, value) };
+        return;
+    }
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let null = Zval::new();
+        let offset = unsafe { ZvalRef::from_ptr(offset) }.map_or(&null, ZvalRef::get);
+        let value = unsafe { ZvalRef::from_ptr(value) }.ok_or("Invalid value given")?;
+        obj.offset_set(offset, value.get())
+    });
+
+    if let Err(e) = result {
+        let _ = e.throw();
+    }
+}
+
+pub(super) unsafe extern "C" fn has_dimension<T: PhpArrayAccess>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+    check_empty: c_int,
+) -> c_int {
+    if unsafe { is_subclass::<T>(object) } {
+        return unsafe { zend_std_has_dimension(object, offset, check_empty) };
+    }
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let offset = unsafe { ZvalRef::from_ptr(offset) }
+            .ok_or("Invalid offset given")?
+            .get();
+        if !obj.offset_exists(offset)? {
+            return Ok(false);
+        }
+        if check_empty == 0 {
+            return Ok(true);
+        }
+        let val = obj.offset_get(offset)?;
+        Ok(val.is_some_and(|val| val.is_truthy()))
+    });
+
+    match result {
+        Ok(has) => has.into(),
+        Err(e) => {
+            let _ = e.throw();
+            0
+        }
+    }
+}
+
+pub(super) unsafe extern "C" fn unset_dimension<T: PhpArrayAccess>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+) {
+    if unsafe { is_subclass::<T>(object) } {
+        unsafe { zend_std_unset_dimension(object, offset) };
+        return;
+    }
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let offset = unsafe { ZvalRef::from_ptr(offset) }.ok_or("Invalid offset given")?;
+        obj.offset_unset(offset.get())
+    });
+
+    if let Err(e) = result {
+        let _ = e.throw();
+    }
+}
@@ -0,0 +1,224 @@
+//! Non-blocking PHP streams driven by a tokio reactor.
+
+use std::{
+    io,
+    os::fd::RawFd,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf, unix::AsyncFd};
+
+use crate::{boxed::ZBox, error::Error};
+
+use super::Stream;
+
+/// Adapter exposing a PHP stream backed by a file descriptor, such as a
+/// socket opened with [`Stream::connect`], as [`AsyncRead`] and
+/// [`AsyncWrite`].
+///
+/// The stream is switched to non-blocking mode and its file descriptor is
+/// registered with the reactor of the current tokio runtime, so reading and
+/// writing wait for readiness instead of blocking the request thread.
+///
+/// PHP streams may only be used on the thread running the request, so the
+/// adapter is not [`Send`] and must be polled on that thread, e.g. with a
+/// current thread runtime or a [`tokio::task::LocalSet`].
+///
+/// Reading and writing through the extension traits of tokio requires its
+/// `io-util` feature:
+///
+/// ```rust,no_run
+/// use ext_php_rs::zend::{AsyncStream, Stream};
+/// use tokio::io::{AsyncReadExt, AsyncWriteExt};
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let stream = Stream::connect("tcp://127.0.0.1:8080", None)
+///     .map_err(|err| std::io::Error::other(err.to_string()))?;
+/// let mut stream = AsyncStream::new(stream)?;
+///
+/// stream.write_all(b"ping").await?;
+/// let mut buf = [0; 4];
+/// stream.read_exact(&mut buf).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncStream {
+    fd: AsyncFd<RawFd>,
+    stream: ZBox<Stream>,
+}
+
+impl AsyncStream {
+    /// Creates an adapter for the given stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream is not backed by a file descriptor,
+    /// does not support non-blocking mode or if there is no tokio runtime
+    /// with IO enabled.
+    pub fn new(mut stream: ZBox<Stream>) -> io::Result<Self> {
+        let fd = stream.as_raw_fd().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Stream is not backed by a file descriptor",
+            )
+        })?;
+        stream.set_blocking(false).map_err(into_io_error)?;
+
+        Ok(Self {
+            fd: AsyncFd::new(fd)?,
+            stream,
+        })
+    }
+
+    /// Returns the wrapped stream.
+    #[must_use]
+    pub fn get_ref(&self) -> &Stream {
+        &self.stream
+    }
+
+    /// Returns the wrapped stream, switched back to blocking mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream could not be switched back to blocking
+    /// mode.
+    pub fn into_inner(self) -> io::Result<ZBox<Stream>> {
+        let Self { fd, mut stream } = self;
+        // The descriptor must be deregistered before the stream may close it.
+        drop(fd);
+        stream.set_blocking(true).map_err(into_io_error)?;
+        Ok(stream)
+    }
+
+    /// Reads from the stream, failing with [`io::ErrorKind::WouldBlock`] if
+    /// no data is available yet.
+    fn try_read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.stream.read(buf) {
+            Ok(0) if !buf.is_empty() && !self.stream.is_eof() => {
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            Ok(read) => Ok(read),
+            Err(err) => Err(into_io_error(err)),
+        }
+    }
+
+    /// Writes to the stream, failing with [`io::ErrorKind::WouldBlock`] if
+    /// the stream cannot accept data yet.
+    fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.stream.write(buf) {
+            Ok(0) if !buf.is_empty() => Err(io::ErrorKind::WouldBlock.into()),
+            Ok(written) => Ok(written),
+            Err(err) => Err(into_io_error(err)),
+        }
+    }
+}
+
+impl AsyncRead for AsyncStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let unfilled = buf.initialize_unfilled();
+
+        // The stream may have buffered data while the descriptor is not readable,
+        // so read before waiting for readiness.
+        let read = loop {
+            match this.try_read(unfilled) {
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+                result => break result?,
+            }
+
+            let mut guard = ready!(this.fd.poll_read_ready(cx))?;
+            guard.clear_ready();
+        };
+
+        buf.advance(read);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for AsyncStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            match this.try_write(buf) {
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+                result => return Poll::Ready(result),
+            }
+
+            let mut guard = ready!(this.fd.poll_write_ready(cx))?;
+            guard.clear_ready();
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_mut().stream.flush().map_err(into_io_error))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+/// Converts an error of a stream operation, which is not [`Send`] as it may
+/// hold an exception, into an IO error.
+fn into_io_error(err: Error) -> io::Error {
+    io::Error::other(err.to_string())
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        runtime::Builder,
+    };
+
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_read_write() {
+        Embed::run(|| {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let address = format!("tcp://{}", listener.local_addr().unwrap());
+            let stream = Stream::connect(&address, None).unwrap();
+            let (mut peer, _) = listener.accept().unwrap();
+
+            let runtime = Builder::new_current_thread().enable_io().build().unwrap();
+            let _guard = runtime.enter();
+            let mut stream = AsyncStream::new(stream).unwrap();
+
+            runtime.block_on(stream.write_all(b"ping")).unwrap();
+            let mut buf = [0; 4];
+            peer.read_exact(&mut buf).unwrap();
+            assert_eq!(&buf, b"ping");
+
+            peer.write_all(b"pong").unwrap();
+            let mut buf = [0; 4];
+            runtime.block_on(stream.read_exact(&mut buf)).unwrap();
+            assert_eq!(&buf, b"pong");
+
+            drop(peer);
+            let mut rest = Vec::new();
+            runtime.block_on(stream.read_to_end(&mut rest)).unwrap();
+            assert!(rest.is_empty());
+
+            let stream = stream.into_inner().unwrap();
+            assert!(stream.is_eof());
+        });
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     boxed::ZBox,
     convert::{FromZval, IntoZval},
     error::{Error, Result},
-    ffi::{ZEND_RESULT_CODE_SUCCESS, zend_class_entry},
+    ffi::{ZEND_INTERNAL_CLASS, ZEND_RESULT_CODE_SUCCESS, zend_class_entry},
     flags::ClassFlags,
     types::{ZendObject, ZendStr},
     zend::ExecutorGlobals,
@@ -60,6 +60,13 @@ impl ClassEntry {
         self.flags().contains(ClassFlags::Interface)
     }
 
+    /// Returns `true` if the class was registered by an extension, and `false`
+    /// if it was declared by userland code.
+    #[must_use]
+    pub fn is_internal(&self) -> bool {
+        i64::from(self.type_) == i64::from(ZEND_INTERNAL_CLASS)
+    }
+
     /// Checks if the class is an instance of another class or interface.
     ///
     /// # Parameters
@@ -0,0 +1,22 @@
+//! Tracking of the runs of the engine, allowing values which live as long as
+//! the engine, e.g. resource types or permanent strings, to detect that it
+//! has been shut down and possibly started again.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Number of times the engine has been shut down.
+static ENGINE_SHUTDOWNS: AtomicU64 = AtomicU64::new(0);
+
+/// Returns an identifier of the current run of the engine, which changes once
+/// the engine has been shut down.
+///
+/// Runs are tracked for modules built with a
+/// [`ModuleBuilder`](crate::builders::ModuleBuilder) and for the embed SAPI.
+pub(crate) fn current_engine() -> u64 {
+    ENGINE_SHUTDOWNS.load(Ordering::Acquire)
+}
+
+/// Marks the current run of the engine as finished.
+pub(crate) fn finish_engine() {
+    ENGINE_SHUTDOWNS.fetch_add(1, Ordering::AcqRel);
+}
@@ -196,6 +196,17 @@ impl ExecuteData {
         self.This.object_mut()
     }
 
+    /// Returns the arguments passed to the function, without parsing them.
+    #[must_use]
+    pub fn args(&self) -> Vec<&Zval> {
+        // SAFETY: All fields of the `u2` union are the same type.
+        let n_args = unsafe { self.This.u2.num_args };
+        (0..n_args)
+            // SAFETY: The arguments live as long as the execution data.
+            .filter_map(|i| unsafe { self.zend_call_arg(i as usize) }.map(|arg| &*arg))
+            .collect()
+    }
+
     /// Attempt to retrieve the function that is being called.
     #[must_use]
     pub fn function(&self) -> Option<&Function> {
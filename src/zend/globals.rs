@@ -9,16 +9,17 @@ use std::str;
 use std::sync::{Arc, LazyLock};
 
 use crate::boxed::ZBox;
-use crate::exception::PhpResult;
-#[cfg(php82)]
-use crate::ffi::zend_atomic_bool_store;
+use crate::constant::ZendConstant;
+use crate::convert::IntoZval;
+use crate::exception::{PhpResult, throw_object};
 use crate::ffi::{
     _sapi_module_struct, _zend_compiler_globals, _zend_executor_globals, TRACK_VARS_COOKIE,
     TRACK_VARS_ENV, TRACK_VARS_FILES, TRACK_VARS_GET, TRACK_VARS_POST, TRACK_VARS_SERVER,
     ext_php_rs_compiler_globals, ext_php_rs_executor_globals, ext_php_rs_file_globals,
     ext_php_rs_process_globals, ext_php_rs_sapi_globals, ext_php_rs_sapi_module, php_core_globals,
     php_file_globals, sapi_globals_struct, sapi_header_struct, sapi_headers_struct,
-    sapi_request_info, zend_ini_entry, zend_is_auto_global,
+    sapi_request_info, zend_error_handling_t_EH_THROW, zend_ini_entry, zend_is_auto_global,
+    zend_multibyte_get_encoding_name, zend_rebuild_symbol_table,
 };
 #[cfg(not(php81))]
 use crate::ffi::{_zend_hash_find_known_hash, _zend_string};
@@ -27,10 +28,14 @@ use crate::ffi::{
     _zend_known_string_id_ZEND_STR_AUTOGLOBAL_REQUEST, zend_hash_find_known_hash,
     zend_known_strings,
 };
+#[cfg(php82)]
+use crate::ffi::{zend_atomic_bool_load, zend_atomic_bool_store};
 
+use crate::flags::{CompilerOptions, ErrorType, FunctionType};
 use crate::types::{ZendHashTable, ZendObject, ZendStr};
 
 use super::linked_list::ZendLinkedListIterator;
+use super::{ClassEntry, ExecuteData, Function, SymbolTable};
 
 /// Stores global variables used in the PHP executor.
 pub type ExecutorGlobals = _zend_executor_globals;
@@ -148,6 +153,142 @@ impl ExecutorGlobals {
         unsafe { self.zend_constants.as_ref() }
     }
 
+    /// Returns an iterator over the constants defined in the current request,
+    /// in the order they were defined.
+    pub fn defined_constants(&self) -> impl DoubleEndedIterator<Item = &ZendConstant> {
+        self.constants()
+            .into_iter()
+            .flat_map(ZendHashTable::values)
+            // SAFETY: The constants table stores pointers to constants.
+            .filter_map(|value| unsafe { value.ptr::<ZendConstant>()?.as_ref() })
+    }
+
+    /// Returns the value of the `include_path` ini directive in the current
+    /// request.
+    #[must_use]
+    pub fn include_path(&self) -> Option<&str> {
+        let directives = unsafe { self.ini_directives.as_ref() }?;
+        let entry = unsafe {
+            directives
+                .get("include_path")?
+                .ptr::<zend_ini_entry>()?
+                .as_ref()
+        }?;
+        unsafe { entry.value.as_ref() }?.as_str().ok()
+    }
+
+    /// Returns the table of files included in the current request, keyed by
+    /// their resolved path.
+    #[must_use]
+    pub fn included_files(&self) -> &ZendHashTable {
+        &self.included_files
+    }
+
+    /// Returns the execute data of the function currently being executed, or
+    /// [`None`] if no code is being executed.
+    #[must_use]
+    pub fn current_execute_data(&self) -> Option<&ExecuteData> {
+        unsafe { self.current_execute_data.as_ref() }
+    }
+
+    /// Returns the function currently being executed.
+    #[must_use]
+    pub fn current_function(&self) -> Option<&Function> {
+        self.current_execute_data()?.function()
+    }
+
+    /// Returns the class scope of the code currently being executed, i.e. the
+    /// class `self` refers to, or [`None`] outside of a class.
+    ///
+    /// Internal functions which are not methods are executed in the scope of
+    /// their caller. Translation of `zend_get_executed_scope`.
+    #[must_use]
+    pub fn current_scope(&self) -> Option<&ClassEntry> {
+        if let Some(scope) = unsafe { self.fake_scope.as_ref() } {
+            return Some(scope);
+        }
+
+        let mut ex = self.current_execute_data();
+        while let Some(data) = ex {
+            if let Some(func) = data.function() {
+                let scope = unsafe { func.common.scope };
+                if func.function_type() != FunctionType::Internal || !scope.is_null() {
+                    return unsafe { scope.as_ref() };
+                }
+            }
+            ex = data.previous();
+        }
+        None
+    }
+
+    /// Returns the `$this` object of the code currently being executed, or
+    /// [`None`] outside of an instance method.
+    ///
+    /// Internal functions which are not methods are executed with the `$this`
+    /// of their caller. Translation of `zend_get_this_object`.
+    #[must_use]
+    pub fn this(&self) -> Option<&ZendObject> {
+        let mut ex = self.current_execute_data();
+        while let Some(data) = ex {
+            if let Some(this) = data.This.object() {
+                return Some(this);
+            }
+            if let Some(func) = data.function()
+                && (func.function_type() != FunctionType::Internal
+                    || !unsafe { func.common.scope }.is_null())
+            {
+                return None;
+            }
+            ex = data.previous();
+        }
+        None
+    }
+
+    /// Returns the levels of errors which are currently reported, as set by
+    /// the `error_reporting` ini directive or function.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn error_reporting(&self) -> ErrorType {
+        // `-1` reports all errors, which is preserved by the cast.
+        ErrorType::from_bits_truncate(self.error_reporting as u32)
+    }
+
+    /// Returns whether errors are currently converted into exceptions, e.g.
+    /// while a constructor of an internal class is running.
+    #[must_use]
+    pub fn throws_errors(&self) -> bool {
+        self.error_handling == zend_error_handling_t_EH_THROW
+    }
+
+    /// Returns the exit status of the current request, as set by `exit()` or
+    /// a fatal error.
+    #[must_use]
+    pub fn exit_status(&self) -> i32 {
+        self.exit_status
+    }
+
+    /// Returns the local variables of the innermost PHP code being executed,
+    /// or [`None`] if no PHP code is being executed.
+    ///
+    /// The compiled variables of the function are attached to its symbol
+    /// table the first time it is requested. Translation of
+    /// `zend_rebuild_symbol_table`.
+    ///
+    /// Overwriting or unsetting a variable may run destructors of PHP
+    /// objects, which deadlock if they try to access the executor globals
+    /// while the returned table is held.
+    pub fn symbol_table(&mut self) -> Option<SymbolTable<'_>> {
+        let table = unsafe { zend_rebuild_symbol_table().as_mut() }?;
+        Some(SymbolTable::new(table))
+    }
+
+    /// Returns the exception which is currently being thrown, without
+    /// clearing it.
+    #[must_use]
+    pub fn exception(&self) -> Option<&ZendObject> {
+        unsafe { self.exception.as_ref() }
+    }
+
     /// Attempts to extract the last PHP exception captured by the interpreter.
     /// Returned inside a [`ZBox`].
     ///
@@ -178,6 +319,26 @@ impl ExecutorGlobals {
         !Self::get().exception.is_null()
     }
 
+    /// Throws the given exception in place of the exception which is
+    /// currently being thrown, returning the replaced exception.
+    ///
+    /// This function requires the executor globals to be mutably held, which
+    /// could lead to a deadlock if the globals are already borrowed immutably
+    /// or mutably.
+    ///
+    /// # Panics
+    ///
+    /// * If the exception could not be converted into a zval.
+    pub fn replace_exception(exception: ZBox<ZendObject>) -> Option<ZBox<ZendObject>> {
+        let previous = Self::take_exception();
+        let zv = exception
+            .into_zval(false)
+            .expect("Failed to convert exception into zval");
+        // `throw_object` cannot fail.
+        let _ = throw_object(zv);
+        previous
+    }
+
     /// Attempts to extract the last PHP exception captured by the interpreter.
     /// Returned inside a [`PhpResult`].
     ///
@@ -220,7 +381,20 @@ impl ExecutorGlobals {
                     zend_atomic_bool_store(&raw mut self.vm_interrupt, false);
                 }
             } else {
-                self.vm_interrupt = true;
+                self.vm_interrupt = false;
+            }
+        }
+    }
+
+    /// Returns whether an interrupt of the PHP VM has been requested and not
+    /// yet handled.
+    #[must_use]
+    pub fn is_interrupt_requested(&self) -> bool {
+        cfg_if::cfg_if! {
+            if #[cfg(php82)] {
+                unsafe { zend_atomic_bool_load(&raw const self.vm_interrupt) }
+            } else {
+                self.vm_interrupt
             }
         }
     }
@@ -284,6 +458,88 @@ impl CompilerGlobals {
 
         GlobalWriteGuard { globals, guard }
     }
+
+    /// Returns the options of the compiler.
+    #[must_use]
+    pub fn compiler_options(&self) -> CompilerOptions {
+        CompilerOptions::from_bits_retain(self.compiler_options)
+    }
+
+    /// Sets the options of the compiler, which apply to code compiled after
+    /// the options are changed.
+    pub fn set_compiler_options(&mut self, options: CompilerOptions) {
+        self.compiler_options = options.bits();
+    }
+
+    /// Returns whether code is currently being compiled.
+    #[must_use]
+    pub fn in_compilation(&self) -> bool {
+        self.in_compilation
+    }
+
+    /// Returns the class which is currently being compiled.
+    #[must_use]
+    pub fn active_class_entry(&self) -> Option<&ClassEntry> {
+        unsafe { self.active_class_entry.as_ref() }
+    }
+
+    /// Returns the name of the file which is currently being compiled.
+    #[must_use]
+    pub fn compiled_filename(&self) -> Option<&str> {
+        unsafe { self.compiled_filename.as_ref() }?.as_str().ok()
+    }
+
+    /// Returns the line which is currently being compiled.
+    #[must_use]
+    pub fn lineno(&self) -> u32 {
+        u32::try_from(self.zend_lineno).unwrap_or_default()
+    }
+
+    /// Returns the doc comment preceding the declaration which is currently
+    /// being compiled.
+    #[must_use]
+    pub fn doc_comment(&self) -> Option<&str> {
+        unsafe { self.doc_comment.as_ref() }?.as_str().ok()
+    }
+
+    /// Returns whether multibyte scripts are supported, as set by the
+    /// `zend.multibyte` ini directive.
+    #[must_use]
+    pub fn multibyte(&self) -> bool {
+        self.multibyte
+    }
+
+    /// Returns the names of the encodings scripts are detected in, as set by
+    /// the `zend.script_encoding` ini directive. Only used if
+    /// [`multibyte`](Self::multibyte) is enabled.
+    #[must_use]
+    pub fn script_encodings(&self) -> Vec<&str> {
+        if self.script_encoding_list.is_null() {
+            return vec![];
+        }
+
+        // SAFETY: The list holds `script_encoding_list_size` encodings.
+        let encodings = unsafe {
+            slice::from_raw_parts(self.script_encoding_list, self.script_encoding_list_size)
+        };
+        encodings
+            .iter()
+            .filter_map(|encoding| {
+                let name = unsafe { zend_multibyte_get_encoding_name(*encoding) };
+                if name.is_null() {
+                    return None;
+                }
+                unsafe { CStr::from_ptr(name) }.to_str().ok()
+            })
+            .collect()
+    }
+
+    /// Returns whether the script being compiled declared its encoding with
+    /// `declare(encoding=...)`.
+    #[must_use]
+    pub fn encoding_declared(&self) -> bool {
+        self.encoding_declared
+    }
 }
 
 /// Stores the SAPI module used in the PHP executor.
@@ -945,6 +1201,33 @@ mod embed_tests {
         });
     }
 
+    #[test]
+    fn test_executor_globals_interrupt() {
+        Embed::run(|| {
+            ExecutorGlobals::get_mut().request_interrupt();
+            assert!(ExecutorGlobals::get().is_interrupt_requested());
+            ExecutorGlobals::get_mut().cancel_interrupt();
+            assert!(!ExecutorGlobals::get().is_interrupt_requested());
+        });
+    }
+
+    #[test]
+    fn test_executor_globals_replace_exception() {
+        Embed::run(|| {
+            assert!(ExecutorGlobals::get().exception().is_none());
+
+            let exception = ZendObject::new(crate::zend::ce::exception());
+            assert!(ExecutorGlobals::replace_exception(exception).is_none());
+            assert!(ExecutorGlobals::get().exception().is_some());
+
+            let replaced = ExecutorGlobals::take_exception().expect("Exception was not thrown");
+            assert_eq!(
+                replaced.get_class_name().expect("Failed to get class name"),
+                "Exception"
+            );
+        });
+    }
+
     #[test]
     fn test_compiler_globals() {
         Embed::run(|| {
@@ -955,4 +1238,20 @@ mod embed_tests {
             assert_eq!(changed, !state);
         });
     }
+
+    #[test]
+    fn test_compiler_options() {
+        Embed::run(|| {
+            let options = CompilerGlobals::get().compiler_options();
+            CompilerGlobals::get_mut()
+                .set_compiler_options(options | CompilerOptions::ExtendedInfo);
+            let changed = CompilerGlobals::get().compiler_options();
+            CompilerGlobals::get_mut().set_compiler_options(options);
+
+            assert!(changed.contains(CompilerOptions::ExtendedInfo));
+            assert_eq!(CompilerGlobals::get().compiler_options(), options);
+            assert!(!CompilerGlobals::get().in_compilation());
+            assert!(CompilerGlobals::get().active_class_entry().is_none());
+        });
+    }
 }
@@ -1,18 +1,25 @@
 use std::{ffi::CString, ffi::c_void, mem::MaybeUninit, os::raw::c_int, ptr};
 
 use crate::{
+    boxed::ZBox,
+    builders::FunctionBuilder,
     class::RegisteredClass,
-    exception::PhpResult,
+    exception::{PhpException, PhpResult},
     ffi::{
-        ext_php_rs_executor_globals, instanceof_function_slow, std_object_handlers,
-        zend_class_entry, zend_is_true, zend_object_handlers, zend_object_std_dtor,
-        zend_std_get_properties, zend_std_has_property, zend_std_read_property,
-        zend_std_write_property, zend_throw_error,
+        IS_STRING, ZEND_RESULT_CODE_FAILURE, ZEND_RESULT_CODE_SUCCESS, ext_php_rs_executor_globals,
+        instanceof_function_slow, std_object_handlers, zend_class_entry, zend_object_handlers,
+        zend_object_std_dtor, zend_result, zend_std_cast_object_tostring, zend_std_get_properties,
+        zend_std_get_property_ptr_ptr, zend_std_has_property, zend_std_read_property,
+        zend_std_unset_property, zend_std_write_property, zend_throw_error,
     },
-    flags::{PropertyFlags, ZvalTypeFlags},
-    types::{ZendClassObject, ZendHashTable, ZendObject, ZendStr, Zval},
+    flags::{DataType, FetchMode, PropertyCheck, PropertyFlags, ZvalTypeFlags},
+    types::{ZendClassObject, ZendHashTable, ZendObject, ZendStr, Zval, ZvalOwned, ZvalRef},
+    zend::ExecuteData,
+    zend_fastcall,
 };
 
+use super::array_access::{self, PhpArrayAccess};
+
 /// A set of functions associated with a PHP class.
 pub type ZendObjectHandlers = zend_object_handlers;
 
@@ -55,6 +62,30 @@ impl ZendObjectHandlers {
         unsafe { (*ptr).write_property = Some(Self::write_property::<T>) };
         unsafe { (*ptr).get_properties = Some(Self::get_properties::<T>) };
         unsafe { (*ptr).has_property = Some(Self::has_property::<T>) };
+
+        if T::PROXY_PROPERTIES {
+            unsafe { (*ptr).get_property_ptr_ptr = Some(Self::get_property_ptr_ptr::<T>) };
+        }
+
+        if let Some(properties) = T::PROPERTY_HANDLERS {
+            unsafe { (*ptr).has_property = Some(properties.has) };
+            unsafe { (*ptr).unset_property = Some(properties.unset) };
+        }
+
+        if let Some(DebugInfoHandler(debug_info)) = T::DEBUG_INFO_HANDLER {
+            unsafe { (*ptr).get_debug_info = Some(debug_info) };
+        }
+
+        if let Some(ToStringHandler { cast, .. }) = T::TO_STRING_HANDLER {
+            unsafe { (*ptr).cast_object = Some(cast) };
+        }
+
+        if let Some(dimensions) = T::DIMENSION_HANDLERS {
+            unsafe { (*ptr).read_dimension = Some(dimensions.read) };
+            unsafe { (*ptr).write_dimension = Some(dimensions.write) };
+            unsafe { (*ptr).has_dimension = Some(dimensions.has) };
+            unsafe { (*ptr).unset_dimension = Some(dimensions.unset) };
+        }
     }
 
     unsafe extern "C" fn free_obj<T: RegisteredClass>(object: *mut ZendObject) {
@@ -201,6 +232,25 @@ impl ZendObjectHandlers {
         }
     }
 
+    /// Returns no pointer for the `#[php(prop)]` fields of classes proxying
+    /// their properties, so that PHP falls back to reading and writing the
+    /// field for operations like `$obj->count++`.
+    unsafe extern "C" fn get_property_ptr_ptr<T: RegisteredClass>(
+        object: *mut ZendObject,
+        member: *mut ZendStr,
+        type_: c_int,
+        cache_slot: *mut *mut c_void,
+    ) -> *mut Zval {
+        let is_field = unsafe { member.as_ref() }
+            .and_then(|name| name.as_str().ok())
+            .is_some_and(|name| T::get_metadata().get_properties().contains_key(name));
+        if is_field {
+            return ptr::null_mut();
+        }
+
+        unsafe { zend_std_get_property_ptr_ptr(object, member, type_, cache_slot) }
+    }
+
     unsafe extern "C" fn get_properties<T: RegisteredClass>(
         object: *mut ZendObject,
     ) -> *mut ZendHashTable {
@@ -289,51 +339,23 @@ impl ZendObjectHandlers {
             let prop = props.get(prop_name.as_str()?);
             let self_ = &mut *obj;
 
-            match has_set_exists {
-                //
-                // * 0 (has) whether property exists and is not NULL
-                0 => {
-                    if let Some(val) = prop {
-                        let mut zv = Zval::new();
-                        val.prop.get(self_, &mut zv)?;
-                        if !zv.is_null() {
-                            return Ok(1);
-                        }
-                    }
-                }
-                //
-                // * 1 (set) whether property exists and is true
-                1 => {
-                    if let Some(val) = prop {
-                        let mut zv = Zval::new();
-                        val.prop.get(self_, &mut zv)?;
-
-                        cfg_if::cfg_if! {
-                            if #[cfg(php84)] {
-                                #[allow(clippy::unnecessary_mut_passed)]
-                                if unsafe { zend_is_true(&raw mut zv) } {
-                                    return Ok(1);
-                                }
-                            } else {
-                                #[allow(clippy::unnecessary_mut_passed)]
-                                if unsafe { zend_is_true(&raw mut zv) } == 1 {
-                                    return Ok(1);
-                                }
-                            }
-                        }
-                    }
+            let check = PropertyCheck::from_raw(has_set_exists).ok_or(
+                "Invalid value given for `has_set_exists` in struct `has_property` function.",
+            )?;
+
+            if let Some(val) = prop {
+                if check == PropertyCheck::Exists {
+                    return Ok(1);
                 }
-                //
-                // * 2 (exists) whether property exists
-                2 => {
-                    if prop.is_some() {
-                        return Ok(1);
-                    }
+                let mut zv = Zval::new();
+                val.prop.get(self_, &mut zv)?;
+                let is_set = match check {
+                    PropertyCheck::NotEmpty => zv.is_truthy(),
+                    _ => !zv.is_null(),
+                };
+                if is_set {
+                    return Ok(1);
                 }
-                _ => return Err(
-                    "Invalid value given for `has_set_exists` in struct `has_property` function."
-                        .into(),
-                ),
             }
 
             Ok(unsafe { zend_std_has_property(object, member, has_set_exists, cache_slot) })
@@ -347,6 +369,624 @@ impl ZendObjectHandlers {
             }
         }
     }
+
+    unsafe extern "C" fn unset_property<T: RegisteredClass>(
+        object: *mut ZendObject,
+        member: *mut ZendStr,
+        cache_slot: *mut *mut c_void,
+    ) {
+        // TODO: Measure this
+        #[allow(clippy::inline_always)]
+        #[inline(always)]
+        unsafe fn internal<T: RegisteredClass>(
+            object: *mut ZendObject,
+            member: *mut ZendStr,
+            cache_slot: *mut *mut c_void,
+        ) -> PhpResult {
+            let prop_name = unsafe {
+                member
+                    .as_ref()
+                    .ok_or("Invalid property name pointer given")?
+            };
+            let props = T::get_metadata().get_properties();
+
+            match props.get(prop_name.as_str()?) {
+                // Properties backed by fields cannot be removed from the struct.
+                Some(prop_info) => {
+                    let object_ce = unsafe { (*object).ce };
+                    if !unsafe { check_property_access(prop_info.flags, object_ce) } {
+                        let is_private = prop_info.flags.contains(PropertyFlags::Private);
+                        unsafe {
+                            throw_property_access_error(
+                                T::CLASS_NAME,
+                                prop_name.as_str()?,
+                                is_private,
+                            );
+                        }
+                        return Ok(());
+                    }
+                    Err(PhpException::new(
+                        format!(
+                            "Cannot unset property {}::${}",
+                            T::CLASS_NAME,
+                            prop_name.as_str()?
+                        ),
+                        0,
+                        crate::zend::ce::error(),
+                    ))
+                }
+                None => {
+                    unsafe { zend_std_unset_property(object, member, cache_slot) };
+                    Ok(())
+                }
+            }
+        }
+
+        if let Err(e) = unsafe { internal::<T>(object, member, cache_slot) } {
+            let _ = e.throw();
+        }
+    }
+}
+
+/// Handlers for using objects of a class as arrays, e.g. `$obj['key']`,
+/// without implementing `ArrayAccess`.
+///
+/// The handlers are called directly by the engine, which avoids the method
+/// calls made for `ArrayAccess`. They are installed by adding the
+/// `#[php(dimensions)]` attribute to the class:
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use std::collections::HashMap;
+/// use ext_php_rs::{
+///     prelude::*, exception::PhpResult, flags::FetchMode, types::Zval,
+///     zend::DimensionHandlers,
+/// };
+///
+/// #[php_class]
+/// #[php(dimensions)]
+/// #[derive(Default)]
+/// pub struct Bag {
+///     items: HashMap<String, Zval>,
+/// }
+///
+/// impl DimensionHandlers for Bag {
+///     fn read_dimension(&mut self, offset: Option<&Zval>, _: FetchMode) -> PhpResult<Option<Zval>> {
+///         let key = offset.and_then(Zval::string).ok_or("Invalid key")?;
+///         Ok(self.items.get(&key).map(Zval::shallow_clone))
+///     }
+///
+///     fn write_dimension(&mut self, offset: Option<&Zval>, value: &Zval) -> PhpResult<()> {
+///         let key = offset.and_then(Zval::string).ok_or("Cannot append to a bag")?;
+///         self.items.insert(key, value.shallow_clone());
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// Handlers which are not implemented throw an `Error`, as is done for
+/// objects which cannot be used as arrays.
+pub trait DimensionHandlers: RegisteredClass {
+    /// Reads the element at the given offset, as done by `$obj[$offset]`.
+    /// Returning `None` reads `null`.
+    ///
+    /// # Parameters
+    ///
+    /// * `offset` - The offset of the element, which is `None` when an
+    ///   appended element is fetched, e.g. `$obj[][] = 1`.
+    /// * `mode` - The mode in which the element is fetched. Elements fetched
+    ///   for [`FetchMode::Isset`] should not raise notices if they do not
+    ///   exist.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn read_dimension(
+        &mut self,
+        offset: Option<&Zval>,
+        mode: FetchMode,
+    ) -> PhpResult<Option<Zval>> {
+        let _ = (offset, mode);
+        Err(cannot_use_as_array::<Self>())
+    }
+
+    /// Writes the element at the given offset, as done by
+    /// `$obj[$offset] = $value`.
+    ///
+    /// # Parameters
+    ///
+    /// * `offset` - The offset of the element, which is `None` when the
+    ///   element is appended, e.g. `$obj[] = $value`.
+    /// * `value` - The value to write.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn write_dimension(&mut self, offset: Option<&Zval>, value: &Zval) -> PhpResult<()> {
+        let _ = (offset, value);
+        Err(cannot_use_as_array::<Self>())
+    }
+
+    /// Returns whether the element at the given offset exists, as checked by
+    /// `isset($obj[$offset])`. `empty($obj[$offset])` additionally reads the
+    /// element to check whether it is truthy.
+    ///
+    /// By default, the element is read in [`FetchMode::Isset`] and exists if
+    /// it is not `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn has_dimension(&mut self, offset: &Zval) -> PhpResult<bool> {
+        Ok(self
+            .read_dimension(Some(offset), FetchMode::Isset)?
+            .is_some_and(|val| !val.is_null()))
+    }
+
+    /// Removes the element at the given offset, as done by
+    /// `unset($obj[$offset])`.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn unset_dimension(&mut self, offset: &Zval) -> PhpResult<()> {
+        let _ = offset;
+        Err(cannot_use_as_array::<Self>())
+    }
+}
+
+/// The dimension handlers of a class implementing [`DimensionHandlers`],
+/// see [`RegisteredClass::DIMENSION_HANDLERS`].
+#[derive(Clone, Copy)]
+pub struct DimensionHandlerTable {
+    read: unsafe extern "C" fn(*mut ZendObject, *mut Zval, c_int, *mut Zval) -> *mut Zval,
+    write: unsafe extern "C" fn(*mut ZendObject, *mut Zval, *mut Zval),
+    has: unsafe extern "C" fn(*mut ZendObject, *mut Zval, c_int) -> c_int,
+    unset: unsafe extern "C" fn(*mut ZendObject, *mut Zval),
+}
+
+impl DimensionHandlerTable {
+    /// Returns the dimension handlers calling the [`DimensionHandlers`]
+    /// implementation of `T`.
+    #[must_use]
+    pub const fn of<T: DimensionHandlers>() -> Self {
+        Self {
+            read: read_dimension::<T>,
+            write: write_dimension::<T>,
+            has: has_dimension::<T>,
+            unset: unset_dimension::<T>,
+        }
+    }
+
+    /// Returns the dimension handlers calling the [`PhpArrayAccess`]
+    /// implementation of `T`.
+    #[must_use]
+    pub const fn of_array_access<T: PhpArrayAccess>() -> Self {
+        Self {
+            read: array_access::read_dimension::<T>,
+            write: array_access::write_dimension::<T>,
+            has: array_access::has_dimension::<T>,
+            unset: array_access::unset_dimension::<T>,
+        }
+    }
+}
+
+fn cannot_use_as_array<T: RegisteredClass>() -> PhpException {
+    PhpException::new(
+        format!("Cannot use object of type {} as array", T::CLASS_NAME),
+        0,
+        crate::zend::ce::error(),
+    )
+}
+
+/// Returns the Rust object of the given PHP object.
+pub(super) unsafe fn class_object<'a, T: RegisteredClass>(
+    object: *mut ZendObject,
+) -> PhpResult<&'a mut T> {
+    let obj = unsafe { object.as_mut() }
+        .and_then(|obj| ZendClassObject::<T>::from_zend_obj_mut(obj))
+        .ok_or("Invalid object pointer given")?;
+    Ok(&mut **obj)
+}
+
+unsafe extern "C" fn read_dimension<T: DimensionHandlers>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+    type_: c_int,
+    rv: *mut Zval,
+) -> *mut Zval {
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let mode = FetchMode::from(u32::try_from(type_).unwrap_or_default());
+        let offset = unsafe { ZvalRef::from_ptr(offset) };
+        obj.read_dimension(offset.map(ZvalRef::get), mode)
+    });
+
+    if rv.is_null() {
+        return rv;
+    }
+    let value = match result {
+        Ok(val) => val.map(ZvalOwned::from).unwrap_or_default(),
+        Err(e) => {
+            let _ = e.throw();
+            ZvalOwned::new()
+        }
+    };
+    // SAFETY: `rv` is uninitialized, and the engine releases the value.
+    unsafe { value.write_to(rv) };
+    rv
+}
+
+unsafe extern "C" fn write_dimension<T: DimensionHandlers>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+    value: *mut Zval,
+) {
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let value = unsafe { ZvalRef::from_ptr(value) }.ok_or("Invalid value given")?;
+        let offset = unsafe { ZvalRef::from_ptr(offset) };
+        obj.write_dimension(offset.map(ZvalRef::get), value.get())
+    });
+
+    if let Err(e) = result {
+        let _ = e.throw();
+    }
+}
+
+unsafe extern "C" fn has_dimension<T: DimensionHandlers>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+    check_empty: c_int,
+) -> c_int {
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let offset = unsafe { ZvalRef::from_ptr(offset) }
+            .ok_or("Invalid offset given")?
+            .get();
+        if !obj.has_dimension(offset)? {
+            return Ok(false);
+        }
+        if check_empty == 0 {
+            return Ok(true);
+        }
+        let val = obj.read_dimension(Some(offset), FetchMode::Isset)?;
+        Ok(val.is_some_and(|val| val.is_truthy()))
+    });
+
+    match result {
+        Ok(has) => has.into(),
+        Err(e) => {
+            let _ = e.throw();
+            0
+        }
+    }
+}
+
+unsafe extern "C" fn unset_dimension<T: DimensionHandlers>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+) {
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let offset = unsafe { ZvalRef::from_ptr(offset) }.ok_or("Invalid offset given")?;
+        obj.unset_dimension(offset.get())
+    });
+
+    if let Err(e) = result {
+        let _ = e.throw();
+    }
+}
+
+/// Handlers for checking and removing properties of a class, e.g. for classes
+/// exposing virtual properties which are not backed by fields.
+///
+/// The handlers are installed by adding the `#[php(property_handlers)]`
+/// attribute to the class, and are called for every property of the object.
+/// Properties which are not handled fall back to the default behaviour, which
+/// checks the `#[php(prop)]` fields of the class, then the declared and
+/// dynamic properties of the object and finally `__isset()` and `__unset()`.
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use std::collections::HashMap;
+/// use ext_php_rs::{
+///     prelude::*, exception::PhpResult, flags::PropertyCheck, zend::PropertyHandlers,
+/// };
+///
+/// #[php_class]
+/// #[php(property_handlers)]
+/// pub struct Config {
+///     values: HashMap<String, String>,
+/// }
+///
+/// impl PropertyHandlers for Config {
+///     fn has_property(&mut self, name: &str, check: PropertyCheck) -> PhpResult<Option<bool>> {
+///         let value = self.values.get(name);
+///         Ok(Some(match check {
+///             PropertyCheck::NotEmpty => value.is_some_and(|value| !value.is_empty()),
+///             _ => value.is_some(),
+///         }))
+///     }
+///
+///     fn unset_property(&mut self, name: &str) -> PhpResult<bool> {
+///         self.values.remove(name);
+///         Ok(true)
+///     }
+/// }
+/// ```
+pub trait PropertyHandlers: RegisteredClass {
+    /// Returns whether the property with the given name is set, as checked by
+    /// `isset($obj->name)`, `empty($obj->name)` and `property_exists()`.
+    /// Returns `None` to fall back to the default behaviour.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the property.
+    /// * `check` - The check made for the property. [`PropertyCheck::NotEmpty`]
+    ///   is used by `empty()`, which has to read the property to check
+    ///   whether it is truthy.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn has_property(&mut self, name: &str, check: PropertyCheck) -> PhpResult<Option<bool>> {
+        let _ = (name, check);
+        Ok(None)
+    }
+
+    /// Removes the property with the given name, as done by
+    /// `unset($obj->name)`. Returns `false` to fall back to the default
+    /// behaviour.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn unset_property(&mut self, name: &str) -> PhpResult<bool> {
+        let _ = name;
+        Ok(false)
+    }
+}
+
+/// The property handlers of a class implementing [`PropertyHandlers`], see
+/// [`RegisteredClass::PROPERTY_HANDLERS`].
+#[derive(Clone, Copy)]
+pub struct PropertyHandlerTable {
+    has: unsafe extern "C" fn(*mut ZendObject, *mut ZendStr, c_int, *mut *mut c_void) -> c_int,
+    unset: unsafe extern "C" fn(*mut ZendObject, *mut ZendStr, *mut *mut c_void),
+}
+
+impl PropertyHandlerTable {
+    /// Returns the property handlers calling the [`PropertyHandlers`]
+    /// implementation of `T`.
+    #[must_use]
+    pub const fn of<T: PropertyHandlers>() -> Self {
+        Self {
+            has: has_property::<T>,
+            unset: unset_property::<T>,
+        }
+    }
+}
+
+unsafe extern "C" fn has_property<T: PropertyHandlers>(
+    object: *mut ZendObject,
+    member: *mut ZendStr,
+    has_set_exists: c_int,
+    cache_slot: *mut *mut c_void,
+) -> c_int {
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let name = unsafe { member.as_ref() }.ok_or("Invalid property name pointer given")?;
+        let check = PropertyCheck::from_raw(has_set_exists)
+            .ok_or("Invalid value given for `has_set_exists` in `has_property` function.")?;
+        obj.has_property(name.as_str()?, check)
+    });
+
+    match result {
+        Ok(Some(has)) => has.into(),
+        Ok(None) => unsafe {
+            ZendObjectHandlers::has_property::<T>(object, member, has_set_exists, cache_slot)
+        },
+        Err(e) => {
+            let _ = e.throw();
+            0
+        }
+    }
+}
+
+unsafe extern "C" fn unset_property<T: PropertyHandlers>(
+    object: *mut ZendObject,
+    member: *mut ZendStr,
+    cache_slot: *mut *mut c_void,
+) {
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let name = unsafe { member.as_ref() }.ok_or("Invalid property name pointer given")?;
+        obj.unset_property(name.as_str()?)
+    });
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => unsafe { ZendObjectHandlers::unset_property::<T>(object, member, cache_slot) },
+        Err(e) => {
+            let _ = e.throw();
+        }
+    }
+}
+
+/// Provides the properties of objects of a class shown by `var_dump()`,
+/// `print_r()` and `var_export()`, e.g. to show the internal state of the
+/// Rust struct.
+///
+/// The handler is installed by adding the `#[php(debug_info)]` attribute to
+/// the class, and takes precedence over a `__debugInfo()` method.
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{boxed::ZBox, prelude::*, types::ZendHashTable, zend::DebugInfo};
+///
+/// #[php_class]
+/// #[php(debug_info)]
+/// pub struct Counter {
+///     count: i64,
+/// }
+///
+/// impl DebugInfo for Counter {
+///     fn debug_info(&self) -> ZBox<ZendHashTable> {
+///         let mut info = ZendHashTable::new();
+///         let _ = info.insert("count", self.count);
+///         info
+///     }
+/// }
+/// ```
+pub trait DebugInfo: RegisteredClass {
+    /// Returns the properties shown for the object.
+    fn debug_info(&self) -> ZBox<ZendHashTable>;
+}
+
+/// The debug info handler of a class implementing [`DebugInfo`], see
+/// [`RegisteredClass::DEBUG_INFO_HANDLER`].
+#[derive(Clone, Copy)]
+pub struct DebugInfoHandler(
+    unsafe extern "C" fn(*mut ZendObject, *mut c_int) -> *mut ZendHashTable,
+);
+
+impl DebugInfoHandler {
+    /// Returns the debug info handler calling the [`DebugInfo`]
+    /// implementation of `T`.
+    #[must_use]
+    pub const fn of<T: DebugInfo>() -> Self {
+        Self(get_debug_info::<T>)
+    }
+}
+
+unsafe extern "C" fn get_debug_info<T: DebugInfo>(
+    object: *mut ZendObject,
+    is_temp: *mut c_int,
+) -> *mut ZendHashTable {
+    let Ok(obj) = (unsafe { class_object::<T>(object) }) else {
+        // Shows the properties of the object, which are owned by the object.
+        if let Some(is_temp) = unsafe { is_temp.as_mut() } {
+            *is_temp = 0;
+        }
+        return unsafe { zend_std_get_properties(object) };
+    };
+
+    // The table is released by the engine once it has been shown.
+    if let Some(is_temp) = unsafe { is_temp.as_mut() } {
+        *is_temp = 1;
+    }
+    obj.debug_info().into_raw()
+}
+
+/// Converts objects of a class to strings, e.g. when they are echoed,
+/// concatenated or passed as a `string` parameter.
+///
+/// The handler is installed by adding the `#[php(to_string)]` attribute to
+/// the class, and takes precedence over a `__toString()` method. Objects
+/// without the handler cannot be converted to strings and throw an `Error`.
+/// Unless the class declares its own, a `__toString()` method calling the
+/// implementation is also added, and the class implements `Stringable`.
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{exception::PhpResult, prelude::*, zend::ToPhpString};
+///
+/// #[php_class]
+/// #[php(to_string)]
+/// pub struct Point {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// impl ToPhpString for Point {
+///     fn to_php_string(&self) -> PhpResult<String> {
+///         Ok(format!("({}, {})", self.x, self.y))
+///     }
+/// }
+/// ```
+pub trait ToPhpString: RegisteredClass {
+    /// Returns the string representation of the object.
+    ///
+    /// # Errors
+    ///
+    /// Returned errors are thrown as exceptions.
+    fn to_php_string(&self) -> PhpResult<String>;
+}
+
+/// The cast handler and `__toString()` method of a class implementing
+/// [`ToPhpString`], see [`RegisteredClass::TO_STRING_HANDLER`].
+#[derive(Clone, Copy)]
+pub struct ToStringHandler {
+    cast: unsafe extern "C" fn(*mut ZendObject, *mut Zval, c_int) -> zend_result,
+    method: fn() -> FunctionBuilder<'static>,
+}
+
+impl ToStringHandler {
+    /// Returns the cast handler calling the [`ToPhpString`] implementation of
+    /// `T`.
+    #[must_use]
+    pub const fn of<T: ToPhpString>() -> Self {
+        Self {
+            cast: cast_object::<T>,
+            method: to_string_method::<T>,
+        }
+    }
+
+    /// Returns the builder of the `__toString()` method, which is added to
+    /// the class together with the `Stringable` interface.
+    pub(crate) fn method_builder(self) -> FunctionBuilder<'static> {
+        (self.method)()
+    }
+}
+
+fn to_string_method<T: ToPhpString>() -> FunctionBuilder<'static> {
+    FunctionBuilder::new("__toString", to_string::<T>).returns(DataType::String, false, false)
+}
+
+zend_fastcall! {
+    extern "C" fn to_string<T: ToPhpString>(ex: &mut ExecuteData, retval: &mut Zval) {
+        let (parser, this) = ex.parser_method::<T>();
+        let Some(this) = this else {
+            PhpException::default("Failed to retrieve reference to `$this`".into())
+                .throw()
+                .expect("Failed to throw exception");
+            return;
+        };
+        if parser.parse().is_err() {
+            return;
+        }
+
+        let result = this
+            .to_php_string()
+            .and_then(|string| retval.set_string(&string, false).map_err(Into::into));
+        if let Err(e) = result {
+            let _ = e.throw();
+        }
+    }
+}
+
+unsafe extern "C" fn cast_object<T: ToPhpString>(
+    object: *mut ZendObject,
+    retval: *mut Zval,
+    type_: c_int,
+) -> zend_result {
+    // Other casts, e.g. to `bool`, keep the default behaviour.
+    if u32::try_from(type_).ok() != Some(IS_STRING) {
+        return unsafe { zend_std_cast_object_tostring(object, retval, type_) };
+    }
+
+    let Some(rv) = (unsafe { retval.as_mut() }) else {
+        return ZEND_RESULT_CODE_FAILURE;
+    };
+    // retval needs to be treated as initialized, so we set the type to null
+    rv.u1.type_info = ZvalTypeFlags::Null.bits();
+    let result = unsafe { class_object::<T>(object) }.and_then(|obj| {
+        let string = obj.to_php_string()?;
+        rv.set_string(&string, false).map_err(Into::into)
+    });
+    match result {
+        Ok(()) => ZEND_RESULT_CODE_SUCCESS,
+        Err(e) => {
+            // The engine only throws its own error if no exception was thrown.
+            let _ = e.throw();
+            ZEND_RESULT_CODE_FAILURE
+        }
+    }
 }
 
 /// Gets the current calling scope from the executor globals.
@@ -0,0 +1,243 @@
+//! Writing the information of an extension shown by `phpinfo()`.
+
+use std::{borrow::Cow, fmt::Display};
+
+use crate::ffi::{
+    php_info_print_box_end, php_info_print_box_start, php_info_print_hr, php_info_print_table_end,
+    php_info_print_table_start, sapi_module,
+};
+
+use super::output_write;
+
+/// Width of the text output of `phpinfo()`, used to center headers.
+const TEXT_WIDTH: usize = 74;
+
+/// Writes the information of an extension to the output of `phpinfo()`.
+///
+/// The output is formatted as HTML or plain text depending on the SAPI, e.g.
+/// plain text on the CLI. All text given to the writer is escaped
+/// accordingly, except for the HTML passed to [`ModuleInfo::html`].
+///
+/// A `ModuleInfo` is passed to the `#[php(minfo = ...)]` hook of
+/// `#[php_module]`, and may also be created inside a function set with
+/// [`ModuleBuilder::info_function`].
+///
+/// ```rust,no_run
+/// use ext_php_rs::zend::ModuleInfo;
+///
+/// fn minfo(info: &mut ModuleInfo) {
+///     info.table(|table| {
+///         table.header(&["my_ext support", "enabled"]);
+///         table.entry("Version", "1.0.0");
+///     });
+///     info.section("Connections", |table| {
+///         table.entry("Active", 3);
+///         table.section("Pool", |table| {
+///             table.entry("Size", 10);
+///         });
+///     });
+///     info.text("Diagnostics are <b>not</b> HTML and will be escaped.");
+/// }
+/// ```
+///
+/// [`ModuleBuilder::info_function`]: crate::builders::ModuleBuilder::info_function
+#[derive(Debug)]
+pub struct ModuleInfo {
+    html: bool,
+}
+
+impl ModuleInfo {
+    /// Creates a writer for the information of an extension. Should only be
+    /// used inside of the info function of a module.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            // SAFETY: The SAPI module is initialized before `phpinfo()` can be called.
+            html: unsafe { sapi_module.phpinfo_as_text } == 0,
+        }
+    }
+
+    /// Returns whether the information is written as HTML, or as plain text
+    /// otherwise.
+    #[must_use]
+    pub fn is_html(&self) -> bool {
+        self.html
+    }
+
+    /// Writes a table, filled by the given function.
+    pub fn table(&mut self, f: impl FnOnce(&mut InfoTable)) {
+        unsafe { php_info_print_table_start() };
+        f(&mut InfoTable { html: self.html });
+        unsafe { php_info_print_table_end() };
+    }
+
+    /// Writes a table with the given title, filled by the given function.
+    pub fn section(&mut self, title: &str, f: impl FnOnce(&mut InfoTable)) {
+        self.table(|table| {
+            table.colspan_header(2, title);
+            f(table);
+        });
+    }
+
+    /// Writes a block of text. Line breaks are kept.
+    pub fn text(&mut self, text: &str) {
+        if self.html {
+            unsafe { php_info_print_box_start(0) };
+            write(&html_escape(text).replace('\n', "<br />\n"));
+            unsafe { php_info_print_box_end() };
+        } else {
+            write(text);
+            write("\n");
+        }
+    }
+
+    /// Writes a block of raw HTML if the information is written as HTML, or
+    /// the given text otherwise.
+    ///
+    /// The HTML is written as is, so it must not contain untrusted input.
+    pub fn html(&mut self, html: &str, text: &str) {
+        if self.html {
+            write(html);
+        } else {
+            self.text(text);
+        }
+    }
+
+    /// Writes a horizontal rule.
+    pub fn hr(&mut self) {
+        unsafe { php_info_print_hr() };
+    }
+}
+
+impl Default for ModuleInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A table in the output of `phpinfo()`, see [`ModuleInfo::table`].
+#[derive(Debug)]
+pub struct InfoTable {
+    html: bool,
+}
+
+impl InfoTable {
+    /// Writes a header row with the given cells.
+    pub fn header(&mut self, cells: &[&str]) {
+        if self.html {
+            let mut row = String::from("<tr class=\"h\">");
+            for cell in cells {
+                row.push_str("<th>");
+                row.push_str(&html_escape(cell));
+                row.push_str("</th>");
+            }
+            row.push_str("</tr>\n");
+            write(&row);
+        } else {
+            write(&format!("{}\n", cells.join(" => ")));
+        }
+    }
+
+    /// Writes a header spanning the given number of columns.
+    pub fn colspan_header(&mut self, cols: usize, title: &str) {
+        if self.html {
+            write(&format!(
+                "<tr class=\"h\"><th colspan=\"{cols}\">{}</th></tr>\n",
+                html_escape(title)
+            ));
+        } else {
+            let padding = " ".repeat(TEXT_WIDTH.saturating_sub(title.len()) / 2);
+            write(&format!("\n{padding}{title}{padding}\n"));
+        }
+    }
+
+    /// Writes a row with the given cells. The first cell is formatted as the
+    /// key of the row.
+    pub fn row(&mut self, cells: &[&str]) {
+        if self.html {
+            let mut row = String::from("<tr>");
+            for (i, cell) in cells.iter().enumerate() {
+                row.push_str(if i == 0 {
+                    "<td class=\"e\">"
+                } else {
+                    "<td class=\"v\">"
+                });
+                if cell.is_empty() {
+                    row.push_str("<i>no value</i>");
+                } else {
+                    row.push_str(&html_escape(cell));
+                }
+                row.push_str(" </td>");
+            }
+            row.push_str("</tr>\n");
+            write(&row);
+        } else {
+            let cells = cells
+                .iter()
+                .map(|cell| if cell.is_empty() { "no value" } else { cell })
+                .collect::<Vec<_>>();
+            write(&format!("{}\n", cells.join(" => ")));
+        }
+    }
+
+    /// Writes a row with a key and a value.
+    pub fn entry(&mut self, key: &str, value: impl Display) {
+        self.row(&[key, &value.to_string()]);
+    }
+
+    /// Writes a row for every key and value of the given iterator.
+    pub fn entries<K, V>(&mut self, entries: impl IntoIterator<Item = (K, V)>)
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        for (key, value) in entries {
+            self.entry(key.as_ref(), value);
+        }
+    }
+
+    /// Writes a nested section with the given title, filled by the given
+    /// function.
+    pub fn section(&mut self, title: &str, f: impl FnOnce(&mut InfoTable)) {
+        self.colspan_header(2, title);
+        f(self);
+    }
+}
+
+fn write(data: &str) {
+    let _ = output_write(data.as_bytes());
+}
+
+/// Escapes the special HTML characters of the given text.
+fn html_escape(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#039;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_escape() {
+        assert!(matches!(html_escape("plain"), Cow::Borrowed("plain")));
+        assert_eq!(
+            html_escape("<a href=\"x\">'&'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&#039;&amp;&#039;&lt;/a&gt;"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Registered ini entries and their change handlers.
+
+use std::{
+    ffi::c_void,
+    mem,
+    os::raw::{c_char, c_int},
+};
+
+use crate::{
+    error::{Error, Result},
+    ffi::{
+        ZEND_RESULT_CODE_FAILURE, ZEND_RESULT_CODE_SUCCESS, zend_alter_ini_entry_chars,
+        zend_ini_entry, zend_restore_ini_entry, zend_string,
+    },
+    flags::{IniEntryPermission, IniStage},
+    types::ZendStr,
+};
+
+use super::ExecutorGlobals;
+
+/// An ini entry registered with PHP.
+pub type IniEntry = zend_ini_entry;
+
+/// Handler called when the value of an ini entry is changed, see
+/// [`IniEntryDef::on_modify`].
+///
+/// The handler is given the entry, the new value and the stage at which the
+/// value is changed. Returning `false` rejects the new value, e.g. making
+/// `ini_set()` fail.
+///
+/// [`IniEntryDef::on_modify`]: super::IniEntryDef::on_modify
+pub type IniOnModify = fn(entry: &IniEntry, new_value: Option<&str>, stage: IniStage) -> bool;
+
+impl IniEntry {
+    /// Attempts to find the registered ini entry with the given name.
+    #[must_use]
+    pub fn find(name: &str) -> Option<&'static Self> {
+        let directives = unsafe { ExecutorGlobals::get().ini_directives.as_ref() }?;
+        let entry = directives.get(name)?.ptr::<Self>()?;
+        unsafe { entry.as_ref() }
+    }
+
+    /// Returns the name of the entry.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        unsafe { self.name.as_ref() }.and_then(|name| name.as_str().ok())
+    }
+
+    /// Returns the current value of the entry.
+    #[must_use]
+    pub fn value(&self) -> Option<&str> {
+        unsafe { self.value.as_ref() }.and_then(|value| value.as_str().ok())
+    }
+
+    /// Returns the value of the entry before it was changed in the current
+    /// request, which is the current value if it was not changed.
+    #[must_use]
+    pub fn original_value(&self) -> Option<&str> {
+        if self.is_modified() {
+            unsafe { self.orig_value.as_ref() }.and_then(|value| value.as_str().ok())
+        } else {
+            self.value()
+        }
+    }
+
+    /// Returns whether the value of the entry was changed in the current
+    /// request. Changed values are restored when the request ends.
+    #[must_use]
+    pub fn is_modified(&self) -> bool {
+        self.modified != 0
+    }
+
+    /// Returns where the value of the entry may be changed.
+    #[must_use]
+    pub fn permission(&self) -> IniEntryPermission {
+        IniEntryPermission::from_bits_truncate(self.modifiable.into())
+    }
+
+    /// Changes the value of the ini entry with the given name for the rest of
+    /// the request, as done by `ini_set()`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::IniModificationFailure`] - If the entry does not exist, may
+    ///   not be changed at runtime or its change handler rejected the value.
+    pub fn set(name: &str, value: &str) -> Result<()> {
+        let mut name = ZendStr::new(name, false);
+        let result = unsafe {
+            zend_alter_ini_entry_chars(
+                &raw mut *name,
+                value.as_ptr().cast::<c_char>(),
+                value.len(),
+                IniEntryPermission::User.bits().try_into()?,
+                IniStage::Runtime.bits().try_into()?,
+            )
+        };
+        if result != ZEND_RESULT_CODE_SUCCESS {
+            return Err(Error::IniModificationFailure);
+        }
+        Ok(())
+    }
+
+    /// Restores the original value of the ini entry with the given name, as
+    /// done by `ini_restore()`.
+    ///
+    /// Values changed during a request are restored by PHP when the request
+    /// ends, in which case the change handler is called with
+    /// [`IniStage::Deactivate`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::IniModificationFailure`] - If the entry does not exist.
+    pub fn restore(name: &str) -> Result<()> {
+        let mut name = ZendStr::new(name, false);
+        let result =
+            unsafe { zend_restore_ini_entry(&raw mut *name, IniStage::Runtime.bits().try_into()?) };
+        if result != ZEND_RESULT_CODE_SUCCESS {
+            return Err(Error::IniModificationFailure);
+        }
+        Ok(())
+    }
+}
+
+/// Calls the [`IniOnModify`] handler stored in the first argument of an ini
+/// entry.
+pub(crate) unsafe extern "C" fn on_modify_handler(
+    entry: *mut zend_ini_entry,
+    new_value: *mut zend_string,
+    mh_arg1: *mut c_void,
+    _mh_arg2: *mut c_void,
+    _mh_arg3: *mut c_void,
+    stage: c_int,
+) -> c_int {
+    // SAFETY: The argument is only set to an `IniOnModify` by `IniEntryDef::on_modify`.
+    let handler = unsafe { mem::transmute::<*mut c_void, IniOnModify>(mh_arg1) };
+    let Some(entry) = (unsafe { entry.as_ref() }) else {
+        return ZEND_RESULT_CODE_FAILURE;
+    };
+    let new_value = unsafe { new_value.as_ref() }.and_then(|value| value.as_str().ok());
+    let stage = IniStage::from_bits_truncate(stage.try_into().unwrap_or_default());
+
+    if handler(entry, new_value, stage) {
+        ZEND_RESULT_CODE_SUCCESS
+    } else {
+        ZEND_RESULT_CODE_FAILURE
+    }
+}
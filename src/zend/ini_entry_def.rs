@@ -3,6 +3,7 @@
 
 use std::{ffi::CString, os::raw::c_char, ptr};
 
+use super::{IniOnModify, ini_entry::on_modify_handler};
 use crate::{ffi::zend_ini_entry_def, ffi::zend_register_ini_entries, flags::IniEntryPermission};
 
 /// A Zend ini entry definition.
@@ -49,6 +50,19 @@ impl IniEntryDef {
         template
     }
 
+    /// Sets the handler called when the value of the entry is changed,
+    /// including when it is first read at startup and when it is restored at
+    /// the end of a request. The handler is given the [`IniStage`] of the
+    /// change, and may reject the new value.
+    ///
+    /// [`IniStage`]: crate::flags::IniStage
+    #[must_use]
+    pub fn on_modify(mut self, handler: IniOnModify) -> Self {
+        self.on_modify = Some(on_modify_handler);
+        self.mh_arg1 = handler as *mut _;
+        self
+    }
+
     /// Returns an empty ini entry def, signifying the end of a ini list.
     #[must_use]
     pub fn end() -> Self {
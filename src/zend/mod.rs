@@ -1,27 +1,45 @@
 //! Types used to interact with the Zend engine.
 
 mod _type;
+mod array_access;
+#[cfg(all(feature = "tokio", unix))]
+mod async_stream;
 mod bailout_guard;
 pub mod ce;
 mod class;
+pub(crate) mod engine;
 mod ex;
 mod function;
 mod globals;
 mod handlers;
+mod info;
+mod ini_entry;
 mod ini_entry_def;
 mod linked_list;
 mod module;
+pub(crate) mod request;
+mod sapi_hooks;
+mod stack;
 mod streams;
+mod symbol_table;
 mod try_catch;
 
 use crate::{
     error::Result,
-    ffi::{php_output_write, php_printf, sapi_module},
+    ffi::{
+        php_output_discard, php_output_get_contents, php_output_start_default, php_output_write,
+        php_printf, sapi_module,
+    },
+    types::Zval,
 };
 use std::ffi::CString;
 use std::os::raw::c_char;
 
 pub use _type::ZendType;
+pub use array_access::{ArrayAccessMethods, PhpArrayAccess};
+#[cfg(all(feature = "tokio", unix))]
+#[cfg_attr(docs, doc(cfg(feature = "tokio")))]
+pub use async_stream::AsyncStream;
 pub use bailout_guard::BailoutGuard;
 pub use bailout_guard::run_bailout_cleanups;
 pub use class::ClassEntry;
@@ -35,14 +53,24 @@ pub use globals::SapiGlobals;
 pub use globals::SapiHeader;
 pub use globals::SapiHeaders;
 pub use globals::SapiModule;
-pub use handlers::ZendObjectHandlers;
+pub use handlers::{
+    DebugInfo, DebugInfoHandler, DimensionHandlerTable, DimensionHandlers, PropertyHandlerTable,
+    PropertyHandlers, ToPhpString, ToStringHandler, ZendObjectHandlers,
+};
+pub use info::{InfoTable, ModuleInfo};
+pub use ini_entry::{IniEntry, IniOnModify};
 pub use ini_entry_def::IniEntryDef;
 pub use linked_list::ZendLinkedList;
 pub use module::ModuleEntry;
+pub use sapi_hooks::{FlushHook, LogHook, OutputHook};
+pub use stack::{ensure_stack, remaining_stack};
 pub use streams::*;
+pub use symbol_table::SymbolTable;
 #[cfg(feature = "embed")]
 pub(crate) use try_catch::panic_wrapper;
-pub use try_catch::{CatchError, bailout, try_catch, try_catch_first};
+pub use try_catch::{
+    CatchError, bailout, try_catch, try_catch_first, try_catch_first_once, try_catch_once,
+};
 
 // Used as the format string for `php_printf`.
 const FORMAT_STR: &[u8] = b"%s\0";
@@ -143,6 +171,22 @@ pub fn output_write(data: &[u8]) -> usize {
     unsafe { php_output_write(data.as_ptr().cast::<c_char>(), data.len()) }
 }
 
+/// Runs the given function in a new output buffer and returns everything it
+/// wrote to the output, or [`None`] if the buffer could not be started, e.g.
+/// outside of a request.
+pub(crate) fn capture_output(f: impl FnOnce()) -> Option<Vec<u8>> {
+    if unsafe { php_output_start_default() } < 0 {
+        return None;
+    }
+    f();
+    let mut contents = Zval::new();
+    unsafe {
+        php_output_get_contents(&raw mut contents);
+        php_output_discard();
+    }
+    contents.zend_str().map(|str| str.as_bytes().to_vec())
+}
+
 /// Get the name of the SAPI module.
 ///
 /// # Panics
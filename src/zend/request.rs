@@ -0,0 +1,23 @@
+//! Tracking of the requests handled by the current thread, allowing values
+//! bound to a request to detect that it has ended.
+
+use std::cell::Cell;
+
+thread_local! {
+    /// Number of requests the current thread has finished handling.
+    static FINISHED_REQUESTS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Returns an identifier of the request handled by the current thread, which
+/// changes once the request has been shut down.
+///
+/// Requests are only tracked for modules built with a
+/// [`ModuleBuilder`](crate::builders::ModuleBuilder).
+pub(crate) fn current_request() -> u64 {
+    FINISHED_REQUESTS.get()
+}
+
+/// Marks the request handled by the current thread as finished.
+pub(crate) fn finish_request() {
+    FINISHED_REQUESTS.set(FINISHED_REQUESTS.get() + 1);
+}
@@ -0,0 +1,399 @@
+//! Overrides of the output, flush and log functions of the SAPI, allowing
+//! output and log messages of PHP to be routed to Rust.
+
+use std::{
+    ffi::{CStr, c_char, c_int, c_void},
+    slice,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use parking_lot::RwLock;
+
+use super::{SapiModule, engine};
+
+/// The `ub_write` function of the SAPI.
+type UbWrite = Option<unsafe extern "C" fn(*const c_char, usize) -> usize>;
+/// The `flush` function of the SAPI.
+type Flush = Option<unsafe extern "C" fn(*mut c_void)>;
+/// The `log_message` function of the SAPI.
+type LogMessage = Option<unsafe extern "C" fn(*const c_char, c_int)>;
+
+type OutputFn = dyn Fn(&[u8], &dyn Fn(&[u8]) -> usize) -> usize + Send + Sync;
+type FlushFn = dyn Fn(&dyn Fn()) + Send + Sync;
+type LogFn = dyn Fn(&CStr, i32, &dyn Fn(&CStr, i32)) + Send + Sync;
+
+/// Installed hooks with their identifiers, the most recently installed last.
+type Hooks<F> = Arc<[(u64, Arc<F>)]>;
+
+/// Source of the identifiers of installed hooks.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+static OUTPUT_HOOKS: HookStack<OutputFn, UbWrite> = HookStack::new(None);
+static FLUSH_HOOKS: HookStack<FlushFn, Flush> = HookStack::new(None);
+static LOG_HOOKS: HookStack<LogFn, LogMessage> = HookStack::new(None);
+
+/// The hooks of one function of the SAPI, the function they replaced and the
+/// run of the engine they were installed in.
+struct HookStack<F: ?Sized, H> {
+    state: RwLock<(Option<Hooks<F>>, H, u64)>,
+}
+
+impl<F: ?Sized, H: Copy + PartialEq> HookStack<F, H> {
+    const fn new(original: H) -> Self {
+        Self {
+            state: RwLock::new((None, original, 0)),
+        }
+    }
+
+    /// Installs a hook, replacing the function of the SAPI with the given
+    /// trampoline if it is the first one.
+    fn install(&self, hook: Arc<F>, slot: fn(&mut SapiModule) -> &mut H, trampoline: H) -> u64 {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let mut state = self.state.write();
+        let engine = engine::current_engine();
+        if state.2 != engine {
+            // Hooks installed before the engine was restarted are lost.
+            state.0 = None;
+            state.2 = engine;
+        }
+        let mut hooks = state.0.as_deref().map(<[_]>::to_vec).unwrap_or_default();
+        if hooks.is_empty() {
+            let mut sapi = SapiModule::get_mut();
+            let slot = slot(&mut sapi);
+            // The trampoline is still set if the SAPI was not started again
+            // along with the engine.
+            #[allow(unpredictable_function_pointer_comparisons)]
+            let patched = *slot == trampoline;
+            if !patched {
+                state.1 = *slot;
+            }
+            *slot = trampoline;
+        }
+        hooks.push((id, hook));
+        state.0 = Some(hooks.into());
+        id
+    }
+
+    /// Removes a hook, restoring the function of the SAPI if it was the last
+    /// one.
+    fn remove(&self, id: u64, slot: fn(&mut SapiModule) -> &mut H) {
+        let mut state = self.state.write();
+        let Some(hooks) = state.0.take() else {
+            return;
+        };
+        if state.2 != engine::current_engine() {
+            // The function of the SAPI has not been replaced since the engine
+            // was restarted.
+            return;
+        }
+        let hooks = hooks
+            .iter()
+            .filter(|(hook_id, _)| *hook_id != id)
+            .cloned()
+            .collect::<Vec<_>>();
+        if hooks.is_empty() {
+            *slot(&mut SapiModule::get_mut()) = state.1;
+        } else {
+            state.0 = Some(hooks.into());
+        }
+    }
+
+    /// Returns the installed hooks and the function they replaced.
+    fn current(&self) -> (Option<Hooks<F>>, H) {
+        let state = self.state.read();
+        (state.0.clone(), state.1)
+    }
+}
+
+/// Replaces the `ub_write` function of the SAPI, which receives all output of
+/// PHP which is not captured by output buffering, until it is dropped.
+///
+/// The hook is passed the output and the next handler, which is the
+/// previously installed hook or the original function of the SAPI, and
+/// returns the number of bytes written. Returning less than the length of the
+/// output makes PHP consider the connection to be aborted.
+///
+/// Hooks apply to the whole process. They must be installed after the SAPI
+/// has started, e.g. during module startup or in [`Embed::run`], and are
+/// lost when the engine is shut down. Hooks installed after the engine has
+/// been started again replace the function of the SAPI anew.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{prelude::*, zend::OutputHook};
+///
+/// extern "C" fn startup(_ty: i32, _module_number: i32) -> i32 {
+///     let hook = OutputHook::new(|output, next| {
+///         eprintln!("PHP wrote {} bytes", output.len());
+///         next(output)
+///     });
+///     // Keep the hook installed until the process ends.
+///     std::mem::forget(hook);
+///     0
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.startup_function(startup)
+/// }
+/// # fn main() {}
+/// ```
+///
+/// [`Embed::run`]: crate::embed::Embed::run
+#[must_use = "The hook is removed when it is dropped"]
+pub struct OutputHook {
+    id: u64,
+}
+
+impl OutputHook {
+    /// Installs the given output hook.
+    ///
+    /// # Panics
+    ///
+    /// * If the SAPI module is not set.
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: Fn(&[u8], &dyn Fn(&[u8]) -> usize) -> usize + Send + Sync + 'static,
+    {
+        let id = OUTPUT_HOOKS.install(Arc::new(hook), |sapi| &mut sapi.ub_write, Some(ub_write));
+        Self { id }
+    }
+}
+
+impl Drop for OutputHook {
+    fn drop(&mut self) {
+        OUTPUT_HOOKS.remove(self.id, |sapi| &mut sapi.ub_write);
+    }
+}
+
+/// Replaces the `flush` function of the SAPI, which is called when PHP
+/// flushes its output, until it is dropped.
+///
+/// The hook is passed the next handler, which is the previously installed
+/// hook or the original function of the SAPI. See [`OutputHook`] for when
+/// hooks may be installed.
+#[must_use = "The hook is removed when it is dropped"]
+pub struct FlushHook {
+    id: u64,
+}
+
+impl FlushHook {
+    /// Installs the given flush hook.
+    ///
+    /// # Panics
+    ///
+    /// * If the SAPI module is not set.
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: Fn(&dyn Fn()) + Send + Sync + 'static,
+    {
+        let id = FLUSH_HOOKS.install(Arc::new(hook), |sapi| &mut sapi.flush, Some(flush));
+        Self { id }
+    }
+}
+
+impl Drop for FlushHook {
+    fn drop(&mut self) {
+        FLUSH_HOOKS.remove(self.id, |sapi| &mut sapi.flush);
+    }
+}
+
+/// Replaces the `log_message` function of the SAPI, which receives the
+/// messages PHP logs when the `error_log` INI setting is not set, until it is
+/// dropped.
+///
+/// The hook is passed the message, its syslog priority and the next handler,
+/// which is the previously installed hook or the original function of the
+/// SAPI. See [`OutputHook`] for when hooks may be installed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ext_php_rs::zend::LogHook;
+///
+/// let hook = LogHook::new(|message, priority, _next| {
+///     eprintln!("[{priority}] {}", message.to_string_lossy());
+/// });
+/// ```
+#[must_use = "The hook is removed when it is dropped"]
+pub struct LogHook {
+    id: u64,
+}
+
+impl LogHook {
+    /// Installs the given log hook.
+    ///
+    /// # Panics
+    ///
+    /// * If the SAPI module is not set.
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: Fn(&CStr, i32, &dyn Fn(&CStr, i32)) + Send + Sync + 'static,
+    {
+        let id = LOG_HOOKS.install(
+            Arc::new(hook),
+            |sapi| &mut sapi.log_message,
+            Some(log_message),
+        );
+        Self { id }
+    }
+}
+
+impl Drop for LogHook {
+    fn drop(&mut self) {
+        LOG_HOOKS.remove(self.id, |sapi| &mut sapi.log_message);
+    }
+}
+
+/// Calls the last of the given hooks, or the original function if there are
+/// none left.
+fn write(hooks: &[(u64, Arc<OutputFn>)], original: UbWrite, data: &[u8]) -> usize {
+    match hooks.split_last() {
+        Some(((_, hook), rest)) => hook(data, &|data| write(rest, original, data)),
+        // Output is reported as written without a function, as PHP does.
+        None => original.map_or(data.len(), |original| unsafe {
+            original(data.as_ptr().cast::<c_char>(), data.len())
+        }),
+    }
+}
+
+fn flush_with(hooks: &[(u64, Arc<FlushFn>)], original: Flush, server_context: *mut c_void) {
+    match hooks.split_last() {
+        Some(((_, hook), rest)) => hook(&|| flush_with(rest, original, server_context)),
+        None => {
+            if let Some(original) = original {
+                unsafe { original(server_context) };
+            }
+        }
+    }
+}
+
+fn log_with(hooks: &[(u64, Arc<LogFn>)], original: LogMessage, message: &CStr, priority: i32) {
+    match hooks.split_last() {
+        Some(((_, hook), rest)) => hook(message, priority, &|message, priority| {
+            log_with(rest, original, message, priority);
+        }),
+        None => {
+            if let Some(original) = original {
+                unsafe { original(message.as_ptr(), priority) };
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn ub_write(str_: *const c_char, str_length: usize) -> usize {
+    let (hooks, original) = OUTPUT_HOOKS.current();
+    let data = if str_.is_null() {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(str_.cast::<u8>(), str_length) }
+    };
+    write(hooks.as_deref().unwrap_or_default(), original, data)
+}
+
+unsafe extern "C" fn flush(server_context: *mut c_void) {
+    let (hooks, original) = FLUSH_HOOKS.current();
+    flush_with(
+        hooks.as_deref().unwrap_or_default(),
+        original,
+        server_context,
+    );
+}
+
+unsafe extern "C" fn log_message(message: *const c_char, syslog_type_int: c_int) {
+    if message.is_null() {
+        return;
+    }
+    let (hooks, original) = LOG_HOOKS.current();
+    let message = unsafe { CStr::from_ptr(message) };
+    log_with(
+        hooks.as_deref().unwrap_or_default(),
+        original,
+        message,
+        syslog_type_int,
+    );
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::sync::{Mutex, atomic::AtomicUsize};
+
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_output_hook() {
+        Embed::run(|| {
+            let output = Arc::new(Mutex::new(Vec::new()));
+            {
+                let captured = Arc::clone(&output);
+                let _outer = OutputHook::new(move |data, _| {
+                    captured.lock().unwrap().extend_from_slice(data);
+                    data.len()
+                });
+                let _inner = OutputHook::new(|data, next| next(&data.to_ascii_uppercase()));
+                let _ = Embed::eval("echo 'hello';");
+            }
+            assert_eq!(*output.lock().unwrap(), b"HELLO");
+        });
+    }
+
+    #[test]
+    fn test_output_hook_after_restart() {
+        let stale = Embed::run(|| Some(OutputHook::new(|_, _| 0)));
+        Embed::run(|| {
+            let output = Arc::new(Mutex::new(Vec::new()));
+            let captured = Arc::clone(&output);
+            let hook = OutputHook::new(move |data, _| {
+                captured.lock().unwrap().extend_from_slice(data);
+                data.len()
+            });
+            drop(stale);
+            let _ = Embed::eval("echo 'hello';");
+            drop(hook);
+            assert_eq!(*output.lock().unwrap(), b"hello");
+        });
+    }
+
+    #[test]
+    fn test_flush_hook() {
+        Embed::run(|| {
+            let flushes = Arc::new(AtomicUsize::new(0));
+            let counted = Arc::clone(&flushes);
+            let hook = FlushHook::new(move |next| {
+                counted.fetch_add(1, Ordering::Relaxed);
+                next();
+            });
+            let _ = Embed::eval("flush();");
+            drop(hook);
+            let count = flushes.load(Ordering::Relaxed);
+            assert!(count > 0);
+
+            let _ = Embed::eval("flush();");
+            assert_eq!(flushes.load(Ordering::Relaxed), count);
+        });
+    }
+
+    #[test]
+    fn test_log_hook() {
+        Embed::run(|| {
+            let messages = Arc::new(Mutex::new(Vec::new()));
+            let captured = Arc::clone(&messages);
+            let _hook = LogHook::new(move |message, _, _| {
+                captured
+                    .lock()
+                    .unwrap()
+                    .push(message.to_string_lossy().into_owned());
+            });
+            let _ = Embed::eval("error_log('Something happened');");
+            assert_eq!(*messages.lock().unwrap(), ["Something happened"]);
+        });
+    }
+}
@@ -0,0 +1,129 @@
+//! Checks of the remaining stack space, allowing deeply recursive code to
+//! fail with a PHP error instead of overflowing the stack.
+
+use crate::exception::{PhpException, PhpResult};
+
+#[cfg(php83)]
+use super::ExecutorGlobals;
+use super::ce;
+
+/// Checks that at least `headroom` bytes of stack are left, returning an
+/// `Error` exception for PHP otherwise.
+///
+/// The stack limit tracked by PHP is used, which is the same limit PHP checks
+/// before calling user functions. PHP only tracks the limit since PHP 8.3,
+/// and not at all if `zend.max_allowed_stack_size` is `-1`. As the size of
+/// the stack is unknown then, the check always succeeds.
+///
+/// # Errors
+///
+/// Returns an `Error` exception if less than `headroom` bytes of stack are
+/// left.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{prelude::*, types::Zval, zend::ensure_stack};
+///
+/// fn depth(value: &Zval) -> PhpResult<usize> {
+///     ensure_stack(64 * 1024)?;
+///     let Some(array) = value.array() else {
+///         return Ok(0);
+///     };
+///     let mut max = 0;
+///     for value in array.values() {
+///         max = max.max(depth(value)?);
+///     }
+///     Ok(max + 1)
+/// }
+///
+/// #[php_function]
+/// pub fn array_depth(value: &Zval) -> PhpResult<usize> {
+///     depth(value)
+/// }
+/// # fn main() {}
+/// ```
+pub fn ensure_stack(headroom: usize) -> PhpResult<()> {
+    let Some((position, limit, size)) = stack_bounds() else {
+        return Ok(());
+    };
+    if position.saturating_sub(limit) >= headroom {
+        return Ok(());
+    }
+
+    Err(PhpException::new(
+        format!("Maximum call stack size of {size} bytes reached. Infinite recursion?"),
+        0,
+        ce::error(),
+    ))
+}
+
+/// Returns the number of bytes left on the stack, using the same limit as
+/// [`ensure_stack`], or [`None`] if PHP does not track the limit of the
+/// stack.
+#[must_use]
+pub fn remaining_stack() -> Option<usize> {
+    let (position, limit, _) = stack_bounds()?;
+    Some(position.saturating_sub(limit))
+}
+
+/// Returns the current position, the limit and the size of the stack, which
+/// grows downwards on all platforms supported by PHP, or [`None`] if PHP does
+/// not track the limit of the stack.
+fn stack_bounds() -> Option<(usize, usize, usize)> {
+    cfg_if::cfg_if! {
+        if #[cfg(php83)] {
+            let globals = ExecutorGlobals::get();
+            let (base, limit) = (globals.stack_base.addr(), globals.stack_limit.addr());
+            if limit == 0 {
+                return None;
+            }
+            Some((stack_position(), limit, base.saturating_sub(limit)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns an approximation of the stack pointer of the caller.
+#[cfg(php83)]
+#[inline(never)]
+fn stack_position() -> usize {
+    let marker = 0_u8;
+    std::hint::black_box(&raw const marker).addr()
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    fn recurse(depth: &mut usize) -> PhpResult<()> {
+        ensure_stack(256 * 1024)?;
+        *depth += 1;
+        let padding = std::hint::black_box([0_u8; 1024]);
+        recurse(depth)?;
+        std::hint::black_box(padding);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ensure_stack() {
+        Embed::run(|| {
+            assert!(ensure_stack(1024).is_ok());
+            // Without a limit tracked by PHP, the stack is not checked.
+            let Some(remaining) = remaining_stack() else {
+                assert!(ensure_stack(usize::MAX).is_ok());
+                return;
+            };
+            assert!(remaining > 1024);
+            assert!(ensure_stack(usize::MAX).is_err());
+
+            let mut depth = 0;
+            assert!(recurse(&mut depth).is_err());
+            assert!(depth > 0);
+        });
+    }
+}
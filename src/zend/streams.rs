@@ -1,14 +1,34 @@
-use std::ptr::{self, NonNull};
+use std::{
+    alloc::Layout,
+    ffi::{CStr, CString, c_char, c_int, c_void},
+    mem,
+    ptr::{self, NonNull},
+};
+
+use cfg_if::cfg_if;
 
 use crate::{
-    error::Error,
+    alloc::emalloc,
+    boxed::{ZBox, ZBoxable},
+    convert::IntoZval,
+    error::{Error, Result},
     ffi::{
-        php_register_url_stream_wrapper, php_register_url_stream_wrapper_volatile, php_stream,
-        php_stream_context, php_stream_locate_url_wrapper, php_stream_wrapper,
-        php_stream_wrapper_ops, php_unregister_url_stream_wrapper,
-        php_unregister_url_stream_wrapper_volatile, zend_string,
+        _php_stream_cast, _php_stream_flush, _php_stream_free, _php_stream_open_wrapper_ex,
+        _php_stream_read, _php_stream_set_option, _php_stream_write, _php_stream_xport_create,
+        PHP_STREAM_AS_FD_FOR_SELECT, PHP_STREAM_CAST_INTERNAL, PHP_STREAM_FREE_CLOSE,
+        PHP_STREAM_NOTIFY_AUTH_REQUIRED, PHP_STREAM_NOTIFY_AUTH_RESULT,
+        PHP_STREAM_NOTIFY_COMPLETED, PHP_STREAM_NOTIFY_CONNECT, PHP_STREAM_NOTIFY_FAILURE,
+        PHP_STREAM_NOTIFY_FILE_SIZE_IS, PHP_STREAM_NOTIFY_MIME_TYPE_IS, PHP_STREAM_NOTIFY_PROGRESS,
+        PHP_STREAM_NOTIFY_REDIRECTED, PHP_STREAM_NOTIFY_RESOLVE, PHP_STREAM_NOTIFY_SEVERITY_ERR,
+        PHP_STREAM_NOTIFY_SEVERITY_WARN, PHP_STREAM_OPTION_BLOCKING, REPORT_ERRORS,
+        STREAM_XPORT_CLIENT, STREAM_XPORT_CONNECT, php_register_url_stream_wrapper,
+        php_register_url_stream_wrapper_volatile, php_stream, php_stream_context,
+        php_stream_context_alloc, php_stream_context_get_option, php_stream_context_set_option,
+        php_stream_locate_url_wrapper, php_stream_notification_free, php_stream_notifier,
+        php_stream_wrapper, php_stream_wrapper_ops, php_unregister_url_stream_wrapper,
+        php_unregister_url_stream_wrapper_volatile, zend_list_delete, zend_string,
     },
-    types::ZendStr,
+    types::{ZendStr, Zval},
 };
 
 /// Wrapper for PHP streams
@@ -139,7 +159,602 @@ impl StreamWrapper {
 /// A PHP stream
 pub type Stream = php_stream;
 
+impl Stream {
+    /// Opens a stream with the wrapper registered for the given path or URL,
+    /// as done by `fopen()`. Failures are reported to PHP as warnings.
+    ///
+    /// The stream is closed when the returned box is dropped.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path or URL to open.
+    /// * `mode` - The mode to open the stream with, e.g. `rb`.
+    /// * `context` - The context passed to the stream wrapper, if any.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidCString`] - If the path or mode contains NUL bytes.
+    /// * [`Error::StreamOpenFailure`] - If the stream could not be opened.
+    pub fn open(path: &str, mode: &str, context: Option<&StreamContext>) -> Result<ZBox<Self>> {
+        let path = CString::new(path)?;
+        let mode = CString::new(mode)?;
+        let context = context.map_or(ptr::null_mut(), |context| ptr::from_ref(context).cast_mut());
+        #[allow(clippy::cast_possible_wrap)]
+        let options = REPORT_ERRORS as c_int;
+
+        let stream = unsafe {
+            cfg_if! {
+                if #[cfg(php_debug)] {
+                    #[allow(clippy::used_underscore_items)]
+                    _php_stream_open_wrapper_ex(
+                        path.as_ptr(),
+                        mode.as_ptr(),
+                        options,
+                        ptr::null_mut(),
+                        context,
+                        ptr::null(),
+                        0,
+                        ptr::null(),
+                        0,
+                    )
+                } else {
+                    #[allow(clippy::used_underscore_items)]
+                    _php_stream_open_wrapper_ex(
+                        path.as_ptr(),
+                        mode.as_ptr(),
+                        options,
+                        ptr::null_mut(),
+                        context,
+                    )
+                }
+            }
+        };
+
+        if stream.is_null() {
+            return Err(Error::StreamOpenFailure);
+        }
+        // SAFETY: The stream was allocated by PHP and is released by `free`.
+        Ok(unsafe { ZBox::from_raw(stream) })
+    }
+
+    /// Connects a socket stream to the given address, e.g.
+    /// `tcp://127.0.0.1:8080`, as done by `stream_socket_client()`. The
+    /// connection times out after `default_socket_timeout` seconds. Failures
+    /// are reported to PHP as warnings.
+    ///
+    /// The stream is closed when the returned box is dropped.
+    ///
+    /// # Parameters
+    ///
+    /// * `address` - The address to connect to, including the transport.
+    /// * `context` - The context passed to the transport, if any.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidCString`] - If the address contains NUL bytes.
+    /// * [`Error::StreamOpenFailure`] - If the connection failed.
+    pub fn connect(address: &str, context: Option<&StreamContext>) -> Result<ZBox<Self>> {
+        let name = CString::new(address)?;
+        let context = context.map_or(ptr::null_mut(), |context| ptr::from_ref(context).cast_mut());
+        #[allow(clippy::cast_possible_wrap)]
+        let (options, flags) = (
+            REPORT_ERRORS as c_int,
+            (STREAM_XPORT_CLIENT | STREAM_XPORT_CONNECT) as c_int,
+        );
+
+        let stream = unsafe {
+            cfg_if! {
+                if #[cfg(php_debug)] {
+                    #[allow(clippy::used_underscore_items)]
+                    _php_stream_xport_create(
+                        name.as_ptr(),
+                        address.len(),
+                        options,
+                        flags,
+                        ptr::null(),
+                        ptr::null_mut(),
+                        context,
+                        ptr::null_mut(),
+                        ptr::null_mut(),
+                        ptr::null(),
+                        0,
+                        ptr::null(),
+                        0,
+                    )
+                } else {
+                    #[allow(clippy::used_underscore_items)]
+                    _php_stream_xport_create(
+                        name.as_ptr(),
+                        address.len(),
+                        options,
+                        flags,
+                        ptr::null(),
+                        ptr::null_mut(),
+                        context,
+                        ptr::null_mut(),
+                        ptr::null_mut(),
+                    )
+                }
+            }
+        };
+
+        if stream.is_null() {
+            return Err(Error::StreamOpenFailure);
+        }
+        // SAFETY: The stream was allocated by PHP and is released by `free`.
+        Ok(unsafe { ZBox::from_raw(stream) })
+    }
+
+    /// Reads up to `buf.len()` bytes from the stream, returning the number of
+    /// bytes read. Zero is returned at the end of the stream.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::StreamIoFailure`] - If reading from the stream failed.
+    pub fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        #[allow(clippy::used_underscore_items)]
+        let read = unsafe { _php_stream_read(self, buf.as_mut_ptr().cast::<c_char>(), buf.len()) };
+        usize::try_from(read).map_err(|_| Error::StreamIoFailure)
+    }
+
+    /// Writes the given bytes to the stream, returning the number of bytes
+    /// written.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::StreamIoFailure`] - If writing to the stream failed.
+    pub fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        #[allow(clippy::used_underscore_items)]
+        let written = unsafe { _php_stream_write(self, buf.as_ptr().cast::<c_char>(), buf.len()) };
+        usize::try_from(written).map_err(|_| Error::StreamIoFailure)
+    }
+
+    /// Flushes the data buffered by the stream.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::StreamIoFailure`] - If flushing the stream failed.
+    pub fn flush(&mut self) -> Result<()> {
+        #[allow(clippy::used_underscore_items)]
+        match unsafe { _php_stream_flush(self, 0) } {
+            0 => Ok(()),
+            _ => Err(Error::StreamIoFailure),
+        }
+    }
+
+    /// Returns whether the end of the stream was reached.
+    #[must_use]
+    pub fn is_eof(&self) -> bool {
+        self.eof() != 0
+    }
+
+    /// Sets whether reads and writes block until they can complete, as done
+    /// by `stream_set_blocking()`. Non-blocking reads and writes return zero
+    /// if they would block.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::StreamIoFailure`] - If the stream does not support changing
+    ///   the blocking mode.
+    pub fn set_blocking(&mut self, blocking: bool) -> Result<()> {
+        #[allow(clippy::used_underscore_items, clippy::cast_possible_wrap)]
+        let result = unsafe {
+            _php_stream_set_option(
+                self,
+                PHP_STREAM_OPTION_BLOCKING as c_int,
+                c_int::from(blocking),
+                ptr::null_mut(),
+            )
+        };
+        // Returns the previous mode on success, or -1 on failure.
+        if result == -1 {
+            return Err(Error::StreamIoFailure);
+        }
+        Ok(())
+    }
+
+    /// Returns the file descriptor of the stream which can be polled for
+    /// readiness, e.g. the socket of a network stream. Returns `None` if the
+    /// stream is not backed by a file descriptor.
+    #[cfg(unix)]
+    #[must_use]
+    pub fn as_raw_fd(&mut self) -> Option<std::os::fd::RawFd> {
+        let mut fd: c_int = -1;
+        #[allow(clippy::used_underscore_items, clippy::cast_possible_wrap)]
+        let result = unsafe {
+            _php_stream_cast(
+                self,
+                (PHP_STREAM_AS_FD_FOR_SELECT | PHP_STREAM_CAST_INTERNAL) as c_int,
+                (&raw mut fd).cast::<*mut c_void>(),
+                0,
+            )
+        };
+        (result == 0 && fd >= 0).then_some(fd)
+    }
+
+    /// Returns the context the stream was opened with, if any.
+    #[must_use]
+    pub fn context(&self) -> Option<&StreamContext> {
+        let ctx = unsafe { self.ctx.as_ref() }?;
+        unsafe { ctx.ptr.cast::<StreamContext>().as_ref() }
+    }
+}
+
+unsafe impl ZBoxable for Stream {
+    fn free(&mut self) {
+        #[allow(clippy::used_underscore_items, clippy::cast_possible_wrap)]
+        unsafe {
+            _php_stream_free(self, PHP_STREAM_FREE_CLOSE as c_int);
+        }
+    }
+}
+
 /// Operations that can be performed with a stream wrapper
 pub type StreamWrapperOps = php_stream_wrapper_ops;
 
 impl StreamWrapperOps {}
+
+/// A stream context, holding the options and the notification callback used
+/// by stream wrappers, as created by `stream_context_create()`.
+///
+/// Options are grouped by wrapper, e.g. the `verify_peer` option of the `ssl`
+/// wrapper. See the PHP manual for the options supported by each wrapper.
+///
+/// ```no_run
+/// use ext_php_rs::zend::{Stream, StreamContext};
+///
+/// # fn main() -> ext_php_rs::error::Result<()> {
+/// let mut context = StreamContext::new();
+/// context
+///     .http_method("POST")?
+///     .http_headers(["Content-Type: application/json"])?
+///     .http_content("{}")?
+///     .ssl_verify_peer(true)?;
+/// context.on_notification(|notification| {
+///     if let Some(message) = notification.message {
+///         println!("{:?}: {message}", notification.code);
+///     }
+/// });
+///
+/// let mut stream = Stream::open("https://example.com", "rb", Some(&context))?;
+/// let mut buf = [0; 1024];
+/// let read = stream.read(&mut buf)?;
+/// # Ok(())
+/// # }
+/// ```
+pub type StreamContext = php_stream_context;
+
+impl StreamContext {
+    /// Creates a new stream context without any options. The context is
+    /// registered as a resource of the current request.
+    ///
+    /// # Panics
+    ///
+    /// Panics if PHP fails to allocate the context.
+    #[must_use]
+    pub fn new() -> ZBox<Self> {
+        let context = unsafe { php_stream_context_alloc() };
+        assert!(!context.is_null(), "Failed to allocate stream context");
+        // SAFETY: The context is a reference counted resource released by `free`.
+        unsafe { ZBox::from_raw(context) }
+    }
+
+    /// Sets an option of the context.
+    ///
+    /// # Parameters
+    ///
+    /// * `wrapper` - The wrapper the option belongs to, e.g. `http`.
+    /// * `option` - The name of the option.
+    /// * `value` - The value of the option.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidCString`] - If the wrapper or option contain NUL
+    ///   bytes.
+    /// * If the value could not be converted into a zval.
+    pub fn set_option<V: IntoZval>(
+        &mut self,
+        wrapper: &str,
+        option: &str,
+        value: V,
+    ) -> Result<&mut Self> {
+        let wrapper = CString::new(wrapper)?;
+        let option = CString::new(option)?;
+        let mut value = value.into_zval(false)?;
+        // The option is copied into the context, so our value can be dropped.
+        unsafe {
+            php_stream_context_set_option(self, wrapper.as_ptr(), option.as_ptr(), &raw mut value);
+        }
+        Ok(self)
+    }
+
+    /// Returns the value of an option of the context, if it is set.
+    ///
+    /// # Parameters
+    ///
+    /// * `wrapper` - The wrapper the option belongs to, e.g. `http`.
+    /// * `option` - The name of the option.
+    #[must_use]
+    pub fn option(&self, wrapper: &str, option: &str) -> Option<&Zval> {
+        let wrapper = CString::new(wrapper).ok()?;
+        let option = CString::new(option).ok()?;
+        unsafe {
+            php_stream_context_get_option(
+                ptr::from_ref(self).cast_mut(),
+                wrapper.as_ptr(),
+                option.as_ptr(),
+            )
+            .as_ref()
+        }
+    }
+
+    /// Returns all options of the context, grouped by wrapper.
+    #[must_use]
+    pub fn options(&self) -> &Zval {
+        &self.options
+    }
+
+    /// Sets whether the certificate of the peer is verified by the `ssl`
+    /// wrapper. Enabled by default.
+    ///
+    /// # Errors
+    ///
+    /// See [`StreamContext::set_option`].
+    pub fn ssl_verify_peer(&mut self, verify: bool) -> Result<&mut Self> {
+        self.set_option("ssl", "verify_peer", verify)
+    }
+
+    /// Sets whether the name of the peer is verified by the `ssl` wrapper.
+    /// Enabled by default.
+    ///
+    /// # Errors
+    ///
+    /// See [`StreamContext::set_option`].
+    pub fn ssl_verify_peer_name(&mut self, verify: bool) -> Result<&mut Self> {
+        self.set_option("ssl", "verify_peer_name", verify)
+    }
+
+    /// Sets whether self-signed certificates are accepted by the `ssl`
+    /// wrapper.
+    ///
+    /// # Errors
+    ///
+    /// See [`StreamContext::set_option`].
+    pub fn ssl_allow_self_signed(&mut self, allow: bool) -> Result<&mut Self> {
+        self.set_option("ssl", "allow_self_signed", allow)
+    }
+
+    /// Sets the certificate authority file used by the `ssl` wrapper to
+    /// verify the peer.
+    ///
+    /// # Errors
+    ///
+    /// See [`StreamContext::set_option`].
+    pub fn ssl_cafile(&mut self, path: &str) -> Result<&mut Self> {
+        self.set_option("ssl", "cafile", path)
+    }
+
+    /// Sets the request method used by the `http` wrapper.
+    ///
+    /// # Errors
+    ///
+    /// See [`StreamContext::set_option`].
+    pub fn http_method(&mut self, method: &str) -> Result<&mut Self> {
+        self.set_option("http", "method", method)
+    }
+
+    /// Sets the additional request headers sent by the `http` wrapper, e.g.
+    /// `Accept: text/html`.
+    ///
+    /// # Errors
+    ///
+    /// See [`StreamContext::set_option`].
+    pub fn http_headers<I>(&mut self, headers: I) -> Result<&mut Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let headers: Vec<String> = headers.into_iter().map(Into::into).collect();
+        self.set_option("http", "header", headers)
+    }
+
+    /// Sets the request body sent by the `http` wrapper.
+    ///
+    /// # Errors
+    ///
+    /// See [`StreamContext::set_option`].
+    pub fn http_content(&mut self, content: &str) -> Result<&mut Self> {
+        self.set_option("http", "content", content)
+    }
+
+    /// Sets the read timeout of the `http` wrapper in seconds.
+    ///
+    /// # Errors
+    ///
+    /// See [`StreamContext::set_option`].
+    pub fn http_timeout(&mut self, seconds: f64) -> Result<&mut Self> {
+        self.set_option("http", "timeout", seconds)
+    }
+
+    /// Sets the function called with the notifications of stream wrappers
+    /// using the context, such as the `notification` parameter of
+    /// `stream_context_set_params()`. Replaces any previous notification
+    /// callback.
+    ///
+    /// # Parameters
+    ///
+    /// * `callback` - The function called for each notification.
+    pub fn on_notification<F>(&mut self, callback: F)
+    where
+        F: FnMut(StreamNotification<'_>) + 'static,
+    {
+        unsafe {
+            if !self.notifier.is_null() {
+                php_stream_notification_free(self.notifier);
+                self.notifier = ptr::null_mut();
+            }
+
+            // The notifier is freed by PHP with `efree`, after calling its destructor.
+            let notifier = emalloc(Layout::new::<Notifier>()).cast::<Notifier>();
+            if notifier.is_null() {
+                return;
+            }
+            notifier.write(Notifier {
+                notifier: mem::zeroed(),
+                callback: Box::new(callback),
+            });
+            (*notifier).notifier.func = Some(notification_handler);
+            (*notifier).notifier.dtor = Some(notification_dtor);
+            self.notifier = notifier.cast::<php_stream_notifier>();
+        }
+    }
+
+    /// Returns a zval holding the context as a resource, which can be passed
+    /// to PHP functions such as `file_get_contents()`.
+    #[must_use]
+    pub fn to_zval(&self) -> Zval {
+        let mut zv = Zval::new();
+        if let Some(res) = unsafe { self.res.as_mut() } {
+            res.gc.refcount += 1;
+            zv.set_resource(res);
+        }
+        zv
+    }
+}
+
+unsafe impl ZBoxable for StreamContext {
+    fn free(&mut self) {
+        if !self.res.is_null() {
+            unsafe { zend_list_delete(self.res) };
+        }
+    }
+}
+
+/// A notifier calling a Rust function, which PHP sees as its first field.
+#[repr(C)]
+struct Notifier {
+    notifier: php_stream_notifier,
+    callback: Box<dyn FnMut(StreamNotification<'_>)>,
+}
+
+/// A notification sent by a stream wrapper, see
+/// [`StreamContext::on_notification`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamNotification<'a> {
+    /// The kind of the notification.
+    pub code: NotificationCode,
+    /// The severity of the notification.
+    pub severity: NotificationSeverity,
+    /// The message of the notification, if any.
+    pub message: Option<&'a str>,
+    /// The code of the message, e.g. the HTTP status code.
+    pub message_code: i32,
+    /// The number of bytes transferred so far.
+    pub bytes_transferred: usize,
+    /// The total number of bytes to transfer, or zero if unknown.
+    pub bytes_max: usize,
+}
+
+/// The kind of a [`StreamNotification`], see the `STREAM_NOTIFY_*` constants
+/// in PHP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationCode {
+    /// A name was resolved.
+    Resolve,
+    /// A connection was established.
+    Connect,
+    /// Authentication is required.
+    AuthRequired,
+    /// The MIME type of the resource is known.
+    MimeTypeIs,
+    /// The size of the resource is known.
+    FileSizeIs,
+    /// The request was redirected.
+    Redirected,
+    /// Data was transferred.
+    Progress,
+    /// There is no more data available.
+    Completed,
+    /// The request failed.
+    Failure,
+    /// Authentication was completed.
+    AuthResult,
+    /// A code not known to this library.
+    Other(i32),
+}
+
+impl From<i32> for NotificationCode {
+    fn from(code: i32) -> Self {
+        match u32::try_from(code) {
+            Ok(PHP_STREAM_NOTIFY_RESOLVE) => Self::Resolve,
+            Ok(PHP_STREAM_NOTIFY_CONNECT) => Self::Connect,
+            Ok(PHP_STREAM_NOTIFY_AUTH_REQUIRED) => Self::AuthRequired,
+            Ok(PHP_STREAM_NOTIFY_MIME_TYPE_IS) => Self::MimeTypeIs,
+            Ok(PHP_STREAM_NOTIFY_FILE_SIZE_IS) => Self::FileSizeIs,
+            Ok(PHP_STREAM_NOTIFY_REDIRECTED) => Self::Redirected,
+            Ok(PHP_STREAM_NOTIFY_PROGRESS) => Self::Progress,
+            Ok(PHP_STREAM_NOTIFY_COMPLETED) => Self::Completed,
+            Ok(PHP_STREAM_NOTIFY_FAILURE) => Self::Failure,
+            Ok(PHP_STREAM_NOTIFY_AUTH_RESULT) => Self::AuthResult,
+            _ => Self::Other(code),
+        }
+    }
+}
+
+/// The severity of a [`StreamNotification`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationSeverity {
+    /// An informational notification.
+    Info,
+    /// A warning.
+    Warn,
+    /// An error.
+    Err,
+}
+
+impl From<i32> for NotificationSeverity {
+    fn from(severity: i32) -> Self {
+        match u32::try_from(severity) {
+            Ok(PHP_STREAM_NOTIFY_SEVERITY_WARN) => Self::Warn,
+            Ok(PHP_STREAM_NOTIFY_SEVERITY_ERR) => Self::Err,
+            _ => Self::Info,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+unsafe extern "C" fn notification_handler(
+    context: *mut php_stream_context,
+    notifycode: c_int,
+    severity: c_int,
+    xmsg: *mut c_char,
+    xcode: c_int,
+    bytes_sofar: usize,
+    bytes_max: usize,
+    _ptr: *mut c_void,
+) {
+    // SAFETY: This handler is only set on notifiers allocated by `on_notification`.
+    let Some(notifier) = (unsafe { context.as_ref() })
+        .and_then(|ctx| unsafe { ctx.notifier.cast::<Notifier>().as_mut() })
+    else {
+        return;
+    };
+    let message = (!xmsg.is_null())
+        .then(|| unsafe { CStr::from_ptr(xmsg) }.to_str().ok())
+        .flatten();
+
+    (notifier.callback)(StreamNotification {
+        code: notifycode.into(),
+        severity: severity.into(),
+        message,
+        message_code: xcode,
+        bytes_transferred: bytes_sofar,
+        bytes_max,
+    });
+}
+
+unsafe extern "C" fn notification_dtor(notifier: *mut php_stream_notifier) {
+    // SAFETY: This destructor is only set on notifiers allocated by
+    // `on_notification`, whose memory is freed by PHP afterwards.
+    unsafe { ptr::drop_in_place(&raw mut (*notifier.cast::<Notifier>()).callback) };
+}
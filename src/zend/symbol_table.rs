@@ -0,0 +1,114 @@
+//! Access to the local variables of the calling PHP code.
+
+use std::ffi::c_char;
+use std::mem::ManuallyDrop;
+
+use crate::{
+    convert::IntoZval,
+    error::{Error, Result},
+    ffi::{ZEND_RESULT_CODE_SUCCESS, zend_hash_str_del_ind, zend_set_local_var_str},
+    flags::DataType,
+    types::{ArrayKey, ZendHashTable, Zval},
+};
+
+/// The local variables of the innermost PHP code being executed, e.g. the
+/// PHP function which called the current Rust function, or the global
+/// variables when called from the top level of a script.
+///
+/// This allows implementing functions like `extract()` and `compact()`,
+/// which read and write the variables of their caller.
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{prelude::*, types::Zval, zend::ExecutorGlobals};
+///
+/// /// Doubles the integer variable with the given name in the calling scope.
+/// #[php_function]
+/// pub fn double_var(name: &str) -> PhpResult<()> {
+///     let mut globals = ExecutorGlobals::get_mut();
+///     let mut vars = globals.symbol_table().ok_or("Not called from PHP code")?;
+///     let value = vars.get(name).and_then(Zval::long).ok_or("Not an integer")?;
+///     vars.set(name, value * 2)?;
+///     Ok(())
+/// }
+/// # fn main() {}
+/// ```
+pub struct SymbolTable<'a> {
+    table: &'a mut ZendHashTable,
+}
+
+impl<'a> SymbolTable<'a> {
+    /// Creates a view of the given symbol table.
+    pub(crate) fn new(table: &'a mut ZendHashTable) -> Self {
+        Self { table }
+    }
+
+    /// Returns the value of the variable with the given name, or [`None`] if
+    /// the variable is not set.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Zval> {
+        Self::value(self.table.get(name)?)
+    }
+
+    /// Returns whether the variable with the given name is set.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Sets the variable with the given name, replacing its previous value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value could not be converted into a zval, or
+    /// if the variable could not be set.
+    pub fn set<V: IntoZval>(&mut self, name: &str, value: V) -> Result<()> {
+        let mut value = ManuallyDrop::new(value.into_zval(false)?);
+        // SAFETY: The value is moved into the variable on success.
+        let result = unsafe {
+            zend_set_local_var_str(
+                name.as_ptr().cast::<c_char>(),
+                name.len(),
+                &raw mut *value,
+                true,
+            )
+        };
+        if result == ZEND_RESULT_CODE_SUCCESS {
+            Ok(())
+        } else {
+            drop(ManuallyDrop::into_inner(value));
+            Err(Error::InvalidScope)
+        }
+    }
+
+    /// Unsets the variable with the given name, returning whether it was set.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let existed = self.contains(name);
+        unsafe {
+            zend_hash_str_del_ind(self.table, name.as_ptr().cast::<c_char>(), name.len());
+        }
+        existed
+    }
+
+    /// Returns an iterator over the names and values of the variables which
+    /// are set.
+    pub fn iter(&self) -> impl Iterator<Item = (ArrayKey<'_>, &Zval)> {
+        self.table
+            .iter()
+            .filter_map(|(key, value)| Some((key, Self::value(value)?)))
+    }
+
+    /// Returns the underlying hash table. Values of compiled variables are
+    /// stored as indirect zvals.
+    #[must_use]
+    pub fn as_table(&self) -> &ZendHashTable {
+        self.table
+    }
+
+    /// Resolves the value of a variable, which is stored indirectly for
+    /// compiled variables and is undefined once these are unset.
+    fn value(value: &Zval) -> Option<&Zval> {
+        let value = value.indirect().unwrap_or(value);
+        (value.get_type() != DataType::Undef).then_some(value)
+    }
+}
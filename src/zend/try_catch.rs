@@ -2,7 +2,7 @@ use crate::ffi::{
     ext_php_rs_zend_bailout, ext_php_rs_zend_first_try_catch, ext_php_rs_zend_try_catch,
 };
 use std::ffi::c_void;
-use std::panic::{UnwindSafe, catch_unwind, resume_unwind};
+use std::panic::{AssertUnwindSafe, UnwindSafe, catch_unwind, resume_unwind};
 use std::ptr::null_mut;
 
 /// Error returned when a bailout occurs
@@ -61,6 +61,55 @@ pub fn try_catch_first<R, F: FnOnce() -> R + UnwindSafe>(func: F) -> Result<R, C
     do_try_catch(func, true)
 }
 
+/// Same as [`try_catch`], without requiring the closure to be
+/// [`UnwindSafe`].
+///
+/// This allows the closure to capture mutable references and types with
+/// interior mutability without wrapping it in [`AssertUnwindSafe`]. This is
+/// sound as panics are never caught: a panic in the closure is resumed once it
+/// left the PHP try catch block, as if it was not wrapped at all.
+///
+/// Like [`try_catch`], destructors of values owned by the closure are not run
+/// when a bailout occurs.
+///
+/// # Returns
+///
+/// * The result of the function
+///
+/// # Errors
+///
+/// * [`CatchError`] - A bailout occurred during the execution
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::zend::try_catch_once;
+///
+/// let mut calls = 0;
+/// let result = try_catch_once(|| {
+///     calls += 1;
+///     calls
+/// });
+/// assert!(matches!(result, Ok(1)));
+/// ```
+pub fn try_catch_once<R, F: FnOnce() -> R>(func: F) -> Result<R, CatchError> {
+    do_try_catch(AssertUnwindSafe(func), false)
+}
+
+/// Same as [`try_catch_first`], without requiring the closure to be
+/// [`UnwindSafe`]. See [`try_catch_once`] for details.
+///
+/// # Returns
+///
+/// * The result of the function
+///
+/// # Errors
+///
+/// * [`CatchError`] - A bailout occurred during the execution
+pub fn try_catch_first_once<R, F: FnOnce() -> R>(func: F) -> Result<R, CatchError> {
+    do_try_catch(AssertUnwindSafe(func), true)
+}
+
 fn do_try_catch<R, F: FnOnce() -> R + UnwindSafe>(func: F, first: bool) -> Result<R, CatchError> {
     let mut panic_ptr = null_mut();
     let has_bailout = unsafe {
@@ -119,7 +168,7 @@ pub unsafe fn bailout() -> ! {
 #[cfg(test)]
 mod tests {
     use crate::embed::Embed;
-    use crate::zend::{bailout, try_catch};
+    use crate::zend::{bailout, try_catch, try_catch_once};
     use std::ptr::null_mut;
 
     #[test]
@@ -194,6 +243,41 @@ mod tests {
         assert_eq!(foo, "foo");
     }
 
+    #[test]
+    fn test_catch_once() {
+        Embed::run(|| {
+            let mut state = vec![1];
+            let state_ref = &mut state;
+            let catch = try_catch_once(move || {
+                state_ref.push(2);
+                unsafe {
+                    bailout();
+                }
+            });
+
+            assert!(catch.is_err());
+            assert_eq!(state, [1, 2]);
+
+            let cell = std::cell::RefCell::new(0);
+            let result = try_catch_once(|| {
+                *cell.borrow_mut() += 1;
+                "foo"
+            });
+            assert!(matches!(result, Ok("foo")));
+            assert_eq!(cell.into_inner(), 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "should panic")]
+    fn test_panic_once() {
+        Embed::run(|| {
+            let _ = try_catch_once(|| {
+                panic!("should panic");
+            });
+        });
+    }
+
     #[test]
     fn test_memory_leak() {
         use std::panic::AssertUnwindSafe;
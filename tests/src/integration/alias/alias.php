@@ -0,0 +1,12 @@
+<?php
+
+assert(test_alias(2) === 4);
+assert(test_alias_legacy(2) === 4);
+assert(test_alias_old(2) === 4);
+
+$alias = new ReflectionFunction('test_alias_legacy');
+assert($alias->getNumberOfParameters() === 1);
+assert($alias->getParameters()[0]->getName() === 'a');
+
+assert(TestAliasClass::value() === 42);
+assert(TestAliasClass::oldValue() === 42);
@@ -0,0 +1,32 @@
+use ext_php_rs::prelude::*;
+
+#[php_function]
+#[php(alias = "test_alias_legacy", alias = "test_alias_old")]
+pub fn test_alias(a: i64) -> i64 {
+    a * 2
+}
+
+#[php_class]
+pub struct TestAliasClass;
+
+#[php_impl]
+impl TestAliasClass {
+    #[php(alias = "oldValue")]
+    pub fn value() -> i64 {
+        42
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_alias))
+        .class::<TestAliasClass>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn alias_works() {
+        assert!(crate::integration::test::run_php("alias/alias.php"));
+    }
+}
@@ -0,0 +1,28 @@
+<?php
+
+assert(test_async_add(1, 2) === 3);
+assert(test_async_yield(3) === 3);
+
+$obj = new TestAsyncClass(2);
+assert($obj->double() === 4);
+assert($obj->add(3) === $obj);
+assert($obj->get() === 5);
+
+test_async_use_fiber_executor();
+
+// Outside of a fiber the executor blocks.
+assert(test_async_yield(2) === 2);
+
+if (PHP_VERSION_ID >= 80100) {
+    $fiber = new Fiber(fn () => test_async_yield(3));
+    $fiber->start();
+
+    $resumed = 0;
+    while (!$fiber->isTerminated()) {
+        $fiber->resume();
+        $resumed++;
+    }
+
+    assert($resumed === 3);
+    assert($fiber->getReturn() === 3);
+}
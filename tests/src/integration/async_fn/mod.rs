@@ -0,0 +1,91 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use ext_php_rs::{
+    async_::{self, FiberExecutor},
+    prelude::*,
+};
+
+/// Future returning [`Poll::Pending`] once before completing.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[php_function]
+pub async fn test_async_add(a: i64, b: i64) -> i64 {
+    a + b
+}
+
+#[php_function]
+pub async fn test_async_yield(count: i64) -> i64 {
+    for _ in 0..count {
+        YieldNow(false).await;
+    }
+    count
+}
+
+#[php_function]
+pub fn test_async_use_fiber_executor() {
+    async_::set_executor(FiberExecutor);
+}
+
+#[php_class]
+pub struct TestAsyncClass {
+    value: Cell<i64>,
+}
+
+#[php_impl]
+impl TestAsyncClass {
+    pub fn __construct(value: i64) -> Self {
+        Self {
+            value: Cell::new(value),
+        }
+    }
+
+    pub async fn double(&self) -> i64 {
+        YieldNow(false).await;
+        self.value.get() * 2
+    }
+
+    pub async fn add(&self, value: i64) -> &Self {
+        YieldNow(false).await;
+        self.value.set(self.value.get() + value);
+        self
+    }
+
+    pub fn get(&self) -> i64 {
+        self.value.get()
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_async_add))
+        .function(wrap_function!(test_async_yield))
+        .function(wrap_function!(test_async_use_fiber_executor))
+        .class::<TestAsyncClass>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn async_fn_works() {
+        assert!(crate::integration::test::run_php("async_fn/async_fn.php"));
+    }
+}
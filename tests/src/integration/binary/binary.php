@@ -9,3 +9,7 @@ assert(in_array(2, $result));
 assert(in_array(3, $result));
 assert(in_array(4, $result));
 assert(in_array(5, $result));
+
+assert(test_binary_bytes("\x00\xff\x80abc") === "cba\x80\xff\x00");
+assert(test_binary_bytes('') === '');
+assert(test_binary_boxed(random_bytes(32)) === 32);
@@ -5,8 +5,21 @@ pub fn test_binary(a: Binary<u32>) -> Binary<u32> {
     a
 }
 
+#[php_function]
+pub fn test_binary_bytes(a: &[u8]) -> Box<[u8]> {
+    a.iter().rev().copied().collect()
+}
+
+#[php_function]
+pub fn test_binary_boxed(a: Box<[u8]>) -> usize {
+    a.len()
+}
+
 pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
-    builder.function(wrap_function!(test_binary))
+    builder
+        .function(wrap_function!(test_binary))
+        .function(wrap_function!(test_binary_bytes))
+        .function(wrap_function!(test_binary_boxed))
 }
 
 #[cfg(test)]
@@ -1,3 +1,20 @@
 <?php
 
 assert(test_callable(fn (string $a) => $a, 'test') === 'test');
+
+$strlen = test_callable_from_name('strlen');
+assert($strlen('test') === 4);
+
+assert(test_callable_iterable([1, 2, 3], null) === 3);
+assert(test_callable_iterable(new ArrayIterator([1, 2]), 'strlen') === 2);
+
+// Type hints of callable and iterable parameters and return values
+$params = (new ReflectionFunction('test_callable'))->getParameters();
+assert((string) $params[0]->getType() === 'callable');
+
+$params = (new ReflectionFunction('test_callable_iterable'))->getParameters();
+assert((string) $params[0]->getType() === 'iterable');
+assert((string) $params[1]->getType() === '?callable');
+
+$return = (new ReflectionFunction('test_callable_from_name'))->getReturnType();
+assert((string) $return === 'callable');
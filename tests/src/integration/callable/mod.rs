@@ -1,12 +1,29 @@
-use ext_php_rs::{prelude::*, types::Zval};
+use ext_php_rs::{
+    prelude::*,
+    types::{Iterable, Zval},
+};
 
 #[php_function]
 pub fn test_callable(call: ZendCallable, a: String) -> Zval {
     call.try_call(vec![&a]).expect("Failed to call function")
 }
 
+#[php_function]
+pub fn test_callable_from_name(name: &str) -> PhpResult<ZendCallable<'static>> {
+    Ok(ZendCallable::try_from_name(name)?)
+}
+
+#[php_function]
+pub fn test_callable_iterable(mut iterable: Iterable, call: Option<ZendCallable>) -> usize {
+    let _ = call;
+    iterable.iter().map_or(0, Iterator::count)
+}
+
 pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
-    builder.function(wrap_function!(test_callable))
+    builder
+        .function(wrap_function!(test_callable))
+        .function(wrap_function!(test_callable_from_name))
+        .function(wrap_function!(test_callable_iterable))
 }
 
 #[cfg(test)]
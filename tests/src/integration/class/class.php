@@ -156,3 +156,35 @@ $output = ob_get_clean();
 assert(strpos($output, 'publicNum') !== false, 'var_dump should show public property');
 // Private properties should show as ClassName::propertyName in var_dump
 // Protected properties should show with * prefix
+
+// Test interfaces which are only implemented if they exist
+$optional = new TestClassOptionalInterfaces();
+assert($optional instanceof JsonSerializable);
+assert(json_encode($optional) === '42');
+assert(count(class_implements($optional)) === 1);
+
+// Test fields which are only accessed through the property handlers
+$proxy = new TestProxyProps();
+$proxy->count++;
+$proxy->count += 2;
+$proxy->name .= 'foo';
+$proxy->name .= 'bar';
+assert($proxy->count === 3);
+assert($proxy->getCount() === 3);
+assert($proxy->getName() === 'foobar');
+assert(isset($proxy->count));
+assert(property_exists($proxy, 'name'));
+assert(get_object_vars($proxy) == ['count' => 3, 'name' => 'foobar']);
+
+// Test instantiations of a generic struct registered as separate classes
+$ints = new TestGenericInt([1, 2]);
+$ints->push(3);
+assert($ints->items() === [1, 2, 3]);
+assert($ints->first === 1);
+assert_exception_thrown(fn() => $ints->push('foo'));
+
+$strings = new TestGenericString(['foo']);
+$strings->push('bar');
+assert($strings->items() === ['foo', 'bar']);
+assert(!($strings instanceof TestGenericInt));
+assert((new ReflectionMethod(TestGenericString::class, 'push'))->getParameters()[0]->getType()->getName() === 'string');
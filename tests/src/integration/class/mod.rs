@@ -1,7 +1,7 @@
 #![allow(clippy::unused_self)]
 use ext_php_rs::{
     class::RegisteredClass,
-    convert::IntoZval,
+    convert::{FromZval, IntoZval},
     prelude::*,
     types::{ZendClassObject, Zval},
     zend::ce,
@@ -322,6 +322,76 @@ impl TestPropertyVisibility {
     }
 }
 
+#[php_class]
+#[php(implements_if_exists = "JsonSerializable")]
+#[php(implements_if_exists = "ExtPhpRs\\Missing\\MissingInterface")]
+pub struct TestClassOptionalInterfaces;
+
+#[php_impl]
+impl TestClassOptionalInterfaces {
+    pub fn __construct() -> Self {
+        Self
+    }
+
+    pub fn json_serialize(&self) -> i64 {
+        42
+    }
+}
+
+#[php_class]
+#[php(proxy_props)]
+pub struct TestProxyProps {
+    #[php(prop)]
+    count: i64,
+    #[php(prop)]
+    name: String,
+}
+
+#[php_impl]
+impl TestProxyProps {
+    pub fn __construct() -> Self {
+        Self {
+            count: 0,
+            name: String::new(),
+        }
+    }
+
+    pub fn get_count(&self) -> i64 {
+        self.count
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[php_class]
+#[php(instantiate(ty = "TestGenericClass<i64>", name = "TestGenericInt"))]
+#[php(instantiate(ty = "TestGenericClass<String>", name = "TestGenericString"))]
+pub struct TestGenericClass<T: 'static> {
+    #[php(prop)]
+    first: Option<T>,
+    items: Vec<T>,
+}
+
+#[php_impl]
+impl<T: Clone + IntoZval + for<'a> FromZval<'a> + 'static> TestGenericClass<T> {
+    pub fn __construct(items: Vec<T>) -> Self {
+        Self {
+            first: items.first().cloned(),
+            items,
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    pub fn items(&self) -> Vec<T> {
+        self.items.clone()
+    }
+}
+
 pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
     builder
         .class::<TestClass>()
@@ -333,6 +403,10 @@ pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
         .class::<TestStaticProps>()
         .class::<FluentBuilder>()
         .class::<TestPropertyVisibility>()
+        .class::<TestClassOptionalInterfaces>()
+        .class::<TestProxyProps>()
+        .class::<TestGenericClass<i64>>()
+        .class::<TestGenericClass<String>>()
         .function(wrap_function!(test_class))
         .function(wrap_function!(throw_exception))
 }
@@ -0,0 +1,94 @@
+<?php
+
+require(__DIR__ . '/../_utils.php');
+
+$dims = new TestDimensions();
+
+// write_dimension
+$dims['a'] = 1;
+$dims[] = 2;
+$dims[] = 3;
+assert($dims->count() === 3);
+
+// read_dimension
+assert($dims['a'] === 1);
+assert($dims[0] === 2);
+assert($dims['1'] === 3);
+assert_exception_thrown(fn () => $dims['missing']);
+assert_exception_thrown(function () use ($dims) {
+    $dims['b'] = 'not an integer';
+});
+
+// has_dimension
+assert(isset($dims['a']));
+assert(!isset($dims['missing']));
+assert(($dims['missing'] ?? 'default') === 'default');
+assert(!empty($dims['a']));
+assert(empty($dims['missing']));
+
+// unset_dimension
+unset($dims['a']);
+assert(!isset($dims['a']));
+assert($dims->count() === 2);
+
+// Handlers which are not implemented throw errors
+$readOnly = new TestDimensionsReadOnly();
+assert($readOnly[21] === 42);
+assert($readOnly['x'] === null);
+assert(isset($readOnly[1]));
+assert(empty($readOnly[0]));
+
+try {
+    $readOnly[1] = 2;
+    assert(false);
+} catch (Error $e) {
+    assert($e->getMessage() === 'Cannot use object of type TestDimensionsReadOnly as array');
+}
+assert_exception_thrown(function () use ($readOnly) {
+    unset($readOnly[1]);
+});
+
+// ArrayAccess
+$access = new TestArrayAccess();
+assert($access instanceof ArrayAccess);
+$access['a'] = 1;
+$access->offsetSet('b', 0);
+assert($access['a'] === 1);
+assert($access->offsetGet('b') === 0);
+assert_exception_thrown(fn () => $access['missing']);
+assert_exception_thrown(function () use ($access) {
+    $access[] = 2;
+});
+assert(isset($access['a']));
+assert(!isset($access['missing']));
+assert(($access['missing'] ?? 'default') === 'default');
+assert($access->offsetExists('b'));
+assert(empty($access['b']));
+unset($access['a']);
+assert(!isset($access['a']));
+$access->offsetUnset('b');
+assert(!$access->offsetExists('b'));
+
+// PHP subclasses overriding the ArrayAccess methods
+class TestArrayAccessChild extends TestArrayAccess
+{
+    public function offsetGet(mixed $offset): mixed
+    {
+        return parent::offsetGet($offset) * 10;
+    }
+
+    public function offsetSet(mixed $offset, mixed $value): void
+    {
+        parent::offsetSet($offset ?? 'appended', $value);
+    }
+}
+
+$child = new TestArrayAccessChild();
+$child['a'] = 2;
+$child[] = 3;
+assert($child['a'] === 20);
+assert($child['appended'] === 30);
+assert(isset($child['a']));
+assert(!isset($child['missing']));
+unset($child['a']);
+assert(!isset($child['a']));
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use ext_php_rs::{
+    convert::IntoZval,
+    exception::PhpResult,
+    flags::FetchMode,
+    prelude::*,
+    types::Zval,
+    zend::{DimensionHandlers, PhpArrayAccess},
+};
+
+#[php_class]
+#[php(dimensions)]
+pub struct TestDimensions {
+    values: HashMap<String, i64>,
+    next: i64,
+}
+
+#[php_impl]
+impl TestDimensions {
+    pub fn __construct() -> Self {
+        Self {
+            values: HashMap::new(),
+            next: 0,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl DimensionHandlers for TestDimensions {
+    fn read_dimension(
+        &mut self,
+        offset: Option<&Zval>,
+        mode: FetchMode,
+    ) -> PhpResult<Option<Zval>> {
+        let key = offset.map(key).unwrap_or_default();
+        match self.values.get(&key).copied() {
+            Some(value) => Ok(Some(value.into_zval(false)?)),
+            None if mode == FetchMode::Isset => Ok(None),
+            None => Err(format!("Undefined offset {key}").into()),
+        }
+    }
+
+    fn write_dimension(&mut self, offset: Option<&Zval>, value: &Zval) -> PhpResult<()> {
+        let key = match offset {
+            Some(offset) => key(offset),
+            None => {
+                self.next += 1;
+                (self.next - 1).to_string()
+            }
+        };
+        let value = value.long().ok_or("Only integers can be stored")?;
+        self.values.insert(key, value);
+        Ok(())
+    }
+
+    fn unset_dimension(&mut self, offset: &Zval) -> PhpResult<()> {
+        self.values.remove(&key(offset));
+        Ok(())
+    }
+}
+
+/// Converts an offset into a key, treating integer and string offsets alike.
+fn key(offset: &Zval) -> String {
+    offset
+        .long()
+        .map(|offset| offset.to_string())
+        .or_else(|| offset.string())
+        .unwrap_or_default()
+}
+
+#[php_class]
+#[php(dimensions)]
+pub struct TestDimensionsReadOnly;
+
+#[php_impl]
+impl TestDimensionsReadOnly {
+    pub fn __construct() -> Self {
+        Self
+    }
+}
+
+impl DimensionHandlers for TestDimensionsReadOnly {
+    fn read_dimension(
+        &mut self,
+        offset: Option<&Zval>,
+        _mode: FetchMode,
+    ) -> PhpResult<Option<Zval>> {
+        Ok(offset.and_then(Zval::long).map(|offset| {
+            let mut zv = Zval::new();
+            zv.set_long(offset * 2);
+            zv
+        }))
+    }
+}
+
+#[php_class]
+#[php(array_access)]
+pub struct TestArrayAccess {
+    values: HashMap<String, i64>,
+}
+
+#[php_impl]
+impl TestArrayAccess {
+    pub fn __construct() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl PhpArrayAccess for TestArrayAccess {
+    fn offset_exists(&mut self, offset: &Zval) -> PhpResult<bool> {
+        Ok(self.values.contains_key(&key(offset)))
+    }
+
+    fn offset_get(&mut self, offset: &Zval) -> PhpResult<Option<Zval>> {
+        let key = key(offset);
+        match self.values.get(&key).copied() {
+            Some(value) => Ok(Some(value.into_zval(false)?)),
+            None => Err(format!("Undefined offset {key}").into()),
+        }
+    }
+
+    fn offset_set(&mut self, offset: &Zval, value: &Zval) -> PhpResult<()> {
+        if offset.is_null() {
+            return Err("Cannot append".into());
+        }
+        let value = value.long().ok_or("Only integers can be stored")?;
+        self.values.insert(key(offset), value);
+        Ok(())
+    }
+
+    fn offset_unset(&mut self, offset: &Zval) -> PhpResult<()> {
+        self.values.remove(&key(offset));
+        Ok(())
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .class::<TestDimensions>()
+        .class::<TestDimensionsReadOnly>()
+        .class::<TestArrayAccess>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn dimension_works() {
+        assert!(crate::integration::test::run_php("dimension/dimension.php"));
+    }
+}
@@ -0,0 +1,12 @@
+<?php
+
+$class = new ReflectionClass('TestDocComment');
+assert($class->getProperty('value')->getDocComment() === "/**\n * The current value.\n */");
+assert($class->getReflectionConstant('DEFAULT')->getDocComment() === "/**\n * The default value.\n */");
+
+if (PHP_VERSION_ID >= 80400) {
+    $function = new ReflectionFunction('test_doc_comment');
+    assert($function->getDocComment() === "/**\n * Adds two numbers.\n *\n * @param int \$a\n * @param int \$b\n */");
+    assert($class->getDocComment() === "/**\n * A documented class.\n */");
+    assert($class->getMethod('get')->getDocComment() === "/**\n * Returns the current value.\n */");
+}
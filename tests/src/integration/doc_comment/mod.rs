@@ -0,0 +1,51 @@
+use ext_php_rs::prelude::*;
+
+/// Adds two numbers.
+///
+/// @param int $a
+/// @param int $b
+#[php_function]
+pub fn test_doc_comment(a: i64, b: i64) -> i64 {
+    a + b
+}
+
+/// A documented class.
+#[php_class]
+pub struct TestDocComment {
+    /// The current value.
+    #[php(prop)]
+    value: i64,
+}
+
+#[php_impl]
+impl TestDocComment {
+    /// The default value.
+    pub const DEFAULT: i64 = 1;
+
+    pub fn __construct() -> Self {
+        Self {
+            value: Self::DEFAULT,
+        }
+    }
+
+    /// Returns the current value.
+    pub fn get(&self) -> i64 {
+        self.value
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_doc_comment))
+        .class::<TestDocComment>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn doc_comment_works() {
+        assert!(crate::integration::test::run_php(
+            "doc_comment/doc_comment.php"
+        ));
+    }
+}
@@ -0,0 +1,23 @@
+<?php
+
+$errors = [];
+set_error_handler(function (int $errno, string $errstr) use (&$errors) {
+    $errors[] = [$errno, $errstr];
+    return true;
+});
+
+test_raise_warning('Something went wrong');
+test_raise_notice('100% done');
+test_raise_user_deprecated('Use something else');
+test_raise_diagnostic(false);
+test_raise_diagnostic(true);
+
+restore_error_handler();
+
+assert($errors[0] === [E_WARNING, 'test_raise_warning(): Something went wrong']);
+assert($errors[1] === [E_NOTICE, 'test_raise_notice(): 100% done']);
+assert($errors[2][0] === E_USER_DEPRECATED);
+assert(str_ends_with($errors[2][1], 'Use something else'));
+assert($errors[3] === [E_NOTICE, 'test_raise_diagnostic(): Soft diagnostic']);
+assert($errors[4] === [E_WARNING, 'test_raise_diagnostic(): Hard diagnostic']);
+assert(count($errors) === 5);
@@ -0,0 +1,72 @@
+use std::fmt;
+
+use ext_php_rs::{
+    error::{Diagnostic, ErrorLevel},
+    prelude::*,
+};
+
+#[derive(Debug)]
+pub enum TestDiagnostic {
+    Soft,
+    Hard,
+}
+
+impl fmt::Display for TestDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Soft => write!(f, "Soft diagnostic"),
+            Self::Hard => write!(f, "Hard diagnostic"),
+        }
+    }
+}
+
+impl Diagnostic for TestDiagnostic {
+    fn error_level(&self) -> ErrorLevel {
+        match self {
+            Self::Soft => ErrorLevel::Notice,
+            Self::Hard => ErrorLevel::Warning,
+        }
+    }
+}
+
+#[php_function]
+pub fn test_raise_warning(message: &str) {
+    ErrorLevel::Warning.raise(message);
+}
+
+#[php_function]
+pub fn test_raise_notice(message: &str) {
+    ErrorLevel::Notice.raise(message);
+}
+
+#[php_function]
+pub fn test_raise_user_deprecated(message: &str) {
+    ErrorLevel::UserDeprecated.raise_with_docref("function.test-raise-user-deprecated", message);
+}
+
+#[php_function]
+pub fn test_raise_diagnostic(hard: bool) {
+    if hard {
+        TestDiagnostic::Hard.raise();
+    } else {
+        TestDiagnostic::Soft.raise();
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_raise_warning))
+        .function(wrap_function!(test_raise_notice))
+        .function(wrap_function!(test_raise_user_deprecated))
+        .function(wrap_function!(test_raise_diagnostic))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn error_level_works() {
+        assert!(crate::integration::test::run_php(
+            "error_level/error_level.php"
+        ));
+    }
+}
@@ -11,3 +11,14 @@ try {
     assert($e instanceof \Test\TestException);
     assert("Not good custom!" === $e->getMessage());
 }
+
+try {
+    throw_chained_exception();
+} catch (\Throwable $e) {
+    assert($e instanceof \Exception);
+    assert("Could not connect" === $e->getMessage());
+    $previous = $e->getPrevious();
+    assert($previous instanceof \Test\TestException);
+    assert("Connection refused" === $previous->getMessage());
+    assert(null === $previous->getPrevious());
+}
@@ -13,6 +13,31 @@ pub fn throw_custom_exception() -> PhpResult<i32> {
     ))
 }
 
+#[derive(Debug)]
+pub struct ConnectError(std::io::Error);
+
+impl std::fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Could not connect")
+    }
+}
+
+impl std::error::Error for ConnectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[php_function]
+pub fn throw_chained_exception() -> PhpResult<i32> {
+    let err = ConnectError(std::io::Error::other("Connection refused"));
+    Err(PhpException::from_error_with(&err, |error| {
+        error
+            .downcast_ref::<std::io::Error>()
+            .map(|error| PhpException::from_class::<TestException>(error.to_string()))
+    }))
+}
+
 #[php_function]
 pub fn throw_default_exception() -> PhpResult<i32> {
     Err(PhpException::default("Not good!".into()))
@@ -23,6 +48,7 @@ pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
         .class::<TestException>()
         .function(wrap_function!(throw_default_exception))
         .function(wrap_function!(throw_custom_exception))
+        .function(wrap_function!(throw_chained_exception))
 }
 
 #[cfg(test)]
@@ -0,0 +1,44 @@
+<?php
+
+require(__DIR__ . '/../_utils.php');
+
+class ExternClassGreeter
+{
+    public function __construct(private string $name) {}
+
+    public function greet(string $greeting): string
+    {
+        return "$greeting, {$this->name}!";
+    }
+
+    public function failing(): void
+    {
+        throw new RuntimeException('Failed');
+    }
+
+    public static function failingStatic(): void
+    {
+        throw new RuntimeException('Failed');
+    }
+
+    public static function create(string $name): static
+    {
+        return new static(strtoupper($name));
+    }
+}
+
+abstract class ExternClassShape
+{
+}
+
+assert(test_extern_class_sum(new ArrayObject([1, 2, 3])) === 16);
+assert_exception_thrown(fn() => test_extern_class_sum(new stdClass()));
+
+$object = test_extern_class_new();
+assert($object instanceof ArrayObject);
+assert($object->getArrayCopy() === [1, 2, 3]);
+
+assert(test_extern_class_greet('world') === 'Hello, world! Bye, WORLD!');
+assert(test_extern_class_is_greeter(new ExternClassGreeter('world')));
+assert(!test_extern_class_is_greeter(new ArrayObject()));
+assert(test_extern_class_errors());
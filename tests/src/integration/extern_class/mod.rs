@@ -0,0 +1,88 @@
+use ext_php_rs::{error::Error, prelude::*, types::Zval};
+
+#[php_extern_class("ArrayObject")]
+pub trait ArrayObject {
+    fn __construct(array: Vec<i64>) -> Self;
+    fn count(&self) -> i64;
+    fn offset_get(&self, key: i64) -> Zval;
+    fn append(&mut self, value: i64);
+}
+
+/// Declared by the PHP test script.
+#[php_extern_class("ExternClassGreeter")]
+pub trait Greeter {
+    fn __construct(name: &str) -> Self;
+    fn greet(&self, greeting: &str) -> String;
+    #[php(name = "failing")]
+    fn fail(&self);
+    fn create(name: &str) -> Self;
+    #[php(name = "greet")]
+    fn greet_static(greeting: &str) -> String;
+    fn failing_static();
+}
+
+/// Declared by the PHP test script.
+#[php_extern_class("ExternClassShape")]
+pub trait Shape {
+    fn __construct() -> Self;
+}
+
+#[php_function]
+pub fn test_extern_class_sum(mut object: ArrayObject) -> PhpResult<i64> {
+    object.append(10)?;
+    let mut sum = 0;
+    for i in 0..object.count()? {
+        sum += object.offset_get(i)?.long().unwrap_or_default();
+    }
+    Ok(sum)
+}
+
+#[php_function]
+pub fn test_extern_class_new() -> PhpResult<ArrayObject> {
+    Ok(ArrayObject::new(vec![1, 2, 3])?)
+}
+
+#[php_function]
+pub fn test_extern_class_greet(name: &str) -> PhpResult<String> {
+    let greeter = Greeter::new(name)?;
+    let created = Greeter::create(name)?;
+    assert!(greeter.fail().is_err());
+    Ok(format!(
+        "{} {}",
+        greeter.greet("Hello")?,
+        created.greet("Bye")?
+    ))
+}
+
+#[php_function]
+pub fn test_extern_class_is_greeter(value: &Zval) -> bool {
+    value.extract::<Greeter>().is_some()
+}
+
+#[php_function]
+pub fn test_extern_class_errors() -> bool {
+    matches!(Shape::new(), Err(Error::UninstantiableClass(_)))
+        && matches!(Greeter::greet_static("Hello"), Err(Error::Callable))
+        && matches!(Greeter::failing_static(), Err(Error::Exception(_)))
+        && matches!(Greeter::class_entry(), Err(Error::UserlandClass(_)))
+        && ArrayObject::class_entry().is_ok()
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_extern_class_sum))
+        .function(wrap_function!(test_extern_class_new))
+        .function(wrap_function!(test_extern_class_greet))
+        .function(wrap_function!(test_extern_class_is_greeter))
+        .function(wrap_function!(test_extern_class_errors))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn extern_class_works() {
+        assert!(crate::integration::test::run_php(
+            "extern_class/extern_class.php"
+        ));
+    }
+}
@@ -6,3 +6,26 @@ assert(test_globals_http_cookie() === []);
 assert(!empty(test_globals_http_server()));
 assert(test_globals_http_request() === []);
 assert(test_globals_http_files() === []);
+
+class GlobalsScope {
+    public function scope(): ?string {
+        return test_globals_scope();
+    }
+
+    public function this(): ?string {
+        return test_globals_this();
+    }
+
+    public static function staticThis(): ?string {
+        return test_globals_this();
+    }
+}
+
+assert(test_globals_scope() === null);
+assert(test_globals_this() === null);
+assert((new GlobalsScope())->scope() === 'GlobalsScope');
+assert((new GlobalsScope())->this() === 'GlobalsScope');
+assert(GlobalsScope::staticThis() === null);
+
+set_include_path('/tmp');
+assert(test_globals_include_path() === '/tmp');
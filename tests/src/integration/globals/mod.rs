@@ -1,4 +1,9 @@
-use ext_php_rs::{boxed::ZBox, prelude::*, types::ZendHashTable, zend::ProcessGlobals};
+use ext_php_rs::{
+    boxed::ZBox,
+    prelude::*,
+    types::ZendHashTable,
+    zend::{ExecutorGlobals, ProcessGlobals},
+};
 
 #[php_function]
 pub fn test_globals_http_get() -> ZBox<ZendHashTable> {
@@ -33,6 +38,25 @@ pub fn test_globals_http_files() -> ZBox<ZendHashTable> {
     ProcessGlobals::get().http_files_vars().to_owned()
 }
 
+#[php_function]
+pub fn test_globals_scope() -> Option<String> {
+    ExecutorGlobals::get()
+        .current_scope()
+        .and_then(|ce| ce.name().map(ToOwned::to_owned))
+}
+
+#[php_function]
+pub fn test_globals_this() -> Option<String> {
+    ExecutorGlobals::get()
+        .this()
+        .and_then(|this| this.get_class_name().ok())
+}
+
+#[php_function]
+pub fn test_globals_include_path() -> Option<String> {
+    ExecutorGlobals::get().include_path().map(ToOwned::to_owned)
+}
+
 pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
     builder
         .function(wrap_function!(test_globals_http_get))
@@ -41,6 +65,9 @@ pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
         .function(wrap_function!(test_globals_http_server))
         .function(wrap_function!(test_globals_http_request))
         .function(wrap_function!(test_globals_http_files))
+        .function(wrap_function!(test_globals_scope))
+        .function(wrap_function!(test_globals_this))
+        .function(wrap_function!(test_globals_include_path))
 }
 
 #[cfg(test)]
@@ -0,0 +1,18 @@
+<?php
+
+ob_start();
+phpinfo(INFO_MODULES);
+$info = ob_get_clean();
+
+// The CLI writes the information as plain text, without escaping.
+assert(str_contains($info, 'ext-php-rs tests => enabled'));
+assert(str_contains($info, 'Test Version => 1.0.0'));
+assert(str_contains($info, 'Test Section'));
+assert(str_contains($info, 'Test Hits => 10'));
+assert(str_contains($info, 'Test Misses => 2'));
+assert(str_contains($info, 'Test Empty => no value'));
+assert(str_contains($info, 'Test Nested'));
+assert(str_contains($info, 'Test Nested Key => <nested>'));
+assert(str_contains($info, 'Test text <b>block</b>'));
+assert(str_contains($info, 'Test HTML fallback'));
+assert(!str_contains($info, '<p>Test HTML block</p>'));
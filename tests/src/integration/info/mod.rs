@@ -0,0 +1,25 @@
+use ext_php_rs::zend::ModuleInfo;
+
+pub fn minfo(info: &mut ModuleInfo) {
+    info.table(|table| {
+        table.header(&["ext-php-rs tests", "enabled"]);
+        table.entry("Test Version", "1.0.0");
+    });
+    info.section("Test Section", |table| {
+        table.entries([("Test Hits", 10), ("Test Misses", 2)]);
+        table.row(&["Test Empty", ""]);
+        table.section("Test Nested", |table| {
+            table.entry("Test Nested Key", "<nested>");
+        });
+    });
+    info.text("Test text <b>block</b>");
+    info.html("<p>Test HTML block</p>", "Test HTML fallback");
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn info_works() {
+        assert!(crate::integration::test::run_php("info/info.php"));
+    }
+}
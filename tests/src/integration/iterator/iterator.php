@@ -9,3 +9,17 @@ assert(iter_next_back([], 2) === [null, null]);
 assert(iter_next_back([1, 2 ,3], 2) === [2, 3, 0, 1, 1, 2, null, null]);
 var_dump(iter_next_back([1, 2, 3, 4, 5], 3));
 assert(iter_next_back([1, 2, 3, 4, 5], 3) === [4, 5, 0, 1, 1, 2, 3, 4, 2, 3, null, null, null]);
+
+assert(iter_buckets_next([]) === []);
+assert(iter_buckets_next([1, 2, 3]) === [0, 1, 1, 2, 2, 3]);
+assert(iter_buckets_back([1, 2, 3]) === [2, 3, 1, 2, 0, 1]);
+assert(iter_buckets_next(['a' => 1, 5 => 'b']) === ['a', 1, 5, 'b']);
+
+$holes = [1, 2, 3, 4];
+unset($holes[1], $holes[3]);
+assert(iter_buckets_next($holes) === iter_next($holes));
+assert(iter_buckets_back($holes) === iter_back($holes));
+
+$holes = ['a' => 1, 'b' => 2, 'c' => 3];
+unset($holes['b']);
+assert(iter_buckets_next($holes) === ['a', 1, 'c', 3]);
@@ -41,6 +41,21 @@ pub fn iter_next_back(ht: &ZendHashTable, modulus: usize) -> Vec<Option<Zval>> {
     result
 }
 
+#[php_function]
+pub fn iter_buckets_next(ht: &ZendHashTable) -> Vec<Zval> {
+    ht.iter_buckets()
+        .flat_map(|(k, v)| [key_to_zval(k), v.shallow_clone()])
+        .collect()
+}
+
+#[php_function]
+pub fn iter_buckets_back(ht: &ZendHashTable) -> Vec<Zval> {
+    ht.iter_buckets()
+        .rev()
+        .flat_map(|(k, v)| [key_to_zval(k), v.shallow_clone()])
+        .collect()
+}
+
 fn key_to_zval(key: ArrayKey) -> Zval {
     match key {
         ArrayKey::String(s) => {
@@ -58,6 +73,17 @@ fn key_to_zval(key: ArrayKey) -> Zval {
             zval.set_long(l);
             zval
         }
+        ArrayKey::Binary(b) => {
+            let mut zval = Zval::new();
+            zval.set_binary(b);
+            zval
+        }
+        ArrayKey::Bytes(b) => {
+            let mut zval = Zval::new();
+            zval.set_binary(b.to_vec());
+            zval
+        }
+        _ => Zval::new(),
     }
 }
 pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
@@ -65,6 +91,8 @@ pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
         .function(wrap_function!(iter_next))
         .function(wrap_function!(iter_back))
         .function(wrap_function!(iter_next_back))
+        .function(wrap_function!(iter_buckets_next))
+        .function(wrap_function!(iter_buckets_back))
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 <?php
 
+require(__DIR__ . '/../_utils.php');
+
 $magicMethod = new MagicMethod();
 
 // __set
@@ -35,3 +37,44 @@ assert(null === $magicMethod->callUndefinedMagicMethod());
 // __call_static
 assert("Hello from static call 1, 2, 3" === MagicMethod::callStaticSomeMagic(1, 2, 3));
 assert(null === MagicMethod::callUndefinedStaticSomeMagic());
+
+// has_property and unset_property handlers
+$props = new MagicProperties();
+$props->name = 'value';
+$props->empty = '';
+assert(isset($props->name));
+assert(!empty($props->name));
+assert(isset($props->empty));
+assert(empty($props->empty));
+assert(!isset($props->missing));
+assert(property_exists($props, 'name'));
+
+unset($props->name);
+assert(!isset($props->name));
+assert(null === $props->name);
+
+// Properties backed by fields fall back to the default behaviour
+assert(isset($props->fixed));
+assert(!empty($props->fixed));
+assert_exception_thrown(function () use ($props) {
+    unset($props->fixed);
+});
+assert(1 === $props->fixed);
+
+// get_debug_info handler
+$debug = print_r(new MagicDebugInfo(), true);
+$expectedDebug = "MagicDebugInfo Object\n(\n    [count] => 3\n    [sum] => 6\n)\n";
+assert($expectedDebug === $debug);
+
+// cast_object handler
+$named = new MagicToString('world');
+assert('Hello, world!' === (string) $named);
+assert('<Hello, world!>' === "<$named>");
+assert('Hello, world!' === strval($named));
+assert(true === (bool) $named);
+assert_exception_thrown(function () {
+    echo new MagicToString('');
+});
+assert($named instanceof Stringable);
+assert('Hello, world!' === $named->__toString());
+assert('Hello, world!' === (fn (Stringable $value): string => (string) $value)($named));
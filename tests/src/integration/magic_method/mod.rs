@@ -1,7 +1,10 @@
 #![allow(clippy::unused_self)]
 use ext_php_rs::{
+    boxed::ZBox,
+    flags::PropertyCheck,
     prelude::*,
-    types::{ArrayKey, Zval},
+    types::{ArrayKey, ZendHashTable, Zval},
+    zend::{DebugInfo, PropertyHandlers, ToPhpString},
 };
 use std::collections::HashMap;
 
@@ -94,8 +97,100 @@ impl MagicMethod {
     }
 }
 
+#[php_class]
+#[php(property_handlers)]
+pub struct MagicProperties {
+    values: HashMap<String, String>,
+    #[php(prop)]
+    fixed: i64,
+}
+
+#[php_impl]
+impl MagicProperties {
+    pub fn __construct() -> Self {
+        Self {
+            values: HashMap::new(),
+            fixed: 1,
+        }
+    }
+
+    pub fn __get(&self, name: String) -> Option<String> {
+        self.values.get(&name).cloned()
+    }
+
+    pub fn __set(&mut self, name: String, value: String) {
+        self.values.insert(name, value);
+    }
+}
+
+impl PropertyHandlers for MagicProperties {
+    fn has_property(&mut self, name: &str, check: PropertyCheck) -> PhpResult<Option<bool>> {
+        let Some(value) = self.values.get(name) else {
+            return Ok(None);
+        };
+        Ok(Some(match check {
+            PropertyCheck::NotEmpty => !value.is_empty(),
+            PropertyCheck::Isset | PropertyCheck::Exists => true,
+        }))
+    }
+
+    fn unset_property(&mut self, name: &str) -> PhpResult<bool> {
+        Ok(self.values.remove(name).is_some())
+    }
+}
+
+#[php_class]
+#[php(debug_info)]
+pub struct MagicDebugInfo {
+    values: Vec<i64>,
+}
+
+#[php_impl]
+impl MagicDebugInfo {
+    pub fn __construct() -> Self {
+        Self {
+            values: vec![1, 2, 3],
+        }
+    }
+}
+
+impl DebugInfo for MagicDebugInfo {
+    fn debug_info(&self) -> ZBox<ZendHashTable> {
+        let mut info = ZendHashTable::new();
+        let _ = info.insert("count", self.values.len());
+        let _ = info.insert("sum", self.values.iter().sum::<i64>());
+        info
+    }
+}
+
+#[php_class]
+#[php(to_string)]
+pub struct MagicToString {
+    name: String,
+}
+
+#[php_impl]
+impl MagicToString {
+    pub fn __construct(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl ToPhpString for MagicToString {
+    fn to_php_string(&self) -> PhpResult<String> {
+        if self.name.is_empty() {
+            return Err("Name is empty".into());
+        }
+        Ok(format!("Hello, {}!", self.name))
+    }
+}
+
 pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
-    builder.class::<MagicMethod>()
+    builder
+        .class::<MagicMethod>()
+        .class::<MagicProperties>()
+        .class::<MagicDebugInfo>()
+        .class::<MagicToString>()
 }
 
 #[cfg(test)]
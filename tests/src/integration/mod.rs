@@ -1,4 +1,6 @@
+pub mod alias;
 pub mod array;
+pub mod async_fn;
 pub mod bailout;
 pub mod binary;
 pub mod bool;
@@ -6,18 +8,29 @@ pub mod callable;
 pub mod class;
 pub mod closure;
 pub mod defaults;
+pub mod dimension;
+pub mod doc_comment;
 #[cfg(feature = "enum")]
 pub mod enum_;
+pub mod error_level;
 pub mod exception;
+pub mod extern_class;
 pub mod globals;
+pub mod info;
 pub mod interface;
 pub mod iterator;
 pub mod magic_method;
+pub mod namespace;
 pub mod nullable;
 pub mod number;
 pub mod object;
 pub mod persistent_string;
+pub mod requirements;
+pub mod return_types;
+pub mod startup_hook;
+pub mod stream;
 pub mod string;
+pub mod symbol_table;
 pub mod types;
 pub mod variadic_args;
 
@@ -0,0 +1,41 @@
+use ext_php_rs::prelude::*;
+
+#[php_const]
+#[php(namespace = "ExtPhpRs\\Test")]
+pub const TEST_NAMESPACE_CONST: i64 = 42;
+
+#[php_function]
+#[php(namespace = "ExtPhpRs\\Test", alias = "test_namespace_alias")]
+pub fn test_namespace_function() -> &'static str {
+    "namespaced"
+}
+
+#[php_class]
+#[php(namespace = "ExtPhpRs\\Test")]
+pub struct TestNamespaceClass;
+
+#[php_impl]
+impl TestNamespaceClass {
+    pub fn __construct() -> Self {
+        Self
+    }
+
+    pub fn create() -> Self {
+        Self
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .constant(wrap_constant!(TEST_NAMESPACE_CONST))
+        .function(wrap_function!(test_namespace_function))
+        .class::<TestNamespaceClass>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn namespace_works() {
+        assert!(crate::integration::test::run_php("namespace/namespace.php"));
+    }
+}
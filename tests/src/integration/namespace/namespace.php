@@ -0,0 +1,24 @@
+<?php
+
+use ExtPhpRs\Test\TestNamespaceClass;
+
+use function ExtPhpRs\Test\test_namespace_function;
+use function ExtPhpRs\Test\test_namespace_alias;
+
+use const ExtPhpRs\Test\TEST_NAMESPACE_CONST;
+
+assert(TEST_NAMESPACE_CONST === 42);
+assert(!defined('TEST_NAMESPACE_CONST'));
+
+assert(test_namespace_function() === 'namespaced');
+assert(test_namespace_alias() === 'namespaced');
+assert(!function_exists('test_namespace_function'));
+
+$obj = new TestNamespaceClass();
+assert($obj instanceof TestNamespaceClass);
+assert(get_class($obj) === 'ExtPhpRs\Test\TestNamespaceClass');
+assert(TestNamespaceClass::create() instanceof TestNamespaceClass);
+assert(!class_exists('TestNamespaceClass'));
+
+$method = new ReflectionMethod(TestNamespaceClass::class, 'create');
+assert($method->getReturnType()->getName() === TestNamespaceClass::class);
@@ -1,12 +1,30 @@
-use ext_php_rs::prelude::*;
+use ext_php_rs::{prelude::*, types::MaybeUndef};
 
 #[php_function]
 pub fn test_nullable(a: Option<String>) -> Option<String> {
     a
 }
 
+#[php_function]
+#[php(nullable)]
+pub fn test_nullable_override(#[php(not_null)] a: Option<String>) -> String {
+    a.unwrap_or_default()
+}
+
+#[php_function]
+pub fn test_maybe_undef(a: i64, b: MaybeUndef<Option<String>>) -> String {
+    match b {
+        MaybeUndef::Undef => format!("{a}: undef"),
+        MaybeUndef::Value(None) => format!("{a}: null"),
+        MaybeUndef::Value(Some(b)) => format!("{a}: {b}"),
+    }
+}
+
 pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
-    builder.function(wrap_function!(test_nullable))
+    builder
+        .function(wrap_function!(test_nullable))
+        .function(wrap_function!(test_nullable_override))
+        .function(wrap_function!(test_maybe_undef))
 }
 
 #[cfg(test)]
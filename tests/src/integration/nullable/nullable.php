@@ -2,3 +2,18 @@
 
 assert(is_null(test_nullable()));
 assert(!is_null(test_nullable('value')));
+
+$override = new ReflectionFunction('test_nullable_override');
+assert(!$override->getParameters()[0]->allowsNull());
+assert($override->getParameters()[0]->isOptional());
+assert($override->getReturnType()->allowsNull());
+assert(test_nullable_override() === '');
+assert(test_nullable_override('value') === 'value');
+
+assert(test_maybe_undef(1) === '1: undef');
+assert(test_maybe_undef(1, null) === '1: null');
+assert(test_maybe_undef(1, 'value') === '1: value');
+
+$maybeUndef = new ReflectionFunction('test_maybe_undef');
+assert($maybeUndef->getParameters()[1]->allowsNull());
+assert($maybeUndef->getParameters()[1]->isOptional());
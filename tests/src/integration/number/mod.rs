@@ -1,4 +1,4 @@
-use ext_php_rs::prelude::*;
+use ext_php_rs::{prelude::*, types::Zval};
 
 #[php_function]
 pub fn test_number_signed(a: i32) -> i32 {
@@ -15,11 +15,32 @@ pub fn test_number_float(a: f32) -> f32 {
     a
 }
 
+#[php_function]
+#[php(instantiate(name = "test_number_sum_int", types = "i64"))]
+#[php(instantiate(name = "test_number_sum_float", types = "f64"))]
+pub fn test_number_sum<T: std::iter::Sum<T>>(values: Vec<T>) -> T {
+    values.into_iter().sum()
+}
+
+#[php_function]
+pub fn test_number_add(a: &Zval, b: &Zval) -> PhpResult<Zval> {
+    Ok(a.try_add(b)?)
+}
+
+#[php_function]
+pub fn test_number_div(a: &Zval, b: &Zval) -> PhpResult<Zval> {
+    Ok(a.try_div(b)?)
+}
+
 pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
     builder
         .function(wrap_function!(test_number_signed))
         .function(wrap_function!(test_number_unsigned))
         .function(wrap_function!(test_number_float))
+        .function(wrap_function!(test_number_sum_int))
+        .function(wrap_function!(test_number_sum_float))
+        .function(wrap_function!(test_number_add))
+        .function(wrap_function!(test_number_div))
 }
 
 #[cfg(test)]
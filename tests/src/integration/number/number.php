@@ -16,3 +16,15 @@ assert_exception_thrown(fn () => test_number_unsigned(-12));
 assert(round(test_number_float(-1.2), 2) === round(-1.2, 2));
 assert(round(test_number_float(0.0), 2) === round(0.0, 2));
 assert(round(test_number_float(1.2), 2) === round(1.2, 2));
+
+// Generic instantiations
+assert(test_number_sum_int([1, 2, 3]) === 6);
+assert(round(test_number_sum_float([0.5, 1.25]), 2) === 1.75);
+assert(!function_exists('test_number_sum'));
+
+// Operators
+assert(test_number_add(1, '2.5') === 3.5);
+assert(test_number_add([1], [2, 3]) === [1, 3]);
+assert_exception_thrown(fn () => test_number_add([], 1));
+assert(test_number_div(9, 3) === 3);
+assert_exception_thrown(fn () => test_number_div(1, 0));
@@ -0,0 +1,59 @@
+use ext_php_rs::prelude::*;
+
+#[php_const]
+#[php(requires_ext = "standard")]
+pub const TEST_REQUIREMENTS_CONST: i64 = 1;
+
+#[php_const]
+#[php(requires_ext = "ext_php_rs_missing")]
+pub const TEST_REQUIREMENTS_MISSING_CONST: i64 = 2;
+
+#[php_function]
+#[php(requires_ext = "standard", min_php = "8.0")]
+pub fn test_requirements_met() -> bool {
+    true
+}
+
+#[php_function]
+#[php(
+    requires_ext = "ext_php_rs_missing",
+    alias = "test_requirements_missing_alias"
+)]
+pub fn test_requirements_missing() -> bool {
+    true
+}
+
+#[php_function]
+#[php(min_php = "99.0")]
+pub fn test_requirements_future() -> bool {
+    true
+}
+
+#[php_class]
+#[php(requires_ext = "Core")]
+pub struct TestRequirementsClass;
+
+#[php_class]
+#[php(min_php = "99.0")]
+pub struct TestRequirementsFutureClass;
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .constant(wrap_constant!(TEST_REQUIREMENTS_CONST))
+        .constant(wrap_constant!(TEST_REQUIREMENTS_MISSING_CONST))
+        .function(wrap_function!(test_requirements_met))
+        .function(wrap_function!(test_requirements_missing))
+        .function(wrap_function!(test_requirements_future))
+        .class::<TestRequirementsClass>()
+        .class::<TestRequirementsFutureClass>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn requirements_work() {
+        assert!(crate::integration::test::run_php(
+            "requirements/requirements.php"
+        ));
+    }
+}
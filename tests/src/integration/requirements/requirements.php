@@ -0,0 +1,12 @@
+<?php
+
+assert(TEST_REQUIREMENTS_CONST === 1);
+assert(!defined('TEST_REQUIREMENTS_MISSING_CONST'));
+
+assert(test_requirements_met() === true);
+assert(!function_exists('test_requirements_missing'));
+assert(!function_exists('test_requirements_missing_alias'));
+assert(!function_exists('test_requirements_future'));
+
+assert(class_exists('TestRequirementsClass'));
+assert(!class_exists('TestRequirementsFutureClass'));
@@ -0,0 +1,66 @@
+use ext_php_rs::prelude::*;
+use ext_php_rs::zend::bailout;
+
+#[php_function]
+pub fn test_return_void() {}
+
+#[php_function]
+pub fn test_return_never() -> ! {
+    unsafe { bailout() }
+}
+
+#[php_function]
+pub fn test_return_exit(code: i32) {
+    std::process::exit(code)
+}
+
+#[php_function]
+#[php(mixed)]
+pub fn test_return_mixed(value: i64) -> i64 {
+    value
+}
+
+#[php_class]
+#[derive(Default)]
+pub struct TestReturnTypes {
+    count: i64,
+}
+
+#[php_impl]
+impl TestReturnTypes {
+    pub fn __construct() -> Self {
+        Self::default()
+    }
+
+    pub fn increment(&mut self) {
+        self.count += 1;
+    }
+
+    pub fn count(&self) -> i64 {
+        self.count
+    }
+
+    #[php(mixed)]
+    pub fn value(&self) -> Option<i64> {
+        (self.count > 0).then_some(self.count)
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_return_void))
+        .function(wrap_function!(test_return_never))
+        .function(wrap_function!(test_return_exit))
+        .function(wrap_function!(test_return_mixed))
+        .class::<TestReturnTypes>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn return_types_work() {
+        assert!(crate::integration::test::run_php(
+            "return_types/return_types.php"
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+<?php
+
+function returnType(ReflectionFunctionAbstract $function): string {
+    return (string) $function->getReturnType();
+}
+
+$never = PHP_VERSION_ID >= 80100 ? 'never' : 'void';
+
+assert(returnType(new ReflectionFunction('test_return_void')) === 'void');
+assert(test_return_void() === null);
+assert(returnType(new ReflectionFunction('test_return_never')) === $never);
+assert(returnType(new ReflectionFunction('test_return_exit')) === $never);
+assert(returnType(new ReflectionFunction('test_return_mixed')) === 'mixed');
+assert(test_return_mixed(5) === 5);
+
+$class = new ReflectionClass(TestReturnTypes::class);
+assert(!$class->getMethod('__construct')->hasReturnType());
+assert(returnType($class->getMethod('increment')) === 'void');
+assert(returnType($class->getMethod('count')) === 'int');
+assert(returnType($class->getMethod('value')) === 'mixed');
+
+$obj = new TestReturnTypes();
+assert($obj->value() === null);
+assert($obj->increment() === null);
+assert($obj->count() === 1);
+assert($obj->value() === 1);
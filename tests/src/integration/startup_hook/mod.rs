@@ -0,0 +1,31 @@
+use ext_php_rs::prelude::*;
+
+#[php_const]
+pub const TEST_STARTUP_HOOK_CONST: i64 = 42;
+
+#[php_function]
+pub fn test_startup_hook() -> &'static str {
+    "registered at startup"
+}
+
+#[php_class]
+pub struct TestStartupHookClass;
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder.startup_hook(|module| {
+        module
+            .constant(wrap_constant!(TEST_STARTUP_HOOK_CONST))
+            .function(wrap_function!(test_startup_hook))
+            .class::<TestStartupHookClass>()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn startup_hook_works() {
+        assert!(crate::integration::test::run_php(
+            "startup_hook/startup_hook.php"
+        ));
+    }
+}
@@ -0,0 +1,8 @@
+<?php
+
+assert(TEST_STARTUP_HOOK_CONST === 42);
+assert(test_startup_hook() === 'registered at startup');
+assert(class_exists('TestStartupHookClass'));
+
+$function = new ReflectionFunction('test_startup_hook');
+assert($function->isInternal());
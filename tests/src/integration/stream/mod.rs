@@ -0,0 +1,61 @@
+use ext_php_rs::{
+    prelude::*,
+    types::Zval,
+    zend::{Stream, StreamContext},
+};
+
+#[php_function]
+pub fn test_stream_context() -> PhpResult<Zval> {
+    let mut context = StreamContext::new();
+    context
+        .http_method("POST")?
+        .http_headers(["Accept: text/plain", "X-Test: 1"])?
+        .ssl_verify_peer(false)?
+        .set_option("custom", "answer", 42)?;
+
+    if context.option("http", "method").and_then(Zval::str) != Some("POST") {
+        return Err("Option was not set".into());
+    }
+    Ok(context.to_zval())
+}
+
+#[php_function]
+pub fn test_stream_read(path: &str) -> PhpResult<String> {
+    let context = StreamContext::new();
+    let mut stream = Stream::open(path, "rb", Some(&context))?;
+    if stream.context().is_none() {
+        return Err("Stream has no context".into());
+    }
+
+    let mut contents = Vec::new();
+    let mut buf = [0; 4];
+    loop {
+        let read = stream.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        contents.extend_from_slice(&buf[..read]);
+    }
+    String::from_utf8(contents).map_err(|_| "Invalid UTF-8".into())
+}
+
+#[php_function]
+pub fn test_stream_write(path: &str, data: &str) -> PhpResult<usize> {
+    let mut stream = Stream::open(path, "wb", None)?;
+    Ok(stream.write(data.as_bytes())?)
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_stream_context))
+        .function(wrap_function!(test_stream_read))
+        .function(wrap_function!(test_stream_write))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn stream_works() {
+        assert!(crate::integration::test::run_php("stream/stream.php"));
+    }
+}
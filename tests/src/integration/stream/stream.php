@@ -0,0 +1,21 @@
+<?php
+
+require(__DIR__ . '/../_utils.php');
+
+$context = test_stream_context();
+assert(is_resource($context));
+assert(get_resource_type($context) === 'stream-context');
+
+$options = stream_context_get_options($context);
+assert($options['http']['method'] === 'POST');
+assert($options['http']['header'] === ['Accept: text/plain', 'X-Test: 1']);
+assert($options['ssl']['verify_peer'] === false);
+assert($options['custom']['answer'] === 42);
+
+$path = tempnam(sys_get_temp_dir(), 'ext-php-rs');
+assert(test_stream_write($path, 'Hello from a stream') === 19);
+assert(file_get_contents($path) === 'Hello from a stream');
+assert(test_stream_read($path) === 'Hello from a stream');
+unlink($path);
+
+assert_exception_thrown(fn () => @test_stream_read($path));
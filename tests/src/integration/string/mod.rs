@@ -1,4 +1,8 @@
-use ext_php_rs::prelude::*;
+use ext_php_rs::{
+    boxed::ZBox,
+    prelude::*,
+    types::{ZendStr, Zval},
+};
 
 #[php_function]
 pub fn test_str(a: &str) -> &str {
@@ -10,10 +14,28 @@ pub fn test_string(a: String) -> String {
     a
 }
 
+#[php_function]
+pub fn test_zend_string(a: &str, times: usize) -> ZBox<ZendStr> {
+    ZendStr::new(a.repeat(times), false)
+}
+
+#[php_function]
+pub fn test_string_cast(a: &Zval) -> PhpResult<ZBox<ZendStr>> {
+    Ok(a.cast_to_string()?)
+}
+
+#[php_function]
+pub fn test_string_coerce(#[php(coerce)] a: String, #[php(coerce)] b: Option<&str>) -> String {
+    format!("{a}{}", b.unwrap_or("-"))
+}
+
 pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
     builder
         .function(wrap_function!(test_str))
         .function(wrap_function!(test_string))
+        .function(wrap_function!(test_zend_string))
+        .function(wrap_function!(test_string_cast))
+        .function(wrap_function!(test_string_coerce))
 }
 
 #[cfg(test)]
@@ -1,4 +1,26 @@
 <?php
 
+require(__DIR__ . '/../_utils.php');
+
 assert(test_str('abc') === 'abc');
 assert(test_string('abc') === 'abc');
+assert(test_zend_string('abc', 3) === 'abcabcabc');
+assert(strlen(test_zend_string('x', 1 << 20)) === 1 << 20);
+assert(test_string_cast(1.5) === '1.5');
+assert(test_string_cast(new class { public function __toString(): string { return 'obj'; } }) === 'obj');
+assert_exception_thrown(fn () => test_string_cast(new stdClass()));
+
+$stringable = new class { public function __toString(): string { return 'obj'; } };
+assert(test_string_coerce('a', 'b') === 'ab');
+assert(test_string_coerce(5, null) === '5-');
+assert(test_string_coerce($stringable, 1.5) === 'obj1.5');
+assert(test_string_coerce(true, false) === '1');
+assert_exception_thrown(fn () => test_string_coerce([], null));
+assert_exception_thrown(fn () => test_string_coerce(new stdClass(), null));
+assert_exception_thrown(fn () => test_string_coerce(null, null));
+try {
+    test_string_coerce('a', []);
+    assert(false);
+} catch (TypeError $e) {
+    assert($e->getMessage() === 'test_string_coerce(): Argument #2 ($b) must be of type string, array given');
+}
@@ -0,0 +1,62 @@
+use ext_php_rs::{boxed::ZBox, prelude::*, types::ZendHashTable, zend::ExecutorGlobals};
+
+/// Collects the variables of the caller with the given names, like
+/// `compact()`.
+#[php_function]
+pub fn test_symbol_table_compact(names: Vec<String>) -> PhpResult<ZBox<ZendHashTable>> {
+    let mut globals = ExecutorGlobals::get_mut();
+    let vars = globals.symbol_table().ok_or("Not called from PHP code")?;
+    let mut result = ZendHashTable::new();
+    for name in names {
+        if let Some(value) = vars.get(&name) {
+            result.insert(name.as_str(), value.shallow_clone())?;
+        }
+    }
+    Ok(result)
+}
+
+/// Sets variables of the caller from the given array, like `extract()`.
+#[php_function]
+pub fn test_symbol_table_extract(values: &ZendHashTable) -> PhpResult<i64> {
+    let mut globals = ExecutorGlobals::get_mut();
+    let mut vars = globals.symbol_table().ok_or("Not called from PHP code")?;
+    let mut count = 0;
+    for (key, value) in values {
+        vars.set(&key.to_string(), value.shallow_clone())?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[php_function]
+pub fn test_symbol_table_unset(name: &str) -> bool {
+    ExecutorGlobals::get_mut()
+        .symbol_table()
+        .is_some_and(|mut vars| vars.remove(name))
+}
+
+#[php_function]
+pub fn test_symbol_table_names() -> Vec<String> {
+    ExecutorGlobals::get_mut()
+        .symbol_table()
+        .map(|vars| vars.iter().map(|(key, _)| key.to_string()).collect())
+        .unwrap_or_default()
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_symbol_table_compact))
+        .function(wrap_function!(test_symbol_table_extract))
+        .function(wrap_function!(test_symbol_table_unset))
+        .function(wrap_function!(test_symbol_table_names))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn symbol_table_works() {
+        assert!(crate::integration::test::run_php(
+            "symbol_table/symbol_table.php"
+        ));
+    }
+}
@@ -0,0 +1,40 @@
+<?php
+
+function local_scope(): void {
+    $a = 1;
+    $b = 'two';
+    $unset = null;
+    unset($unset);
+
+    assert(test_symbol_table_compact(['a', 'b', 'unset', 'missing']) === ['a' => 1, 'b' => 'two']);
+    assert(test_symbol_table_names() === ['a', 'b']);
+
+    assert(test_symbol_table_extract(['a' => 10, 'c' => [3]]) === 2);
+    assert($a === 10);
+    assert($c === [3]);
+
+    assert(test_symbol_table_unset('b'));
+    assert(!isset($b));
+    assert(!test_symbol_table_unset('b'));
+    assert(test_symbol_table_unset('c'));
+    assert(!isset($c));
+}
+
+local_scope();
+
+$global = 'value';
+assert(test_symbol_table_compact(['global']) === ['global' => 'value']);
+test_symbol_table_extract(['global' => 'changed']);
+assert($global === 'changed');
+
+// Unset globals leave undefined slots in the symbol table, which must be
+// skipped when iterating.
+$gone = 1;
+unset($gone);
+$names = test_symbol_table_names();
+assert(in_array('global', $names, true));
+assert(!in_array('gone', $names, true));
+
+$removed = 2;
+assert(test_symbol_table_unset('removed'));
+assert(!in_array('removed', test_symbol_table_names(), true));
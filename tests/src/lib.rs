@@ -9,9 +9,14 @@ use ext_php_rs::prelude::*;
 
 mod integration;
 
+use integration::info::minfo;
+
 #[php_module]
+#[php(minfo = minfo)]
 pub fn build_module(module: ModuleBuilder) -> ModuleBuilder {
-    let mut module = integration::array::build_module(module);
+    let mut module = integration::alias::build_module(module);
+    module = integration::array::build_module(module);
+    module = integration::async_fn::build_module(module);
     module = integration::bailout::build_module(module);
     module = integration::binary::build_module(module);
     module = integration::bool::build_module(module);
@@ -19,19 +24,29 @@ pub fn build_module(module: ModuleBuilder) -> ModuleBuilder {
     module = integration::class::build_module(module);
     module = integration::closure::build_module(module);
     module = integration::defaults::build_module(module);
+    module = integration::dimension::build_module(module);
+    module = integration::doc_comment::build_module(module);
     #[cfg(feature = "enum")]
     {
         module = integration::enum_::build_module(module);
     }
+    module = integration::error_level::build_module(module);
     module = integration::exception::build_module(module);
+    module = integration::extern_class::build_module(module);
     module = integration::globals::build_module(module);
     module = integration::iterator::build_module(module);
     module = integration::magic_method::build_module(module);
+    module = integration::namespace::build_module(module);
     module = integration::nullable::build_module(module);
     module = integration::number::build_module(module);
     module = integration::object::build_module(module);
     module = integration::persistent_string::build_module(module);
+    module = integration::requirements::build_module(module);
+    module = integration::return_types::build_module(module);
+    module = integration::startup_hook::build_module(module);
+    module = integration::stream::build_module(module);
     module = integration::string::build_module(module);
+    module = integration::symbol_table::build_module(module);
     module = integration::variadic_args::build_module(module);
     module = integration::interface::build_module(module);
 
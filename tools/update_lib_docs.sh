@@ -46,6 +46,7 @@ function update_docs() {
 update_docs "classes"
 update_docs "constant"
 update_docs "extern"
+update_docs "extern_class"
 update_docs "function"
 update_docs "impl"
 update_docs "module"